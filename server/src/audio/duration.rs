@@ -1,12 +1,26 @@
 use crate::error::Result;
 use hound::WavReader;
-use std::io::Cursor;
+use std::io::{Cursor, Read};
+use std::path::Path;
 
 /// Calculate duration in milliseconds from WAV file bytes
 pub fn calculate(wav_bytes: &[u8]) -> Result<f64> {
     let cursor = Cursor::new(wav_bytes);
     let reader = WavReader::new(cursor)?;
 
+    Ok(duration_ms(&reader))
+}
+
+/// Calculate duration in milliseconds of a WAV file on disk
+///
+/// Only the header is read, so this is cheap even for very long clips.
+pub fn calculate_file(path: &Path) -> Result<f64> {
+    let reader = WavReader::open(path)?;
+
+    Ok(duration_ms(&reader))
+}
+
+fn duration_ms<R: Read>(reader: &WavReader<R>) -> f64 {
     let spec = reader.spec();
     let num_samples = reader.len() as f64;
     let sample_rate = spec.sample_rate as f64;
@@ -15,9 +29,7 @@ pub fn calculate(wav_bytes: &[u8]) -> Result<f64> {
     // reader.len() returns total samples across all channels
     // We need frames (samples per channel) for duration calculation
     let num_frames = num_samples / num_channels;
-    let duration_ms = (num_frames / sample_rate) * 1000.0;
-
-    Ok(duration_ms)
+    (num_frames / sample_rate) * 1000.0
 }
 
 #[cfg(test)]
@@ -182,4 +194,24 @@ mod tests {
             duration
         );
     }
+
+    #[test]
+    fn test_calculate_file_matches_bytes() {
+        let wav_data = create_test_wav(24000, 2, 12000, 16);
+        let path = std::env::temp_dir().join(format!("duration_{}.wav", uuid::Uuid::new_v4()));
+        std::fs::write(&path, &wav_data).unwrap();
+
+        let from_file = calculate_file(&path).unwrap();
+        let from_bytes = calculate(&wav_data).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(from_file, from_bytes);
+        assert_eq!(from_file, 500.0);
+    }
+
+    #[test]
+    fn test_calculate_file_missing() {
+        let path = std::env::temp_dir().join(format!("missing_{}.wav", uuid::Uuid::new_v4()));
+        assert!(calculate_file(&path).is_err());
+    }
 }
@@ -1,3 +1,6 @@
 pub mod duration;
+pub mod resample;
 pub mod segmentation;
+pub mod wav_metadata;
 pub mod wav_utils;
+pub mod waveform;
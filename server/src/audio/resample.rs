@@ -0,0 +1,223 @@
+use crate::audio::wav_utils::{read_normalized_samples, write_normalized_samples};
+use crate::config::constants::SUPPORTED_SAMPLE_RATES;
+use crate::error::{Result, TtsError};
+use hound::WavSpec;
+
+/// Validate a requested output sample rate against the supported set
+pub fn validate_sample_rate(sample_rate: Option<u32>) -> Result<()> {
+    match sample_rate {
+        Some(rate) if !SUPPORTED_SAMPLE_RATES.contains(&rate) => {
+            Err(TtsError::InvalidRequest(format!(
+                "Unsupported sample rate: {} Hz (supported: {:?})",
+                rate, SUPPORTED_SAMPLE_RATES
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Resample interleaved samples from one rate to another using linear interpolation
+///
+/// Each channel is interpolated independently. The output frame count is
+/// the input frame count scaled by `to_rate / from_rate`.
+pub fn resample_linear(samples: &[f32], channels: usize, from_rate: u32, to_rate: u32) -> Vec<f32> {
+    if from_rate == to_rate || channels == 0 || samples.len() < channels {
+        return samples.to_vec();
+    }
+
+    let in_frames = samples.len() / channels;
+    let out_frames = (in_frames as u64 * to_rate as u64 / from_rate as u64) as usize;
+    let step = from_rate as f64 / to_rate as f64;
+
+    let mut output = Vec::with_capacity(out_frames * channels);
+    for frame in 0..out_frames {
+        let position = frame as f64 * step;
+        let index = position.floor() as usize;
+        let fraction = (position - index as f64) as f32;
+        let next = (index + 1).min(in_frames - 1);
+
+        for channel in 0..channels {
+            let current = samples[index * channels + channel];
+            let following = samples[next * channels + channel];
+            output.push(current + (following - current) * fraction);
+        }
+    }
+
+    output
+}
+
+/// Resample WAV bytes to the target sample rate, rewriting the header accordingly
+///
+/// Channel count, bit depth and sample format are preserved. Audio that is
+/// already at the target rate is returned unchanged.
+pub fn resample_wav(wav_bytes: &[u8], target_rate: u32) -> Result<Vec<u8>> {
+    let (spec, samples) = read_normalized_samples(wav_bytes)?;
+
+    if spec.sample_rate == target_rate {
+        return Ok(wav_bytes.to_vec());
+    }
+
+    let resampled = resample_linear(
+        &samples,
+        spec.channels as usize,
+        spec.sample_rate,
+        target_rate,
+    );
+
+    write_normalized_samples(
+        &resampled,
+        WavSpec {
+            sample_rate: target_rate,
+            ..spec
+        },
+    )
+}
+
+/// Raise or lower the pitch of WAV audio by `factor`, shortening it by the same factor
+///
+/// The audio is squeezed into `1 / factor` of its frames and played back at
+/// the original rate, so callers wanting the original duration should
+/// synthesize `factor` times slower first.
+pub fn shift_pitch_wav(wav_bytes: &[u8], factor: f32) -> Result<Vec<u8>> {
+    if factor == 1.0 {
+        return Ok(wav_bytes.to_vec());
+    }
+
+    let (spec, samples) = read_normalized_samples(wav_bytes)?;
+    let squeezed_rate = (spec.sample_rate as f32 / factor).round() as u32;
+    let shifted = resample_linear(
+        &samples,
+        spec.channels as usize,
+        spec.sample_rate,
+        squeezed_rate,
+    );
+
+    write_normalized_samples(&shifted, spec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hound::{SampleFormat, WavReader, WavWriter};
+    use std::io::Cursor;
+
+    fn create_tone_wav(sample_rate: u32, channels: u16, num_frames: u32) -> Vec<u8> {
+        let spec = WavSpec {
+            channels,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+
+        let mut buffer = Vec::new();
+        {
+            let cursor = Cursor::new(&mut buffer);
+            let mut writer = WavWriter::new(cursor, spec).unwrap();
+
+            // 440 Hz sine tone at half amplitude
+            for frame in 0..num_frames {
+                let t = frame as f32 / sample_rate as f32;
+                let value = (t * 440.0 * 2.0 * std::f32::consts::PI).sin() * 0.5;
+                for _ in 0..channels {
+                    writer
+                        .write_sample((value * i16::MAX as f32) as i16)
+                        .unwrap();
+                }
+            }
+
+            writer.finalize().unwrap();
+        }
+        buffer
+    }
+
+    #[test]
+    fn test_shift_pitch_keeps_rate_and_scales_length() {
+        let wav = create_tone_wav(24000, 2, 12000);
+        let shifted = shift_pitch_wav(&wav, 2.0).unwrap();
+
+        let reader = WavReader::new(Cursor::new(&shifted)).unwrap();
+        assert_eq!(reader.spec().sample_rate, 24000);
+        assert_eq!(reader.spec().channels, 2);
+        assert_eq!(reader.duration(), 6000);
+    }
+
+    #[test]
+    fn test_shift_pitch_unity_is_unchanged() {
+        let wav = create_tone_wav(24000, 1, 1000);
+        assert_eq!(shift_pitch_wav(&wav, 1.0).unwrap(), wav);
+    }
+
+    #[test]
+    fn test_resample_24k_to_16k_scales_length() {
+        let wav = create_tone_wav(24000, 1, 24000);
+        let resampled = resample_wav(&wav, 16000).unwrap();
+
+        let reader = WavReader::new(Cursor::new(&resampled)).unwrap();
+        assert_eq!(reader.spec().sample_rate, 16000);
+        assert_eq!(reader.spec().channels, 1);
+        assert_eq!(reader.spec().bits_per_sample, 16);
+        assert_eq!(reader.len(), 16000);
+    }
+
+    #[test]
+    fn test_resample_24k_to_48k_scales_length() {
+        let wav = create_tone_wav(24000, 2, 12000);
+        let resampled = resample_wav(&wav, 48000).unwrap();
+
+        let reader = WavReader::new(Cursor::new(&resampled)).unwrap();
+        assert_eq!(reader.spec().sample_rate, 48000);
+        // 12000 frames * 2 (rate ratio) * 2 channels
+        assert_eq!(reader.len(), 48000);
+    }
+
+    #[test]
+    fn test_resample_preserves_duration() {
+        let wav = create_tone_wav(24000, 1, 12000);
+        let original_duration = crate::audio::duration::calculate(&wav).unwrap();
+
+        let resampled = resample_wav(&wav, 16000).unwrap();
+        let resampled_duration = crate::audio::duration::calculate(&resampled).unwrap();
+
+        assert!(
+            (original_duration - resampled_duration).abs() < 1.0,
+            "Expected ~{}ms, got {}ms",
+            original_duration,
+            resampled_duration
+        );
+    }
+
+    #[test]
+    fn test_resample_same_rate_is_unchanged() {
+        let wav = create_tone_wav(24000, 1, 2400);
+        let resampled = resample_wav(&wav, 24000).unwrap();
+        assert_eq!(resampled, wav);
+    }
+
+    #[test]
+    fn test_resample_linear_interpolates_midpoints() {
+        let samples = vec![0.0, 1.0, 0.0, -1.0];
+        let upsampled = resample_linear(&samples, 1, 1, 2);
+        assert_eq!(upsampled.len(), 8);
+        assert!((upsampled[1] - 0.5).abs() < 1e-6);
+        assert!((upsampled[5] + 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_resample_invalid_wav_data() {
+        let result = resample_wav(&[0u8; 16], 16000);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_sample_rate() {
+        assert!(validate_sample_rate(None).is_ok());
+        assert!(validate_sample_rate(Some(16000)).is_ok());
+        assert!(validate_sample_rate(Some(24000)).is_ok());
+        assert!(validate_sample_rate(Some(48000)).is_ok());
+
+        match validate_sample_rate(Some(12345)) {
+            Err(TtsError::InvalidRequest(msg)) => assert!(msg.contains("12345")),
+            other => panic!("Expected InvalidRequest error, got: {:?}", other),
+        }
+    }
+}
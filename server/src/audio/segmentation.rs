@@ -1,5 +1,10 @@
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+
 use crate::text_processing::normalization::normalize_simple;
-use crate::text_processing::sentence_splitting::split_sentences;
+use crate::text_processing::sentence_splitting::{
+    split_sentences_with_endings, DEFAULT_SENTENCE_ENDINGS,
+};
 
 /// Configuration for text segmentation behavior
 #[derive(Debug, Clone)]
@@ -18,6 +23,11 @@ pub struct SegmentationConfig {
 
     /// Whether to treat em-dashes as sentence boundaries
     pub emdash_as_boundary: bool,
+
+    /// Characters that end a sentence (default: `.`, `!` and `?`)
+    ///
+    /// Periods keep their abbreviation and decimal guards.
+    pub sentence_endings: Vec<char>,
 }
 
 impl Default for SegmentationConfig {
@@ -35,6 +45,7 @@ impl SegmentationConfig {
             respect_comma_boundaries: true,
             separate_punctuation: false,
             emdash_as_boundary: false,
+            sentence_endings: DEFAULT_SENTENCE_ENDINGS.to_vec(),
         }
     }
 
@@ -47,6 +58,7 @@ impl SegmentationConfig {
             respect_comma_boundaries: true,
             separate_punctuation: true,
             emdash_as_boundary: false,
+            sentence_endings: DEFAULT_SENTENCE_ENDINGS.to_vec(),
         }
     }
 
@@ -59,6 +71,7 @@ impl SegmentationConfig {
             respect_comma_boundaries: true,
             separate_punctuation: false,
             emdash_as_boundary: true,
+            sentence_endings: DEFAULT_SENTENCE_ENDINGS.to_vec(),
         }
     }
 
@@ -71,6 +84,7 @@ impl SegmentationConfig {
             respect_comma_boundaries: false,
             separate_punctuation: false,
             emdash_as_boundary: false,
+            sentence_endings: DEFAULT_SENTENCE_ENDINGS.to_vec(),
         }
     }
 }
@@ -90,63 +104,160 @@ fn preprocess_dashes(text: &str, emdash_as_boundary: bool) -> String {
     }
 }
 
-/// Internal: Segment words preserving punctuation (current behavior)
-fn segment_words_preserve_punctuation(text: &str) -> Vec<String> {
-    let mut words = Vec::new();
-    let mut current_word = String::new();
+/// Punctuation split into its own token by `separate_punctuation`
+///
+/// Besides ASCII, covers the Arabic comma, semicolon, question mark and full
+/// stop, the Hebrew sof pasuq and common CJK punctuation.
+const SEPARATED_PUNCTUATION: &[char] = &[
+    '.', '!', '?', ',', ';', ':', '"', '\'', '(', ')', '[', ']', '{', '}', '\u{060C}', '\u{061B}',
+    '\u{061F}', '\u{06D4}', '\u{05C3}', '\u{3001}', '\u{3002}', '\u{FF0C}', '\u{FF01}', '\u{FF1F}',
+];
+
+/// Clause separators for comma-aware phrasing, including the Arabic comma
+/// and semicolon
+const CLAUSE_SEPARATORS: &[char] = &[',', ';', '\u{060C}', '\u{061B}'];
+
+/// Whether `ch` separates words: any Unicode whitespace, plus the zero-width
+/// space that marks word breaks in scripts like Thai and Khmer
+fn is_word_separator(ch: char) -> bool {
+    ch.is_whitespace() || ch == '\u{200B}'
+}
 
-    for ch in text.chars() {
-        if ch.is_whitespace() {
-            if !current_word.is_empty() {
-                words.push(current_word.clone());
-                current_word.clear();
-            }
-        } else {
-            current_word.push(ch);
-        }
-    }
+/// Bidirectional formatting characters: LRM/RLM, the Arabic letter mark,
+/// embeddings, overrides and isolates
+///
+/// They're invisible and only steer display direction in mixed LTR/RTL text,
+/// so they stay attached to the word they're in but never form one alone.
+fn is_bidi_control(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{200E}' | '\u{200F}' | '\u{061C}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}'
+    )
+}
 
-    if !current_word.is_empty() {
-        words.push(current_word);
-    }
+/// Whether a word-boundary segment holds a word rather than punctuation,
+/// symbols or space
+fn is_word_segment(segment: &str) -> bool {
+    segment.chars().any(char::is_alphanumeric)
+}
 
-    words
+/// Whether `segment` is written without spaces between words: Chinese and
+/// Japanese ideographs and kana, where each boundary segment is a word
+fn is_ideographic(segment: &str) -> bool {
+    segment.chars().any(|ch| {
+        matches!(
+            ch,
+            '\u{3040}'..='\u{30FF}'
+                | '\u{3400}'..='\u{4DBF}'
+                | '\u{4E00}'..='\u{9FFF}'
+                | '\u{F900}'..='\u{FAFF}'
+                | '\u{20000}'..='\u{2FFFF}'
+        )
+    })
 }
 
-/// Internal: Segment words separating punctuation
-fn segment_words_separate_punctuation(text: &str) -> Vec<String> {
-    let mut words = Vec::new();
-    let mut current_word = String::new();
-
-    // Common sentence-ending punctuation
-    let sentence_punct = ['.', '!', '?'];
-    // Other punctuation to separate
-    let other_punct = [',', ';', ':', '"', '\'', '(', ')', '[', ']', '{', '}'];
-
-    for ch in text.chars() {
-        if ch.is_whitespace() {
-            if !current_word.is_empty() {
-                words.push(current_word.clone());
-                current_word.clear();
+/// Byte range of each word in `text`, found from Unicode (UAX #29) word
+/// boundaries
+///
+/// Words end at whitespace (and the zero-width space), and between Chinese
+/// or Japanese ideographs, which UAX #29 gives one segment each. Elsewhere
+/// the boundaries UAX #29 puts around punctuation are not word breaks, so
+/// "state-of-the-art" and "Hello," stay whole; scripts like Thai that need a
+/// dictionary to find words are left as written. Punctuation is split off
+/// only with `separate_punctuation`. Right-to-left runs are kept whole and
+/// in logical order, and spans of nothing but bidi controls are dropped.
+fn word_spans(text: &str, separate_punctuation: bool) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut current: Option<Range<usize>> = None;
+    // The last word segment in `current`, if any, and whether it was ideographic
+    let mut last_word: Option<bool> = None;
+
+    let mut finish = |current: &mut Option<Range<usize>>| {
+        if let Some(span) = current.take() {
+            if text[span.clone()].chars().any(|ch| !is_bidi_control(ch)) {
+                spans.push(span);
+            }
+        }
+    };
+    let extend = |current: &mut Option<Range<usize>>, span: Range<usize>| {
+        *current = Some(match current.take() {
+            Some(word) => word.start..span.end,
+            None => span,
+        });
+    };
+
+    for (start, segment) in text.split_word_bound_indices() {
+        let span = start..start + segment.len();
+        if segment.contains(is_word_separator)
+            && segment
+                .chars()
+                .all(|ch| is_word_separator(ch) || is_bidi_control(ch))
+        {
+            // UAX #29 attaches bidi controls to the space before them; those
+            // after the last space belong to the next word
+            let first = segment.find(is_word_separator).unwrap_or(0);
+            let (last, ch) = segment
+                .char_indices()
+                .rev()
+                .find(|(_, ch)| is_word_separator(*ch))
+                .unwrap_or((0, ' '));
+            if first > 0 {
+                extend(&mut current, start..start + first);
             }
-        } else if sentence_punct.contains(&ch) || other_punct.contains(&ch) {
-            // Push current word if exists
-            if !current_word.is_empty() {
-                words.push(current_word.clone());
-                current_word.clear();
+            finish(&mut current);
+            last_word = None;
+            let rest = start + last + ch.len_utf8();
+            if rest < span.end {
+                current = Some(rest..span.end);
             }
-            // Push punctuation as separate token
-            words.push(ch.to_string());
+        } else if separate_punctuation
+            && segment.chars().count() == 1
+            && segment
+                .chars()
+                .all(|ch| SEPARATED_PUNCTUATION.contains(&ch))
+        {
+            // Punctuation as its own token
+            finish(&mut current);
+            current = Some(span);
+            finish(&mut current);
+            last_word = None;
+        } else if is_word_segment(segment) {
+            let ideographic = is_ideographic(segment);
+            if last_word.is_some_and(|last| last || ideographic) {
+                finish(&mut current);
+            }
+            extend(&mut current, span);
+            last_word = Some(ideographic);
         } else {
-            current_word.push(ch);
+            extend(&mut current, span);
         }
     }
+    finish(&mut current);
 
-    if !current_word.is_empty() {
-        words.push(current_word);
-    }
+    spans
+}
+
+/// Words of `text` split on Unicode word boundaries, as owned strings
+fn segment_word_bounds(text: &str, separate_punctuation: bool) -> Vec<String> {
+    word_spans(text, separate_punctuation)
+        .into_iter()
+        .map(|span| text[span].to_string())
+        .collect()
+}
 
-    words
+/// Rebuild a phrase from consecutive word spans of `text`
+///
+/// Words that were apart in `text` are joined by a single space; words that
+/// touched, like CJK ideographs, stay together.
+fn join_spans(text: &str, spans: &[Range<usize>]) -> String {
+    let mut phrase = String::new();
+    for (i, span) in spans.iter().enumerate() {
+        if i > 0 && spans[i - 1].end < span.start {
+            phrase.push(' ');
+        }
+        phrase.push_str(&text[span.clone()]);
+    }
+    phrase
 }
 
 /// Split text into words with configuration options
@@ -157,11 +268,7 @@ pub fn segment_words_with_config(text: &str, config: &SegmentationConfig) -> Vec
         text.to_string()
     };
 
-    if config.separate_punctuation {
-        segment_words_separate_punctuation(&text)
-    } else {
-        segment_words_preserve_punctuation(&text)
-    }
+    segment_word_bounds(&text, config.separate_punctuation)
 }
 
 /// Split text into words, preserving punctuation with words (backward compatible)
@@ -170,22 +277,21 @@ pub fn segment_words(text: &str) -> Vec<String> {
 }
 
 /// Internal: Simple phrase segmentation (improved version of current)
-fn segment_phrases_simple(text: &str, max_words: usize) -> Vec<String> {
+fn segment_phrases_simple(text: &str, max_words: usize, endings: &[char]) -> Vec<String> {
     let mut phrases = Vec::new();
 
     // Use smart sentence splitting
-    let sentences = split_sentences(text);
+    let sentences = split_sentences_with_endings(text, endings);
 
     for sentence in sentences.iter() {
-        let words = segment_words_preserve_punctuation(sentence);
+        let words = word_spans(sentence, false);
 
         if words.len() <= max_words {
             phrases.push(sentence.to_string());
         } else {
             // Split into max_words chunks
             for chunk in words.chunks(max_words) {
-                let phrase = chunk.join(" ");
-                phrases.push(phrase);
+                phrases.push(join_spans(sentence, chunk));
             }
         }
     }
@@ -194,22 +300,22 @@ fn segment_phrases_simple(text: &str, max_words: usize) -> Vec<String> {
 }
 
 /// Internal: Comma-aware phrase segmentation
-fn segment_phrases_comma_aware(text: &str, max_words: usize) -> Vec<String> {
+fn segment_phrases_comma_aware(text: &str, max_words: usize, endings: &[char]) -> Vec<String> {
     let mut phrases = Vec::new();
 
     // Use smart sentence splitting
-    let sentences = split_sentences(text);
+    let sentences = split_sentences_with_endings(text, endings);
 
     for sentence in sentences {
         // Split by commas and semicolons
         let clauses: Vec<&str> = sentence
-            .split([',', ';'])
+            .split(CLAUSE_SEPARATORS)
             .map(|s| s.trim())
             .filter(|s| !s.is_empty())
             .collect();
 
         for clause in clauses {
-            let words = segment_words_preserve_punctuation(clause);
+            let words = word_spans(clause, false);
 
             if words.len() <= max_words {
                 // Clause fits within limit, use as-is
@@ -217,8 +323,7 @@ fn segment_phrases_comma_aware(text: &str, max_words: usize) -> Vec<String> {
             } else {
                 // Clause too long, split into chunks
                 for chunk in words.chunks(max_words) {
-                    let phrase = chunk.join(" ");
-                    phrases.push(phrase);
+                    phrases.push(join_spans(clause, chunk));
                 }
             }
         }
@@ -240,14 +345,15 @@ pub fn segment_phrases_with_config(text: &str, config: &SegmentationConfig) -> V
     }
 
     if config.respect_comma_boundaries {
-        segment_phrases_comma_aware(&text, config.max_phrase_words)
+        segment_phrases_comma_aware(&text, config.max_phrase_words, &config.sentence_endings)
     } else {
-        segment_phrases_simple(&text, config.max_phrase_words)
+        segment_phrases_simple(&text, config.max_phrase_words, &config.sentence_endings)
     }
 }
 
 /// Split text into phrases (backward compatible with new default)
 /// Now uses 8-word chunks instead of 5 for better breath groups
+#[cfg(test)]
 pub fn segment_phrases(text: &str) -> Vec<String> {
     segment_phrases_with_config(text, &SegmentationConfig::default())
 }
@@ -255,6 +361,7 @@ pub fn segment_phrases(text: &str) -> Vec<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::text_processing::sentence_splitting::split_sentences;
 
     #[test]
     fn test_segmentation_config_default() {
@@ -651,4 +758,145 @@ mod tests {
         assert_eq!(config.max_phrase_words, 12);
         assert_eq!(config.respect_comma_boundaries, false);
     }
+
+    #[test]
+    fn test_custom_sentence_ending_splits() {
+        let text = "Chapter one: the start | Chapter two: the end";
+        let mut config = SegmentationConfig::for_reading();
+        assert_eq!(segment_phrases_with_config(text, &config).len(), 1);
+
+        config.sentence_endings.push('|');
+        assert_eq!(
+            segment_phrases_with_config(text, &config),
+            vec!["Chapter one: the start |", "Chapter two: the end"]
+        );
+    }
+
+    #[test]
+    fn test_removed_sentence_ending_keeps_questions_attached() {
+        let text = "Ready? Dr. Smith starts at 3.5 hours. Go!";
+        let config = SegmentationConfig {
+            sentence_endings: vec!['.', '!'],
+            ..SegmentationConfig::for_reading()
+        };
+        assert_eq!(
+            segment_phrases_with_config(text, &config),
+            vec!["Ready? Dr. Smith starts at 3.5 hours.", "Go!"]
+        );
+    }
+
+    #[test]
+    fn test_mixed_ltr_rtl_words_stay_whole() {
+        let words = segment_words("Hello مرحبا بالعالم and שלום עולם!");
+        assert_eq!(
+            words,
+            vec!["Hello", "مرحبا", "بالعالم", "and", "שלום", "עולם!"]
+        );
+    }
+
+    #[test]
+    fn test_bidi_marks_never_form_words() {
+        let text = "Say \u{200F}مرحبا\u{200F} \u{200E} \u{2067}\u{2069} now";
+        assert_eq!(
+            segment_words(text),
+            vec!["Say", "\u{200F}مرحبا\u{200F}", "now"]
+        );
+    }
+
+    #[test]
+    fn test_rtl_punctuation_is_separated() {
+        let config = SegmentationConfig::for_linguistic_analysis();
+        assert_eq!(
+            segment_words_with_config("مرحبا، كيف حالك؟", &config),
+            vec!["مرحبا", "،", "كيف", "حالك", "؟"]
+        );
+        assert_eq!(
+            segment_words_with_config("Hi (שלום) there.", &config),
+            vec!["Hi", "(", "שלום", ")", "there", "."]
+        );
+    }
+
+    #[test]
+    fn test_zero_width_space_separates_words() {
+        assert_eq!(segment_words("สวัสดี\u{200B}ครับ"), vec!["สวัสดี", "ครับ"]);
+    }
+
+    #[test]
+    fn test_cjk_words_split_per_ideograph() {
+        assert_eq!(segment_words("你好世界"), vec!["你", "好", "世", "界"]);
+        assert_eq!(
+            segment_words("我们走吧。好的！"),
+            vec!["我", "们", "走", "吧。", "好", "的！"]
+        );
+        assert_eq!(
+            segment_words("Drink コーヒー in 東京"),
+            vec!["Drink", "コーヒー", "in", "東", "京"]
+        );
+
+        let config = SegmentationConfig::for_linguistic_analysis();
+        assert_eq!(
+            segment_words_with_config("你好。世界！", &config),
+            vec!["你", "好", "。", "世", "界", "！"]
+        );
+    }
+
+    #[test]
+    fn test_cjk_phrases_are_not_spaced_out() {
+        let config = SegmentationConfig {
+            max_phrase_words: 4,
+            ..SegmentationConfig::default()
+        };
+        assert_eq!(
+            segment_phrases_with_config("一二三四五六七八九十", &config),
+            vec!["一二三四", "五六七八", "九十"]
+        );
+    }
+
+    #[test]
+    fn test_arabic_comma_is_a_clause_boundary() {
+        assert_eq!(
+            segment_phrases("مرحبا، كيف حالك اليوم"),
+            vec!["مرحبا", "كيف حالك اليوم"]
+        );
+    }
+
+    #[test]
+    fn test_mixed_script_segmentation_is_panic_free() {
+        let texts = [
+            "Hello مرحبا world",
+            "\u{202B}עברית\u{202C} and English\u{200F}.",
+            "مرحبا123abc، ok؟ 你好。世界！",
+            "\u{200F}",
+            "ا\u{0651}\u{064E} e\u{0301} 👋🏽 مرحبا—world",
+        ];
+        let configs = [
+            SegmentationConfig::for_tts(),
+            SegmentationConfig::for_linguistic_analysis(),
+            SegmentationConfig::for_subtitles(),
+            SegmentationConfig::for_reading(),
+        ];
+
+        for text in texts {
+            for config in &configs {
+                let words = segment_words_with_config(text, config);
+                assert!(words.iter().all(|w| !w.is_empty()), "{:?}", text);
+                let _ = segment_phrases_with_config(text, config);
+            }
+
+            // Without punctuation splitting, no visible character is lost
+            let visible = |s: &str| -> String {
+                s.chars()
+                    .filter(|&c| !is_word_separator(c) && !is_bidi_control(c))
+                    .collect()
+            };
+            let words = segment_words_with_config(
+                text,
+                &SegmentationConfig {
+                    normalize_unicode: false,
+                    ..SegmentationConfig::default()
+                },
+            );
+            assert_eq!(visible(&words.concat()), visible(text), "{:?}", text);
+        }
+    }
 }
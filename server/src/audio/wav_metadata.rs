@@ -0,0 +1,193 @@
+use crate::error::{Result, TtsError};
+
+/// Longest text snippet embedded in the metadata, in characters
+const MAX_TEXT_SNIPPET_CHARS: usize = 200;
+
+/// Describe a generation as RIFF INFO fields
+///
+/// The title (`INAM`) is a snippet of the source text, the artist (`IART`)
+/// the voice ID, and the comment (`ICMT`) the voice and speed.
+pub fn request_info(text: &str, voice: &str, speed: f32) -> Vec<([u8; 4], String)> {
+    let mut snippet: String = text.chars().take(MAX_TEXT_SNIPPET_CHARS).collect();
+    if text.chars().count() > MAX_TEXT_SNIPPET_CHARS {
+        snippet.push_str("...");
+    }
+
+    vec![
+        (*b"INAM", snippet),
+        (*b"IART", voice.to_string()),
+        (*b"ICMT", format!("voice={} speed={}", voice, speed)),
+        (
+            *b"ISFT",
+            format!("Porua Server {}", env!("CARGO_PKG_VERSION")),
+        ),
+    ]
+}
+
+/// Insert a `LIST`/`INFO` chunk with the given fields into WAV bytes
+///
+/// The chunk goes right before `data`, where players expect metadata, and
+/// the RIFF size is updated. Any existing `LIST` chunk is replaced. Values
+/// are stored as NUL-terminated UTF-8.
+pub fn embed_info(wav_bytes: &[u8], fields: &[([u8; 4], String)]) -> Result<Vec<u8>> {
+    let chunks = parse_chunks(wav_bytes)?;
+
+    let mut list = b"INFO".to_vec();
+    for (id, value) in fields {
+        let mut data: Vec<u8> = value.bytes().filter(|&b| b != 0).collect();
+        data.push(0);
+        push_chunk(&mut list, id, &data);
+    }
+
+    let mut body = b"WAVE".to_vec();
+    for (id, data) in chunks {
+        if &id == b"LIST" {
+            continue;
+        }
+        if &id == b"data" {
+            push_chunk(&mut body, b"LIST", &list);
+        }
+        push_chunk(&mut body, &id, data);
+    }
+
+    let mut output = Vec::with_capacity(body.len() + 8);
+    output.extend_from_slice(b"RIFF");
+    output.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    output.extend_from_slice(&body);
+    Ok(output)
+}
+
+/// Read the `LIST`/`INFO` fields from WAV bytes, in file order
+///
+/// Returns an empty list when the file carries no INFO metadata.
+#[cfg(test)]
+pub fn read_info(wav_bytes: &[u8]) -> Result<Vec<([u8; 4], String)>> {
+    let mut fields = Vec::new();
+
+    for (id, data) in parse_chunks(wav_bytes)? {
+        if &id != b"LIST" || !data.starts_with(b"INFO") {
+            continue;
+        }
+        for (field, value) in parse_subchunks(&data[4..])? {
+            let value = value.split(|&b| b == 0).next().unwrap_or_default();
+            fields.push((field, String::from_utf8_lossy(value).into_owned()));
+        }
+    }
+
+    Ok(fields)
+}
+
+/// Split a RIFF/WAVE file into its top-level chunks
+fn parse_chunks(wav_bytes: &[u8]) -> Result<Vec<([u8; 4], &[u8])>> {
+    if wav_bytes.len() < 12 || &wav_bytes[0..4] != b"RIFF" || &wav_bytes[8..12] != b"WAVE" {
+        return Err(TtsError::AudioParsing("Not a RIFF/WAVE file".to_string()));
+    }
+    parse_subchunks(&wav_bytes[12..])
+}
+
+/// Split a run of RIFF chunks (id, size, data, pad byte to even length)
+fn parse_subchunks(mut bytes: &[u8]) -> Result<Vec<([u8; 4], &[u8])>> {
+    let mut chunks = Vec::new();
+
+    while bytes.len() >= 8 {
+        let id = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        let size = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as usize;
+        let data = bytes.get(8..8 + size).ok_or_else(|| {
+            TtsError::AudioParsing(format!(
+                "Truncated '{}' chunk",
+                String::from_utf8_lossy(&id)
+            ))
+        })?;
+        chunks.push((id, data));
+
+        let padded = 8 + size + size % 2;
+        bytes = bytes.get(padded..).unwrap_or_default();
+    }
+
+    Ok(chunks)
+}
+
+fn push_chunk(output: &mut Vec<u8>, id: &[u8; 4], data: &[u8]) {
+    output.extend_from_slice(id);
+    output.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    output.extend_from_slice(data);
+    if data.len() % 2 == 1 {
+        output.push(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+    use std::io::Cursor;
+
+    fn wav(samples: &[i16]) -> Vec<u8> {
+        let spec = WavSpec {
+            channels: 1,
+            sample_rate: 24000,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+        let mut output = Cursor::new(Vec::new());
+        let mut writer = WavWriter::new(&mut output, spec).unwrap();
+        for &sample in samples {
+            writer.write_sample(sample).unwrap();
+        }
+        writer.finalize().unwrap();
+        output.into_inner()
+    }
+
+    #[test]
+    fn test_embedded_info_reads_back() {
+        let fields = request_info("Hello world", "af_heart", 1.25);
+        let tagged = embed_info(&wav(&[1, 2, 3]), &fields).unwrap();
+
+        let info = read_info(&tagged).unwrap();
+        assert_eq!(info, fields);
+        assert_eq!(info[0], (*b"INAM", "Hello world".to_string()));
+        assert_eq!(info[2], (*b"ICMT", "voice=af_heart speed=1.25".to_string()));
+    }
+
+    #[test]
+    fn test_audio_unchanged_by_metadata() {
+        let original = wav(&[100, -100, 7]);
+        // Odd-length value exercises chunk padding
+        let tagged = embed_info(&original, &[(*b"INAM", "odd".to_string())]).unwrap();
+
+        let riff_size = u32::from_le_bytes(tagged[4..8].try_into().unwrap()) as usize;
+        assert_eq!(riff_size, tagged.len() - 8);
+
+        let mut reader = WavReader::new(Cursor::new(&tagged)).unwrap();
+        let samples: Vec<i16> = reader.samples().map(|s| s.unwrap()).collect();
+        assert_eq!(samples, vec![100, -100, 7]);
+    }
+
+    #[test]
+    fn test_embedding_replaces_existing_info() {
+        let once = embed_info(&wav(&[1]), &[(*b"INAM", "first".to_string())]).unwrap();
+        let twice = embed_info(&once, &[(*b"INAM", "second".to_string())]).unwrap();
+
+        assert_eq!(
+            read_info(&twice).unwrap(),
+            vec![(*b"INAM", "second".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_text_snippet_is_truncated() {
+        let text = "a".repeat(500);
+        let info = request_info(&text, "bf_lily", 1.0);
+        assert_eq!(info[0].1.len(), MAX_TEXT_SNIPPET_CHARS + 3);
+        assert!(info[0].1.ends_with("..."));
+    }
+
+    #[test]
+    fn test_plain_wav_has_no_info() {
+        assert!(read_info(&wav(&[1, 2])).unwrap().is_empty());
+        assert!(matches!(
+            embed_info(b"not a wav", &[]),
+            Err(TtsError::AudioParsing(_))
+        ));
+    }
+}
@@ -1,7 +1,83 @@
+use crate::audio::resample::resample_wav;
+use crate::config::constants::{SUPPORTED_BIT_DEPTHS, SUPPORTED_SAMPLE_RATES};
 use crate::error::{Result, TtsError};
-use hound::{SampleFormat, WavReader, WavWriter};
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use serde::{Serialize, Serializer};
 use std::io::Cursor;
 
+/// How [`concatenate_with`] handles inputs at different sample rates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SampleRateMismatch {
+    /// Reject inputs whose formats differ
+    #[default]
+    Strict,
+    /// Resample every input to the highest input rate
+    ResampleToMax,
+    /// Resample every input to the given rate
+    ResampleTo(u32),
+}
+
+/// Written the way `TTS_CONCAT_SAMPLE_RATE` takes it: `strict`, `max` or a rate
+impl Serialize for SampleRateMismatch {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            Self::Strict => serializer.serialize_str("strict"),
+            Self::ResampleToMax => serializer.serialize_str("max"),
+            Self::ResampleTo(rate) => serializer.serialize_u32(*rate),
+        }
+    }
+}
+
+impl SampleRateMismatch {
+    /// Parse `strict`, `max`, or a supported sample rate in Hz
+    pub fn parse(value: &str) -> std::result::Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "strict" => Ok(Self::Strict),
+            "max" => Ok(Self::ResampleToMax),
+            other => match other.parse::<u32>() {
+                Ok(rate) if SUPPORTED_SAMPLE_RATES.contains(&rate) => Ok(Self::ResampleTo(rate)),
+                _ => Err(format!(
+                    "Invalid concatenation sample rate '{}' (expected strict, max, or one of {:?})",
+                    value, SUPPORTED_SAMPLE_RATES
+                )),
+            },
+        }
+    }
+
+    /// Human-readable description for startup logs
+    pub fn description(&self) -> String {
+        match self {
+            Self::Strict => "strict (mismatched chunks are an error)".to_string(),
+            Self::ResampleToMax => "resample to the highest chunk rate".to_string(),
+            Self::ResampleTo(rate) => format!("resample to {} Hz", rate),
+        }
+    }
+}
+
+/// Concatenate WAV files, resampling mismatched rates as `mismatch` directs
+///
+/// Only the sample rate is reconciled; channel count and sample format must
+/// still agree.
+pub fn concatenate_with(wav_files: Vec<Vec<u8>>, mismatch: SampleRateMismatch) -> Result<Vec<u8>> {
+    let target_rate = match mismatch {
+        SampleRateMismatch::Strict => return concatenate(wav_files),
+        SampleRateMismatch::ResampleTo(rate) => rate,
+        SampleRateMismatch::ResampleToMax => {
+            let mut max_rate = 0;
+            for wav_data in &wav_files {
+                max_rate = max_rate.max(WavReader::new(Cursor::new(wav_data))?.spec().sample_rate);
+            }
+            max_rate
+        }
+    };
+
+    let resampled = wav_files
+        .iter()
+        .map(|wav_data| resample_wav(wav_data, target_rate))
+        .collect::<Result<Vec<_>>>()?;
+    concatenate(resampled)
+}
+
 /// Concatenate multiple WAV files into a single WAV file
 pub fn concatenate(wav_files: Vec<Vec<u8>>) -> Result<Vec<u8>> {
     if wav_files.is_empty() {
@@ -25,8 +101,9 @@ pub fn concatenate(wav_files: Vec<Vec<u8>>) -> Result<Vec<u8>> {
         SampleFormat::Int => {
             // Handle different bit depths for integers
             match spec.bits_per_sample {
+                8 => concatenate_typed::<i8>(wav_files, spec),
                 16 => concatenate_typed::<i16>(wav_files, spec),
-                32 => concatenate_typed::<i32>(wav_files, spec),
+                24 | 32 => concatenate_typed::<i32>(wav_files, spec),
                 _ => Err(TtsError::WavConcatenation(format!(
                     "Unsupported bits per sample: {}",
                     spec.bits_per_sample
@@ -77,3 +154,248 @@ where
 
     Ok(output.into_inner())
 }
+
+/// Append `duration` of silence to a WAV, keeping its format
+pub fn append_silence(wav_bytes: &[u8], duration: std::time::Duration) -> Result<Vec<u8>> {
+    let (spec, mut samples) = read_normalized_samples(wav_bytes)?;
+    let frames = (spec.sample_rate as f64 * duration.as_secs_f64()).round() as usize;
+    samples.resize(samples.len() + frames * spec.channels as usize, 0.0);
+    write_normalized_samples(&samples, spec)
+}
+
+/// Scale a WAV's volume by `gain_db` decibels, keeping its format
+///
+/// Integer samples that would overflow are clipped.
+pub fn apply_gain(wav_bytes: &[u8], gain_db: f32) -> Result<Vec<u8>> {
+    let (spec, mut samples) = read_normalized_samples(wav_bytes)?;
+    let factor = 10f32.powf(gain_db / 20.0);
+    for sample in &mut samples {
+        *sample *= factor;
+    }
+    write_normalized_samples(&samples, spec)
+}
+
+/// Validate a requested output bit depth against the supported set
+pub fn validate_bit_depth(bit_depth: Option<u16>) -> Result<()> {
+    match bit_depth {
+        Some(bits) if !SUPPORTED_BIT_DEPTHS.contains(&bits) => {
+            Err(TtsError::InvalidRequest(format!(
+                "Unsupported bit depth: {} (supported: {:?})",
+                bits, SUPPORTED_BIT_DEPTHS
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Re-quantize WAV audio to `bits` per sample, rewriting the header accordingly
+///
+/// Samples are scaled to the new range and rounded, so 8-bit output loses
+/// detail while 24-bit output is the same audio in a wider container. Audio
+/// already at the target depth is returned unchanged.
+pub fn convert_bit_depth(wav_bytes: &[u8], bits: u16) -> Result<Vec<u8>> {
+    let (spec, samples) = read_normalized_samples(wav_bytes)?;
+
+    if spec.sample_format == SampleFormat::Int && spec.bits_per_sample == bits {
+        return Ok(wav_bytes.to_vec());
+    }
+
+    write_normalized_samples(
+        &samples,
+        WavSpec {
+            bits_per_sample: bits,
+            sample_format: SampleFormat::Int,
+            ..spec
+        },
+    )
+}
+
+/// Decode WAV bytes into interleaved samples normalized to [-1.0, 1.0]
+pub fn read_normalized_samples(wav_bytes: &[u8]) -> Result<(WavSpec, Vec<f32>)> {
+    let reader = WavReader::new(Cursor::new(wav_bytes))?;
+    let spec = reader.spec();
+
+    let samples = match spec.sample_format {
+        SampleFormat::Float => reader
+            .into_samples::<f32>()
+            .collect::<std::result::Result<Vec<f32>, _>>()?,
+        SampleFormat::Int => {
+            let scale = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .into_samples::<i32>()
+                .map(|sample| sample.map(|s| s as f32 / scale))
+                .collect::<std::result::Result<Vec<f32>, _>>()?
+        }
+    };
+
+    Ok((spec, samples))
+}
+
+/// Encode normalized interleaved samples into WAV bytes using the given spec
+pub fn write_normalized_samples(samples: &[f32], spec: WavSpec) -> Result<Vec<u8>> {
+    let mut output = Cursor::new(Vec::new());
+    {
+        let mut writer = WavWriter::new(&mut output, spec)?;
+
+        match spec.sample_format {
+            SampleFormat::Float => {
+                for &sample in samples {
+                    writer.write_sample(sample)?;
+                }
+            }
+            SampleFormat::Int => {
+                let max = ((1i64 << (spec.bits_per_sample - 1)) - 1) as f32;
+                let min = -(1i64 << (spec.bits_per_sample - 1)) as f32;
+                for &sample in samples {
+                    let scaled = (sample * (max + 1.0)).round().clamp(min, max);
+                    writer.write_sample(scaled as i32)?;
+                }
+            }
+        }
+
+        writer.finalize()?;
+    }
+
+    Ok(output.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wav(sample_rate: u32, frames: usize) -> Vec<u8> {
+        let spec = WavSpec {
+            channels: 1,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+        write_normalized_samples(&vec![0.25; frames], spec).unwrap()
+    }
+
+    fn spec_and_frames(wav_bytes: &[u8]) -> (u32, u32) {
+        let reader = WavReader::new(Cursor::new(wav_bytes)).unwrap();
+        (reader.spec().sample_rate, reader.duration())
+    }
+
+    #[test]
+    fn test_append_silence() {
+        let padded =
+            append_silence(&wav(24000, 2400), std::time::Duration::from_millis(250)).unwrap();
+        assert_eq!(spec_and_frames(&padded), (24000, 2400 + 6000));
+
+        let (_, samples) = read_normalized_samples(&padded).unwrap();
+        assert_eq!(samples[2399], 0.25);
+        assert!(samples[2400..].iter().all(|&s| s == 0.0));
+    }
+
+    #[test]
+    fn test_apply_gain() {
+        let quieter = apply_gain(&wav(24000, 100), -6.0206).unwrap();
+        assert_eq!(spec_and_frames(&quieter), (24000, 100));
+        let (_, samples) = read_normalized_samples(&quieter).unwrap();
+        assert!((samples[0] - 0.125).abs() < 1e-4);
+
+        // Integer samples clip rather than wrap around
+        let louder = apply_gain(&wav(24000, 100), 20.0).unwrap();
+        let (_, samples) = read_normalized_samples(&louder).unwrap();
+        assert!(samples.iter().all(|&s| s > 0.99));
+    }
+
+    #[test]
+    fn test_convert_bit_depth() {
+        for bits in [8, 16, 24] {
+            let converted = convert_bit_depth(&wav(24000, 2400), bits).unwrap();
+            let reader = WavReader::new(Cursor::new(&converted)).unwrap();
+            assert_eq!(reader.spec().bits_per_sample, bits);
+            assert_eq!(reader.duration(), 2400);
+            assert_eq!(
+                crate::audio::duration::calculate(&converted).unwrap(),
+                100.0
+            );
+
+            let (_, samples) = read_normalized_samples(&converted).unwrap();
+            let step = 1.0 / (1i64 << (bits - 1)) as f32;
+            assert!((samples[0] - 0.25).abs() <= step, "{} bits", bits);
+        }
+
+        let original = wav(24000, 100);
+        assert_eq!(convert_bit_depth(&original, 16).unwrap(), original);
+    }
+
+    #[test]
+    fn test_concatenate_other_bit_depths() {
+        for bits in [8, 24] {
+            let chunk = convert_bit_depth(&wav(24000, 2400), bits).unwrap();
+            let joined = concatenate(vec![chunk.clone(), chunk]).unwrap();
+            let reader = WavReader::new(Cursor::new(&joined)).unwrap();
+            assert_eq!(reader.spec().bits_per_sample, bits);
+            assert_eq!(reader.duration(), 4800);
+        }
+    }
+
+    #[test]
+    fn test_validate_bit_depth() {
+        assert!(validate_bit_depth(None).is_ok());
+        assert!(validate_bit_depth(Some(24)).is_ok());
+        assert!(matches!(
+            validate_bit_depth(Some(12)),
+            Err(TtsError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_strict_rejects_mismatched_rates() {
+        let files = vec![wav(24000, 2400), wav(16000, 1600)];
+        assert!(matches!(
+            concatenate_with(files, SampleRateMismatch::Strict),
+            Err(TtsError::WavConcatenation(_))
+        ));
+    }
+
+    #[test]
+    fn test_resample_to_max_joins_mismatched_rates() {
+        // 100ms at 24 kHz followed by 100ms at 16 kHz
+        let files = vec![wav(24000, 2400), wav(16000, 1600)];
+        let joined = concatenate_with(files, SampleRateMismatch::ResampleToMax).unwrap();
+
+        assert_eq!(spec_and_frames(&joined), (24000, 4800));
+    }
+
+    #[test]
+    fn test_resample_to_explicit_rate() {
+        let files = vec![wav(24000, 2400), wav(16000, 1600)];
+        let joined = concatenate_with(files, SampleRateMismatch::ResampleTo(16000)).unwrap();
+
+        assert_eq!(spec_and_frames(&joined), (16000, 3200));
+    }
+
+    #[test]
+    fn test_uniform_rates_concatenate_in_every_mode() {
+        for mismatch in [
+            SampleRateMismatch::Strict,
+            SampleRateMismatch::ResampleToMax,
+        ] {
+            let joined = concatenate_with(vec![wav(24000, 100), wav(24000, 50)], mismatch).unwrap();
+            assert_eq!(spec_and_frames(&joined), (24000, 150));
+        }
+    }
+
+    #[test]
+    fn test_parse_mismatch_policy() {
+        assert_eq!(
+            SampleRateMismatch::parse("strict"),
+            Ok(SampleRateMismatch::Strict)
+        );
+        assert_eq!(
+            SampleRateMismatch::parse(" MAX "),
+            Ok(SampleRateMismatch::ResampleToMax)
+        );
+        assert_eq!(
+            SampleRateMismatch::parse("22050"),
+            Ok(SampleRateMismatch::ResampleTo(22050))
+        );
+        assert!(SampleRateMismatch::parse("12345").is_err());
+        assert!(SampleRateMismatch::parse("loose").is_err());
+    }
+}
@@ -0,0 +1,79 @@
+use crate::audio::wav_utils::read_normalized_samples;
+use crate::error::Result;
+
+/// Peak amplitude of each of `points` equal slices of the audio, in [0, 1]
+///
+/// Channels are combined by taking the loudest. Slices that hold no frames
+/// (audio shorter than `points` frames) are 0.0, so the envelope always has
+/// exactly `points` values.
+pub fn peak_envelope(wav_bytes: &[u8], points: usize) -> Result<Vec<f32>> {
+    let (spec, samples) = read_normalized_samples(wav_bytes)?;
+    let channels = spec.channels.max(1) as usize;
+    let frames = samples.len() / channels;
+
+    Ok((0..points)
+        .map(|i| {
+            let start = i * frames / points;
+            let end = (i + 1) * frames / points;
+            let peak = samples[start * channels..end * channels]
+                .iter()
+                .fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+            // Three decimals is finer than any waveform display needs
+            (peak.min(1.0) * 1000.0).round() / 1000.0
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::wav_utils::write_normalized_samples;
+    use hound::{SampleFormat, WavSpec};
+
+    fn wav(channels: u16, samples: &[f32]) -> Vec<u8> {
+        let spec = WavSpec {
+            channels,
+            sample_rate: 24000,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+        write_normalized_samples(samples, spec).unwrap()
+    }
+
+    #[test]
+    fn test_envelope_has_requested_length_and_range() {
+        let samples: Vec<f32> = (0..24000)
+            .map(|i| (i as f32 / 24000.0) * if i % 2 == 0 { 1.0 } else { -1.0 })
+            .collect();
+        let audio = wav(1, &samples);
+
+        for points in [1, 7, 100, 1000] {
+            let envelope = peak_envelope(&audio, points).unwrap();
+            assert_eq!(envelope.len(), points);
+            assert!(envelope.iter().all(|peak| (0.0..=1.0).contains(peak)));
+        }
+
+        // A rising ramp has rising peaks
+        let envelope = peak_envelope(&audio, 10).unwrap();
+        assert!(envelope.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(envelope[9] > 0.99);
+    }
+
+    #[test]
+    fn test_envelope_takes_loudest_channel() {
+        // Left channel quiet, right channel loud
+        let samples: Vec<f32> = (0..200).flat_map(|_| [0.1, -0.8]).collect();
+        let envelope = peak_envelope(&wav(2, &samples), 4).unwrap();
+        assert_eq!(envelope, vec![0.8; 4]);
+    }
+
+    #[test]
+    fn test_envelope_of_short_audio_pads_with_silence() {
+        let envelope = peak_envelope(&wav(1, &[0.5, 0.5]), 4).unwrap();
+        assert_eq!(envelope.len(), 4);
+        assert_eq!(envelope.iter().filter(|&&peak| peak == 0.5).count(), 2);
+        assert!(envelope.iter().all(|&peak| peak == 0.5 || peak == 0.0));
+
+        assert_eq!(peak_envelope(&wav(1, &[]), 3).unwrap(), vec![0.0; 3]);
+    }
+}
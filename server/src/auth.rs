@@ -6,7 +6,7 @@ use axum::{
     Json,
 };
 use serde::Serialize;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
@@ -16,6 +16,8 @@ use crate::utils::header_utils::extract_api_key;
 #[derive(Debug, Clone)]
 pub struct ApiKeys {
     keys: HashSet<String>,
+    /// Per-key overrides of `MAX_TEXT_LENGTH`, e.g. a lower cap for free keys
+    max_text_lengths: HashMap<String, usize>,
 }
 
 impl ApiKeys {
@@ -23,20 +25,36 @@ impl ApiKeys {
     pub fn empty() -> Self {
         Self {
             keys: HashSet::new(),
+            max_text_lengths: HashMap::new(),
         }
     }
 
     /// Create a new ApiKeys instance from a set of keys (for testing)
     #[allow(dead_code)]
     pub fn from_keys(keys: HashSet<String>) -> Self {
-        Self { keys }
+        Self {
+            keys,
+            max_text_lengths: HashMap::new(),
+        }
+    }
+
+    /// Give `key` its own maximum text length (for testing)
+    #[cfg(test)]
+    pub fn with_max_text_length(mut self, key: &str, max_text_length: usize) -> Self {
+        self.max_text_lengths
+            .insert(key.to_string(), max_text_length);
+        self
     }
 
     /// Load API keys from a file
+    ///
+    /// One key per line, optionally followed by `max_text_length=N` to give
+    /// that key its own text length limit. An invalid option is reported and
+    /// ignored rather than failing the file, which would turn auth off.
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, std::io::Error> {
         let file = File::open(path)?;
         let reader = BufReader::new(file);
-        let mut keys = HashSet::new();
+        let mut keys = Self::empty();
 
         for line in reader.lines() {
             let line = line?;
@@ -47,10 +65,26 @@ impl ApiKeys {
                 continue;
             }
 
-            keys.insert(trimmed.to_string());
+            let mut fields = trimmed.split_whitespace();
+            let Some(key) = fields.next() else {
+                continue;
+            };
+            for option in fields {
+                match parse_max_text_length(option) {
+                    Some(max_text_length) => {
+                        keys.max_text_lengths
+                            .insert(key.to_string(), max_text_length);
+                    }
+                    None => println!(
+                        "⚠ Warning: Ignoring invalid API key option {:?} (expected max_text_length=N)",
+                        option
+                    ),
+                }
+            }
+            keys.keys.insert(key.to_string());
         }
 
-        Ok(Self { keys })
+        Ok(keys)
     }
 
     /// Check if authentication is enabled (i.e., keys are configured)
@@ -67,6 +101,22 @@ impl ApiKeys {
     pub fn count(&self) -> usize {
         self.keys.len()
     }
+
+    /// The key's own maximum text length, if it has one
+    pub fn max_text_length(&self, key: &str) -> Option<usize> {
+        self.max_text_lengths.get(key).copied()
+    }
+
+    /// Number of keys with their own maximum text length
+    pub fn max_text_length_count(&self) -> usize {
+        self.max_text_lengths.len()
+    }
+}
+
+/// Parse a `max_text_length=N` key option (N > 0)
+fn parse_max_text_length(option: &str) -> Option<usize> {
+    let value = option.strip_prefix("max_text_length=")?;
+    value.parse().ok().filter(|&length| length > 0)
 }
 
 #[derive(Debug, Serialize)]
@@ -202,7 +252,7 @@ mod tests {
         key_set.insert("valid-key-1".to_string());
         key_set.insert("valid-key-2".to_string());
 
-        let keys = ApiKeys { keys: key_set };
+        let keys = ApiKeys::from_keys(key_set);
 
         assert!(keys.is_enabled());
         assert_eq!(keys.count(), 2);
@@ -233,6 +283,40 @@ mod tests {
         assert!(!keys.validate("# comment line"));
     }
 
+    #[test]
+    fn test_api_keys_from_file_with_max_text_length() {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, "free-key max_text_length=2000").unwrap();
+        writeln!(temp_file, "paid-key\tmax_text_length=50000").unwrap();
+        writeln!(temp_file, "plain-key").unwrap();
+        writeln!(temp_file, "typo-key max_text_len=10").unwrap();
+        temp_file.flush().unwrap();
+
+        let keys = ApiKeys::from_file(temp_file.path()).unwrap();
+
+        assert_eq!(keys.count(), 4);
+        assert!(keys.validate("free-key"));
+        assert!(!keys.validate("free-key max_text_length=2000"));
+        assert_eq!(keys.max_text_length("free-key"), Some(2000));
+        assert_eq!(keys.max_text_length("paid-key"), Some(50000));
+        assert_eq!(keys.max_text_length("plain-key"), None);
+        // Invalid options are ignored, but the key still works
+        assert!(keys.validate("typo-key"));
+        assert_eq!(keys.max_text_length("typo-key"), None);
+        assert_eq!(keys.max_text_length_count(), 2);
+    }
+
+    #[test]
+    fn test_parse_max_text_length() {
+        assert_eq!(parse_max_text_length("max_text_length=500"), Some(500));
+        assert_eq!(parse_max_text_length("max_text_length=0"), None);
+        assert_eq!(parse_max_text_length("max_text_length=lots"), None);
+        assert_eq!(parse_max_text_length("tier=free"), None);
+    }
+
     #[test]
     fn test_api_keys_from_file_not_found() {
         let result = ApiKeys::from_file("/nonexistent/path/to/file.txt");
@@ -316,7 +400,7 @@ mod tests {
         let mut key_set = HashSet::new();
         key_set.insert("CaseSensitiveKey".to_string());
 
-        let keys = ApiKeys { keys: key_set };
+        let keys = ApiKeys::from_keys(key_set);
 
         assert!(keys.validate("CaseSensitiveKey"));
         assert!(!keys.validate("casesensitivekey"));
@@ -328,7 +412,7 @@ mod tests {
         let mut key_set = HashSet::new();
         key_set.insert("key-1".to_string());
 
-        let keys = ApiKeys { keys: key_set };
+        let keys = ApiKeys::from_keys(key_set);
         let cloned = keys.clone();
 
         assert_eq!(cloned.count(), 1);
@@ -1,13 +1,21 @@
-use porua_server::kokoro::{voice_config::Voice, TTS};
+use porua_server::kokoro::{
+    sample_sentences::{generate_sample, SampleSentences},
+    voice_config::Voice,
+    TTS,
+};
 use std::path::Path;
 
-const SAMPLE_TEXT: &str = "Hello, I'm here to help you read any text on the web. Whether it's an article, a blog post, or a long document, I can read it aloud for you in a natural and clear voice. Just select the text you want to hear, and I'll take care of the rest.";
-
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let model_path = "models/kokoro-v1.0.onnx";
     let voices_path = "models/voices-v1.0.bin";
 
+    // Per-language (or per-voice) sentences, overridable from a JSON file
+    let sentences = match std::env::var("SAMPLE_SENTENCES_FILE") {
+        Ok(path) => SampleSentences::load(Path::new(&path))?,
+        Err(_) => SampleSentences::default(),
+    };
+
     println!("Initializing TTS engine...");
     let tts = TTS::new(model_path, voices_path).await?;
 
@@ -19,17 +27,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     std::fs::create_dir_all(samples_dir)?;
 
     println!("\nGenerating {} voice samples...", voices.len());
-    println!("Sample text: \"{}\"", SAMPLE_TEXT);
     println!();
 
     for voice in &voices {
         let voice_id = voice.id();
-        let output_path = samples_dir.join(format!("{}.wav", voice_id));
 
         print!("Generating: {:<20} ", voice_id);
 
-        match tts.speak(SAMPLE_TEXT, output_path.to_str().unwrap(), voice_id, 1.0) {
-            Ok(_) => {
+        match generate_sample(&tts, &sentences, *voice, samples_dir) {
+            Ok(output_path) => {
                 let size = std::fs::metadata(&output_path)?.len();
                 println!("✓ ({} KB)", size / 1024);
             }
@@ -1,5 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, TtsError};
 use crate::text_processing::sentence_splitting::split_sentences;
 
+/// How far past `max_chunk_size` the sentence strategy lets a single sentence
+/// run before it is split after all
+const SENTENCE_OVERFLOW_FACTOR: usize = 2;
+
+/// Where chunk boundaries may fall
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChunkStrategy {
+    /// Pack sentences up to the size limit, splitting long sentences at clauses
+    #[default]
+    Size,
+    /// Only end chunks between sentences; a long sentence stays whole unless
+    /// it is far over the limit
+    Sentence,
+}
+
 /// Configuration for text chunking
 #[derive(Debug, Clone)]
 pub struct ChunkingConfig {
@@ -8,6 +27,7 @@ pub struct ChunkingConfig {
     /// Minimum characters per chunk (to avoid too many tiny chunks)
     #[allow(dead_code)]
     pub min_chunk_size: usize,
+    pub strategy: ChunkStrategy,
 }
 
 impl Default for ChunkingConfig {
@@ -15,10 +35,97 @@ impl Default for ChunkingConfig {
         Self {
             max_chunk_size: 200, // Lowered for faster streaming - split at ~1-2 sentences
             min_chunk_size: 50,  // Allow smaller chunks for better streaming
+            strategy: ChunkStrategy::Size,
+        }
+    }
+}
+
+/// What to do with a token (run of non-whitespace) longer than the limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LongTokenPolicy {
+    /// Break the token into pieces of at most the limit
+    #[default]
+    Split,
+    /// Reject the request
+    Reject,
+}
+
+impl LongTokenPolicy {
+    /// Parse a `TTS_LONG_TOKEN_POLICY` value; anything unrecognized means `split`
+    pub fn parse(value: &str) -> Self {
+        match value.to_lowercase().as_str() {
+            "reject" => Self::Reject,
+            _ => Self::Split,
+        }
+    }
+}
+
+/// Guard against pathological tokens, like a 500-character string with no
+/// spaces, which the engine either fails on or reads as gibberish
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct LongTokenConfig {
+    /// Longest token allowed, in characters (0 = no limit)
+    pub max_length: usize,
+    pub policy: LongTokenPolicy,
+}
+
+impl Default for LongTokenConfig {
+    fn default() -> Self {
+        Self {
+            max_length: 100,
+            policy: LongTokenPolicy::Split,
         }
     }
 }
 
+impl LongTokenConfig {
+    /// Apply the policy to `text`, returning the text to synthesize
+    pub fn apply(&self, text: &str) -> Result<String> {
+        let longest = longest_token(text);
+        if self.max_length == 0 || longest <= self.max_length {
+            return Ok(text.to_string());
+        }
+
+        match self.policy {
+            LongTokenPolicy::Split => Ok(split_long_tokens(text, self.max_length)),
+            LongTokenPolicy::Reject => Err(TtsError::InvalidRequest(format!(
+                "Text contains a {}-character word without spaces (max {})",
+                longest, self.max_length
+            ))),
+        }
+    }
+}
+
+/// Length in characters of the longest run of non-whitespace in `text`
+pub fn longest_token(text: &str) -> usize {
+    text.split_whitespace()
+        .map(|token| token.chars().count())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Insert spaces so no run of non-whitespace exceeds `max_length` characters
+pub fn split_long_tokens(text: &str, max_length: usize) -> String {
+    let mut result = String::with_capacity(text.len() + text.len() / max_length.max(1));
+    let mut run = 0;
+
+    for ch in text.chars() {
+        if ch.is_whitespace() {
+            run = 0;
+        } else {
+            if run == max_length {
+                result.push(' ');
+                run = 0;
+            }
+            run += 1;
+        }
+        result.push(ch);
+    }
+
+    result
+}
+
 /// Splits text into chunks at sentence boundaries while respecting size limits
 pub fn chunk_text(text: &str, config: &ChunkingConfig) -> Vec<String> {
     // If text is short enough, return as-is
@@ -36,7 +143,7 @@ pub fn chunk_text(text: &str, config: &ChunkingConfig) -> Vec<String> {
         let sentence_len = sentence.len();
 
         // If a single sentence is too long, split it further
-        if sentence_len > config.max_chunk_size {
+        if sentence_len > longest_sentence(config) {
             // Flush current chunk if it has content
             if !current_chunk.is_empty() {
                 chunks.push(current_chunk.trim().to_string());
@@ -76,6 +183,14 @@ pub fn chunk_text(text: &str, config: &ChunkingConfig) -> Vec<String> {
     chunks
 }
 
+/// Longest sentence `chunk_text` keeps in one chunk
+fn longest_sentence(config: &ChunkingConfig) -> usize {
+    match config.strategy {
+        ChunkStrategy::Size => config.max_chunk_size,
+        ChunkStrategy::Sentence => config.max_chunk_size * SENTENCE_OVERFLOW_FACTOR,
+    }
+}
+
 /// Splits a long sentence into smaller chunks at clause boundaries
 fn split_long_sentence(sentence: &str, max_size: usize) -> Vec<String> {
     let mut chunks = Vec::new();
@@ -161,6 +276,7 @@ mod tests {
         let config = ChunkingConfig {
             max_chunk_size: 50,
             min_chunk_size: 10,
+            ..Default::default()
         };
         let text = "This is sentence one. This is sentence two. This is sentence three.";
         let chunks = chunk_text(text, &config);
@@ -176,6 +292,7 @@ mod tests {
         let config = ChunkingConfig {
             max_chunk_size: 100,
             min_chunk_size: 20,
+            ..Default::default()
         };
         let text = "This is a very long sentence that goes on and on, with many clauses separated by commas, and it should be split into multiple chunks even though it's technically one sentence.";
         let chunks = chunk_text(text, &config);
@@ -210,6 +327,7 @@ mod tests {
         let config = ChunkingConfig {
             max_chunk_size: 20,
             min_chunk_size: 5,
+            ..Default::default()
         };
         let text = "A".repeat(20);
         let chunks = chunk_text(&text, &config);
@@ -221,6 +339,7 @@ mod tests {
         let config = ChunkingConfig {
             max_chunk_size: 20,
             min_chunk_size: 5,
+            ..Default::default()
         };
         let text = "Short one. This is a bit longer.";
         let chunks = chunk_text(&text, &config);
@@ -232,6 +351,7 @@ mod tests {
         let config = ChunkingConfig {
             max_chunk_size: 15,
             min_chunk_size: 5,
+            ..Default::default()
         };
         let text = "First sentence. Second sentence. Third sentence.";
         let chunks = chunk_text(&text, &config);
@@ -247,6 +367,7 @@ mod tests {
         let config = ChunkingConfig {
             max_chunk_size: 50,
             min_chunk_size: 10,
+            ..Default::default()
         };
         let text = "This is a long sentence with many clauses, separated by commas, which should be split appropriately.";
         let chunks = chunk_text(&text, &config);
@@ -258,6 +379,7 @@ mod tests {
         let config = ChunkingConfig {
             max_chunk_size: 30,
             min_chunk_size: 10,
+            ..Default::default()
         };
         // Very long single sentence with no punctuation
         let text = "word ".repeat(20).trim().to_string();
@@ -324,6 +446,7 @@ mod tests {
         let config = ChunkingConfig {
             max_chunk_size: 40,
             min_chunk_size: 10,
+            ..Default::default()
         };
         let text = "First clause; second clause; third clause; fourth clause.";
         let chunks = chunk_text(&text, &config);
@@ -335,6 +458,7 @@ mod tests {
         let config = ChunkingConfig {
             max_chunk_size: 20,
             min_chunk_size: 5,
+            ..Default::default()
         };
         let long_word = "a".repeat(50);
         let text = format!("Short. {} More text.", long_word);
@@ -343,6 +467,59 @@ mod tests {
         assert!(chunks.len() >= 1);
     }
 
+    #[test]
+    fn test_long_token_split() {
+        let config = LongTokenConfig::default();
+        let text = "x".repeat(1000);
+
+        let split = config.apply(&text).unwrap();
+        assert_eq!(longest_token(&split), 100);
+        assert_eq!(split.split(' ').count(), 10);
+        assert_eq!(split.replace(' ', ""), text);
+    }
+
+    #[test]
+    fn test_long_token_reject() {
+        let config = LongTokenConfig {
+            max_length: 100,
+            policy: LongTokenPolicy::Reject,
+        };
+        let text = format!("Read this: {}", "x".repeat(1000));
+
+        match config.apply(&text) {
+            Err(TtsError::InvalidRequest(msg)) => {
+                assert!(msg.contains("1000-character"), "{}", msg);
+            }
+            other => panic!("Expected InvalidRequest, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_long_token_limit_leaves_normal_text_alone() {
+        let config = LongTokenConfig {
+            max_length: 10,
+            policy: LongTokenPolicy::Reject,
+        };
+        let text = "Plain words only.\nNothing long here";
+        assert_eq!(config.apply(text).unwrap(), text);
+
+        // Multi-byte characters count once and are never cut in half
+        assert_eq!(split_long_tokens("ééééé", 2), "éé éé é");
+
+        let unlimited = LongTokenConfig {
+            max_length: 0,
+            policy: LongTokenPolicy::Reject,
+        };
+        assert!(unlimited.apply(&"x".repeat(5000)).is_ok());
+    }
+
+    #[test]
+    fn test_long_token_policy_parse() {
+        assert_eq!(LongTokenPolicy::parse("REJECT"), LongTokenPolicy::Reject);
+        assert_eq!(LongTokenPolicy::parse("split"), LongTokenPolicy::Split);
+        assert_eq!(LongTokenPolicy::parse("other"), LongTokenPolicy::Split);
+    }
+
     #[test]
     fn test_config_clone() {
         let config = ChunkingConfig::default();
@@ -357,4 +534,58 @@ mod tests {
         let debug_str = format!("{:?}", config);
         assert!(debug_str.contains("ChunkingConfig"));
     }
+
+    /// Sentences of each chunk, in order, flattened
+    fn chunk_sentences(chunks: &[String]) -> Vec<String> {
+        chunks
+            .iter()
+            .flat_map(|chunk| split_sentences(chunk))
+            .collect()
+    }
+
+    #[test]
+    fn test_sentence_strategy_never_splits_a_sentence() {
+        let text = "Dr. Smith arrived at 3.15 in the afternoon. The meeting, which had been \
+            postponed twice, finally started with everyone present and listening. It was short. \
+            Nobody asked questions! Would they meet again? Probably, but not this month.";
+        let sentence = ChunkingConfig {
+            max_chunk_size: 60,
+            strategy: ChunkStrategy::Sentence,
+            ..Default::default()
+        };
+
+        let chunks = chunk_text(text, &sentence);
+        assert!(chunks.len() > 1);
+        assert_eq!(chunk_sentences(&chunks), split_sentences(text));
+        for chunk in &chunks {
+            assert!(chunk.len() <= 60 || split_sentences(chunk).len() == 1);
+        }
+
+        // The size strategy cuts the long middle sentence at its commas
+        let size = ChunkingConfig {
+            max_chunk_size: 60,
+            ..Default::default()
+        };
+        assert_ne!(
+            chunk_sentences(&chunk_text(text, &size)),
+            split_sentences(text)
+        );
+    }
+
+    #[test]
+    fn test_sentence_strategy_splits_runaway_sentence() {
+        let config = ChunkingConfig {
+            max_chunk_size: 40,
+            strategy: ChunkStrategy::Sentence,
+            ..Default::default()
+        };
+        let text = "Short start. This sentence keeps going, and going, and going, well past \
+            any reasonable length, with no end in sight at all. Short end.";
+
+        let chunks = chunk_text(text, &config);
+        assert_eq!(chunks[0], "Short start.");
+        assert_eq!(chunks.last().unwrap(), "Short end.");
+        assert!(chunks.len() > 3);
+        assert!(chunks.iter().all(|chunk| chunk.len() <= 40));
+    }
 }
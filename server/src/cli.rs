@@ -11,6 +11,10 @@ pub fn print_help() {
     println!("OPTIONS:");
     println!("    --server              Start HTTP server mode");
     println!("    --port <PORT>         Server port (default: 3000)");
+    println!(
+        "    --uds <PATH>          Listen on a Unix socket or Windows named pipe instead of TCP"
+    );
+    println!("    --check               Validate models, voices and normalization, then exit");
     println!("    -h, --help            Print this help message");
     println!("    -v, --version         Print version information");
     println!();
@@ -21,27 +25,184 @@ pub fn print_help() {
     println!("    # Start server on custom port");
     println!("    porua_server --server --port 8080");
     println!();
+    println!("    # Start server on a Unix socket (no TCP port)");
+    println!("    porua_server --server --uds /tmp/porua.sock");
+    println!();
+    println!("    # Deploy health check: exits 0 if everything loads, 1 otherwise");
+    println!("    porua_server --check");
+    println!();
     println!("    # CLI mode: Generate speech from text");
     println!("    porua_server \"Hello, world!\"");
     println!();
     println!("    # CLI mode saves to output.wav and output.json by default");
     println!();
+    println!("    # CLI mode: Read long text from stdin, one output_NNN.wav per chunk");
+    println!("    cat book.txt | porua_server -");
+    println!();
+    println!("    # Same, joined into a single output.wav");
+    println!("    cat book.txt | porua_server - --concat");
+    println!();
     println!("SERVER ENDPOINTS:");
     println!("    POST   /tts          - Generate speech from text");
     println!("    POST   /tts/stream   - Stream speech with chunked response");
+    println!("    POST   /tts/file     - Stream speech for an uploaded text file");
+    println!("    POST   /tts/archive  - Download each chunk as a WAV in a zip");
+    println!("    POST   /tts/estimate - Estimate audio duration and compute time");
+    println!("    POST   /tts/phonemes - Show the phonemes text is read as");
+    println!("    POST   /tts/jobs     - Queue a background synthesis job");
+    println!("    GET    /tts/jobs/{{id}} - Job status; /audio fetches the finished WAV");
+    println!("    DELETE /tts/jobs/{{id}} - Cancel a queued or running job");
     println!("    GET    /voices       - List available voices");
+    println!("    POST   /voices/mix/preview - Preview a voice mix");
     println!("    GET    /health       - Health check");
+    println!("    GET    /health/ready - Readiness check (engines, eSpeak-ng data)");
     println!("    GET    /stats        - Pool statistics");
+    println!("    POST   /admin/reload - Reload TTS engines (requires API keys)");
+    println!("    GET    /admin/config - Effective configuration (requires API keys)");
     println!();
     println!("ENVIRONMENT VARIABLES:");
+    println!("    PORUA_CONFIG                     - JSON config file (default: ./porua.json if present)");
+    println!("                                       Most variables below also have a file key, e.g. fair_queue");
     println!("    TTS_MODEL_DIR                    - Directory containing TTS models");
+    println!("    TTS_WARMUP                       - Warm up each engine before serving (default: false)");
+    println!("    TTS_STARTUP_SELFTEST             - Check a test phrase synthesizes before serving (default: false)");
+    println!("    TTS_MODEL_WAIT_SECONDS           - Wait this long for missing model files (default: 0)");
     println!("    TTS_POOL_SIZE                    - Number of TTS engines (default: 2)");
+    println!("    TTS_MAX_POOL_SIZE                - Larger pool sizes are clamped to this (default: 16)");
+    println!("    TTS_UNIX_SOCKET                  - Listen on this Unix socket or named pipe instead of TCP");
     println!("    PIPER_ESPEAKNG_DATA_DIRECTORY    - Path to espeak-ng-data parent directory");
     println!("    TTS_API_KEY_FILE                 - Path to API keys file");
     println!(
         "    RATE_LIMIT_MODE                  - Rate limit mode (auto/per-key/per-ip/disabled)"
     );
+    println!(
+        "    TRUSTED_PROXIES                  - CIDRs allowed to set X-Forwarded-For (default: loopback)"
+    );
     println!("    REQUEST_TIMEOUT_SECONDS          - Request timeout in seconds (default: 60)");
+    println!("    MAX_REQUEST_TIMEOUT_SECONDS      - Longest timeout X-Request-Timeout may ask for (default: 300)");
+    println!(
+        "    CIRCUIT_BREAKER_FAILURES         - Engine failures before fast-failing with 503 (default: 5, 0 = off)"
+    );
+    println!(
+        "    MAX_IN_FLIGHT_REQUESTS           - Max concurrent TTS requests before 503 (0 = off)"
+    );
+    println!(
+        "    TTS_FAIR_QUEUE                   - Rotate waiting requests between clients (default: false)"
+    );
+    println!(
+        "    TTS_ENGINE_SELECTION             - free-first or round-robin engine picking (default: free-first)"
+    );
+    println!(
+        "    MAX_CONNECTIONS                  - Max open client connections before 503 (default: 512, 0 = off)"
+    );
+    println!("    TTS_VOICE_ALIASES                - Voice aliases, e.g. narrator=bm_lewis,assistant=af_sarah");
+    println!("    TTS_FALLBACK_VOICE               - Voice /tts retries with if the requested one is unavailable");
+    println!("    TTS_VOICE_RATES                  - Per-voice ms of audio per character, e.g. bm_george=80,af_sky=55");
+    println!("    TTS_VOICE_SPEEDS                 - Per-voice default speed, e.g. am_santa=0.9");
+    println!("    TTS_VOICE_PITCHES                - Per-voice pitch factor (0.5-2.0), e.g. am_santa=0.95");
+    println!("    CORS_ALLOWED_ORIGINS             - Comma-separated allowed origins (default: *)");
+    println!(
+        "    RESPONSE_HEADERS                 - Headers added to every response, as a JSON object"
+    );
+    println!("    MAX_AUDIO_DURATION_MS            - Max generated audio length in ms");
+    println!(
+        "    MAX_NORMALIZED_LENGTH            - Max text length after normalization (default: 40000)"
+    );
+    println!(
+        "    TTS_CHUNK_RETRIES                - Retries for a failed streamed chunk (default: 2)"
+    );
+    println!("    TTS_JOB_TTL_SECONDS              - Keep finished /tts/jobs results this long (default: 600)");
+    println!("    TTS_MAX_PENDING_JOBS             - Most /tts/jobs queued or running at once (default: 100)");
+    println!("    TTS_CHUNK_FAILURE_MODE           - After the last retry: skip or abort (default: skip)");
+    println!("    TTS_STREAM_KEEP_ALIVE_MS         - Send an empty part after this long without output (default: 15000, 0 = off)");
+    println!(
+        "    TTS_MAX_TOKEN_LENGTH             - Longest word without spaces, in chars (default: 100, 0 = off)"
+    );
+    println!(
+        "    TTS_LONG_TOKEN_POLICY            - Words over the limit: split or reject (default: split)"
+    );
+    println!(
+        "    TTS_SPEED_CLAMP                  - Clamp out-of-range speeds instead of rejecting"
+    );
+    println!(
+        "    TTS_MIN_SPEED                    - Slowest accepted speed, 0.1-3.0 (default: 0.25)"
+    );
+    println!("    CHUNKING_MIN_LENGTH              - Chunk /tts text longer than this many bytes (default: 200)");
+    println!("    TTS_CONCAT_SAMPLE_RATE           - Chunks at mixed rates: strict/max/<Hz> (default: strict)");
+    println!("    TTS_WAV_METADATA                 - Embed voice/speed/text INFO metadata in WAVs");
+    println!(
+        "    TTS_VALIDATION_HEADERS           - Report /tts timing validation warnings in a header"
+    );
+    println!(
+        "    TTS_DEFAULT_AUDIO_FORMAT         - Format when Accept picks none; only wav for now"
+    );
+    println!("    TTS_TEMP_DIR                     - Directory for temporary audio files");
+    println!("    TTS_CACHE_DIR                    - Enable on-disk audio cache in this directory");
+    println!(
+        "    TTS_CACHE_MAX_BYTES              - Audio cache size cap in bytes (default: 1 GiB)"
+    );
+    println!("    TTS_FRACTION_STYLE               - How fractions are read (named/over)");
+    println!("    TTS_SYMBOL_STYLE                 - Symbol expansion (conservative/full/off)");
+    println!("    TTS_EMOJI_STYLE                  - Emoji handling (strip/words/keep)");
+    println!(
+        "    TTS_UNITS                        - Expand unit abbreviations like 5kg (default: false)"
+    );
+    println!(
+        "    TTS_ROMAN_NUMERALS               - Read roman numerals in headings/names (default: false)"
+    );
+    println!(
+        "    TTS_TIME_ZONES                   - Expand zones after times like 3 PM EST (default: false)"
+    );
+    println!(
+        "    TTS_ALL_CAPS                     - Lowercase all-caps words, spell acronyms (default: false)"
+    );
+    println!(
+        "    TTS_DEHYPHENATE                  - Rejoin words hyphenated across line breaks (default: false)"
+    );
+    println!(
+        "    TTS_DECADES                      - Read decades like 1990s as words (default: false)"
+    );
+    println!(
+        "    TTS_MATH                         - Read math operators like = and < as words (default: false)"
+    );
+    println!(
+        "    TTS_ALPHANUMERICS                - Read COVID-19, MP3, G7 as words (default: false)"
+    );
+    println!("    TTS_VERSIONS                     - Read v2 as \"version two\" (default: false)");
+    println!("    TTS_FOLD_FORMS                   - Spell out ligatures, fold fullwidth text to ASCII (default: false)");
+    println!(
+        "    TTS_ELLIPSIS_PAUSE_MS            - Silence after an ellipsis in ms (default: 0 = off)"
+    );
+    println!(
+        "    TTS_LISTS                        - Pause between list items and speak item numbers (true/false)"
+    );
+    println!(
+        "    TTS_LIST_PAUSE_MS                - Silence after each list item in ms (default: 400)"
+    );
+    println!(
+        "    TTS_LIST_NUMBERS                 - Speak numbered items as \"One, ...\" (default: true)"
+    );
+    println!(
+        "    TTS_ASIDES                       - Speak parenthetical asides faster and quieter (true/false)"
+    );
+    println!("    TTS_ASIDE_SPEED_DELTA            - Speed added for asides (default: 0.1)");
+    println!("    TTS_ASIDE_GAIN_DB                - Aside volume change in dB (default: -3)");
+    println!(
+        "    TTS_SAY_PUNCTUATION              - Read punctuation aloud by name (default: false)"
+    );
+    println!(
+        "    TTS_PUNCTUATION_NAMES            - Punctuation name overrides (.=full stop;!=bang)"
+    );
+    println!(
+        "    TTS_QUOTE_STYLE                  - Quotation marks: ignore, prosody or spoken (default: ignore)"
+    );
+    println!("    TTS_QUOTE_SPEED_DELTA            - Speed added for quotations (default: 0)");
+    println!(
+        "    TTS_QUOTE_PITCH                  - Pitch multiplier for quotations (default: 1.05)"
+    );
+    println!(
+        "    TTS_QUOTE_PAUSE_MS               - Silence around quotations in ms (default: 150)"
+    );
     println!("    RUST_LOG                         - Log level (error/warn/info/debug/trace)");
     println!();
     println!("CONFIGURATION:");
@@ -0,0 +1,213 @@
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    extract::{Request, State},
+    http::HeaderValue,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::error::TtsError;
+
+/// Global ceiling on in-flight TTS requests
+///
+/// The TTS pool already bounds how many generations run at once, but requests
+/// waiting in `TTSPool::acquire` queue without limit. This limiter rejects work
+/// up front once the ceiling is reached, so excess clients get a fast 503
+/// instead of holding a connection open in the queue.
+#[derive(Clone)]
+pub struct ConcurrencyLimiter {
+    semaphore: Arc<Semaphore>,
+    max_in_flight: usize,
+}
+
+impl ConcurrencyLimiter {
+    /// Create a limiter that admits at most `max_in_flight` concurrent requests
+    pub fn new(max_in_flight: usize) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(max_in_flight)),
+            max_in_flight,
+        }
+    }
+
+    /// Try to admit a request, returning a permit that frees the slot when dropped
+    pub fn try_acquire(&self) -> Option<OwnedSemaphorePermit> {
+        self.semaphore.clone().try_acquire_owned().ok()
+    }
+
+    /// Configured ceiling
+    pub fn max_in_flight(&self) -> usize {
+        self.max_in_flight
+    }
+
+    /// Number of requests currently in flight
+    pub fn in_flight(&self) -> usize {
+        self.max_in_flight - self.semaphore.available_permits()
+    }
+}
+
+/// Middleware to enforce the in-flight request ceiling
+///
+/// The permit is held until the response body has been fully sent, so
+/// streaming responses count against the ceiling for their whole lifetime.
+pub async fn concurrency_limit_middleware(
+    State(limiter): State<ConcurrencyLimiter>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(permit) = limiter.try_acquire() else {
+        tracing::warn!(
+            "Rejecting request: {} requests already in flight (max {})",
+            limiter.in_flight(),
+            limiter.max_in_flight()
+        );
+
+        let mut response = TtsError::ServerBusy.into_response();
+        response
+            .headers_mut()
+            .insert("Retry-After", HeaderValue::from_static("1"));
+        return response;
+    };
+
+    let (parts, body) = next.run(request).await.into_parts();
+    Response::from_parts(
+        parts,
+        Body::new(PermitBody {
+            inner: body,
+            _permit: permit,
+        }),
+    )
+}
+
+/// Response body that keeps a concurrency permit alive until it is dropped
+struct PermitBody {
+    inner: Body,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl HttpBody for PermitBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<http_body::Frame<Self::Data>, Self::Error>>> {
+        Pin::new(&mut self.inner).poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::StatusCode, middleware, routing::get, Router};
+    use std::time::{Duration, Instant};
+    use tower::ServiceExt;
+
+    fn slow_app(limiter: ConcurrencyLimiter) -> Router {
+        Router::new()
+            .route(
+                "/slow",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_millis(500)).await;
+                    "done"
+                }),
+            )
+            .layer(middleware::from_fn_with_state(
+                limiter,
+                concurrency_limit_middleware,
+            ))
+    }
+
+    async fn send(app: Router) -> (StatusCode, Duration) {
+        let start = Instant::now();
+        let request = Request::builder().uri("/slow").body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        // Drain the body so the permit is held for the full response
+        let _ = axum::body::to_bytes(response.into_body(), usize::MAX).await;
+        (status, start.elapsed())
+    }
+
+    #[test]
+    fn test_limiter_tracks_in_flight() {
+        let limiter = ConcurrencyLimiter::new(2);
+        assert_eq!(limiter.in_flight(), 0);
+
+        let first = limiter.try_acquire();
+        let second = limiter.try_acquire();
+        assert!(first.is_some() && second.is_some());
+        assert_eq!(limiter.in_flight(), 2);
+        assert!(limiter.try_acquire().is_none());
+
+        drop(first);
+        assert_eq!(limiter.in_flight(), 1);
+        assert!(limiter.try_acquire().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_flood_beyond_ceiling_returns_503_quickly() {
+        let limiter = ConcurrencyLimiter::new(2);
+        let app = slow_app(limiter.clone());
+
+        let handles: Vec<_> = (0..8).map(|_| tokio::spawn(send(app.clone()))).collect();
+
+        let mut ok = 0;
+        let mut busy = 0;
+        for handle in handles {
+            let (status, elapsed) = handle.await.unwrap();
+            match status {
+                StatusCode::OK => ok += 1,
+                StatusCode::SERVICE_UNAVAILABLE => {
+                    busy += 1;
+                    assert!(
+                        elapsed < Duration::from_millis(250),
+                        "Rejected request should not queue (took {:?})",
+                        elapsed
+                    );
+                }
+                other => panic!("Unexpected status: {}", other),
+            }
+        }
+
+        assert_eq!(ok, 2);
+        assert_eq!(busy, 6);
+        assert_eq!(limiter.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_busy_response_has_retry_after() {
+        let limiter = ConcurrencyLimiter::new(1);
+        let _held = limiter.try_acquire().unwrap();
+
+        let request = Request::builder().uri("/slow").body(Body::empty()).unwrap();
+        let response = slow_app(limiter).oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers().get("Retry-After").unwrap(), "1");
+    }
+
+    #[tokio::test]
+    async fn test_permit_released_after_response() {
+        let limiter = ConcurrencyLimiter::new(1);
+        let app = slow_app(limiter.clone());
+
+        let (first, _) = send(app.clone()).await;
+        let (second, _) = send(app).await;
+
+        assert_eq!(first, StatusCode::OK);
+        assert_eq!(second, StatusCode::OK);
+        assert_eq!(limiter.in_flight(), 0);
+    }
+}
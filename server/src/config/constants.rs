@@ -1,14 +1,183 @@
+use crate::utils::accept::AudioFormat;
+
 /// Maximum allowed text length for TTS requests (in characters)
 ///
 /// This limit helps prevent abuse and ensures reasonable response times.
 /// Requests exceeding this limit will be rejected with an error.
 pub const MAX_TEXT_LENGTH: usize = 10_000;
 
+/// Largest `/tts/file` request body: a full-length text file plus room for
+/// the form's other fields and part headers
+pub const MAX_UPLOAD_BODY_BYTES: usize = MAX_TEXT_LENGTH + 16 * 1024;
+
+/// Default ceiling on the number of TTS engines
+///
+/// Every engine loads its own copy of the model (several hundred MB), so a
+/// mistyped `TTS_POOL_SIZE=10000` would exhaust memory at startup instead of
+/// failing cleanly. Configurable with `TTS_MAX_POOL_SIZE`.
+pub const DEFAULT_MAX_POOL_SIZE: usize = 16;
+
+/// Default text length (in bytes) above which `/tts` splits text into chunks
+///
+/// Shorter requests are synthesized in one piece even with `enable_chunking`.
+/// Configurable with `CHUNKING_MIN_LENGTH`.
+pub const DEFAULT_CHUNKING_MIN_LENGTH: usize = 200;
+
+/// Fastest accepted speaking speed
+pub const MAX_SPEED: f32 = 3.0;
+
+/// Lowest value the minimum speed can be configured to
+pub const MIN_SPEED: f32 = 0.1;
+
+/// Default slowest accepted speaking speed
+///
+/// Slower speeds stretch the audio enormously (0.01 would make a sentence
+/// minutes long). Configurable with `TTS_MIN_SPEED`, which is also the floor
+/// for speeds derived from the request's, such as asides.
+pub const DEFAULT_MIN_SPEED: f32 = 0.25;
+
+/// Lowest accepted pitch factor (one octave down)
+pub const MIN_PITCH: f32 = 0.5;
+
+/// Highest accepted pitch factor (one octave up)
+pub const MAX_PITCH: f32 = 2.0;
+
+/// Words per metadata phrase unless the request asks otherwise
+pub const DEFAULT_MAX_PHRASE_WORDS: usize = 8;
+
+/// Accepted range for a request's `max_phrase_words`
+pub const MIN_PHRASE_WORDS: usize = 1;
+pub const MAX_PHRASE_WORDS: usize = 50;
+
+/// Largest `min_phrase_ms` a request may ask for
+pub const MAX_MIN_PHRASE_MS: u32 = 2000;
+
+/// Largest `waveform_points` a request may ask for per metadata block
+pub const MAX_WAVEFORM_POINTS: usize = 4096;
+
 /// Boundary string used for multipart responses in streaming mode
 ///
 /// This separator is used to delineate chunks in the streaming response.
 pub const MULTIPART_BOUNDARY: &str = "tts_chunk_boundary";
 
+/// Request header used to select the streaming metadata schema version
+pub const METADATA_VERSION_HEADER: &str = "accept-metadata-version";
+
+/// Response header carrying the number of metadata validation warnings
+///
+/// Only set on `/tts` responses when `TTS_VALIDATION_HEADERS` is enabled.
+pub const VALIDATION_WARNINGS_HEADER: &str = "x-tts-validation-warnings";
+
+/// Response header naming the voice used when `/tts` fell back from the
+/// requested one
+pub const VOICE_FALLBACK_HEADER: &str = "x-tts-voice-fallback";
+
+/// Request header asking for a longer (or shorter) timeout than the default,
+/// in seconds; capped at the configured maximum
+pub const REQUEST_TIMEOUT_HEADER: &str = "x-request-timeout";
+
+/// Default ceiling on timeouts requested with `X-Request-Timeout` (in seconds)
+///
+/// Long enough for a full audiobook chapter. Configurable with
+/// `MAX_REQUEST_TIMEOUT_SECONDS`; never below `REQUEST_TIMEOUT_SECONDS`.
+pub const DEFAULT_MAX_REQUEST_TIMEOUT_SECS: u64 = 300;
+
+/// Metadata schema versions clients may request
+///
+/// - `1.0`: chunk timing and phrases only (text, start, duration)
+/// - `2.0` (default): adds the `version` field, original (pre-normalization)
+///   text, phrase character offsets, `validation` and `debug_info`
+pub const SUPPORTED_METADATA_VERSIONS: &[&str] = &["1.0", "2.0"];
+
+/// Output sample rates (in Hz) that clients may request
+///
+/// Kokoro generates audio at 24kHz natively; any other rate in this list is
+/// produced by resampling the generated PCM before responding.
+pub const SUPPORTED_SAMPLE_RATES: &[u32] = &[8000, 16000, 22050, 24000, 44100, 48000];
+
+/// Output bit depths (bits per sample) that clients may request
+///
+/// Kokoro generates 16-bit PCM; 8 and 24 bits are produced by re-quantizing it.
+pub const SUPPORTED_BIT_DEPTHS: &[u16] = &[8, 16, 24];
+
+/// Audio formats the server can encode
+///
+/// Clients may ask for MP3 or Ogg, but until an encoder for them is built in
+/// those requests are answered with 406 (or 400 for the `format` field).
+pub const SUPPORTED_AUDIO_FORMATS: &[AudioFormat] = &[AudioFormat::Wav];
+
+/// Default for `TTS_DEFAULT_AUDIO_FORMAT`
+pub const DEFAULT_AUDIO_FORMAT: AudioFormat = AudioFormat::Wav;
+
+/// Default upper bound on the length of normalized text (in bytes)
+///
+/// Normalization spells out numbers, currency and symbols, so text under
+/// `MAX_TEXT_LENGTH` can grow several times over ("$1 $1 $1 ..." reads as
+/// "one dollar one dollar ..."). This caps what reaches chunking and the engine.
+pub const DEFAULT_MAX_NORMALIZED_LENGTH: usize = MAX_TEXT_LENGTH * 4;
+
+/// Default upper bound on the duration of generated audio (in milliseconds)
+///
+/// Guards against pathological inputs that stay under `MAX_TEXT_LENGTH` but
+/// still synthesize into clips large enough to exhaust memory.
+pub const DEFAULT_MAX_AUDIO_DURATION_MS: u64 = 30 * 60 * 1000;
+
+/// `Cache-Control` sent with voice samples, which only change when the server is upgraded
+pub const SAMPLES_CACHE_CONTROL: &str = "public, max-age=86400";
+
+/// Age after which leftover temp files are removed at startup (in seconds)
+///
+/// Generations never run this long, so anything older was orphaned by a crash.
+pub const STALE_TEMP_FILE_AGE_SECS: u64 = 60 * 60;
+
+/// Estimated audio per input character at speed 1.0 (in milliseconds)
+///
+/// Used by `/tts/estimate` and stream offsets until enough generations have
+/// been observed to calibrate it (roughly 15 characters of English per second).
+pub const DEFAULT_AUDIO_MS_PER_CHAR: f64 = 65.0;
+
+/// Estimated synthesis time per millisecond of generated audio
+///
+/// A CPU real-time factor, used by `/tts/estimate` until calibrated.
+pub const DEFAULT_GENERATION_MS_PER_AUDIO_MS: f64 = 0.3;
+
+/// Characters that must have been synthesized before estimates are calibrated
+/// from observed generations instead of the static defaults
+pub const ESTIMATE_CALIBRATION_MIN_CHARS: u64 = 500;
+
+/// Default size cap for the on-disk audio cache (in bytes)
+pub const DEFAULT_AUDIO_CACHE_MAX_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Default ceiling on simultaneously open client connections
+///
+/// Kept well under the common 1024 file descriptor limit, leaving room for
+/// model files, temp files and the cache.
+pub const DEFAULT_MAX_CONNECTIONS: usize = 512;
+
+/// Quiet time after which a multipart stream sends a keep-alive part, in ms
+///
+/// Well under the 60 seconds many proxies allow an idle connection.
+pub const DEFAULT_STREAM_KEEP_ALIVE_MS: u64 = 15_000;
+
+/// Seconds a finished `/tts/jobs` job (and its audio) is kept
+pub const DEFAULT_JOB_TTL_SECS: u64 = 600;
+
+/// Most `/tts/jobs` jobs that may be queued or running at once
+pub const DEFAULT_MAX_PENDING_JOBS: usize = 100;
+
+/// Retry-After sent when the job queue is full, in seconds
+pub const JOB_QUEUE_RETRY_AFTER_SECS: u64 = 30;
+
+/// Longest silence that may be inserted after an ellipsis
+pub const MAX_ELLIPSIS_PAUSE_MS: u64 = 5000;
+
+/// Sentence spoken by `/voices/mix/preview`, kept short for quick previews
+pub const MIX_PREVIEW_TEXT: &str = "Hello, this is how the blended voice sounds.";
+
+/// Where `voices-v1.0.bin` is downloaded from, as in the installer's `MODELS` table
+pub const VOICES_DOWNLOAD_URL: &str =
+    "https://github.com/thewh1teagle/kokoro-onnx/releases/download/model-files-v1.0/voices-v1.0.bin";
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -23,4 +192,15 @@ mod tests {
     fn test_multipart_boundary_not_empty() {
         assert!(!MULTIPART_BOUNDARY.is_empty());
     }
+
+    #[test]
+    fn test_default_max_audio_duration_reasonable() {
+        // Must comfortably fit MAX_TEXT_LENGTH of normal-speed speech (~80ms/char)
+        assert!(DEFAULT_MAX_AUDIO_DURATION_MS > MAX_TEXT_LENGTH as u64 * 80);
+    }
+
+    #[test]
+    fn test_supported_sample_rates_include_native_rate() {
+        assert!(SUPPORTED_SAMPLE_RATES.contains(&24000));
+    }
 }
@@ -0,0 +1,254 @@
+use axum::http::{HeaderName, HeaderValue, Method};
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, Any, CorsLayer};
+
+/// Cross-origin policy for the HTTP API
+///
+/// Each list is `None` when any value is allowed (`*`), which is the default
+/// for local development.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CorsConfig {
+    pub allowed_origins: Option<Vec<HeaderValue>>,
+    pub allowed_methods: Option<Vec<Method>>,
+    pub allowed_headers: Option<Vec<HeaderName>>,
+}
+
+impl CorsConfig {
+    /// Load CORS settings from `CORS_ALLOWED_ORIGINS`, `CORS_ALLOWED_METHODS`
+    /// and `CORS_ALLOWED_HEADERS` (comma-separated lists, or `*`)
+    pub fn from_env() -> Result<Self, String> {
+        let var = |name: &str| std::env::var(name).ok();
+
+        Self::parse(
+            var("CORS_ALLOWED_ORIGINS").as_deref(),
+            var("CORS_ALLOWED_METHODS").as_deref(),
+            var("CORS_ALLOWED_HEADERS").as_deref(),
+        )
+    }
+
+    /// Parse and validate CORS settings; unset or `*` means "allow any"
+    pub fn parse(
+        origins: Option<&str>,
+        methods: Option<&str>,
+        headers: Option<&str>,
+    ) -> Result<Self, String> {
+        Ok(Self {
+            allowed_origins: parse_list(origins, parse_origin)?,
+            allowed_methods: parse_list(methods, |m| {
+                Method::from_bytes(m.to_uppercase().as_bytes())
+                    .map_err(|_| format!("Invalid CORS method: {}", m))
+            })?,
+            allowed_headers: parse_list(headers, |h| {
+                HeaderName::from_bytes(h.as_bytes())
+                    .map_err(|_| format!("Invalid CORS header: {}", h))
+            })?,
+        })
+    }
+
+    /// Build the tower-http layer for this policy
+    pub fn layer(&self) -> CorsLayer {
+        let origins = match &self.allowed_origins {
+            Some(origins) => AllowOrigin::list(origins.clone()),
+            None => AllowOrigin::from(Any),
+        };
+        let methods = match &self.allowed_methods {
+            Some(methods) => AllowMethods::list(methods.clone()),
+            None => AllowMethods::from(Any),
+        };
+        let headers = match &self.allowed_headers {
+            Some(headers) => AllowHeaders::list(headers.clone()),
+            None => AllowHeaders::from(Any),
+        };
+
+        CorsLayer::new()
+            .allow_origin(origins)
+            .allow_methods(methods)
+            .allow_headers(headers)
+            .expose_headers(Any) // Expose all response headers for streaming compatibility
+    }
+
+    /// Human-readable summary of the allowed origins
+    pub fn origins_description(&self) -> String {
+        match &self.allowed_origins {
+            Some(origins) => origins
+                .iter()
+                .filter_map(|o| o.to_str().ok())
+                .collect::<Vec<_>>()
+                .join(", "),
+            None => "* (any)".to_string(),
+        }
+    }
+}
+
+/// Parse a comma-separated list, returning None for an unset, empty or `*` value
+fn parse_list<T>(
+    value: Option<&str>,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Result<Option<Vec<T>>, String> {
+    let value = match value.map(str::trim) {
+        None | Some("") | Some("*") => return Ok(None),
+        Some(value) => value,
+    };
+
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(parse)
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
+
+/// Validate an origin of the form `scheme://host[:port]`
+///
+/// Any scheme is accepted, so browser extensions (`chrome-extension://<id>`,
+/// `moz-extension://<uuid>`) can be allowed like web pages.
+fn parse_origin(origin: &str) -> Result<HeaderValue, String> {
+    let invalid = || format!("Invalid CORS origin: {}", origin);
+
+    let (scheme, rest) = origin.split_once("://").ok_or_else(invalid)?;
+    if !is_valid_scheme(scheme) || rest.is_empty() || rest.contains('/') {
+        return Err(invalid());
+    }
+
+    HeaderValue::from_str(origin).map_err(|_| invalid())
+}
+
+/// A URL scheme: a letter, then letters, digits, `+`, `-` or `.` (RFC 3986)
+fn is_valid_scheme(scheme: &str) -> bool {
+    scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request, routing::post, Router};
+    use tower::ServiceExt;
+
+    fn app(config: &CorsConfig) -> Router {
+        Router::new()
+            .route("/tts", post(|| async { "ok" }))
+            .layer(config.layer())
+    }
+
+    async fn preflight(app: Router, origin: &str) -> Option<String> {
+        let request = Request::builder()
+            .method("OPTIONS")
+            .uri("/tts")
+            .header("Origin", origin)
+            .header("Access-Control-Request-Method", "POST")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        response
+            .headers()
+            .get("access-control-allow-origin")
+            .map(|v| v.to_str().unwrap().to_string())
+    }
+
+    #[test]
+    fn test_parse_defaults_to_any() {
+        assert_eq!(
+            CorsConfig::parse(None, None, None).unwrap(),
+            CorsConfig::default()
+        );
+        assert_eq!(
+            CorsConfig::parse(Some("*"), Some("*"), Some(" * ")).unwrap(),
+            CorsConfig::default()
+        );
+    }
+
+    #[test]
+    fn test_parse_origin_list() {
+        let config = CorsConfig::parse(
+            Some("https://app.example.com, http://localhost:5173"),
+            None,
+            None,
+        )
+        .unwrap();
+        let origins = config.allowed_origins.unwrap();
+        assert_eq!(origins.len(), 2);
+        assert_eq!(origins[0], "https://app.example.com");
+        assert_eq!(origins[1], "http://localhost:5173");
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_origins() {
+        for origin in [
+            "example.com",
+            "://example.com",
+            "1http://example.com",
+            "https://",
+            "https://a.com/path",
+        ] {
+            assert!(
+                CorsConfig::parse(Some(origin), None, None).is_err(),
+                "{} should be rejected",
+                origin
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_extension_origins() {
+        let config = CorsConfig::parse(
+            Some("chrome-extension://abcdefghijklmnopabcdefghijklmnop, moz-extension://4b1f0c1e-8d1a-4c6e-9f3b-2a7d5e6f8c90"),
+            None,
+            None,
+        )
+        .unwrap();
+        let origins = config.allowed_origins.unwrap();
+        assert_eq!(
+            origins[0],
+            "chrome-extension://abcdefghijklmnopabcdefghijklmnop"
+        );
+        assert_eq!(
+            origins[1],
+            "moz-extension://4b1f0c1e-8d1a-4c6e-9f3b-2a7d5e6f8c90"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_preflight_extension_origin_passes() {
+        let origin = "chrome-extension://abcdefghijklmnopabcdefghijklmnop";
+        let config = CorsConfig::parse(Some(origin), None, None).unwrap();
+        let allowed = preflight(app(&config), origin).await;
+        assert_eq!(allowed.as_deref(), Some(origin));
+    }
+
+    #[test]
+    fn test_parse_methods_and_headers() {
+        let config =
+            CorsConfig::parse(None, Some("get, post"), Some("x-api-key,content-type")).unwrap();
+        assert_eq!(
+            config.allowed_methods.unwrap(),
+            vec![Method::GET, Method::POST]
+        );
+        assert_eq!(config.allowed_headers.unwrap().len(), 2);
+
+        assert!(CorsConfig::parse(None, None, Some("bad header")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_preflight_allowed_origin_passes() {
+        let config = CorsConfig::parse(Some("https://app.example.com"), None, None).unwrap();
+        let allowed = preflight(app(&config), "https://app.example.com").await;
+        assert_eq!(allowed.as_deref(), Some("https://app.example.com"));
+    }
+
+    #[tokio::test]
+    async fn test_preflight_disallowed_origin_rejected() {
+        let config = CorsConfig::parse(Some("https://app.example.com"), None, None).unwrap();
+        let allowed = preflight(app(&config), "https://evil.example.com").await;
+        assert_eq!(allowed, None);
+    }
+
+    #[tokio::test]
+    async fn test_preflight_any_origin_by_default() {
+        let allowed = preflight(app(&CorsConfig::default()), "https://anything.test").await;
+        assert_eq!(allowed.as_deref(), Some("*"));
+    }
+}
@@ -1,2 +1,6 @@
-/// Configuration module for shared constants
+/// Configuration module for shared constants and runtime settings
 pub mod constants;
+pub mod cors;
+pub mod response_headers;
+pub mod server_config;
+pub mod trusted_proxies;
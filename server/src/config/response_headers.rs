@@ -0,0 +1,220 @@
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use serde::{Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::config::constants::SAMPLES_CACHE_CONTROL;
+
+/// Headers that describe the body itself; setting them globally would break responses
+const RESERVED_HEADERS: &[HeaderName] = &[
+    header::CONTENT_LENGTH,
+    header::CONTENT_TYPE,
+    header::TRANSFER_ENCODING,
+];
+
+/// Header names containing any of these have their value hidden when the
+/// configuration is reported
+const SENSITIVE_NAME_PARTS: &[&str] = &["authorization", "cookie", "key", "secret", "token"];
+
+/// Stands in for a hidden header value
+const REDACTED: &str = "[redacted]";
+
+/// Static headers added to every response, such as security or caching headers
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResponseHeaders {
+    headers: Vec<(HeaderName, HeaderValue)>,
+}
+
+impl ResponseHeaders {
+    /// Validate a `name → value` map, as written in the config file
+    pub fn parse(headers: &BTreeMap<String, String>) -> Result<Self, String> {
+        let headers = headers
+            .iter()
+            .map(|(name, value)| {
+                let name = HeaderName::from_bytes(name.trim().as_bytes())
+                    .map_err(|_| format!("Invalid response header name: {:?}", name))?;
+                if RESERVED_HEADERS.contains(&name) {
+                    return Err(format!(
+                        "Response header {} can't be set globally",
+                        name.as_str()
+                    ));
+                }
+                let value = HeaderValue::from_str(value.trim()).map_err(|_| {
+                    format!("Invalid value for response header {}: {:?}", name, value)
+                })?;
+                Ok((name, value))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Self { headers })
+    }
+
+    /// Parse a JSON object of header names to values (the `RESPONSE_HEADERS` format)
+    pub fn parse_json(json: &str) -> Result<Self, String> {
+        let headers: BTreeMap<String, String> = serde_json::from_str(json)
+            .map_err(|e| format!("Invalid RESPONSE_HEADERS (expected a JSON object): {}", e))?;
+        Self::parse(&headers)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty()
+    }
+
+    /// Names of the configured headers, in order
+    pub fn names(&self) -> Vec<&str> {
+        self.headers.iter().map(|(name, _)| name.as_str()).collect()
+    }
+}
+
+/// A `name → value` map like the config file's, with sensitive values hidden
+impl Serialize for ResponseHeaders {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.headers.iter().map(|(name, value)| {
+            let sensitive = SENSITIVE_NAME_PARTS
+                .iter()
+                .any(|part| name.as_str().contains(part));
+            let value = if sensitive {
+                REDACTED
+            } else {
+                value.to_str().unwrap_or(REDACTED)
+            };
+            (name.as_str(), value)
+        }))
+    }
+}
+
+/// Middleware adding the configured headers to every response
+///
+/// A header the response already carries is left alone, so endpoints that set
+/// their own (such as `Cache-Control` on `/samples`) keep it.
+pub async fn response_headers_middleware(
+    State(headers): State<Arc<ResponseHeaders>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let mut response = next.run(request).await;
+    for (name, value) in &headers.headers {
+        if !response.headers().contains_key(name) {
+            response.headers_mut().insert(name.clone(), value.clone());
+        }
+    }
+    response
+}
+
+/// Let browsers and CDNs cache voice samples, which only change on upgrade
+pub async fn samples_cache_headers(mut response: Response) -> Response {
+    if response.status().is_success() {
+        response.headers_mut().insert(
+            header::CACHE_CONTROL,
+            HeaderValue::from_static(SAMPLES_CACHE_CONTROL),
+        );
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn map(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_valid_headers() {
+        let headers = ResponseHeaders::parse(&map(&[
+            ("X-Frame-Options", "DENY"),
+            ("Access-Control-Max-Age", " 600 "),
+        ]))
+        .unwrap();
+        assert_eq!(
+            headers.names(),
+            vec!["access-control-max-age", "x-frame-options"]
+        );
+        assert!(ResponseHeaders::default().is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_headers() {
+        assert!(ResponseHeaders::parse(&map(&[("Bad Header", "x")])).is_err());
+        assert!(ResponseHeaders::parse(&map(&[("X-Test", "line\nbreak")])).is_err());
+        assert!(ResponseHeaders::parse(&map(&[("Content-Length", "0")])).is_err());
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let headers = ResponseHeaders::parse_json(r#"{"X-Frame-Options": "DENY"}"#).unwrap();
+        assert_eq!(headers.names(), vec!["x-frame-options"]);
+
+        assert!(ResponseHeaders::parse_json("X-Frame-Options: DENY").is_err());
+        assert!(ResponseHeaders::parse_json(r#"{"X-Count": 3}"#).is_err());
+    }
+
+    #[test]
+    fn test_serialize_redacts_sensitive_values() {
+        let headers = ResponseHeaders::parse(&map(&[
+            ("X-Frame-Options", "DENY"),
+            ("X-Upstream-Token", "s3cret"),
+            ("Proxy-Authorization", "Basic abc"),
+        ]))
+        .unwrap();
+        let json = serde_json::to_value(&headers).unwrap();
+
+        assert_eq!(json["x-frame-options"], "DENY");
+        assert_eq!(json["x-upstream-token"], REDACTED);
+        assert_eq!(json["proxy-authorization"], REDACTED);
+        assert!(!json.to_string().contains("s3cret"));
+    }
+
+    #[tokio::test]
+    async fn test_samples_are_cacheable() {
+        let app = Router::new()
+            .route("/found", get(|| async { "wav" }))
+            .layer(middleware::map_response(samples_cache_headers));
+
+        let request = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let found = app.clone().oneshot(request("/found")).await.unwrap();
+        assert_eq!(
+            found.headers()[header::CACHE_CONTROL],
+            SAMPLES_CACHE_CONTROL
+        );
+
+        let missing = app.oneshot(request("/missing")).await.unwrap();
+        assert!(!missing.headers().contains_key(header::CACHE_CONTROL));
+    }
+
+    #[tokio::test]
+    async fn test_middleware_keeps_headers_set_by_the_handler() {
+        let headers = ResponseHeaders::parse(&map(&[
+            ("Cache-Control", "no-store"),
+            ("X-Frame-Options", "DENY"),
+        ]))
+        .unwrap();
+        let app = Router::new()
+            .route(
+                "/",
+                get(|| async { ([(header::CACHE_CONTROL, "max-age=60")], "ok") }),
+            )
+            .layer(middleware::from_fn_with_state(
+                Arc::new(headers),
+                response_headers_middleware,
+            ));
+
+        let response = app
+            .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["x-frame-options"], "DENY");
+        assert_eq!(response.headers()[header::CACHE_CONTROL], "max-age=60");
+    }
+}
@@ -0,0 +1,1544 @@
+use crate::audio::wav_utils::SampleRateMismatch;
+use crate::chunking::{LongTokenConfig, LongTokenPolicy};
+use crate::config::constants::{
+    DEFAULT_AUDIO_CACHE_MAX_BYTES, DEFAULT_AUDIO_FORMAT, DEFAULT_CHUNKING_MIN_LENGTH,
+    DEFAULT_JOB_TTL_SECS, DEFAULT_MAX_AUDIO_DURATION_MS, DEFAULT_MAX_CONNECTIONS,
+    DEFAULT_MAX_NORMALIZED_LENGTH, DEFAULT_MAX_PENDING_JOBS, DEFAULT_MAX_POOL_SIZE,
+    DEFAULT_MAX_REQUEST_TIMEOUT_SECS, DEFAULT_MIN_SPEED, DEFAULT_STREAM_KEEP_ALIVE_MS,
+    MAX_ELLIPSIS_PAUSE_MS, SUPPORTED_AUDIO_FORMATS,
+};
+use crate::config::response_headers::ResponseHeaders;
+use crate::kokoro::circuit_breaker::BreakerConfig;
+use crate::kokoro::EngineSelection;
+use crate::rate_limit::RateLimitConfig;
+use crate::services::streaming::{ChunkFailureMode, ChunkRetryConfig};
+use crate::text_processing::asides::AsideConfig;
+use crate::text_processing::lists::ListConfig;
+use crate::text_processing::normalization::{EmojiStyle, NormalizationConfig, SymbolStyle};
+use crate::text_processing::punctuation::PunctuationNames;
+use crate::text_processing::quotes::{QuoteProsody, QuoteStyle};
+use crate::text_processing::semantic_normalization::FractionStyle;
+use crate::utils::accept::AudioFormat;
+use crate::utils::speed::parse_min_speed;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Config file read from the working directory when `PORUA_CONFIG` is unset
+pub const DEFAULT_CONFIG_FILE: &str = "porua.json";
+
+/// How requests are rate limited
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RateLimitMode {
+    /// Per API key when keys are configured, per IP otherwise
+    #[default]
+    Auto,
+    PerKey,
+    PerIp,
+    Disabled,
+}
+
+impl RateLimitMode {
+    /// Parse a `RATE_LIMIT_MODE` value; anything unrecognized means `auto`
+    fn parse(value: &str) -> Self {
+        match value.to_lowercase().as_str() {
+            "disabled" => Self::Disabled,
+            "per-key" => Self::PerKey,
+            "per-ip" => Self::PerIp,
+            _ => Self::Auto,
+        }
+    }
+}
+
+/// Rate limit overrides for one kind of client, as written in the config file
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateFileConfig {
+    pub per_second: Option<u32>,
+    pub burst_size: Option<u32>,
+}
+
+/// The `rate_limit` section of the config file
+///
+/// `per_second`/`burst_size` apply to both kinds of client unless the
+/// `authenticated` or `unauthenticated` section says otherwise.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitFileConfig {
+    pub mode: Option<RateLimitMode>,
+    pub per_second: Option<u32>,
+    pub burst_size: Option<u32>,
+    pub authenticated: RateFileConfig,
+    pub unauthenticated: RateFileConfig,
+}
+
+/// The `circuit_breaker` section of the config file
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BreakerFileConfig {
+    pub failure_threshold: Option<u32>,
+    pub window_seconds: Option<u64>,
+    pub cooldown_seconds: Option<u64>,
+}
+
+/// Server settings as written in the config file; every field is optional
+///
+/// Flat keys are named after their environment variable, lowercased and
+/// without the `TTS_` prefix: `TTS_FAIR_QUEUE` is `fair_queue`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FileConfig {
+    pub pool_size: Option<usize>,
+    pub max_pool_size: Option<usize>,
+    pub request_timeout_seconds: Option<u64>,
+    pub max_request_timeout_seconds: Option<u64>,
+    pub chunking_min_length: Option<usize>,
+    pub espeak_data_dir: Option<PathBuf>,
+    pub rate_limit: RateLimitFileConfig,
+    pub circuit_breaker: BreakerFileConfig,
+    /// Static headers added to every response, by name
+    pub response_headers: BTreeMap<String, String>,
+
+    // Startup and limits
+    pub unix_socket: Option<PathBuf>,
+    pub model_wait_seconds: Option<u64>,
+    pub warmup: Option<bool>,
+    pub startup_selftest: Option<bool>,
+    pub max_in_flight_requests: Option<usize>,
+    pub max_connections: Option<usize>,
+    pub fair_queue: Option<bool>,
+    pub engine_selection: Option<String>,
+    pub max_audio_duration_ms: Option<u64>,
+    pub max_normalized_length: Option<usize>,
+    pub min_speed: Option<f32>,
+    pub speed_clamp: Option<bool>,
+    pub fallback_voice: Option<String>,
+    pub voice_speeds: Option<String>,
+    pub voice_pitches: Option<String>,
+
+    // Chunking, streaming and jobs
+    pub max_token_length: Option<usize>,
+    pub long_token_policy: Option<String>,
+    pub chunk_retries: Option<u32>,
+    pub chunk_failure_mode: Option<String>,
+    pub stream_keep_alive_ms: Option<u64>,
+    pub concat_sample_rate: Option<String>,
+    pub job_ttl_seconds: Option<u64>,
+    pub max_pending_jobs: Option<usize>,
+
+    // Output
+    pub wav_metadata: Option<bool>,
+    pub validation_headers: Option<bool>,
+    pub default_audio_format: Option<String>,
+    pub cache_dir: Option<PathBuf>,
+    pub cache_max_bytes: Option<u64>,
+
+    // Reading
+    pub ellipsis_pause_ms: Option<u64>,
+    pub lists: Option<bool>,
+    pub list_pause_ms: Option<u64>,
+    pub list_numbers: Option<bool>,
+    pub asides: Option<bool>,
+    pub aside_speed_delta: Option<f32>,
+    pub aside_gain_db: Option<f32>,
+    pub quote_style: Option<String>,
+    pub quote_speed_delta: Option<f32>,
+    pub quote_pitch: Option<f32>,
+    pub quote_pause_ms: Option<u64>,
+    pub say_punctuation: Option<bool>,
+    pub punctuation_names: Option<String>,
+
+    // Text normalization
+    pub fraction_style: Option<String>,
+    pub symbol_style: Option<String>,
+    pub emoji_style: Option<String>,
+    pub roman_numerals: Option<bool>,
+    pub units: Option<bool>,
+    pub time_zones: Option<bool>,
+    pub all_caps: Option<bool>,
+    pub dehyphenate: Option<bool>,
+    pub decades: Option<bool>,
+    pub math: Option<bool>,
+    pub alphanumerics: Option<bool>,
+    pub versions: Option<bool>,
+    pub fold_forms: Option<bool>,
+}
+
+impl FileConfig {
+    /// Parse a JSON config file
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read config file {}: {}", path.display(), e))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| format!("Invalid config file {}: {}", path.display(), e))
+    }
+}
+
+/// Rate limiting settings after merging file, environment and defaults
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RateLimitSettings {
+    pub mode: RateLimitMode,
+    pub authenticated: RateLimitConfig,
+    pub unauthenticated: RateLimitConfig,
+}
+
+/// Check a requested engine count, capping it at `max`
+///
+/// Zero is an error rather than a silent default, since it can only be a
+/// mistake; anything above `max` is clamped with a warning.
+fn clamp_pool_size(requested: usize, max: usize) -> Result<usize, String> {
+    if requested == 0 {
+        return Err(
+            "Pool size must be at least 1; check TTS_POOL_SIZE or pool_size in the config file"
+                .to_string(),
+        );
+    }
+    let max = max.max(1);
+    if requested > max {
+        tracing::warn!(
+            "Pool size {} exceeds the maximum of {}; using {} engines (raise TTS_MAX_POOL_SIZE to allow more)",
+            requested,
+            max,
+            max
+        );
+        return Ok(max);
+    }
+    Ok(requested)
+}
+
+/// Parse an on/off setting named `name`
+///
+/// Accepts true/false, 1/0, yes/no and on/off in any case.
+pub fn parse_flag(name: &str, value: &str) -> Result<bool, String> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "1" | "yes" | "on" => Ok(true),
+        "false" | "0" | "no" | "off" => Ok(false),
+        _ => Err(format!(
+            "Invalid {}: '{}' (expected true or false)",
+            name,
+            value.trim()
+        )),
+    }
+}
+
+/// Look up and parse an environment variable, ignoring unparseable values
+fn parsed<T: FromStr>(env: &impl Fn(&str) -> Option<String>, name: &str) -> Option<T> {
+    env(name).and_then(|v| v.parse().ok())
+}
+
+/// Resolve an on/off setting from the environment, then the file, then `default`
+///
+/// Unlike numbers, a flag that isn't true or false is an error rather than
+/// quietly meaning the default.
+fn flag(
+    env: &impl Fn(&str) -> Option<String>,
+    name: &str,
+    file: Option<bool>,
+    default: bool,
+) -> Result<bool, String> {
+    match env(name) {
+        Some(value) => parse_flag(name, &value),
+        None => Ok(file.unwrap_or(default)),
+    }
+}
+
+/// Resolve a finite number from the environment, then the file
+fn finite(env: &impl Fn(&str) -> Option<String>, name: &str, file: Option<f32>) -> Option<f32> {
+    env(name)
+        .and_then(|v| v.trim().parse::<f32>().ok())
+        .filter(|v| v.is_finite())
+        .or(file.filter(|v| v.is_finite()))
+}
+
+/// A default audio format, which must be one the server can encode
+fn parse_default_audio_format(value: &str) -> Result<AudioFormat, String> {
+    let format = AudioFormat::parse(value)?;
+    if SUPPORTED_AUDIO_FORMATS.contains(&format) {
+        Ok(format)
+    } else {
+        Err(format!(
+            "Default audio format {:?} can't be encoded yet",
+            format.as_str()
+        ))
+    }
+}
+
+/// A pause in milliseconds, capped like the ellipsis pause
+fn capped_pause(ms: u64) -> Duration {
+    Duration::from_millis(ms.min(MAX_ELLIPSIS_PAUSE_MS))
+}
+
+/// Resolved server settings
+///
+/// Each value comes from the environment if set and valid, then the config
+/// file, then the built-in default. Durations serialize as milliseconds.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ServerConfig {
+    pub pool_size: usize,
+    #[serde(
+        rename = "request_timeout_ms",
+        serialize_with = "crate::utils::millis::serialize"
+    )]
+    pub request_timeout: Duration,
+    /// Longest timeout a request may ask for with `X-Request-Timeout`
+    #[serde(
+        rename = "max_request_timeout_ms",
+        serialize_with = "crate::utils::millis::serialize"
+    )]
+    pub max_request_timeout: Duration,
+    /// Text longer than this (in bytes) is chunked when chunking is enabled
+    pub chunking_min_length: usize,
+    /// Directory containing `espeak-ng-data` (None = espeak-ng's built-in path)
+    pub espeak_data_dir: Option<PathBuf>,
+    pub rate_limit: RateLimitSettings,
+    pub circuit_breaker: BreakerConfig,
+    pub response_headers: ResponseHeaders,
+    /// Local socket to listen on instead of TCP (`--uds` takes precedence)
+    pub unix_socket: Option<PathBuf>,
+    /// How long to wait for missing model files before giving up
+    #[serde(
+        rename = "model_wait_ms",
+        serialize_with = "crate::utils::millis::serialize"
+    )]
+    pub model_wait: Duration,
+    /// Whether each engine runs a dummy synthesis before serving
+    pub warmup: bool,
+    /// Whether a known phrase is synthesized and checked before serving
+    pub startup_selftest: bool,
+    /// In-flight request ceiling (None = a few per loaded engine, 0 = unlimited)
+    pub max_in_flight_requests: Option<usize>,
+    /// Open connection ceiling (0 = unlimited)
+    pub max_connections: usize,
+    /// Whether waiting requests take turns by client instead of arrival order
+    pub fair_queue: bool,
+    pub engine_selection: EngineSelection,
+    #[serde(
+        rename = "max_audio_duration_ms",
+        serialize_with = "crate::utils::millis::serialize"
+    )]
+    pub max_audio_duration: Duration,
+    pub max_normalized_length: usize,
+    /// Slowest accepted speed
+    pub min_speed: f32,
+    /// Whether out-of-range speeds are clamped instead of rejected
+    pub speed_clamp: bool,
+    /// Voice used when a requested one is unavailable, as configured (None = fail instead)
+    pub fallback_voice: Option<String>,
+    /// Default speeds by voice, as configured (`voice=speed,...`)
+    pub voice_speeds: Option<String>,
+    /// Default pitch factors by voice, as configured (`voice=pitch,...`)
+    pub voice_pitches: Option<String>,
+    pub long_tokens: LongTokenConfig,
+    pub chunk_retry: ChunkRetryConfig,
+    /// How long a multipart stream may go quiet before a keep-alive part (None = never)
+    #[serde(
+        rename = "stream_keep_alive_ms",
+        serialize_with = "crate::utils::millis::serialize_option"
+    )]
+    pub stream_keep_alive: Option<Duration>,
+    pub concat_sample_rate: SampleRateMismatch,
+    /// How long finished background jobs are kept
+    #[serde(
+        rename = "job_ttl_ms",
+        serialize_with = "crate::utils::millis::serialize"
+    )]
+    pub job_ttl: Duration,
+    /// How many jobs may be queued or running at once
+    pub max_pending_jobs: usize,
+    /// Whether WAV INFO metadata is embedded by default
+    pub wav_metadata: bool,
+    /// Whether `/tts` reports metadata validation warnings in a header
+    pub validation_headers: bool,
+    /// Format produced when neither `format` nor `Accept` asks for one
+    pub default_audio_format: AudioFormat,
+    /// Directory of the on-disk audio cache (None = no cache)
+    pub cache_dir: Option<PathBuf>,
+    pub cache_max_bytes: u64,
+    /// Silence inserted after an ellipsis (None = read as punctuation)
+    #[serde(
+        rename = "ellipsis_pause_ms",
+        serialize_with = "crate::utils::millis::serialize_option"
+    )]
+    pub ellipsis_pause: Option<Duration>,
+    /// How list items are spoken (None = read like other text)
+    pub lists: Option<ListConfig>,
+    /// How parenthetical asides are spoken (None = read like other text)
+    pub asides: Option<AsideConfig>,
+    pub quotes: QuoteStyle,
+    /// Names punctuation is read aloud by (None = not read aloud)
+    pub say_punctuation: Option<PunctuationNames>,
+    pub normalization: NormalizationConfig,
+    /// Config file the settings were read from, if any
+    pub source: Option<PathBuf>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self::resolve(FileConfig::default(), |_| None).expect("default settings are valid")
+    }
+}
+
+impl ServerConfig {
+    /// Load settings from the config file (if any) and the environment
+    ///
+    /// `PORUA_CONFIG` names the file, which must then exist; otherwise
+    /// `porua.json` in the working directory is used when present.
+    pub fn load() -> Result<Self, String> {
+        let path = match std::env::var("PORUA_CONFIG") {
+            Ok(path) => Some(PathBuf::from(path)),
+            Err(_) => Some(PathBuf::from(DEFAULT_CONFIG_FILE)).filter(|path| path.is_file()),
+        };
+
+        let file = match &path {
+            Some(path) => FileConfig::read(path)?,
+            None => FileConfig::default(),
+        };
+
+        Ok(Self {
+            source: path,
+            ..Self::resolve(file, |name| std::env::var(name).ok())?
+        })
+    }
+
+    /// Merge file settings with variables looked up through `env`
+    ///
+    /// Unparseable numbers are ignored, as they always have been. Fails for
+    /// settings that can't work, like an empty engine pool, and for invalid
+    /// flags or named options.
+    pub fn resolve(file: FileConfig, env: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let max_pool_size = parsed(&env, "TTS_MAX_POOL_SIZE")
+            .or(file.max_pool_size)
+            .unwrap_or(DEFAULT_MAX_POOL_SIZE);
+        let pool_size = clamp_pool_size(
+            parsed(&env, "TTS_POOL_SIZE")
+                .or(file.pool_size)
+                .unwrap_or(2),
+            max_pool_size,
+        )?;
+
+        let timeout_seconds = parsed(&env, "REQUEST_TIMEOUT_SECONDS")
+            .or(file.request_timeout_seconds)
+            .unwrap_or(60);
+        let max_timeout_seconds = parsed(&env, "MAX_REQUEST_TIMEOUT_SECONDS")
+            .or(file.max_request_timeout_seconds)
+            .unwrap_or(DEFAULT_MAX_REQUEST_TIMEOUT_SECS);
+
+        let chunking_min_length = parsed(&env, "CHUNKING_MIN_LENGTH")
+            .or(file.chunking_min_length)
+            .unwrap_or(DEFAULT_CHUNKING_MIN_LENGTH);
+
+        let espeak_data_dir = env("PIPER_ESPEAKNG_DATA_DIRECTORY")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or(file.espeak_data_dir);
+
+        let rate_file = file.rate_limit;
+        let mode = env("RATE_LIMIT_MODE")
+            .map(|v| RateLimitMode::parse(&v))
+            .or(rate_file.mode)
+            .unwrap_or_default();
+
+        // Most specific first: env for this client kind, shared env, file for
+        // this client kind, shared file value, default
+        let rate = |kind: &str, file_kind: &RateFileConfig, defaults: (u32, u32)| {
+            let per_second = parsed(&env, &format!("RATE_LIMIT_{}_PER_SECOND", kind))
+                .or_else(|| parsed(&env, "RATE_LIMIT_PER_SECOND"))
+                .or(file_kind.per_second)
+                .or(rate_file.per_second)
+                .unwrap_or(defaults.0);
+            let burst_size = parsed(&env, &format!("RATE_LIMIT_{}_BURST_SIZE", kind))
+                .or_else(|| parsed(&env, "RATE_LIMIT_BURST_SIZE"))
+                .or(file_kind.burst_size)
+                .or(rate_file.burst_size)
+                .unwrap_or(defaults.1);
+            RateLimitConfig {
+                per_second,
+                burst_size,
+            }
+        };
+
+        let rate_limit = RateLimitSettings {
+            mode,
+            authenticated: rate("AUTHENTICATED", &rate_file.authenticated, (10, 20)),
+            // More restrictive defaults for unauthenticated clients
+            unauthenticated: rate("UNAUTHENTICATED", &rate_file.unauthenticated, (5, 10)),
+        };
+
+        let breaker_file = file.circuit_breaker;
+        let breaker_defaults = BreakerConfig::default();
+        let circuit_breaker = BreakerConfig {
+            failure_threshold: parsed(&env, "CIRCUIT_BREAKER_FAILURES")
+                .or(breaker_file.failure_threshold)
+                .unwrap_or(breaker_defaults.failure_threshold),
+            window: parsed(&env, "CIRCUIT_BREAKER_WINDOW_SECONDS")
+                .or(breaker_file.window_seconds)
+                .map(Duration::from_secs)
+                .unwrap_or(breaker_defaults.window),
+            cooldown: parsed(&env, "CIRCUIT_BREAKER_COOLDOWN_SECONDS")
+                .or(breaker_file.cooldown_seconds)
+                .map(Duration::from_secs)
+                .unwrap_or(breaker_defaults.cooldown),
+        };
+
+        // Unlike other settings, bad headers fail loudly instead of being ignored
+        let response_headers = match env("RESPONSE_HEADERS") {
+            Some(json) => ResponseHeaders::parse_json(&json)?,
+            None => ResponseHeaders::parse(&file.response_headers)?,
+        };
+
+        let min_speed = match env("TTS_MIN_SPEED") {
+            Some(value) => parse_min_speed(&value)?,
+            None => match file.min_speed {
+                Some(speed) => parse_min_speed(&speed.to_string())?,
+                None => DEFAULT_MIN_SPEED,
+            },
+        };
+
+        let engine_selection = match env("TTS_ENGINE_SELECTION").or(file.engine_selection) {
+            Some(value) => EngineSelection::parse(&value)?,
+            None => EngineSelection::default(),
+        };
+
+        let default_audio_format =
+            match env("TTS_DEFAULT_AUDIO_FORMAT").or(file.default_audio_format) {
+                Some(value) => parse_default_audio_format(&value)?,
+                None => DEFAULT_AUDIO_FORMAT,
+            };
+
+        let token_defaults = LongTokenConfig::default();
+        let long_tokens = LongTokenConfig {
+            max_length: parsed(&env, "TTS_MAX_TOKEN_LENGTH")
+                .or(file.max_token_length)
+                .unwrap_or(token_defaults.max_length),
+            policy: env("TTS_LONG_TOKEN_POLICY")
+                .or(file.long_token_policy)
+                .map(|v| LongTokenPolicy::parse(&v))
+                .unwrap_or(token_defaults.policy),
+        };
+
+        let retry_defaults = ChunkRetryConfig::default();
+        let chunk_retry = ChunkRetryConfig {
+            retries: parsed(&env, "TTS_CHUNK_RETRIES")
+                .or(file.chunk_retries)
+                .unwrap_or(retry_defaults.retries),
+            failure_mode: env("TTS_CHUNK_FAILURE_MODE")
+                .or(file.chunk_failure_mode)
+                .map(|v| ChunkFailureMode::parse(&v))
+                .unwrap_or(retry_defaults.failure_mode),
+        };
+
+        let keep_alive_ms = parsed(&env, "TTS_STREAM_KEEP_ALIVE_MS")
+            .or(file.stream_keep_alive_ms)
+            .unwrap_or(DEFAULT_STREAM_KEEP_ALIVE_MS);
+
+        let concat_sample_rate = match env("TTS_CONCAT_SAMPLE_RATE").or(file.concat_sample_rate) {
+            Some(value) => SampleRateMismatch::parse(&value)?,
+            None => SampleRateMismatch::default(),
+        };
+
+        let lists = if flag(&env, "TTS_LISTS", file.lists, false)? {
+            let defaults = ListConfig::default();
+            Some(ListConfig {
+                pause: parsed(&env, "TTS_LIST_PAUSE_MS")
+                    .or(file.list_pause_ms)
+                    .map(capped_pause)
+                    .unwrap_or(defaults.pause),
+                speak_numbers: flag(
+                    &env,
+                    "TTS_LIST_NUMBERS",
+                    file.list_numbers,
+                    defaults.speak_numbers,
+                )?,
+            })
+        } else {
+            None
+        };
+
+        let asides = if flag(&env, "TTS_ASIDES", file.asides, false)? {
+            let defaults = AsideConfig::default();
+            Some(AsideConfig {
+                speed_delta: finite(&env, "TTS_ASIDE_SPEED_DELTA", file.aside_speed_delta)
+                    .unwrap_or(defaults.speed_delta),
+                gain_db: finite(&env, "TTS_ASIDE_GAIN_DB", file.aside_gain_db)
+                    .unwrap_or(defaults.gain_db),
+            })
+        } else {
+            None
+        };
+
+        let prosody_defaults = QuoteProsody::default();
+        let prosody = QuoteProsody {
+            speed_delta: finite(&env, "TTS_QUOTE_SPEED_DELTA", file.quote_speed_delta)
+                .unwrap_or(prosody_defaults.speed_delta),
+            pitch_scale: finite(&env, "TTS_QUOTE_PITCH", file.quote_pitch)
+                .unwrap_or(prosody_defaults.pitch_scale),
+            pause: env("TTS_QUOTE_PAUSE_MS")
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .or(file.quote_pause_ms)
+                .map(capped_pause)
+                .unwrap_or(prosody_defaults.pause),
+        };
+        let quotes = match env("TTS_QUOTE_STYLE").or(file.quote_style) {
+            Some(style) => QuoteStyle::parse(&style, prosody)?,
+            None => QuoteStyle::Ignore,
+        };
+
+        let say_punctuation = if flag(&env, "TTS_SAY_PUNCTUATION", file.say_punctuation, false)? {
+            match env("TTS_PUNCTUATION_NAMES").or(file.punctuation_names) {
+                Some(spec) => Some(PunctuationNames::with_overrides(&spec)?),
+                None => Some(PunctuationNames::default()),
+            }
+        } else {
+            None
+        };
+
+        // The heuristic passes are opt-in, like `TTS_MATH`: each can misread
+        // text it wasn't written for
+        let defaults = NormalizationConfig::default();
+        let normalization = NormalizationConfig {
+            fraction_style: env("TTS_FRACTION_STYLE")
+                .or(file.fraction_style)
+                .map(|v| FractionStyle::parse(&v))
+                .unwrap_or(defaults.fraction_style),
+            symbol_style: env("TTS_SYMBOL_STYLE")
+                .or(file.symbol_style)
+                .map(|v| SymbolStyle::parse(&v))
+                .unwrap_or(defaults.symbol_style),
+            emoji_style: env("TTS_EMOJI_STYLE")
+                .or(file.emoji_style)
+                .map(|v| EmojiStyle::parse(&v))
+                .unwrap_or(defaults.emoji_style),
+            roman_numerals: flag(
+                &env,
+                "TTS_ROMAN_NUMERALS",
+                file.roman_numerals,
+                defaults.roman_numerals,
+            )?,
+            units: flag(&env, "TTS_UNITS", file.units, defaults.units)?,
+            time_zones: flag(&env, "TTS_TIME_ZONES", file.time_zones, defaults.time_zones)?,
+            all_caps: flag(&env, "TTS_ALL_CAPS", file.all_caps, defaults.all_caps)?,
+            dehyphenate: flag(
+                &env,
+                "TTS_DEHYPHENATE",
+                file.dehyphenate,
+                defaults.dehyphenate,
+            )?,
+            decades: flag(&env, "TTS_DECADES", file.decades, defaults.decades)?,
+            math: flag(&env, "TTS_MATH", file.math, defaults.math)?,
+            alphanumerics: flag(
+                &env,
+                "TTS_ALPHANUMERICS",
+                file.alphanumerics,
+                defaults.alphanumerics,
+            )?,
+            versions: flag(&env, "TTS_VERSIONS", file.versions, defaults.versions)?,
+            fold_forms: flag(&env, "TTS_FOLD_FORMS", file.fold_forms, defaults.fold_forms)?,
+        };
+
+        Ok(Self {
+            pool_size,
+            request_timeout: Duration::from_secs(timeout_seconds),
+            max_request_timeout: Duration::from_secs(max_timeout_seconds),
+            chunking_min_length,
+            espeak_data_dir,
+            rate_limit,
+            circuit_breaker,
+            response_headers,
+            unix_socket: env("TTS_UNIX_SOCKET")
+                .filter(|path| !path.trim().is_empty())
+                .map(PathBuf::from)
+                .or(file.unix_socket),
+            model_wait: Duration::from_secs(
+                parsed(&env, "TTS_MODEL_WAIT_SECONDS")
+                    .or(file.model_wait_seconds)
+                    .unwrap_or(0),
+            ),
+            warmup: flag(&env, "TTS_WARMUP", file.warmup, false)?,
+            startup_selftest: flag(&env, "TTS_STARTUP_SELFTEST", file.startup_selftest, false)?,
+            max_in_flight_requests: parsed(&env, "MAX_IN_FLIGHT_REQUESTS")
+                .or(file.max_in_flight_requests),
+            max_connections: parsed(&env, "MAX_CONNECTIONS")
+                .or(file.max_connections)
+                .unwrap_or(DEFAULT_MAX_CONNECTIONS),
+            fair_queue: flag(&env, "TTS_FAIR_QUEUE", file.fair_queue, false)?,
+            engine_selection,
+            max_audio_duration: Duration::from_millis(
+                parsed(&env, "MAX_AUDIO_DURATION_MS")
+                    .or(file.max_audio_duration_ms)
+                    .unwrap_or(DEFAULT_MAX_AUDIO_DURATION_MS),
+            ),
+            max_normalized_length: parsed(&env, "MAX_NORMALIZED_LENGTH")
+                .or(file.max_normalized_length)
+                .unwrap_or(DEFAULT_MAX_NORMALIZED_LENGTH),
+            min_speed,
+            speed_clamp: flag(&env, "TTS_SPEED_CLAMP", file.speed_clamp, false)?,
+            fallback_voice: env("TTS_FALLBACK_VOICE")
+                .filter(|voice| !voice.trim().is_empty())
+                .or(file.fallback_voice)
+                .map(|voice| voice.trim().to_string())
+                .filter(|voice| !voice.is_empty()),
+            voice_speeds: env("TTS_VOICE_SPEEDS").or(file.voice_speeds),
+            voice_pitches: env("TTS_VOICE_PITCHES").or(file.voice_pitches),
+            long_tokens,
+            chunk_retry,
+            stream_keep_alive: (keep_alive_ms > 0).then(|| Duration::from_millis(keep_alive_ms)),
+            concat_sample_rate,
+            job_ttl: Duration::from_secs(
+                parsed(&env, "TTS_JOB_TTL_SECONDS")
+                    .or(file.job_ttl_seconds)
+                    .unwrap_or(DEFAULT_JOB_TTL_SECS),
+            ),
+            max_pending_jobs: parsed(&env, "TTS_MAX_PENDING_JOBS")
+                .or(file.max_pending_jobs)
+                .filter(|&max| max > 0)
+                .unwrap_or(DEFAULT_MAX_PENDING_JOBS),
+            wav_metadata: flag(&env, "TTS_WAV_METADATA", file.wav_metadata, false)?,
+            validation_headers: flag(
+                &env,
+                "TTS_VALIDATION_HEADERS",
+                file.validation_headers,
+                false,
+            )?,
+            default_audio_format,
+            cache_dir: env("TTS_CACHE_DIR")
+                .map(|dir| dir.trim().to_string())
+                .filter(|dir| !dir.is_empty())
+                .map(PathBuf::from)
+                .or(file.cache_dir)
+                .filter(|dir| !dir.as_os_str().is_empty()),
+            cache_max_bytes: parsed(&env, "TTS_CACHE_MAX_BYTES")
+                .or(file.cache_max_bytes)
+                .unwrap_or(DEFAULT_AUDIO_CACHE_MAX_BYTES),
+            ellipsis_pause: parsed(&env, "TTS_ELLIPSIS_PAUSE_MS")
+                .or(file.ellipsis_pause_ms)
+                .filter(|&ms| ms > 0)
+                .map(capped_pause),
+            lists,
+            asides,
+            quotes,
+            say_punctuation,
+            normalization,
+            source: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::io::Write;
+
+    fn env_of(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    fn file(json: &str) -> FileConfig {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn rate(per_second: u32, burst_size: u32) -> RateLimitConfig {
+        RateLimitConfig {
+            per_second,
+            burst_size,
+        }
+    }
+
+    #[test]
+    fn test_fully_defaulted_config() {
+        let config = ServerConfig::resolve(FileConfig::default(), |_| None).unwrap();
+
+        assert_eq!(config.pool_size, 2);
+        assert_eq!(config.request_timeout, Duration::from_secs(60));
+        assert_eq!(config.max_request_timeout, Duration::from_secs(300));
+        assert_eq!(config.chunking_min_length, 200);
+        assert_eq!(config.espeak_data_dir, None);
+        assert_eq!(config.rate_limit.mode, RateLimitMode::Auto);
+        assert_eq!(config.rate_limit.authenticated, rate(10, 20));
+        assert_eq!(config.rate_limit.unauthenticated, rate(5, 10));
+        assert_eq!(config.circuit_breaker, BreakerConfig::default());
+        assert_eq!(config.source, None);
+        assert_eq!(config, ServerConfig::default());
+    }
+
+    #[test]
+    fn test_empty_file_is_fully_defaulted() {
+        assert_eq!(
+            ServerConfig::resolve(file("{}"), |_| None).unwrap(),
+            ServerConfig::default()
+        );
+    }
+
+    #[test]
+    fn test_file_values_used_without_env() {
+        let config = ServerConfig::resolve(
+            file(
+                r#"{
+                    "pool_size": 4,
+                    "request_timeout_seconds": 120,
+                    "rate_limit": {
+                        "mode": "per-ip",
+                        "per_second": 7,
+                        "unauthenticated": { "burst_size": 3 }
+                    }
+                }"#,
+            ),
+            |_| None,
+        )
+        .unwrap();
+
+        assert_eq!(config.pool_size, 4);
+        assert_eq!(config.request_timeout, Duration::from_secs(120));
+        assert_eq!(config.rate_limit.mode, RateLimitMode::PerIp);
+        assert_eq!(config.rate_limit.authenticated, rate(7, 20));
+        assert_eq!(config.rate_limit.unauthenticated, rate(7, 3));
+    }
+
+    #[test]
+    fn test_env_overrides_file() {
+        let config = ServerConfig::resolve(
+            file(
+                r#"{
+                    "pool_size": 4,
+                    "request_timeout_seconds": 120,
+                    "rate_limit": { "mode": "per-ip", "authenticated": { "per_second": 7 } }
+                }"#,
+            ),
+            env_of(&[
+                ("TTS_POOL_SIZE", "8"),
+                ("REQUEST_TIMEOUT_SECONDS", "30"),
+                ("RATE_LIMIT_MODE", "disabled"),
+                ("RATE_LIMIT_PER_SECOND", "50"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(config.pool_size, 8);
+        assert_eq!(config.request_timeout, Duration::from_secs(30));
+        assert_eq!(config.rate_limit.mode, RateLimitMode::Disabled);
+        // Even the shared env value beats a per-kind file value
+        assert_eq!(config.rate_limit.authenticated.per_second, 50);
+        assert_eq!(config.rate_limit.unauthenticated.per_second, 50);
+    }
+
+    #[test]
+    fn test_specific_env_beats_shared_env() {
+        let config = ServerConfig::resolve(
+            FileConfig::default(),
+            env_of(&[
+                ("RATE_LIMIT_BURST_SIZE", "40"),
+                ("RATE_LIMIT_UNAUTHENTICATED_BURST_SIZE", "2"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(config.rate_limit.authenticated, rate(10, 40));
+        assert_eq!(config.rate_limit.unauthenticated, rate(5, 2));
+    }
+
+    #[test]
+    fn test_invalid_env_falls_back_to_file_then_default() {
+        let config = ServerConfig::resolve(
+            file(r#"{ "request_timeout_seconds": 90 }"#),
+            env_of(&[("REQUEST_TIMEOUT_SECONDS", "-1"), ("TTS_POOL_SIZE", "many")]),
+        )
+        .unwrap();
+
+        assert_eq!(config.request_timeout, Duration::from_secs(90));
+        assert_eq!(config.pool_size, 2);
+    }
+
+    #[test]
+    fn test_zero_pool_size_is_an_error() {
+        let err = ServerConfig::resolve(FileConfig::default(), env_of(&[("TTS_POOL_SIZE", "0")]))
+            .unwrap_err();
+        assert!(err.contains("at least 1"));
+
+        assert!(ServerConfig::resolve(file(r#"{ "pool_size": 0 }"#), |_| None).is_err());
+    }
+
+    #[test]
+    fn test_huge_pool_size_is_clamped() {
+        let config =
+            ServerConfig::resolve(FileConfig::default(), env_of(&[("TTS_POOL_SIZE", "10000")]))
+                .unwrap();
+        assert_eq!(config.pool_size, DEFAULT_MAX_POOL_SIZE);
+
+        // The ceiling itself is configurable, from the file or the environment
+        let raised =
+            ServerConfig::resolve(file(r#"{ "pool_size": 40, "max_pool_size": 32 }"#), |_| {
+                None
+            })
+            .unwrap();
+        assert_eq!(raised.pool_size, 32);
+
+        let lowered = ServerConfig::resolve(
+            file(r#"{ "max_pool_size": 32 }"#),
+            env_of(&[("TTS_POOL_SIZE", "6"), ("TTS_MAX_POOL_SIZE", "4")]),
+        )
+        .unwrap();
+        assert_eq!(lowered.pool_size, 4);
+    }
+
+    #[test]
+    fn test_normal_pool_size_passes_through() {
+        for size in [1, 3, DEFAULT_MAX_POOL_SIZE] {
+            let config = ServerConfig::resolve(
+                FileConfig::default(),
+                env_of(&[("TTS_POOL_SIZE", &size.to_string())]),
+            )
+            .unwrap();
+            assert_eq!(config.pool_size, size);
+        }
+    }
+
+    #[test]
+    fn test_request_timeout_edge_values() {
+        for (value, expected) in [("0", 0), ("3600", 3600), ("invalid", 60)] {
+            let config = ServerConfig::resolve(
+                FileConfig::default(),
+                env_of(&[("REQUEST_TIMEOUT_SECONDS", value)]),
+            )
+            .unwrap();
+            assert_eq!(config.request_timeout, Duration::from_secs(expected));
+        }
+    }
+
+    #[test]
+    fn test_max_request_timeout_from_file_and_env() {
+        let json = r#"{ "max_request_timeout_seconds": 900 }"#;
+
+        let from_file = ServerConfig::resolve(file(json), |_| None).unwrap();
+        assert_eq!(from_file.max_request_timeout, Duration::from_secs(900));
+
+        let from_env = ServerConfig::resolve(
+            file(json),
+            env_of(&[("MAX_REQUEST_TIMEOUT_SECONDS", "120")]),
+        )
+        .unwrap();
+        assert_eq!(from_env.max_request_timeout, Duration::from_secs(120));
+    }
+
+    #[test]
+    fn test_unknown_rate_limit_mode_env_means_auto() {
+        let config = ServerConfig::resolve(
+            file(r#"{ "rate_limit": { "mode": "disabled" } }"#),
+            env_of(&[("RATE_LIMIT_MODE", "sometimes")]),
+        )
+        .unwrap();
+        assert_eq!(config.rate_limit.mode, RateLimitMode::Auto);
+    }
+
+    #[test]
+    fn test_chunking_min_length_from_file_and_env() {
+        let from_file =
+            ServerConfig::resolve(file(r#"{ "chunking_min_length": 500 }"#), |_| None).unwrap();
+        assert_eq!(from_file.chunking_min_length, 500);
+
+        let from_env = ServerConfig::resolve(
+            file(r#"{ "chunking_min_length": 500 }"#),
+            env_of(&[("CHUNKING_MIN_LENGTH", "80")]),
+        )
+        .unwrap();
+        assert_eq!(from_env.chunking_min_length, 80);
+    }
+
+    #[test]
+    fn test_espeak_data_dir_from_file_and_env() {
+        let json = r#"{ "espeak_data_dir": "/opt/porua/share" }"#;
+
+        let from_file = ServerConfig::resolve(file(json), |_| None).unwrap();
+        assert_eq!(
+            from_file.espeak_data_dir,
+            Some(PathBuf::from("/opt/porua/share"))
+        );
+
+        let from_env = ServerConfig::resolve(
+            file(json),
+            env_of(&[("PIPER_ESPEAKNG_DATA_DIRECTORY", "/usr/local/porua/share")]),
+        )
+        .unwrap();
+        assert_eq!(
+            from_env.espeak_data_dir,
+            Some(PathBuf::from("/usr/local/porua/share"))
+        );
+    }
+
+    #[test]
+    fn test_circuit_breaker_from_file_and_env() {
+        let config = ServerConfig::resolve(
+            file(r#"{ "circuit_breaker": { "failure_threshold": 3, "cooldown_seconds": 10 } }"#),
+            env_of(&[("CIRCUIT_BREAKER_COOLDOWN_SECONDS", "45")]),
+        )
+        .unwrap();
+
+        assert_eq!(config.circuit_breaker.failure_threshold, 3);
+        assert_eq!(config.circuit_breaker.window, Duration::from_secs(60));
+        assert_eq!(config.circuit_breaker.cooldown, Duration::from_secs(45));
+    }
+
+    #[test]
+    fn test_response_headers_from_file_and_env() {
+        let json = r#"{ "response_headers": { "X-Frame-Options": "DENY" } }"#;
+
+        let from_file = ServerConfig::resolve(file(json), |_| None).unwrap();
+        assert_eq!(from_file.response_headers.names(), vec!["x-frame-options"]);
+
+        // The environment replaces the file's headers as a whole
+        let from_env = ServerConfig::resolve(
+            file(json),
+            env_of(&[("RESPONSE_HEADERS", r#"{"Referrer-Policy": "no-referrer"}"#)]),
+        )
+        .unwrap();
+        assert_eq!(from_env.response_headers.names(), vec!["referrer-policy"]);
+
+        assert!(ServerConfig::resolve(
+            file(r#"{ "response_headers": { "Bad Name": "x" } }"#),
+            |_| None
+        )
+        .is_err());
+        assert!(
+            ServerConfig::resolve(file("{}"), env_of(&[("RESPONSE_HEADERS", "DENY")])).is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_flag() {
+        for value in ["true", "TRUE", " 1 ", "yes", "On"] {
+            assert_eq!(parse_flag("TTS_UNITS", value), Ok(true), "{:?}", value);
+        }
+        for value in ["false", "False", "0", "no", "OFF"] {
+            assert_eq!(parse_flag("TTS_UNITS", value), Ok(false), "{:?}", value);
+        }
+
+        let err = parse_flag("TTS_UNITS", "maybe").unwrap_err();
+        assert!(err.contains("TTS_UNITS"), "{}", err);
+        assert!(err.contains("maybe"), "{}", err);
+        assert!(parse_flag("TTS_UNITS", "").is_err());
+    }
+
+    fn env_only(vars: &[(&str, &str)]) -> ServerConfig {
+        ServerConfig::resolve(FileConfig::default(), env_of(vars)).unwrap()
+    }
+
+    #[test]
+    fn test_flat_settings_from_file() {
+        let config = ServerConfig::resolve(
+            file(
+                r#"{
+                    "unix_socket": "/run/porua.sock",
+                    "warmup": true,
+                    "fair_queue": true,
+                    "engine_selection": "round-robin",
+                    "max_in_flight_requests": 0,
+                    "min_speed": 0.5,
+                    "fallback_voice": "Narrator",
+                    "chunk_failure_mode": "abort",
+                    "concat_sample_rate": "max",
+                    "job_ttl_seconds": 30,
+                    "cache_dir": "/var/cache/porua",
+                    "lists": true,
+                    "quote_style": "spoken",
+                    "emoji_style": "words",
+                    "units": true
+                }"#,
+            ),
+            |_| None,
+        )
+        .unwrap();
+
+        assert_eq!(config.unix_socket, Some(PathBuf::from("/run/porua.sock")));
+        assert!(config.warmup);
+        assert!(config.fair_queue);
+        assert_eq!(config.engine_selection, EngineSelection::RoundRobin);
+        assert_eq!(config.max_in_flight_requests, Some(0));
+        assert_eq!(config.min_speed, 0.5);
+        assert_eq!(config.fallback_voice.as_deref(), Some("Narrator"));
+        assert_eq!(config.chunk_retry.failure_mode, ChunkFailureMode::Abort);
+        assert_eq!(config.concat_sample_rate, SampleRateMismatch::ResampleToMax);
+        assert_eq!(config.job_ttl, Duration::from_secs(30));
+        assert_eq!(config.cache_dir, Some(PathBuf::from("/var/cache/porua")));
+        assert_eq!(config.lists, Some(ListConfig::default()));
+        assert_eq!(config.quotes, QuoteStyle::Spoken);
+        assert_eq!(config.normalization.emoji_style, EmojiStyle::Words);
+        assert!(config.normalization.units);
+    }
+
+    #[test]
+    fn test_env_overrides_file_flags_and_names() {
+        let config = ServerConfig::resolve(
+            file(r#"{ "warmup": true, "engine_selection": "round-robin", "units": true }"#),
+            env_of(&[
+                ("TTS_WARMUP", "off"),
+                ("TTS_ENGINE_SELECTION", "free-first"),
+                ("TTS_UNITS", "no"),
+            ]),
+        )
+        .unwrap();
+
+        assert!(!config.warmup);
+        assert_eq!(config.engine_selection, EngineSelection::FreeFirst);
+        assert!(!config.normalization.units);
+    }
+
+    #[test]
+    fn test_invalid_flags_and_names_are_errors() {
+        for (name, value) in [
+            ("TTS_UNITS", "maybe"),
+            ("TTS_SPEED_CLAMP", "nope"),
+            ("TTS_VALIDATION_HEADERS", "sometimes"),
+            ("TTS_ENGINE_SELECTION", "fastest"),
+            ("TTS_MIN_SPEED", "0.01"),
+            ("TTS_CONCAT_SAMPLE_RATE", "bogus"),
+            ("TTS_QUOTE_STYLE", "shout"),
+        ] {
+            let err = ServerConfig::resolve(FileConfig::default(), env_of(&[(name, value)]));
+            assert!(err.is_err(), "{}={}", name, value);
+        }
+
+        assert!(ServerConfig::resolve(file(r#"{ "min_speed": 0.01 }"#), |_| None).is_err());
+        assert!(
+            ServerConfig::resolve(file(r#"{ "engine_selection": "fastest" }"#), |_| None).is_err()
+        );
+        // File flags are JSON booleans, so a string is rejected when the file is read
+        assert!(serde_json::from_str::<FileConfig>(r#"{ "units": "maybe" }"#).is_err());
+    }
+
+    #[test]
+    fn test_limits() {
+        let defaults = ServerConfig::default();
+        assert_eq!(defaults.max_in_flight_requests, None);
+        assert_eq!(defaults.max_connections, DEFAULT_MAX_CONNECTIONS);
+        assert_eq!(defaults.model_wait, Duration::ZERO);
+        assert_eq!(
+            defaults.max_audio_duration,
+            Duration::from_millis(DEFAULT_MAX_AUDIO_DURATION_MS)
+        );
+        assert_eq!(
+            defaults.max_normalized_length,
+            DEFAULT_MAX_NORMALIZED_LENGTH
+        );
+
+        let config = env_only(&[
+            ("MAX_IN_FLIGHT_REQUESTS", "16"),
+            ("MAX_CONNECTIONS", "0"),
+            ("TTS_MODEL_WAIT_SECONDS", "30"),
+            ("MAX_AUDIO_DURATION_MS", "60000"),
+            ("MAX_NORMALIZED_LENGTH", "lots"),
+        ]);
+        assert_eq!(config.max_in_flight_requests, Some(16));
+        assert_eq!(config.max_connections, 0);
+        assert_eq!(config.model_wait, Duration::from_secs(30));
+        assert_eq!(config.max_audio_duration, Duration::from_secs(60));
+        assert_eq!(config.max_normalized_length, DEFAULT_MAX_NORMALIZED_LENGTH);
+    }
+
+    #[test]
+    fn test_startup_flags() {
+        let defaults = ServerConfig::default();
+        assert!(!defaults.warmup);
+        assert!(!defaults.startup_selftest);
+        assert!(!defaults.fair_queue);
+        assert!(!defaults.speed_clamp);
+        assert!(!defaults.wav_metadata);
+        assert!(!defaults.validation_headers);
+
+        let config = env_only(&[
+            ("TTS_WARMUP", "true"),
+            ("TTS_STARTUP_SELFTEST", "true"),
+            ("TTS_FAIR_QUEUE", "true"),
+            ("TTS_SPEED_CLAMP", "true"),
+            ("TTS_WAV_METADATA", "true"),
+            ("TTS_VALIDATION_HEADERS", "true"),
+        ]);
+        assert!(config.warmup);
+        assert!(config.startup_selftest);
+        assert!(config.fair_queue);
+        assert!(config.speed_clamp);
+        assert!(config.wav_metadata);
+        assert!(config.validation_headers);
+    }
+
+    #[test]
+    fn test_speed_and_engine_selection() {
+        let defaults = ServerConfig::default();
+        assert_eq!(defaults.min_speed, DEFAULT_MIN_SPEED);
+        assert_eq!(defaults.engine_selection, EngineSelection::FreeFirst);
+
+        let config = env_only(&[
+            ("TTS_MIN_SPEED", "0.5"),
+            ("TTS_ENGINE_SELECTION", "round-robin"),
+        ]);
+        assert_eq!(config.min_speed, 0.5);
+        assert_eq!(config.engine_selection, EngineSelection::RoundRobin);
+    }
+
+    #[test]
+    fn test_unix_socket_and_fallback_voice() {
+        let defaults = ServerConfig::default();
+        assert_eq!(defaults.unix_socket, None);
+        assert_eq!(defaults.fallback_voice, None);
+
+        let config = env_only(&[
+            ("TTS_UNIX_SOCKET", "/run/porua.sock"),
+            ("TTS_FALLBACK_VOICE", " af_heart "),
+        ]);
+        assert_eq!(config.unix_socket, Some(PathBuf::from("/run/porua.sock")));
+        assert_eq!(config.fallback_voice.as_deref(), Some("af_heart"));
+
+        // Blank values count as unset
+        let blank = env_only(&[("TTS_UNIX_SOCKET", " "), ("TTS_FALLBACK_VOICE", " ")]);
+        assert_eq!(blank.unix_socket, None);
+        assert_eq!(blank.fallback_voice, None);
+    }
+
+    #[test]
+    fn test_voice_defaults_from_file_and_env() {
+        let json = r#"{ "voice_speeds": "am_santa=0.9", "voice_pitches": "am_santa=0.95" }"#;
+
+        let from_file = ServerConfig::resolve(file(json), |_| None).unwrap();
+        assert_eq!(from_file.voice_speeds.as_deref(), Some("am_santa=0.9"));
+        assert_eq!(from_file.voice_pitches.as_deref(), Some("am_santa=0.95"));
+
+        let from_env =
+            ServerConfig::resolve(file(json), env_of(&[("TTS_VOICE_SPEEDS", "af_sky=1.1")]))
+                .unwrap();
+        assert_eq!(from_env.voice_speeds.as_deref(), Some("af_sky=1.1"));
+        assert_eq!(from_env.voice_pitches.as_deref(), Some("am_santa=0.95"));
+    }
+
+    #[test]
+    fn test_long_tokens_and_chunk_retries() {
+        let defaults = ServerConfig::default();
+        assert_eq!(defaults.long_tokens, LongTokenConfig::default());
+        assert_eq!(defaults.chunk_retry, ChunkRetryConfig::default());
+
+        let config = env_only(&[
+            ("TTS_MAX_TOKEN_LENGTH", "40"),
+            ("TTS_LONG_TOKEN_POLICY", "reject"),
+            ("TTS_CHUNK_RETRIES", "0"),
+            ("TTS_CHUNK_FAILURE_MODE", "Abort"),
+        ]);
+        assert_eq!(config.long_tokens.max_length, 40);
+        assert_eq!(config.long_tokens.policy, LongTokenPolicy::Reject);
+        assert_eq!(config.chunk_retry.retries, 0);
+        assert_eq!(config.chunk_retry.failure_mode, ChunkFailureMode::Abort);
+
+        let invalid = env_only(&[("TTS_MAX_TOKEN_LENGTH", "lots")]);
+        assert_eq!(invalid.long_tokens.max_length, 100);
+    }
+
+    #[test]
+    fn test_stream_keep_alive() {
+        let default = Some(Duration::from_millis(DEFAULT_STREAM_KEEP_ALIVE_MS));
+        assert_eq!(ServerConfig::default().stream_keep_alive, default);
+
+        for (value, expected) in [
+            ("5000", Some(Duration::from_millis(5000))),
+            ("0", None),
+            ("often", default),
+        ] {
+            let config = env_only(&[("TTS_STREAM_KEEP_ALIVE_MS", value)]);
+            assert_eq!(config.stream_keep_alive, expected, "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_jobs() {
+        let defaults = ServerConfig::default();
+        assert_eq!(defaults.job_ttl, Duration::from_secs(DEFAULT_JOB_TTL_SECS));
+        assert_eq!(defaults.max_pending_jobs, DEFAULT_MAX_PENDING_JOBS);
+
+        let config = env_only(&[("TTS_JOB_TTL_SECONDS", "30"), ("TTS_MAX_PENDING_JOBS", "5")]);
+        assert_eq!(config.job_ttl, Duration::from_secs(30));
+        assert_eq!(config.max_pending_jobs, 5);
+
+        let invalid = env_only(&[
+            ("TTS_JOB_TTL_SECONDS", "soon"),
+            ("TTS_MAX_PENDING_JOBS", "0"),
+        ]);
+        assert_eq!(invalid.job_ttl, Duration::from_secs(DEFAULT_JOB_TTL_SECS));
+        assert_eq!(invalid.max_pending_jobs, DEFAULT_MAX_PENDING_JOBS);
+    }
+
+    #[test]
+    fn test_ellipsis_pause() {
+        assert_eq!(ServerConfig::default().ellipsis_pause, None);
+
+        for (value, expected) in [
+            ("600", Some(Duration::from_millis(600))),
+            ("60000", Some(Duration::from_millis(MAX_ELLIPSIS_PAUSE_MS))),
+            ("0", None),
+        ] {
+            let config = env_only(&[("TTS_ELLIPSIS_PAUSE_MS", value)]);
+            assert_eq!(config.ellipsis_pause, expected, "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_lists() {
+        assert_eq!(ServerConfig::default().lists, None);
+        assert_eq!(
+            env_only(&[("TTS_LISTS", "true")]).lists,
+            Some(ListConfig::default())
+        );
+
+        let config = env_only(&[
+            ("TTS_LISTS", "true"),
+            ("TTS_LIST_PAUSE_MS", "60000"),
+            ("TTS_LIST_NUMBERS", "false"),
+        ]);
+        assert_eq!(
+            config.lists,
+            Some(ListConfig {
+                pause: Duration::from_millis(MAX_ELLIPSIS_PAUSE_MS),
+                speak_numbers: false,
+            })
+        );
+
+        assert!(ServerConfig::resolve(
+            FileConfig::default(),
+            env_of(&[("TTS_LISTS", "true"), ("TTS_LIST_NUMBERS", "never")])
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_asides() {
+        assert_eq!(ServerConfig::default().asides, None);
+        assert_eq!(
+            env_only(&[("TTS_ASIDES", "true")]).asides,
+            Some(AsideConfig::default())
+        );
+
+        let config = env_only(&[
+            ("TTS_ASIDES", "true"),
+            ("TTS_ASIDE_SPEED_DELTA", "0.25"),
+            ("TTS_ASIDE_GAIN_DB", "loud"),
+        ]);
+        assert_eq!(
+            config.asides,
+            Some(AsideConfig {
+                speed_delta: 0.25,
+                gain_db: AsideConfig::default().gain_db,
+            })
+        );
+
+        let off = env_only(&[("TTS_ASIDES", "false"), ("TTS_ASIDE_SPEED_DELTA", "0.25")]);
+        assert_eq!(off.asides, None);
+    }
+
+    #[test]
+    fn test_quotes() {
+        assert_eq!(ServerConfig::default().quotes, QuoteStyle::Ignore);
+        assert_eq!(
+            env_only(&[("TTS_QUOTE_STYLE", "spoken")]).quotes,
+            QuoteStyle::Spoken
+        );
+
+        let config = env_only(&[
+            ("TTS_QUOTE_STYLE", "prosody"),
+            ("TTS_QUOTE_PITCH", "1.2"),
+            ("TTS_QUOTE_PAUSE_MS", "99999"),
+        ]);
+        assert_eq!(
+            config.quotes,
+            QuoteStyle::Prosody(QuoteProsody {
+                pitch_scale: 1.2,
+                pause: Duration::from_millis(MAX_ELLIPSIS_PAUSE_MS),
+                ..QuoteProsody::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_say_punctuation() {
+        assert_eq!(ServerConfig::default().say_punctuation, None);
+        assert_eq!(
+            env_only(&[("TTS_SAY_PUNCTUATION", "true")]).say_punctuation,
+            Some(PunctuationNames::default())
+        );
+
+        let names = env_only(&[
+            ("TTS_SAY_PUNCTUATION", "true"),
+            ("TTS_PUNCTUATION_NAMES", ".=full stop"),
+        ])
+        .say_punctuation
+        .unwrap();
+        assert_eq!(names.name('.'), Some("full stop"));
+        assert_eq!(names.name(','), Some("comma"));
+
+        assert!(ServerConfig::resolve(
+            FileConfig::default(),
+            env_of(&[
+                ("TTS_SAY_PUNCTUATION", "true"),
+                ("TTS_PUNCTUATION_NAMES", "full stop"),
+            ])
+        )
+        .is_err());
+
+        // Overrides alone don't turn the mode on
+        let off = env_only(&[("TTS_PUNCTUATION_NAMES", ".=full stop")]);
+        assert_eq!(off.say_punctuation, None);
+    }
+
+    #[test]
+    fn test_concat_sample_rate() {
+        assert_eq!(
+            ServerConfig::default().concat_sample_rate,
+            SampleRateMismatch::Strict
+        );
+        assert_eq!(
+            env_only(&[("TTS_CONCAT_SAMPLE_RATE", "max")]).concat_sample_rate,
+            SampleRateMismatch::ResampleToMax
+        );
+    }
+
+    #[test]
+    fn test_default_audio_format() {
+        assert_eq!(
+            ServerConfig::default().default_audio_format,
+            AudioFormat::Wav
+        );
+        assert_eq!(
+            env_only(&[("TTS_DEFAULT_AUDIO_FORMAT", " WAV ")]).default_audio_format,
+            AudioFormat::Wav
+        );
+        let from_file =
+            ServerConfig::resolve(file(r#"{ "default_audio_format": "wav" }"#), |_| None);
+        assert_eq!(from_file.unwrap().default_audio_format, AudioFormat::Wav);
+
+        // Unknown names and formats without an encoder fail at startup
+        for value in ["flac", "mp3", "ogg"] {
+            let env = env_of(&[("TTS_DEFAULT_AUDIO_FORMAT", value)]);
+            assert!(
+                ServerConfig::resolve(FileConfig::default(), env).is_err(),
+                "{}",
+                value
+            );
+        }
+        assert!(
+            ServerConfig::resolve(file(r#"{ "default_audio_format": "mpeg" }"#), |_| None).is_err()
+        );
+    }
+
+    #[test]
+    fn test_audio_cache() {
+        let defaults = ServerConfig::default();
+        assert_eq!(defaults.cache_dir, None);
+        assert_eq!(defaults.cache_max_bytes, DEFAULT_AUDIO_CACHE_MAX_BYTES);
+
+        let config = env_only(&[
+            ("TTS_CACHE_DIR", " /var/cache/porua "),
+            ("TTS_CACHE_MAX_BYTES", "1048576"),
+        ]);
+        assert_eq!(config.cache_dir, Some(PathBuf::from("/var/cache/porua")));
+        assert_eq!(config.cache_max_bytes, 1048576);
+
+        assert_eq!(env_only(&[("TTS_CACHE_DIR", " ")]).cache_dir, None);
+    }
+
+    #[test]
+    fn test_normalization_styles() {
+        let defaults = ServerConfig::default().normalization;
+        assert_eq!(defaults, NormalizationConfig::default());
+
+        let config = env_only(&[
+            ("TTS_FRACTION_STYLE", "Over"),
+            ("TTS_SYMBOL_STYLE", "FULL"),
+            ("TTS_EMOJI_STYLE", "KEEP"),
+        ])
+        .normalization;
+        assert_eq!(config.fraction_style, FractionStyle::Over);
+        assert_eq!(config.symbol_style, SymbolStyle::Full);
+        assert_eq!(config.emoji_style, EmojiStyle::Keep);
+
+        // Unrecognized styles mean the default
+        let bogus = env_only(&[
+            ("TTS_FRACTION_STYLE", "bogus"),
+            ("TTS_SYMBOL_STYLE", "bogus"),
+            ("TTS_EMOJI_STYLE", "bogus"),
+        ])
+        .normalization;
+        assert_eq!(bogus, defaults);
+    }
+
+    #[test]
+    fn test_normalization_passes_are_opt_in() {
+        let names = [
+            "TTS_ROMAN_NUMERALS",
+            "TTS_UNITS",
+            "TTS_TIME_ZONES",
+            "TTS_ALL_CAPS",
+            "TTS_DEHYPHENATE",
+            "TTS_DECADES",
+            "TTS_MATH",
+            "TTS_ALPHANUMERICS",
+            "TTS_VERSIONS",
+            "TTS_FOLD_FORMS",
+        ];
+        let on: Vec<(&str, &str)> = names.iter().map(|&name| (name, "true")).collect();
+        let config = env_only(&on).normalization;
+
+        let defaults = NormalizationConfig::default();
+        for (flag, default, enabled) in [
+            (
+                "roman_numerals",
+                defaults.roman_numerals,
+                config.roman_numerals,
+            ),
+            ("units", defaults.units, config.units),
+            ("time_zones", defaults.time_zones, config.time_zones),
+            ("all_caps", defaults.all_caps, config.all_caps),
+            ("dehyphenate", defaults.dehyphenate, config.dehyphenate),
+            ("decades", defaults.decades, config.decades),
+            ("math", defaults.math, config.math),
+            (
+                "alphanumerics",
+                defaults.alphanumerics,
+                config.alphanumerics,
+            ),
+            ("versions", defaults.versions, config.versions),
+            ("fold_forms", defaults.fold_forms, config.fold_forms),
+        ] {
+            assert!(!default, "{} is on by default", flag);
+            assert!(enabled, "{} was not enabled", flag);
+        }
+
+        for name in names {
+            let err = ServerConfig::resolve(FileConfig::default(), env_of(&[(name, "maybe")]))
+                .unwrap_err();
+            assert!(err.contains(name), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_read_rejects_unknown_fields_and_bad_json() {
+        let mut unknown = tempfile::NamedTempFile::new().unwrap();
+        write!(unknown, r#"{{ "pool_sise": 4 }}"#).unwrap();
+        let err = FileConfig::read(unknown.path()).unwrap_err();
+        assert!(err.contains("pool_sise"));
+
+        let mut broken = tempfile::NamedTempFile::new().unwrap();
+        write!(broken, "{{ not json").unwrap();
+        assert!(FileConfig::read(broken.path()).is_err());
+
+        assert!(FileConfig::read(Path::new("/nonexistent/porua.json")).is_err());
+    }
+
+    #[test]
+    fn test_read_valid_file() {
+        let mut config = tempfile::NamedTempFile::new().unwrap();
+        write!(config, r#"{{ "pool_size": 3 }}"#).unwrap();
+
+        let file = FileConfig::read(config.path()).unwrap();
+        assert_eq!(file.pool_size, Some(3));
+    }
+}
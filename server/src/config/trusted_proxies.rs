@@ -0,0 +1,211 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+/// An IP network in CIDR notation ("10.0.0.0/8", "fd00::/8", or a bare address)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpCidr {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl IpCidr {
+    /// Whether `ip` falls inside this network
+    ///
+    /// IPv4-mapped IPv6 addresses ("::ffff:10.0.0.1") match IPv4 networks.
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.addr, ip.to_canonical()) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => prefix_matches(
+                u32::from(net) as u128,
+                u32::from(ip) as u128,
+                self.prefix,
+                32,
+            ),
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                prefix_matches(u128::from(net), u128::from(ip), self.prefix, 128)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for IpCidr {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid trusted proxy: {}", value);
+
+        let (addr, prefix) = match value.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (value, None),
+        };
+        let addr = addr
+            .parse::<IpAddr>()
+            .map_err(|_| invalid())?
+            .to_canonical();
+        let max_prefix = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(prefix) => prefix.parse::<u8>().map_err(|_| invalid())?,
+            None => max_prefix,
+        };
+        if prefix > max_prefix {
+            return Err(invalid());
+        }
+
+        Ok(Self { addr, prefix })
+    }
+}
+
+impl std::fmt::Display for IpCidr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix)
+    }
+}
+
+/// Compare the top `prefix` bits of two addresses `bits` wide
+fn prefix_matches(net: u128, ip: u128, prefix: u8, bits: u32) -> bool {
+    if prefix == 0 {
+        return true;
+    }
+    let shift = bits - prefix as u32;
+    (net >> shift) == (ip >> shift)
+}
+
+/// Reverse proxies whose `X-Forwarded-For` / `X-Real-IP` headers are believed
+///
+/// Requests from any other peer are identified by their socket address, so
+/// clients can't dodge per-IP rate limits by sending forged headers. Defaults
+/// to loopback, for a proxy running on the same host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustedProxies {
+    networks: Vec<IpCidr>,
+}
+
+impl Default for TrustedProxies {
+    fn default() -> Self {
+        Self {
+            networks: vec![
+                IpCidr {
+                    addr: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 0)),
+                    prefix: 8,
+                },
+                IpCidr {
+                    addr: IpAddr::V6(Ipv6Addr::LOCALHOST),
+                    prefix: 128,
+                },
+            ],
+        }
+    }
+}
+
+impl TrustedProxies {
+    /// Trust no proxies: always use the socket peer address
+    pub fn none() -> Self {
+        Self {
+            networks: Vec::new(),
+        }
+    }
+
+    /// Load from `TRUSTED_PROXIES` (comma-separated CIDRs, or `none`)
+    pub fn from_env() -> Result<Self, String> {
+        Self::parse(std::env::var("TRUSTED_PROXIES").ok().as_deref())
+    }
+
+    /// Parse a comma-separated CIDR list; unset means loopback only
+    pub fn parse(value: Option<&str>) -> Result<Self, String> {
+        let value = match value.map(str::trim) {
+            None => return Ok(Self::default()),
+            Some("") => return Ok(Self::none()),
+            Some(value) if value.eq_ignore_ascii_case("none") => return Ok(Self::none()),
+            Some(value) => value,
+        };
+
+        let networks = value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { networks })
+    }
+
+    /// Whether requests from `ip` may set forwarding headers
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        self.networks.iter().any(|network| network.contains(ip))
+    }
+
+    /// Human-readable summary of the trusted networks
+    pub fn description(&self) -> String {
+        if self.networks.is_empty() {
+            return "none".to_string();
+        }
+        self.networks
+            .iter()
+            .map(|network| network.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_cidr_contains_ipv4() {
+        let network: IpCidr = "10.0.0.0/8".parse().unwrap();
+        assert!(network.contains(ip("10.1.2.3")));
+        assert!(!network.contains(ip("11.0.0.1")));
+        assert!(network.contains(ip("::ffff:10.9.9.9")));
+        assert!(!network.contains(ip("fd00::1")));
+    }
+
+    #[test]
+    fn test_cidr_contains_ipv6() {
+        let network: IpCidr = "fd00::/8".parse().unwrap();
+        assert!(network.contains(ip("fd12:3456::1")));
+        assert!(!network.contains(ip("fe80::1")));
+
+        let single: IpCidr = "2001:db8::7".parse().unwrap();
+        assert!(single.contains(ip("2001:db8::7")));
+        assert!(!single.contains(ip("2001:db8::8")));
+    }
+
+    #[test]
+    fn test_cidr_rejects_invalid() {
+        for value in ["10.0.0.0/33", "::/129", "not-an-ip", "10.0.0.0/x"] {
+            assert!(
+                value.parse::<IpCidr>().is_err(),
+                "{} should be rejected",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_defaults_to_loopback() {
+        let proxies = TrustedProxies::parse(None).unwrap();
+        assert!(proxies.contains(ip("127.0.0.1")));
+        assert!(proxies.contains(ip("::1")));
+        assert!(!proxies.contains(ip("10.0.0.1")));
+    }
+
+    #[test]
+    fn test_parse_list_and_none() {
+        let proxies = TrustedProxies::parse(Some("10.0.0.0/8, fd00::/8")).unwrap();
+        assert!(proxies.contains(ip("10.0.0.1")));
+        assert!(proxies.contains(ip("fd00::1")));
+        assert!(!proxies.contains(ip("127.0.0.1")));
+        assert_eq!(proxies.description(), "10.0.0.0/8, fd00::/8");
+
+        assert_eq!(
+            TrustedProxies::parse(Some("none")).unwrap(),
+            TrustedProxies::none()
+        );
+        assert!(TrustedProxies::parse(Some("10.0.0.0/8, bogus")).is_err());
+    }
+}
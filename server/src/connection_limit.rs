@@ -0,0 +1,297 @@
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, Request},
+    http::HeaderValue,
+    response::{IntoResponse, Response},
+    Router,
+};
+use hyper::body::Incoming;
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo},
+    server::conn::auto::Builder,
+    service::TowerToHyperService,
+};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpListener;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tower::ServiceExt;
+
+use crate::error::TtsError;
+
+/// Connections beyond the cap that may still be answered with a 503
+///
+/// Past this, excess sockets are closed without a response; answering an
+/// unbounded flood politely would exhaust file descriptors all the same.
+const SHED_CONNECTION_SLOTS: usize = 64;
+
+/// How long a shed connection may take to send its request
+const SHED_CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Backoff after a failed `accept`, e.g. when out of file descriptors
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Ceiling on simultaneously open client connections
+///
+/// Request-level limits only apply once a request has been read, so a flood
+/// of idle or slow sockets could otherwise exhaust file descriptors first.
+/// Connections over the cap get a 503 with `Connection: close`, except for
+/// `GET /health`, which is still served so monitoring can tell a saturated
+/// server from a dead one.
+#[derive(Clone)]
+pub struct ConnectionLimiter {
+    semaphore: Arc<Semaphore>,
+    max_connections: usize,
+}
+
+impl ConnectionLimiter {
+    /// Create a limiter that keeps at most `max_connections` connections open
+    pub fn new(max_connections: usize) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(max_connections)),
+            max_connections,
+        }
+    }
+
+    /// Try to admit a connection, returning a permit that frees the slot when dropped
+    pub fn try_acquire(&self) -> Option<OwnedSemaphorePermit> {
+        self.semaphore.clone().try_acquire_owned().ok()
+    }
+
+    /// Configured ceiling
+    pub fn max_connections(&self) -> usize {
+        self.max_connections
+    }
+
+    /// Number of connections currently open
+    pub fn open_connections(&self) -> usize {
+        self.max_connections - self.semaphore.available_permits()
+    }
+}
+
+/// Serve `app` on `listener`, shedding connections over the limiter's cap
+///
+/// Handlers can extract `ConnectInfo<SocketAddr>` just as with
+/// `into_make_service_with_connect_info`.
+pub async fn serve(
+    listener: TcpListener,
+    app: Router,
+    limiter: ConnectionLimiter,
+) -> std::io::Result<()> {
+    let shed_slots = Arc::new(Semaphore::new(SHED_CONNECTION_SLOTS));
+
+    loop {
+        let (stream, remote_addr) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(e) => {
+                tracing::error!("Failed to accept connection: {}", e);
+                tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                continue;
+            }
+        };
+
+        if let Some(permit) = limiter.try_acquire() {
+            let app = app.clone();
+            tokio::spawn(async move {
+                serve_connection(stream, Some(remote_addr), app, false).await;
+                drop(permit);
+            });
+            continue;
+        }
+
+        tracing::warn!(
+            "Shedding connection from {}: {} connections already open (max {})",
+            remote_addr,
+            limiter.open_connections(),
+            limiter.max_connections()
+        );
+
+        // Dropping the stream closes it outright when even the shed slots are full
+        let Ok(shed_permit) = shed_slots.clone().try_acquire_owned() else {
+            continue;
+        };
+        let app = app.clone();
+        tokio::spawn(async move {
+            let _ = tokio::time::timeout(
+                SHED_CONNECTION_TIMEOUT,
+                serve_connection(stream, Some(remote_addr), app, true),
+            )
+            .await;
+            drop(shed_permit);
+        });
+    }
+}
+
+/// Serve one connection; shed connections answer a single request and close
+///
+/// `remote_addr` is `None` for local sockets, whose peers have no IP address;
+/// handlers then see no `ConnectInfo`.
+pub(crate) async fn serve_connection<I>(
+    stream: I,
+    remote_addr: Option<SocketAddr>,
+    app: Router,
+    shed: bool,
+) where
+    I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let service = tower::service_fn(move |mut request: Request<Incoming>| {
+        if let Some(remote_addr) = remote_addr {
+            request.extensions_mut().insert(ConnectInfo(remote_addr));
+        }
+        let app = app.clone();
+        async move {
+            if shed && request.uri().path() != "/health" {
+                return Ok::<_, Infallible>(busy_response());
+            }
+            app.oneshot(request.map(Body::new)).await
+        }
+    });
+
+    let mut builder = Builder::new(TokioExecutor::new());
+    if shed {
+        builder.http1().keep_alive(false);
+    }
+
+    if let Err(e) = builder
+        .serve_connection_with_upgrades(TokioIo::new(stream), TowerToHyperService::new(service))
+        .await
+    {
+        match remote_addr {
+            Some(addr) => tracing::debug!("Connection from {} ended with error: {}", addr, e),
+            None => tracing::debug!("Local connection ended with error: {}", e),
+        }
+    }
+}
+
+fn busy_response() -> Response {
+    let mut response = TtsError::ServerBusy.into_response();
+    response
+        .headers_mut()
+        .insert("Retry-After", HeaderValue::from_static("1"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::get;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+
+    async fn start_server(max_connections: usize) -> (SocketAddr, ConnectionLimiter) {
+        let app =
+            Router::new()
+                .route(
+                    "/slow",
+                    get(|| async {
+                        tokio::time::sleep(Duration::from_millis(500)).await;
+                        "done"
+                    }),
+                )
+                .route("/health", get(|| async { "ok" }))
+                .route(
+                    "/peer",
+                    get(|ConnectInfo(addr): ConnectInfo<SocketAddr>| async move {
+                        addr.ip().to_string()
+                    }),
+                );
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let limiter = ConnectionLimiter::new(max_connections);
+        tokio::spawn(serve(listener, app, limiter.clone()));
+        (addr, limiter)
+    }
+
+    /// Send one request on a fresh connection and return the raw response
+    async fn get_raw(addr: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let request = format!(
+            "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+            path
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut response = Vec::new();
+        tokio::time::timeout(Duration::from_secs(2), stream.read_to_end(&mut response))
+            .await
+            .expect("response should not hang")
+            .unwrap();
+        String::from_utf8_lossy(&response).into_owned()
+    }
+
+    /// Open a connection that holds its slot until dropped
+    async fn hold_connection(addr: SocketAddr) -> TcpStream {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET /slow HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        stream
+    }
+
+    async fn wait_for_open(limiter: &ConnectionLimiter, expected: usize) {
+        for _ in 0..100 {
+            if limiter.open_connections() == expected {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!(
+            "expected {} open connections, found {}",
+            expected,
+            limiter.open_connections()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_serves_requests_under_cap() {
+        let (addr, limiter) = start_server(4).await;
+
+        let response = get_raw(addr, "/peer").await;
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response.ends_with("127.0.0.1"), "{}", response);
+
+        wait_for_open(&limiter, 0).await;
+    }
+
+    #[tokio::test]
+    async fn test_excess_connections_are_shed_with_503() {
+        let (addr, limiter) = start_server(2).await;
+        let _held = [hold_connection(addr).await, hold_connection(addr).await];
+        wait_for_open(&limiter, 2).await;
+
+        let responses = futures::future::join_all((0..10).map(|_| get_raw(addr, "/slow"))).await;
+        for response in responses {
+            assert!(response.starts_with("HTTP/1.1 503"), "{}", response);
+            assert!(response.contains("retry-after: 1"), "{}", response);
+        }
+        assert_eq!(limiter.open_connections(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_health_answers_at_cap() {
+        let (addr, limiter) = start_server(1).await;
+        let _held = hold_connection(addr).await;
+        wait_for_open(&limiter, 1).await;
+
+        let response = get_raw(addr, "/health").await;
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response.ends_with("ok"), "{}", response);
+    }
+
+    #[tokio::test]
+    async fn test_slot_freed_when_connection_closes() {
+        let (addr, limiter) = start_server(1).await;
+        let held = hold_connection(addr).await;
+        wait_for_open(&limiter, 1).await;
+        assert!(get_raw(addr, "/peer").await.starts_with("HTTP/1.1 503"));
+
+        drop(held);
+        wait_for_open(&limiter, 0).await;
+        assert!(get_raw(addr, "/peer").await.starts_with("HTTP/1.1 200"));
+    }
+}
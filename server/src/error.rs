@@ -1,18 +1,42 @@
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use std::fmt;
+use std::path::PathBuf;
+
+use crate::config::constants::{JOB_QUEUE_RETRY_AFTER_SECS, MAX_SPEED, VOICES_DOWNLOAD_URL};
 
 #[derive(Debug)]
 pub enum TtsError {
     // I/O errors
     Io(std::io::Error),
+    /// Reading or writing a temp audio file failed
+    TempIo {
+        operation: &'static str,
+        path: PathBuf,
+        source: std::io::Error,
+    },
     #[allow(dead_code)]
     FileNotFound(String),
 
     // TTS engine errors
     TtsEngine(String),
+    /// The voices file has no style for this voice
+    VoiceUnavailable(String),
+    ModelFilesMissing(Vec<String>),
+    /// The configured espeak-ng data directory can't be used for phonemization
+    EspeakDataInvalid(Vec<String>),
+    /// The voices file is truncated or isn't a voices archive at all
+    VoicesFileCorrupt(String),
     #[allow(dead_code)]
     PoolExhausted,
+    ServerBusy,
+    /// The request ran past its timeout
+    RequestTimeout(std::time::Duration),
+    ReloadInProgress,
+    /// The circuit breaker is open after repeated engine failures
+    EnginesUnavailable {
+        retry_after_secs: u64,
+    },
 
     // Audio processing errors
     AudioParsing(String),
@@ -22,7 +46,27 @@ pub enum TtsError {
     #[allow(dead_code)]
     InvalidRequest(String),
     EmptyText,
-    InvalidSpeed(f32),
+    /// A speed outside `[min, MAX_SPEED]`, where `min` is the configured minimum
+    InvalidSpeed {
+        speed: f32,
+        min: f32,
+    },
+    /// No format listed in `Accept` can be produced
+    NotAcceptable(String),
+    /// An upload whose content type isn't accepted
+    UnsupportedMediaType(String),
+    /// An upload over the size limit
+    PayloadTooLarge(String),
+
+    // Background job errors
+    /// Unknown or expired `/tts/jobs` ID
+    JobNotFound(String),
+    /// The job's audio isn't available (still running, or failed)
+    JobUnavailable(String),
+    /// The job can't be cancelled because it already finished
+    JobFinished(String),
+    /// Too many jobs are queued or running to accept another
+    JobQueueFull,
 
     // Auth errors
     #[allow(dead_code)]
@@ -39,16 +83,73 @@ impl fmt::Display for TtsError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             TtsError::Io(e) => write!(f, "I/O error: {}", e),
+            TtsError::TempIo {
+                operation,
+                path,
+                source,
+            } => write!(
+                f,
+                "Temp file {} failed for {}: {}",
+                operation,
+                path.display(),
+                source
+            ),
             TtsError::FileNotFound(path) => write!(f, "File not found: {}", path),
             TtsError::TtsEngine(msg) => write!(f, "TTS engine error: {}", msg),
+            TtsError::VoiceUnavailable(voice) => {
+                write!(f, "Voice '{}' is not in the voices file", voice)
+            }
+            TtsError::ModelFilesMissing(problems) => write!(
+                f,
+                "Model files unavailable: {}. Set TTS_MODEL_DIR to the directory containing \
+                 kokoro-v1.0.onnx and voices-v1.0.bin",
+                problems.join("; ")
+            ),
+            TtsError::VoicesFileCorrupt(problem) => write!(
+                f,
+                "Voices file is corrupt: {}. Delete it and download voices-v1.0.bin again \
+                 from {}",
+                problem, VOICES_DOWNLOAD_URL
+            ),
+            TtsError::EspeakDataInvalid(problems) => write!(
+                f,
+                "eSpeak-ng data unavailable: {}. Set PIPER_ESPEAKNG_DATA_DIRECTORY to the \
+                 directory containing espeak-ng-data",
+                problems.join("; ")
+            ),
             TtsError::PoolExhausted => write!(f, "TTS pool exhausted"),
+            TtsError::ServerBusy => write!(f, "Server is busy, too many requests in flight"),
+            TtsError::RequestTimeout(after) => {
+                write!(
+                    f,
+                    "Request timed out after {:.1} seconds",
+                    after.as_secs_f64()
+                )
+            }
+            TtsError::ReloadInProgress => write!(f, "A reload is already in progress"),
+            TtsError::EnginesUnavailable { retry_after_secs } => write!(
+                f,
+                "TTS engines are failing, try again in {} seconds",
+                retry_after_secs
+            ),
             TtsError::AudioParsing(msg) => write!(f, "Audio parsing error: {}", msg),
             TtsError::WavConcatenation(msg) => write!(f, "WAV concatenation error: {}", msg),
             TtsError::InvalidRequest(msg) => write!(f, "Invalid request: {}", msg),
             TtsError::EmptyText => write!(f, "Text cannot be empty"),
-            TtsError::InvalidSpeed(speed) => {
-                write!(f, "Invalid speed: {} (must be 0.0-3.0)", speed)
+            TtsError::InvalidSpeed { speed, min } => {
+                write!(
+                    f,
+                    "Invalid speed: {} (must be {}-{})",
+                    speed, min, MAX_SPEED
+                )
             }
+            TtsError::JobNotFound(id) => write!(f, "Job not found: {}", id),
+            TtsError::JobUnavailable(msg) => write!(f, "Job audio unavailable: {}", msg),
+            TtsError::JobFinished(msg) => write!(f, "Job already finished: {}", msg),
+            TtsError::JobQueueFull => write!(f, "Too many background jobs queued, try again later"),
+            TtsError::NotAcceptable(msg) => write!(f, "Not acceptable: {}", msg),
+            TtsError::UnsupportedMediaType(msg) => write!(f, "Unsupported media type: {}", msg),
+            TtsError::PayloadTooLarge(msg) => write!(f, "Payload too large: {}", msg),
             TtsError::Unauthorized => write!(f, "Unauthorized"),
             TtsError::InvalidApiKey => write!(f, "Invalid API key"),
             TtsError::TaskJoin(msg) => write!(f, "Task execution error: {}", msg),
@@ -93,14 +194,34 @@ impl From<Box<dyn std::error::Error>> for TtsError {
 // Axum integration
 impl IntoResponse for TtsError {
     fn into_response(self) -> Response {
+        let retry_after = match &self {
+            TtsError::EnginesUnavailable { retry_after_secs } => Some(*retry_after_secs),
+            TtsError::JobQueueFull => Some(JOB_QUEUE_RETRY_AFTER_SECS),
+            _ => None,
+        };
+
         let (status, message) = match self {
-            TtsError::EmptyText | TtsError::InvalidSpeed(_) | TtsError::InvalidRequest(_) => {
+            TtsError::EmptyText | TtsError::InvalidSpeed { .. } | TtsError::InvalidRequest(_) => {
                 (StatusCode::BAD_REQUEST, self.to_string())
             }
+            TtsError::NotAcceptable(_) => (StatusCode::NOT_ACCEPTABLE, self.to_string()),
+            TtsError::UnsupportedMediaType(_) => {
+                (StatusCode::UNSUPPORTED_MEDIA_TYPE, self.to_string())
+            }
+            TtsError::PayloadTooLarge(_) => (StatusCode::PAYLOAD_TOO_LARGE, self.to_string()),
             TtsError::Unauthorized | TtsError::InvalidApiKey => {
                 (StatusCode::UNAUTHORIZED, self.to_string())
             }
-            TtsError::FileNotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
+            TtsError::FileNotFound(_) | TtsError::JobNotFound(_) => {
+                (StatusCode::NOT_FOUND, self.to_string())
+            }
+            TtsError::ServerBusy | TtsError::EnginesUnavailable { .. } | TtsError::JobQueueFull => {
+                (StatusCode::SERVICE_UNAVAILABLE, self.to_string())
+            }
+            TtsError::RequestTimeout(_) => (StatusCode::REQUEST_TIMEOUT, self.to_string()),
+            TtsError::ReloadInProgress | TtsError::JobUnavailable(_) | TtsError::JobFinished(_) => {
+                (StatusCode::CONFLICT, self.to_string())
+            }
             _ => {
                 tracing::error!("Internal error: {}", self);
                 (
@@ -110,14 +231,21 @@ impl IntoResponse for TtsError {
             }
         };
 
-        (
+        let mut response = (
             status,
             axum::Json(serde_json::json!({
                 "status": "error",
                 "error": message
             })),
         )
-            .into_response()
+            .into_response();
+
+        if let Some(seconds) = retry_after {
+            response
+                .headers_mut()
+                .insert(axum::http::header::RETRY_AFTER, seconds.into());
+        }
+        response
     }
 }
 
@@ -189,7 +317,10 @@ mod tests {
 
     #[test]
     fn test_invalid_speed_returns_400() {
-        let err = TtsError::InvalidSpeed(5.0);
+        let err = TtsError::InvalidSpeed {
+            speed: 5.0,
+            min: 0.25,
+        };
         let response = err.into_response();
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
@@ -237,6 +368,31 @@ mod tests {
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
     }
 
+    #[test]
+    fn test_temp_io_error_returns_500() {
+        let err = TtsError::TempIo {
+            operation: "write",
+            path: PathBuf::from("/tmp/tts_x.wav"),
+            source: std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied"),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Temp file write failed for /tmp/tts_x.wav: denied"
+        );
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[test]
+    fn test_engines_unavailable_returns_503_with_retry_after() {
+        let err = TtsError::EnginesUnavailable {
+            retry_after_secs: 12,
+        };
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()["retry-after"], "12");
+    }
+
     #[test]
     fn test_pool_exhausted_returns_500() {
         let err = TtsError::PoolExhausted;
@@ -244,6 +400,68 @@ mod tests {
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
     }
 
+    #[test]
+    fn test_reload_in_progress_returns_409() {
+        let err = TtsError::ReloadInProgress;
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[test]
+    fn test_job_errors_return_404_and_409() {
+        let response = TtsError::JobNotFound("abc".to_string()).into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = TtsError::JobUnavailable("job is running".to_string()).into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = TtsError::JobFinished("job is done".to_string()).into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = TtsError::JobQueueFull.into_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            response.headers()["retry-after"],
+            JOB_QUEUE_RETRY_AFTER_SECS.to_string()
+        );
+    }
+
+    #[test]
+    fn test_not_acceptable_returns_406() {
+        let err = TtsError::NotAcceptable("audio/ogg".to_string());
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+    }
+
+    #[test]
+    fn test_unsupported_media_type_returns_415() {
+        let err = TtsError::UnsupportedMediaType("image/png".to_string());
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[test]
+    fn test_payload_too_large_returns_413() {
+        let err = TtsError::PayloadTooLarge("file exceeds 10000 bytes".to_string());
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[test]
+    fn test_server_busy_returns_503() {
+        let err = TtsError::ServerBusy;
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[test]
+    fn test_request_timeout_returns_408() {
+        let err = TtsError::RequestTimeout(std::time::Duration::from_millis(1500));
+        assert_eq!(err.to_string(), "Request timed out after 1.5 seconds");
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::REQUEST_TIMEOUT);
+    }
+
     // ===== Error Message Tests =====
 
     #[test]
@@ -254,9 +472,12 @@ mod tests {
 
     #[test]
     fn test_invalid_speed_message() {
-        let err = TtsError::InvalidSpeed(5.5);
+        let err = TtsError::InvalidSpeed {
+            speed: 5.5,
+            min: 0.25,
+        };
         assert!(err.to_string().contains("5.5"));
-        assert!(err.to_string().contains("0.0-3.0"));
+        assert!(err.to_string().contains("0.25-3"));
     }
 
     #[test]
@@ -282,6 +503,38 @@ mod tests {
         assert!(!display_str.is_empty());
     }
 
+    #[test]
+    fn test_model_files_missing_lists_paths_and_fix() {
+        let err = TtsError::ModelFilesMissing(vec![
+            "/models/kokoro-v1.0.onnx (not found)".to_string(),
+            "/models/voices-v1.0.bin (empty)".to_string(),
+        ]);
+        let message = err.to_string();
+        assert!(message
+            .contains("/models/kokoro-v1.0.onnx (not found); /models/voices-v1.0.bin (empty)"));
+        assert!(message.contains("TTS_MODEL_DIR"));
+    }
+
+    #[test]
+    fn test_voices_file_corrupt_says_to_download_again() {
+        let err = TtsError::VoicesFileCorrupt("/models/voices-v1.0.bin (truncated)".to_string());
+        let message = err.to_string();
+        assert!(message.contains("/models/voices-v1.0.bin (truncated)"));
+        assert!(message.contains("download voices-v1.0.bin again"));
+        assert!(message.contains(VOICES_DOWNLOAD_URL));
+    }
+
+    #[test]
+    fn test_espeak_data_invalid_lists_paths_and_fix() {
+        let err =
+            TtsError::EspeakDataInvalid(
+                vec!["/opt/espeak-ng-data/phontab (not found)".to_string()],
+            );
+        let message = err.to_string();
+        assert!(message.contains("/opt/espeak-ng-data/phontab (not found)"));
+        assert!(message.contains("PIPER_ESPEAKNG_DATA_DIRECTORY"));
+    }
+
     #[test]
     fn test_error_implements_debug() {
         let err = TtsError::EmptyText;
@@ -0,0 +1,337 @@
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+
+use crate::config::trusted_proxies::TrustedProxies;
+use crate::utils::header_utils::{extract_api_key, extract_client_ip};
+
+/// Client for work outside a request, or whose client can't be told apart
+pub const ANONYMOUS_CLIENT: &str = "anonymous";
+
+tokio::task_local! {
+    /// Client the current request's engine acquisitions are queued under
+    static CLIENT: Arc<str>;
+}
+
+/// Round-robin turns at the TTS pool across clients
+///
+/// Engines normally go to waiters in arrival order, so a client that submits
+/// many chunked requests at once can hold the pool while everyone else waits
+/// behind its whole backlog. With a fair queue, each client's waiters queue
+/// separately and turns rotate between clients, so a light client waits for
+/// about one acquisition per other busy client rather than for all of them.
+pub struct FairQueue {
+    trusted_proxies: TrustedProxies,
+    /// Tell clients apart by API key rather than by address
+    per_key: bool,
+    state: Mutex<QueueState>,
+}
+
+#[derive(Default)]
+struct QueueState {
+    /// Whether a caller currently holds the turn
+    busy: bool,
+    /// Clients with waiters, in the order their next turn comes up
+    rotation: VecDeque<Arc<str>>,
+    waiters: HashMap<Arc<str>, VecDeque<oneshot::Sender<()>>>,
+}
+
+impl FairQueue {
+    /// Create a queue keyed by API key when `per_key` is set, else by client IP
+    ///
+    /// Only key by API key when keys are checked; unchecked keys would let a
+    /// client claim a fresh turn with every made-up key.
+    pub fn new(trusted_proxies: TrustedProxies, per_key: bool) -> Self {
+        Self {
+            trusted_proxies,
+            per_key,
+            state: Mutex::new(QueueState::default()),
+        }
+    }
+
+    /// Wait for `client`'s turn
+    ///
+    /// The turn passes to the next client in rotation when the returned
+    /// [`Turn`] is dropped.
+    pub async fn turn(self: &Arc<Self>, client: Arc<str>) -> Turn {
+        let receiver = {
+            let mut state = self.lock();
+            if !state.busy {
+                state.busy = true;
+                return Turn {
+                    queue: self.clone(),
+                };
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            let waiters = state.waiters.entry(client.clone()).or_default();
+            waiters.push_back(sender);
+            if waiters.len() == 1 {
+                state.rotation.push_back(client);
+            }
+            receiver
+        };
+
+        let mut waiting = Waiting {
+            receiver: Some(receiver),
+            queue: self.clone(),
+        };
+        if let Some(receiver) = waiting.receiver.as_mut() {
+            // Senders are only dropped unsent along with the queue, which we hold
+            let _ = receiver.await;
+        }
+        waiting.receiver = None;
+
+        Turn {
+            queue: self.clone(),
+        }
+    }
+
+    /// Key identifying the client that sent `request`
+    fn client_key(&self, request: &Request) -> Arc<str> {
+        if self.per_key {
+            if let Some(key) = extract_api_key(request.headers()) {
+                return Arc::from(format!("key:{}", key));
+            }
+        }
+        match extract_client_ip(request, &self.trusted_proxies) {
+            Ok(ip) => Arc::from(format!("ip:{}", ip)),
+            Err(_) => Arc::from(ANONYMOUS_CLIENT),
+        }
+    }
+
+    /// Hand the turn to the next client in rotation, or free it
+    fn pass(&self) {
+        let mut guard = self.lock();
+        let state = &mut *guard;
+
+        while let Some(client) = state.rotation.pop_front() {
+            let Some(waiters) = state.waiters.get_mut(&client) else {
+                continue;
+            };
+            let next = waiters.pop_front();
+            if waiters.is_empty() {
+                state.waiters.remove(&client);
+            } else {
+                state.rotation.push_back(client);
+            }
+
+            // Waiters that gave up have dropped their receivers; skip them
+            if next.is_some_and(|sender| sender.send(()).is_ok()) {
+                return;
+            }
+        }
+
+        state.busy = false;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, QueueState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A client's turn at the pool, passed on when dropped
+pub struct Turn {
+    queue: Arc<FairQueue>,
+}
+
+impl Drop for Turn {
+    fn drop(&mut self) {
+        self.queue.pass();
+    }
+}
+
+/// A caller waiting for its turn
+///
+/// If the caller gives up after its turn was granted, the turn is passed on
+/// rather than lost.
+struct Waiting {
+    receiver: Option<oneshot::Receiver<()>>,
+    queue: Arc<FairQueue>,
+}
+
+impl Drop for Waiting {
+    fn drop(&mut self) {
+        if let Some(mut receiver) = self.receiver.take() {
+            receiver.close();
+            if receiver.try_recv().is_ok() {
+                self.queue.pass();
+            }
+        }
+    }
+}
+
+/// Run `future` with its pool acquisitions queued under `client`
+pub async fn with_client<F: Future>(client: Arc<str>, future: F) -> F::Output {
+    CLIENT.scope(client, future).await
+}
+
+/// Wrap a future to be spawned so it stays queued under the current client
+pub fn inherit_client<F: Future>(future: F) -> impl Future<Output = F::Output> {
+    CLIENT.scope(current_client(), future)
+}
+
+/// Client of the request being handled ([`ANONYMOUS_CLIENT`] outside one)
+pub fn current_client() -> Arc<str> {
+    CLIENT
+        .try_with(Arc::clone)
+        .unwrap_or_else(|_| Arc::from(ANONYMOUS_CLIENT))
+}
+
+/// Middleware recording which client a request belongs to
+///
+/// Engine acquisitions made while handling the request, including from
+/// tasks spawned with [`inherit_client`], are queued under that client.
+pub async fn client_key_middleware(
+    State(queue): State<Arc<FairQueue>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let client = queue.client_key(&request);
+    with_client(client, next.run(request)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kokoro::fake::fake_pool;
+    use crate::kokoro::TTSPool;
+    use std::time::Duration;
+
+    fn waiting(queue: &FairQueue) -> usize {
+        queue.lock().waiters.values().map(VecDeque::len).sum()
+    }
+
+    /// Order in which one heavy client's ten acquisitions and a light
+    /// client's single acquisition get an engine from a one-engine pool
+    async fn acquisition_order(pool: TTSPool) -> Vec<String> {
+        let pool = Arc::new(pool);
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let acquire = |client: &str, name: String| {
+            let pool = pool.clone();
+            let order = order.clone();
+            tokio::spawn(with_client(Arc::from(client), async move {
+                let tts = pool.acquire().await.unwrap();
+                order.lock().unwrap().push(name);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                drop(tts);
+            }))
+        };
+
+        let mut tasks: Vec<_> = (0..10)
+            .map(|i| acquire("heavy", format!("heavy-{}", i)))
+            .collect();
+        // Let the heavy client's requests all queue up first
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        tasks.push(acquire("light", "light".to_string()));
+
+        for task in tasks {
+            task.await.unwrap();
+        }
+        let order = order.lock().unwrap().clone();
+        order
+    }
+
+    #[tokio::test]
+    async fn test_light_client_is_not_starved_by_heavy_client() {
+        let (pool, _) = fake_pool(1, 100, Duration::ZERO);
+        let pool = pool.with_fair_queue(FairQueue::new(TrustedProxies::default(), true));
+
+        let order = acquisition_order(pool).await;
+        let light = order.iter().position(|name| name == "light").unwrap();
+        // Behind the engine's holder, the caller already waiting for it and
+        // at most one turn that was due to the heavy client
+        assert!(light <= 3, "light client served at {}: {:?}", light, order);
+        assert_eq!(order.len(), 11);
+    }
+
+    #[tokio::test]
+    async fn test_without_fair_queue_acquisition_is_fifo() {
+        let (pool, _) = fake_pool(1, 100, Duration::ZERO);
+
+        let order = acquisition_order(pool).await;
+        assert_eq!(order.last().map(String::as_str), Some("light"));
+    }
+
+    #[tokio::test]
+    async fn test_turns_rotate_between_clients() {
+        let queue = Arc::new(FairQueue::new(TrustedProxies::default(), true));
+        let first = queue.turn(Arc::from("a")).await;
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut tasks = Vec::new();
+        for client in ["a", "a", "a", "b", "b"] {
+            let queue = queue.clone();
+            let order = order.clone();
+            tasks.push(tokio::spawn(async move {
+                let _turn = queue.turn(Arc::from(client)).await;
+                order.lock().unwrap().push(client);
+            }));
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(waiting(&queue), 5);
+
+        drop(first);
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert_eq!(*order.lock().unwrap(), vec!["a", "b", "a", "b", "a"]);
+        assert_eq!(waiting(&queue), 0);
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_waiter_does_not_stall_queue() {
+        let queue = Arc::new(FairQueue::new(TrustedProxies::default(), true));
+        let first = queue.turn(Arc::from("a")).await;
+
+        let abandoned = {
+            let queue = queue.clone();
+            tokio::spawn(async move { queue.turn(Arc::from("b")).await })
+        };
+        tokio::task::yield_now().await;
+        abandoned.abort();
+        let _ = abandoned.await;
+
+        drop(first);
+        let next = tokio::time::timeout(Duration::from_secs(1), queue.turn(Arc::from("c"))).await;
+        assert!(next.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_current_client_follows_scope() {
+        assert_eq!(&*current_client(), ANONYMOUS_CLIENT);
+
+        let client = with_client(Arc::from("ip:10.0.0.1"), async {
+            tokio::spawn(inherit_client(async { current_client() }))
+                .await
+                .unwrap()
+        })
+        .await;
+        assert_eq!(&*client, "ip:10.0.0.1");
+    }
+
+    #[test]
+    fn test_client_key_uses_api_key_only_when_per_key() {
+        let request = || {
+            let mut request = Request::new(axum::body::Body::empty());
+            request
+                .headers_mut()
+                .insert("x-api-key", "secret".parse().unwrap());
+            request
+        };
+
+        let per_key = FairQueue::new(TrustedProxies::default(), true);
+        assert_eq!(&*per_key.client_key(&request()), "key:secret");
+
+        // Without a known address every client shares one queue
+        let per_ip = FairQueue::new(TrustedProxies::default(), false);
+        assert_eq!(&*per_ip.client_key(&request()), ANONYMOUS_CLIENT);
+    }
+}
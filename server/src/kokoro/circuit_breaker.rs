@@ -0,0 +1,337 @@
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Whether the pool is accepting work
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    /// Engines are healthy; requests go through
+    Closed,
+    /// Engines kept failing; requests fail fast until the cooldown ends
+    Open,
+    /// Cooldown over; one probe request decides whether to close or reopen
+    HalfOpen,
+}
+
+impl BreakerState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BreakerState::Closed => "closed",
+            BreakerState::Open => "open",
+            BreakerState::HalfOpen => "half-open",
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => BreakerState::Open,
+            2 => BreakerState::HalfOpen,
+            _ => BreakerState::Closed,
+        }
+    }
+
+    fn as_u8(self) -> u8 {
+        match self {
+            BreakerState::Closed => 0,
+            BreakerState::Open => 1,
+            BreakerState::HalfOpen => 2,
+        }
+    }
+}
+
+/// When the breaker trips and how long it stays open
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BreakerConfig {
+    /// Consecutive engine failures that open the breaker (0 = never open)
+    pub failure_threshold: u32,
+    /// The failures must all fall within this window of the first one
+    #[serde(
+        rename = "window_ms",
+        serialize_with = "crate::utils::millis::serialize"
+    )]
+    pub window: Duration,
+    /// How long the breaker stays open before letting a probe through
+    #[serde(
+        rename = "cooldown_ms",
+        serialize_with = "crate::utils::millis::serialize"
+    )]
+    pub cooldown: Duration,
+}
+
+impl Default for BreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            window: Duration::from_secs(60),
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Circuit breaker guarding the engine pool
+///
+/// After `failure_threshold` consecutive engine failures within `window` the
+/// breaker opens and new requests are refused without touching an engine.
+/// Once `cooldown` has passed it half-opens and admits a single probe: a
+/// success closes it again, a failure reopens it for another cooldown.
+pub struct CircuitBreaker {
+    config: BreakerConfig,
+    /// Reference point for the millisecond timestamps below
+    epoch: Instant,
+    state: AtomicU8,
+    consecutive_failures: AtomicU32,
+    first_failure_ms: AtomicU64,
+    opened_at_ms: AtomicU64,
+    probe_in_flight: AtomicBool,
+    times_opened: AtomicU64,
+}
+
+/// Admission decision for one request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    /// Normal request while the breaker is closed
+    Allowed,
+    /// The single request allowed through a half-open breaker
+    Probe,
+    /// Refused; the breaker is open (or a probe is already running)
+    Rejected { retry_after: Duration },
+}
+
+/// The half-open probe, given back to the breaker when dropped
+///
+/// A probe that never reports an outcome (its request was cancelled, or
+/// never reached an engine) would otherwise keep the breaker half-open
+/// with every later request rejected.
+pub struct ProbeGuard {
+    breaker: Arc<CircuitBreaker>,
+}
+
+impl ProbeGuard {
+    pub fn new(breaker: Arc<CircuitBreaker>) -> Self {
+        Self { breaker }
+    }
+}
+
+impl Drop for ProbeGuard {
+    fn drop(&mut self) {
+        self.breaker.release_probe();
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(BreakerConfig::default())
+    }
+}
+
+impl CircuitBreaker {
+    pub fn new(config: BreakerConfig) -> Self {
+        Self {
+            config,
+            epoch: Instant::now(),
+            state: AtomicU8::new(BreakerState::Closed.as_u8()),
+            consecutive_failures: AtomicU32::new(0),
+            first_failure_ms: AtomicU64::new(0),
+            opened_at_ms: AtomicU64::new(0),
+            probe_in_flight: AtomicBool::new(false),
+            times_opened: AtomicU64::new(0),
+        }
+    }
+
+    /// Decide whether a new request may use an engine
+    pub fn admit(&self) -> Admission {
+        match self.state() {
+            BreakerState::Closed => Admission::Allowed,
+            BreakerState::Open => {
+                let open_for = self.elapsed_since(self.opened_at_ms.load(Ordering::SeqCst));
+                if open_for < self.config.cooldown {
+                    return Admission::Rejected {
+                        retry_after: self.config.cooldown - open_for,
+                    };
+                }
+                // Only one caller moves the breaker on; the rest see half-open
+                let _ = self.state.compare_exchange(
+                    BreakerState::Open.as_u8(),
+                    BreakerState::HalfOpen.as_u8(),
+                    Ordering::SeqCst,
+                    Ordering::SeqCst,
+                );
+                self.admit_probe()
+            }
+            BreakerState::HalfOpen => self.admit_probe(),
+        }
+    }
+
+    /// Record a successful synthesis; closes a half-open breaker
+    pub fn record_success(&self) {
+        self.consecutive_failures.store(0, Ordering::SeqCst);
+        if self.state() == BreakerState::HalfOpen {
+            tracing::info!("Engine probe succeeded; circuit breaker closed");
+        }
+        self.state
+            .store(BreakerState::Closed.as_u8(), Ordering::SeqCst);
+        self.probe_in_flight.store(false, Ordering::SeqCst);
+    }
+
+    /// Record a failed synthesis; may open (or reopen) the breaker
+    pub fn record_failure(&self) {
+        if self.config.failure_threshold == 0 {
+            return;
+        }
+
+        let now = self.now_ms();
+        if self.state() == BreakerState::HalfOpen {
+            tracing::warn!("Engine probe failed; circuit breaker reopened");
+            self.open(now);
+            return;
+        }
+
+        // A failure outside the window starts a new streak
+        let first = self.first_failure_ms.load(Ordering::SeqCst);
+        let failures = if self.consecutive_failures.load(Ordering::SeqCst) == 0
+            || now.saturating_sub(first) > self.config.window.as_millis() as u64
+        {
+            self.first_failure_ms.store(now, Ordering::SeqCst);
+            self.consecutive_failures.store(1, Ordering::SeqCst);
+            1
+        } else {
+            self.consecutive_failures.fetch_add(1, Ordering::SeqCst) + 1
+        };
+
+        if failures >= self.config.failure_threshold && self.state() == BreakerState::Closed {
+            tracing::error!(
+                "{} consecutive engine failures; circuit breaker open for {:?}",
+                failures,
+                self.config.cooldown
+            );
+            self.open(now);
+        }
+    }
+
+    /// Give up a probe that never reached an engine, so another can try
+    pub fn release_probe(&self) {
+        self.probe_in_flight.store(false, Ordering::SeqCst);
+    }
+
+    pub fn state(&self) -> BreakerState {
+        BreakerState::from_u8(self.state.load(Ordering::SeqCst))
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures.load(Ordering::SeqCst)
+    }
+
+    /// How many times the breaker has opened since startup
+    pub fn times_opened(&self) -> u64 {
+        self.times_opened.load(Ordering::SeqCst)
+    }
+
+    fn admit_probe(&self) -> Admission {
+        match self
+            .probe_in_flight
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        {
+            Ok(_) => Admission::Probe,
+            // The probe should settle quickly; ask again after a short wait
+            Err(_) => Admission::Rejected {
+                retry_after: Duration::from_secs(1),
+            },
+        }
+    }
+
+    fn open(&self, now_ms: u64) {
+        self.opened_at_ms.store(now_ms, Ordering::SeqCst);
+        self.state
+            .store(BreakerState::Open.as_u8(), Ordering::SeqCst);
+        self.probe_in_flight.store(false, Ordering::SeqCst);
+        self.times_opened.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn now_ms(&self) -> u64 {
+        self.epoch.elapsed().as_millis() as u64
+    }
+
+    fn elapsed_since(&self, ms: u64) -> Duration {
+        Duration::from_millis(self.now_ms().saturating_sub(ms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(failure_threshold: u32, cooldown_ms: u64) -> CircuitBreaker {
+        CircuitBreaker::new(BreakerConfig {
+            failure_threshold,
+            window: Duration::from_secs(60),
+            cooldown: Duration::from_millis(cooldown_ms),
+        })
+    }
+
+    #[test]
+    fn test_opens_after_consecutive_failures() {
+        let breaker = breaker(3, 10_000);
+
+        breaker.record_failure();
+        breaker.record_failure();
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        assert_eq!(breaker.admit(), Admission::Allowed);
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), BreakerState::Open);
+        assert_eq!(breaker.times_opened(), 1);
+        assert!(matches!(breaker.admit(), Admission::Rejected { .. }));
+    }
+
+    #[test]
+    fn test_success_resets_the_streak() {
+        let breaker = breaker(2, 10_000);
+
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        assert_eq!(breaker.consecutive_failures(), 1);
+    }
+
+    #[test]
+    fn test_failures_outside_window_start_a_new_streak() {
+        let breaker = CircuitBreaker::new(BreakerConfig {
+            failure_threshold: 2,
+            window: Duration::from_millis(20),
+            cooldown: Duration::from_secs(10),
+        });
+
+        breaker.record_failure();
+        std::thread::sleep(Duration::from_millis(40));
+        breaker.record_failure();
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        assert_eq!(breaker.consecutive_failures(), 1);
+    }
+
+    #[test]
+    fn test_half_open_admits_one_probe() {
+        let breaker = breaker(1, 20);
+        breaker.record_failure();
+        assert!(matches!(breaker.admit(), Admission::Rejected { .. }));
+
+        std::thread::sleep(Duration::from_millis(40));
+        assert_eq!(breaker.admit(), Admission::Probe);
+        assert_eq!(breaker.state(), BreakerState::HalfOpen);
+        assert!(matches!(breaker.admit(), Admission::Rejected { .. }));
+
+        // An abandoned probe lets the next request try instead
+        breaker.release_probe();
+        assert_eq!(breaker.admit(), Admission::Probe);
+    }
+
+    #[test]
+    fn test_zero_threshold_never_opens() {
+        let breaker = breaker(0, 10_000);
+        for _ in 0..100 {
+            breaker.record_failure();
+        }
+        assert_eq!(breaker.state(), BreakerState::Closed);
+    }
+}
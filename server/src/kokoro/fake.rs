@@ -0,0 +1,195 @@
+use hound::{SampleFormat, WavSpec, WavWriter};
+use std::error::Error;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::{SpeechEngine, TTSPool};
+
+/// Sample rate of the audio written by [`FakeEngine`], matching Kokoro's output
+pub const FAKE_SAMPLE_RATE: u32 = 24000;
+
+/// Record of the work done by fake engines, shared with the test
+#[derive(Default)]
+pub struct FakeEngineLog {
+    calls: AtomicUsize,
+    texts: Mutex<Vec<String>>,
+    voices: Mutex<Vec<String>>,
+    speeds: Mutex<Vec<f32>>,
+    outputs: Mutex<Vec<PathBuf>>,
+}
+
+impl FakeEngineLog {
+    /// Number of `speak` calls so far
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    /// Text passed to every `speak` call, in call order
+    pub fn texts(&self) -> Vec<String> {
+        self.texts.lock().unwrap().clone()
+    }
+
+    /// Voice passed to every `speak` call, in call order
+    pub fn voices(&self) -> Vec<String> {
+        self.voices.lock().unwrap().clone()
+    }
+
+    /// Speed passed to every `speak` call, in call order
+    pub fn speeds(&self) -> Vec<f32> {
+        self.speeds.lock().unwrap().clone()
+    }
+
+    /// Every output path that was written
+    pub fn outputs(&self) -> Vec<PathBuf> {
+        self.outputs.lock().unwrap().clone()
+    }
+}
+
+/// Test double that writes silent WAV audio instead of running the model
+pub struct FakeEngine {
+    /// Duration of the generated clip at speed 1.0 (scaled by 1/speed)
+    pub duration_ms: u32,
+    /// Simulated synthesis time
+    pub delay: Duration,
+    /// Shared call log
+    pub log: Arc<FakeEngineLog>,
+}
+
+impl SpeechEngine for FakeEngine {
+    fn speak(
+        &self,
+        text: &str,
+        output_path: &str,
+        style: &str,
+        speed: f32,
+        mono: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        self.log.calls.fetch_add(1, Ordering::SeqCst);
+        self.log.texts.lock().unwrap().push(text.to_string());
+        self.log.voices.lock().unwrap().push(style.to_string());
+        self.log.speeds.lock().unwrap().push(speed);
+        std::thread::sleep(self.delay);
+
+        let channels = if mono { 1 } else { 2 };
+        let spec = WavSpec {
+            channels,
+            sample_rate: FAKE_SAMPLE_RATE,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+
+        let duration_ms = self.duration_ms as f64 / speed as f64;
+        let frames = (FAKE_SAMPLE_RATE as f64 * duration_ms / 1000.0).round() as u64;
+        let mut writer = WavWriter::create(output_path, spec)?;
+        for _ in 0..frames * channels as u64 {
+            writer.write_sample(0i16)?;
+        }
+        writer.finalize()?;
+
+        self.log
+            .outputs
+            .lock()
+            .unwrap()
+            .push(PathBuf::from(output_path));
+
+        Ok(())
+    }
+}
+
+/// Test double that writes a tone derived from the text instead of silence
+///
+/// The output is a pure function of the inputs, so any difference between
+/// two responses comes from the server's own processing. Unlike
+/// [`FakeEngine`] it has non-zero samples that resampling and pitch shifting
+/// actually change.
+pub struct ToneEngine;
+
+impl SpeechEngine for ToneEngine {
+    fn speak(
+        &self,
+        text: &str,
+        output_path: &str,
+        _style: &str,
+        speed: f32,
+        mono: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        let channels = if mono { 1 } else { 2 };
+        let spec = WavSpec {
+            channels,
+            sample_rate: FAKE_SAMPLE_RATE,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+
+        // 60ms per character at speed 1.0, pitched by the text's length
+        let frames =
+            (text.chars().count() as f64 * 0.06 / speed as f64 * FAKE_SAMPLE_RATE as f64) as u64;
+        let frequency = 200.0 + (text.len() % 20) as f64 * 10.0;
+        let mut writer = WavWriter::create(output_path, spec)?;
+        for frame in 0..frames {
+            let t = frame as f64 / FAKE_SAMPLE_RATE as f64;
+            let sample = (8000.0 * (2.0 * std::f64::consts::PI * frequency * t).sin()) as i16;
+            for _ in 0..channels {
+                writer.write_sample(sample)?;
+            }
+        }
+        writer.finalize()?;
+        Ok(())
+    }
+}
+
+/// Build a pool of `size` tone engines
+pub fn tone_pool(size: usize) -> TTSPool {
+    let engines = (0..size)
+        .map(|_| Box::new(ToneEngine) as Box<dyn SpeechEngine>)
+        .collect();
+    TTSPool::from_engines(engines).unwrap()
+}
+
+/// Build a pool of `size` fake engines sharing one call log
+pub fn fake_pool(size: usize, duration_ms: u32, delay: Duration) -> (TTSPool, Arc<FakeEngineLog>) {
+    let log = Arc::new(FakeEngineLog::default());
+    let engines = (0..size)
+        .map(|_| {
+            Box::new(FakeEngine {
+                duration_ms,
+                delay,
+                log: log.clone(),
+            }) as Box<dyn SpeechEngine>
+        })
+        .collect();
+
+    (TTSPool::from_engines(engines).unwrap(), log)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fake_pool_generates_requested_duration() {
+        let (pool, log) = fake_pool(1, 500, Duration::ZERO);
+        let path = std::env::temp_dir().join(format!("fake_{}.wav", uuid::Uuid::new_v4()));
+        let path_str = path.to_str().unwrap().to_string();
+
+        let tts = pool.acquire().await.unwrap();
+        tts.speak("hello", &path_str, "af_heart", 1.0, true)
+            .await
+            .unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        let duration = crate::audio::duration::calculate(&bytes).unwrap();
+        assert!((duration - 500.0).abs() < 1.0);
+        assert_eq!(log.calls(), 1);
+        assert_eq!(log.outputs(), vec![path.clone()]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_from_engines_rejects_empty() {
+        assert!(TTSPool::from_engines(Vec::new()).is_err());
+    }
+}
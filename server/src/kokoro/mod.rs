@@ -1,12 +1,76 @@
+pub mod circuit_breaker;
+#[cfg(test)]
+pub mod fake;
 pub mod model_paths;
+// Used by the generate_samples binary, not the server
+#[allow(dead_code)]
+pub mod sample_sentences;
+pub mod voice_aliases;
 pub mod voice_config;
+pub mod voice_defaults;
 
 use kokoros::tts::koko::{TTSKoko, TTSOpts};
+use serde::Serialize;
 use std::error::Error;
+use std::future::Future;
+use std::path::Path;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, Semaphore};
 
+use crate::error::{Result as TtsResult, TtsError};
+use crate::fair_queue::{self, FairQueue};
+use crate::utils::temp_file::TempFile;
+use circuit_breaker::{Admission, BreakerConfig, BreakerState, CircuitBreaker, ProbeGuard};
+
+/// Text synthesized by each engine during warm-up
+const WARMUP_TEXT: &str = "Hello.";
+
+/// Voice used for warm-up synthesis
+const WARMUP_VOICE: &str = "bf_lily";
+
+/// Phrase synthesized by the startup self-test
+const SELF_TEST_TEXT: &str = "The quick brown fox jumps over the lazy dog.";
+
+/// Plausible length of the self-test phrase, in ms
+const SELF_TEST_DURATION_MS: std::ops::RangeInclusive<f64> = 1000.0..=10_000.0;
+
+/// Language the engine phonemizes and speaks in
+const ENGINE_LANGUAGE: &str = "en-us";
+
+/// A speech synthesis backend that the pool hands out to requests
+///
+/// Implemented by the Kokoro-backed [`TTS`]; tests substitute lightweight fakes.
+pub trait SpeechEngine: Send + Sync {
+    /// Synthesize `text` to a WAV file at `output_path`
+    fn speak(
+        &self,
+        text: &str,
+        output_path: &str,
+        style: &str,
+        speed: f32,
+        mono: bool,
+    ) -> Result<(), Box<dyn Error>>;
+}
+
+/// How Kokoro reports a voice style missing from the voices file
+const MISSING_STYLE_MESSAGE: &str = "can not found from styles_map: ";
+
+/// Turn a synthesis error into a `TtsError`
+///
+/// Kokoro reports a missing voice only as text, so that message is recognized
+/// here, next to the engine, and becomes `TtsError::VoiceUnavailable`.
+pub fn engine_error(error: impl std::fmt::Display) -> TtsError {
+    let message = error.to_string();
+    match message.split_once(MISSING_STYLE_MESSAGE) {
+        Some((_, voice)) if !voice.trim().is_empty() => {
+            TtsError::VoiceUnavailable(voice.trim().to_string())
+        }
+        _ => TtsError::TtsEngine(message),
+    }
+}
+
 #[allow(clippy::upper_case_acronyms)]
 pub struct TTS {
     engine: TTSKoko,
@@ -23,126 +87,586 @@ impl TTS {
         Ok(TTS { engine })
     }
 
+    /// Synthesize `text` to a WAV file at `output_path`
+    ///
+    /// When `mono` is false the engine duplicates the (mono) speech signal
+    /// into two channels.
     pub fn speak(
         &self,
         text: &str,
         output_path: &str,
         style: &str,
         speed: f32,
+        mono: bool,
     ) -> Result<(), Box<dyn Error>> {
         self.engine.tts(TTSOpts {
             txt: text,
-            lan: "en-us",
+            lan: ENGINE_LANGUAGE,
             style_name: style,
             save_path: output_path,
-            mono: false,
+            mono,
             speed,
             initial_silence: None,
         })?;
         Ok(())
     }
+
+    /// Phonemize `text` with the same espeak backend Kokoro uses for synthesis
+    ///
+    /// Needs no loaded engine, so it never waits on the pool.
+    pub fn phonemize(text: &str) -> Result<String, Box<dyn Error>> {
+        let phonemes =
+            kokoros::tts::phonemizer::text_to_phonemes(text, ENGINE_LANGUAGE, None, true, false)
+                .map_err(|e| e.to_string())?;
+        Ok(phonemes.join(""))
+    }
+}
+
+impl SpeechEngine for TTS {
+    fn speak(
+        &self,
+        text: &str,
+        output_path: &str,
+        style: &str,
+        speed: f32,
+        mono: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        TTS::speak(self, text, output_path, style, speed, mono)
+    }
+}
+
+/// How [`TTSPool::acquire`] picks an engine for a request
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EngineSelection {
+    /// Prefer an engine nobody has checked out, falling back to round-robin
+    #[default]
+    FreeFirst,
+    /// Take engines strictly in turn, even if the next one is busy
+    RoundRobin,
+}
+
+impl EngineSelection {
+    /// Parse `free-first` or `round-robin`
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "free-first" => Ok(Self::FreeFirst),
+            "round-robin" => Ok(Self::RoundRobin),
+            other => Err(format!(
+                "Invalid engine selection {:?}: expected free-first or round-robin",
+                other
+            )),
+        }
+    }
+}
+
+/// One generation of engines, replaced wholesale when the pool is reloaded
+struct EngineSet {
+    engines: Vec<Arc<Mutex<Box<dyn SpeechEngine>>>>,
+    /// Outstanding checkouts of each engine
+    checkouts: Vec<Arc<AtomicUsize>>,
+    semaphore: Arc<Semaphore>,
+}
+
+impl EngineSet {
+    fn new(engines: Vec<Box<dyn SpeechEngine>>) -> Self {
+        let pool_size = engines.len();
+        Self {
+            engines: engines
+                .into_iter()
+                .map(|engine| Arc::new(Mutex::new(engine)))
+                .collect(),
+            checkouts: (0..pool_size)
+                .map(|_| Arc::new(AtomicUsize::new(0)))
+                .collect(),
+            semaphore: Arc::new(Semaphore::new(pool_size)),
+        }
+    }
+
+    /// Index of the engine for the `request`th acquire
+    fn select(&self, request: usize, selection: EngineSelection) -> usize {
+        let next = request % self.engines.len();
+        if selection == EngineSelection::RoundRobin {
+            return next;
+        }
+
+        // Scan from the round-robin pick so free engines still share the work;
+        // an engine locked outside a checkout (warm-up, self-test) isn't free
+        (0..self.engines.len())
+            .map(|offset| (next + offset) % self.engines.len())
+            .find(|&index| {
+                self.checkouts[index].load(Ordering::SeqCst) == 0
+                    && self.engines[index].try_lock().is_ok()
+            })
+            .unwrap_or(next)
+    }
 }
 
 /// A pool of TTS engines for concurrent request handling
+///
+/// The engine set can be swapped at runtime with [`TTSPool::reload`]. Engines
+/// already checked out keep the old set alive until they are returned, so
+/// in-flight requests finish on the engines they started with.
 pub struct TTSPool {
-    engines: Vec<Arc<Mutex<TTS>>>,
-    semaphore: Arc<Semaphore>,
+    current: RwLock<Arc<EngineSet>>,
+    /// Engines asked for, which reloads aim for even if fewer loaded
+    pool_size: usize,
+    model_paths: Option<(String, String)>,
+    /// Fingerprint of the model files the engines were loaded from
+    model_id: RwLock<String>,
+    reload_lock: Mutex<()>,
     active_count: Arc<AtomicUsize>,
     total_requests: Arc<AtomicUsize>,
+    breaker: Arc<CircuitBreaker>,
+    fair_queue: Option<Arc<FairQueue>>,
+    selection: EngineSelection,
 }
 
 impl TTSPool {
-    /// Create a new TTS pool with the specified number of engines
+    /// Create a new TTS pool with up to the specified number of engines
+    ///
+    /// Engines that fail to load (e.g. out of memory) are skipped, so the
+    /// pool may come up smaller than `pool_size`; see [`TTSPool::from_loader`].
     pub async fn new(
         pool_size: usize,
         model_path: &str,
         data_path: &str,
     ) -> Result<Self, Box<dyn Error>> {
+        model_paths::check_model_files(Path::new(model_path), Path::new(data_path))?;
+        model_paths::check_voices_file(Path::new(data_path))?;
+
+        let mut pool = Self::from_loader(pool_size, |_| async move {
+            TTS::new(model_path, data_path)
+                .await
+                .map(|tts| Box::new(tts) as Box<dyn SpeechEngine>)
+        })
+        .await?;
+        pool.model_paths = Some((model_path.to_string(), data_path.to_string()));
+        pool.set_model_id(model_paths::model_fingerprint(
+            Path::new(model_path),
+            Path::new(data_path),
+        ));
+        Ok(pool)
+    }
+
+    /// Create a pool from `pool_size` engines built one at a time by `load`
+    ///
+    /// `load` receives the engine's index. Engines that fail to load are
+    /// logged and left out; creation fails only if none load at all.
+    pub async fn from_loader<F, Fut>(pool_size: usize, load: F) -> Result<Self, Box<dyn Error>>
+    where
+        F: FnMut(usize) -> Fut,
+        Fut: Future<Output = Result<Box<dyn SpeechEngine>, Box<dyn Error>>>,
+    {
         if pool_size == 0 {
             return Err("Pool size must be at least 1".into());
         }
 
         tracing::info!("Initializing TTS pool with {} engines...", pool_size);
 
-        let mut engines = Vec::with_capacity(pool_size);
-
-        for i in 0..pool_size {
-            tracing::debug!("Loading TTS engine {}/{}...", i + 1, pool_size);
-            let tts = TTS::new(model_path, data_path).await?;
-            engines.push(Arc::new(Mutex::new(tts)));
+        let engines = load_available(pool_size, load).await?;
+        if engines.len() < pool_size {
+            tracing::warn!(
+                "TTS pool initialized with {} of {} engines",
+                engines.len(),
+                pool_size
+            );
+        } else {
+            tracing::info!("TTS pool initialized with {} engines", engines.len());
         }
 
-        tracing::info!("TTS pool initialized successfully");
+        Ok(Self {
+            pool_size,
+            ..Self::from_engines(engines)?
+        })
+    }
+
+    /// Create a pool from already-constructed engines
+    pub fn from_engines(engines: Vec<Box<dyn SpeechEngine>>) -> Result<Self, Box<dyn Error>> {
+        if engines.is_empty() {
+            return Err("Pool size must be at least 1".into());
+        }
 
         Ok(Self {
-            engines,
-            semaphore: Arc::new(Semaphore::new(pool_size)),
+            pool_size: engines.len(),
+            current: RwLock::new(Arc::new(EngineSet::new(engines))),
+            model_paths: None,
+            model_id: RwLock::default(),
+            reload_lock: Mutex::new(()),
             active_count: Arc::new(AtomicUsize::new(0)),
             total_requests: Arc::new(AtomicUsize::new(0)),
+            breaker: Arc::new(CircuitBreaker::default()),
+            fair_queue: None,
+            selection: EngineSelection::default(),
         })
     }
 
+    /// Replace the default engine selection strategy
+    pub fn with_engine_selection(mut self, selection: EngineSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// How engines are picked for requests
+    pub fn engine_selection(&self) -> EngineSelection {
+        self.selection
+    }
+
+    /// Replace the default circuit breaker settings
+    pub fn with_circuit_breaker(mut self, config: BreakerConfig) -> Self {
+        self.breaker = Arc::new(CircuitBreaker::new(config));
+        self
+    }
+
+    /// Take turns between clients when waiting for engines
+    ///
+    /// See [`FairQueue`]; callers are grouped by [`fair_queue::current_client`].
+    pub fn with_fair_queue(mut self, queue: FairQueue) -> Self {
+        self.fair_queue = Some(Arc::new(queue));
+        self
+    }
+
+    /// The fair queue in front of [`TTSPool::acquire`], if enabled
+    pub fn fair_queue(&self) -> Option<Arc<FairQueue>> {
+        self.fair_queue.clone()
+    }
+
     /// Get a TTS engine from the pool
     /// This will wait if all engines are busy
-    pub async fn acquire(&self) -> Result<PooledTTS, String> {
+    ///
+    /// With a fair queue, waiting callers take turns by client rather than
+    /// in arrival order.
+    ///
+    /// Fails immediately with [`TtsError::EnginesUnavailable`] while the
+    /// circuit breaker is open.
+    pub async fn acquire(&self) -> TtsResult<PooledTTS> {
+        // Guarded from here on, so a cancelled wait gives the probe back
+        let probe = match self.breaker.admit() {
+            Admission::Allowed => None,
+            Admission::Probe => Some(ProbeGuard::new(self.breaker.clone())),
+            Admission::Rejected { retry_after } => {
+                return Err(TtsError::EnginesUnavailable {
+                    retry_after_secs: retry_after.as_secs_f64().ceil() as u64,
+                });
+            }
+        };
+
+        // Hold the client's turn until an engine is ours; dropping it lets
+        // the next client in rotation wait for the following one
+        let _turn = match &self.fair_queue {
+            Some(queue) => Some(queue.turn(fair_queue::current_client()).await),
+            None => None,
+        };
+
+        let set = self.current_set();
+
         // Acquire a permit from the semaphore
-        let permit = self
-            .semaphore
-            .clone()
-            .acquire_owned()
-            .await
-            .map_err(|e| format!("Failed to acquire semaphore: {}", e))?;
+        let permit = match set.semaphore.clone().acquire_owned().await {
+            Ok(permit) => permit,
+            Err(e) => {
+                return Err(TtsError::TtsEngine(format!(
+                    "Failed to acquire semaphore: {}",
+                    e
+                )));
+            }
+        };
 
-        // Find an available engine (round-robin)
         let total_requests = self.total_requests.fetch_add(1, Ordering::SeqCst);
-        let index = total_requests % self.engines.len();
-        let engine = self.engines[index].clone();
+        let index = set.select(total_requests, self.selection);
+        let engine = set.engines[index].clone();
+        let checkout = set.checkouts[index].clone();
+        checkout.fetch_add(1, Ordering::SeqCst);
 
         self.active_count.fetch_add(1, Ordering::SeqCst);
 
         Ok(PooledTTS {
             engine,
+            index,
+            checkout,
             _permit: permit,
             active_count: self.active_count.clone(),
+            breaker: self.breaker.clone(),
+            _probe: probe,
+        })
+    }
+
+    /// Reload all engines from the model files the pool was created with
+    ///
+    /// Returns the new pool size.
+    pub async fn reload(&self) -> TtsResult<usize> {
+        let (model_path, data_path) = self.model_paths.clone().ok_or_else(|| {
+            TtsError::InvalidRequest("Pool was not created from model files".to_string())
+        })?;
+        let model_id =
+            model_paths::model_fingerprint(Path::new(&model_path), Path::new(&data_path));
+
+        let new_size = self
+            .reload_with(|pool_size| async move {
+                // Model loading is CPU-heavy and synchronous; keep it off the async workers
+                tokio::task::spawn_blocking(move || {
+                    futures::executor::block_on(load_engines(pool_size, &model_path, &data_path))
+                        .map_err(|e| e.to_string())
+                })
+                .await
+                .map_err(|e| e.to_string())?
+            })
+            .await?;
+        self.set_model_id(model_id);
+        Ok(new_size)
+    }
+
+    /// Replace the engine set with engines built by `load`
+    ///
+    /// `load` receives the configured pool size, so a pool that started short
+    /// of engines gets the full count back. The swap happens only once `load`
+    /// returns; if it produces no engines the existing ones stay in service.
+    /// Only one reload can run at a time.
+    pub async fn reload_with<F, Fut>(&self, load: F) -> TtsResult<usize>
+    where
+        F: FnOnce(usize) -> Fut,
+        Fut: Future<Output = Result<Vec<Box<dyn SpeechEngine>>, String>>,
+    {
+        let _guard = self
+            .reload_lock
+            .try_lock()
+            .map_err(|_| TtsError::ReloadInProgress)?;
+
+        tracing::info!("Reloading TTS pool with {} engines...", self.pool_size);
+
+        let engines = load(self.pool_size).await.map_err(TtsError::TtsEngine)?;
+        if engines.is_empty() {
+            return Err(TtsError::TtsEngine(
+                "Reload produced no engines".to_string(),
+            ));
+        }
+
+        let new_size = engines.len();
+        let new_set = Arc::new(EngineSet::new(engines));
+        *self.current.write().unwrap_or_else(|e| e.into_inner()) = new_set;
+
+        if new_size < self.pool_size {
+            tracing::warn!(
+                "TTS pool reloaded with {} of {} engines",
+                new_size,
+                self.pool_size
+            );
+        } else {
+            tracing::info!("TTS pool reloaded ({} engines)", new_size);
+        }
+        Ok(new_size)
+    }
+
+    /// Run a tiny synthesis through every engine
+    ///
+    /// Kokoro pays a one-off cost on its first inference; doing it up front
+    /// keeps that latency off the first real requests. Engines warm up in
+    /// parallel. Returns the total time taken.
+    pub async fn warm_up(&self) -> TtsResult<Duration> {
+        let start = Instant::now();
+
+        let set = self.current_set();
+        let warmups = set.engines.iter().map(|engine| {
+            let engine = engine.clone();
+            async move {
+                let engine = engine.lock_owned().await;
+                tokio::task::spawn_blocking(move || {
+                    let temp_file = TempFile::new();
+                    engine
+                        .speak(WARMUP_TEXT, temp_file.as_str(), WARMUP_VOICE, 1.0, true)
+                        .map_err(|e| TtsError::TtsEngine(format!("Warm-up failed: {}", e)))
+                })
+                .await?
+            }
+        });
+        futures::future::try_join_all(warmups).await?;
+
+        Ok(start.elapsed())
+    }
+
+    /// Synthesize a known phrase and check the output is plausible speech
+    ///
+    /// Catches a broken model or eSpeak setup before serving traffic: the
+    /// clip must be a readable WAV whose length suits the phrase. Runs on the
+    /// first engine and returns the clip's duration in ms.
+    pub async fn self_test(&self) -> TtsResult<f64> {
+        let fail = |problem: String| TtsError::TtsEngine(format!("Self-test failed: {}", problem));
+
+        let set = self.current_set();
+        let engine = set
+            .engines
+            .first()
+            .ok_or_else(|| fail("no engines loaded".to_string()))?
+            .clone()
+            .lock_owned()
+            .await;
+        let temp_file = TempFile::new();
+        let path = temp_file.as_str().to_string();
+        tokio::task::spawn_blocking(move || {
+            engine
+                .speak(SELF_TEST_TEXT, &path, WARMUP_VOICE, 1.0, true)
+                .map_err(|e| e.to_string())
         })
+        .await?
+        .map_err(fail)?;
+
+        let audio = temp_file.read().await?;
+        let duration_ms = crate::audio::duration::calculate(&audio)
+            .map_err(|e| fail(format!("output is not a valid WAV ({})", e)))?;
+        if !SELF_TEST_DURATION_MS.contains(&duration_ms) {
+            return Err(fail(format!(
+                "{:.0} ms of audio for \"{}\", expected {:.0}-{:.0} ms",
+                duration_ms,
+                SELF_TEST_TEXT,
+                SELF_TEST_DURATION_MS.start(),
+                SELF_TEST_DURATION_MS.end()
+            )));
+        }
+
+        Ok(duration_ms)
+    }
+
+    /// Fingerprint of the model files in use (empty for engines not loaded
+    /// from files)
+    ///
+    /// Changes when a reload picks up different model files.
+    pub fn model_id(&self) -> String {
+        self.model_id
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    fn set_model_id(&self, model_id: String) {
+        *self.model_id.write().unwrap_or_else(|e| e.into_inner()) = model_id;
+    }
+
+    /// Use `model_id` as the model fingerprint
+    #[cfg(test)]
+    pub fn with_model_id(self, model_id: &str) -> Self {
+        self.set_model_id(model_id.to_string());
+        self
     }
 
     /// Get pool statistics
     pub fn stats(&self) -> PoolStats {
+        let set = self.current_set();
         PoolStats {
-            pool_size: self.engines.len(),
+            pool_size: set.engines.len(),
             active_requests: self.active_count.load(Ordering::SeqCst),
             total_requests: self.total_requests.load(Ordering::SeqCst),
-            available_engines: self.semaphore.available_permits(),
+            available_engines: set.semaphore.available_permits(),
+            breaker_state: self.breaker.state(),
+            consecutive_failures: self.breaker.consecutive_failures(),
+            breaker_opened: self.breaker.times_opened(),
         }
     }
+
+    fn current_set(&self) -> Arc<EngineSet> {
+        self.current
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+/// Build up to `pool_size` engines one at a time with `load`
+///
+/// `load` receives the engine's index. Engines that fail to load are logged
+/// and left out; this fails only if none load at all.
+async fn load_available<F, Fut>(
+    pool_size: usize,
+    mut load: F,
+) -> Result<Vec<Box<dyn SpeechEngine>>, Box<dyn Error>>
+where
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = Result<Box<dyn SpeechEngine>, Box<dyn Error>>>,
+{
+    let mut engines = Vec::with_capacity(pool_size);
+    let mut last_error = None;
+    for i in 0..pool_size {
+        tracing::debug!("Loading TTS engine {}/{}...", i + 1, pool_size);
+        match load(i).await {
+            Ok(engine) => engines.push(engine),
+            Err(e) => {
+                tracing::warn!("Failed to load TTS engine {}/{}: {}", i + 1, pool_size, e);
+                last_error = Some(e);
+            }
+        }
+    }
+
+    if let (true, Some(e)) = (engines.is_empty(), last_error) {
+        return Err(format!("No TTS engines could be loaded: {}", e).into());
+    }
+    Ok(engines)
+}
+
+/// Load up to `pool_size` Kokoro engines from the given model files
+///
+/// Tolerates engines failing to load just like startup does, so a reload
+/// under memory pressure brings back what it can rather than nothing.
+async fn load_engines(
+    pool_size: usize,
+    model_path: &str,
+    data_path: &str,
+) -> Result<Vec<Box<dyn SpeechEngine>>, Box<dyn Error>> {
+    model_paths::check_model_files(Path::new(model_path), Path::new(data_path))?;
+    model_paths::check_voices_file(Path::new(data_path))?;
+
+    load_available(pool_size, |_| async move {
+        TTS::new(model_path, data_path)
+            .await
+            .map(|tts| Box::new(tts) as Box<dyn SpeechEngine>)
+    })
+    .await
 }
 
 /// A TTS engine checked out from the pool
 /// Automatically returned to pool when dropped
 pub struct PooledTTS {
-    engine: Arc<Mutex<TTS>>,
+    engine: Arc<Mutex<Box<dyn SpeechEngine>>>,
+    /// Position of the engine in its set
+    index: usize,
+    checkout: Arc<AtomicUsize>,
     _permit: tokio::sync::OwnedSemaphorePermit,
     active_count: Arc<AtomicUsize>,
+    breaker: Arc<CircuitBreaker>,
+    /// Set when this is the half-open breaker's probe request
+    _probe: Option<ProbeGuard>,
 }
 
 impl PooledTTS {
+    /// Position of the checked-out engine in the pool
+    #[cfg(test)]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
     /// Generate speech using the pooled engine
+    ///
+    /// The outcome is reported to the pool's circuit breaker.
     pub async fn speak(
         &self,
         text: &str,
         output_path: &str,
         style: &str,
         speed: f32,
+        mono: bool,
     ) -> Result<(), Box<dyn Error>> {
         let engine = self.engine.lock().await;
-        engine.speak(text, output_path, style, speed)
+        tracing::debug!("Engine {} synthesizing {} bytes", self.index, text.len());
+        let result = engine.speak(text, output_path, style, speed, mono);
+        match result {
+            Ok(_) => self.breaker.record_success(),
+            Err(_) => self.breaker.record_failure(),
+        }
+        result
     }
 }
 
 impl Drop for PooledTTS {
     fn drop(&mut self) {
         self.active_count.fetch_sub(1, Ordering::SeqCst);
+        self.checkout.fetch_sub(1, Ordering::SeqCst);
     }
 }
 
@@ -153,4 +677,585 @@ pub struct PoolStats {
     pub active_requests: usize,
     pub total_requests: usize,
     pub available_engines: usize,
+    pub breaker_state: BreakerState,
+    pub consecutive_failures: u32,
+    /// How many times the circuit breaker has opened
+    pub breaker_opened: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fake::{fake_pool, FakeEngine, FakeEngineLog};
+    use super::*;
+    use std::time::Duration;
+
+    fn fake_engines(count: usize, log: &Arc<FakeEngineLog>) -> Vec<Box<dyn SpeechEngine>> {
+        (0..count)
+            .map(|_| {
+                Box::new(FakeEngine {
+                    duration_ms: 100,
+                    delay: Duration::ZERO,
+                    log: log.clone(),
+                }) as Box<dyn SpeechEngine>
+            })
+            .collect()
+    }
+
+    fn temp_wav_path() -> String {
+        std::env::temp_dir()
+            .join(format!("pool_{}.wav", uuid::Uuid::new_v4()))
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    async fn speak_once(pool: &TTSPool) {
+        let path = temp_wav_path();
+        let tts = pool.acquire().await.unwrap();
+        tts.speak("hello", &path, "af_heart", 1.0, true)
+            .await
+            .unwrap();
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_acquire_prefers_free_engine() {
+        let (pool, _) = fake_pool(2, 100, Duration::ZERO);
+
+        // Engine 0 stays checked out; engine 1 comes back
+        let held = pool.acquire().await.unwrap();
+        assert_eq!(held.index(), 0);
+        drop(pool.acquire().await.unwrap());
+
+        // Round-robin would hand out the busy engine 0 next
+        let tts = pool.acquire().await.unwrap();
+        assert_eq!(tts.index(), 1);
+        drop(tts);
+
+        // With both free, work still rotates
+        drop(held);
+        let indices = [
+            pool.acquire().await.unwrap().index(),
+            pool.acquire().await.unwrap().index(),
+        ];
+        assert_eq!(indices, [1, 0]);
+    }
+
+    #[tokio::test]
+    async fn test_acquire_skips_engine_locked_outside_checkout() {
+        let (pool, _) = fake_pool(2, 100, Duration::ZERO);
+        let _locked = pool.current_set().engines[0].clone().lock_owned().await;
+
+        assert_eq!(pool.acquire().await.unwrap().index(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_ignores_busy_engines() {
+        let (pool, _) = fake_pool(2, 100, Duration::ZERO);
+        let pool = pool.with_engine_selection(EngineSelection::RoundRobin);
+
+        let held = pool.acquire().await.unwrap();
+        drop(pool.acquire().await.unwrap());
+        assert_eq!(pool.acquire().await.unwrap().index(), held.index());
+    }
+
+    #[test]
+    fn test_parse_engine_selection() {
+        assert_eq!(
+            EngineSelection::parse(" Round-Robin "),
+            Ok(EngineSelection::RoundRobin)
+        );
+        assert_eq!(
+            EngineSelection::parse("free-first"),
+            Ok(EngineSelection::FreeFirst)
+        );
+        assert!(EngineSelection::parse("random").is_err());
+    }
+
+    #[test]
+    #[ignore = "needs the espeak-ng data Kokoro phonemizes with"]
+    fn test_real_phonemizer() {
+        let phonemes = TTS::phonemize("Hello world.").unwrap();
+        assert!(!phonemes.is_empty());
+        assert_ne!(phonemes, "Hello world.");
+    }
+
+    #[tokio::test]
+    async fn test_reload_replaces_engines() {
+        let (pool, old_log) = fake_pool(1, 100, Duration::ZERO);
+        let new_log = Arc::new(FakeEngineLog::default());
+
+        let engines = fake_engines(2, &new_log);
+        let size = pool
+            .reload_with(|_| async move { Ok(engines) })
+            .await
+            .unwrap();
+        assert_eq!(size, 2);
+        assert_eq!(pool.stats().pool_size, 2);
+        assert_eq!(pool.stats().available_engines, 2);
+
+        speak_once(&pool).await;
+        assert_eq!(old_log.calls(), 0);
+        assert_eq!(new_log.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_request_completes_on_old_engines() {
+        let (pool, old_log) = fake_pool(1, 100, Duration::from_millis(200));
+        let pool = Arc::new(pool);
+
+        // Check out an engine from the old set and start a slow synthesis
+        let tts = pool.acquire().await.unwrap();
+        let path = temp_wav_path();
+        let in_flight = {
+            let path = path.clone();
+            tokio::task::spawn_blocking(move || {
+                futures::executor::block_on(tts.speak("hello", &path, "af_heart", 1.0, true))
+                    .map_err(|e| e.to_string())
+            })
+        };
+
+        let new_log = Arc::new(FakeEngineLog::default());
+        let engines = fake_engines(1, &new_log);
+        pool.reload_with(|_| async move { Ok(engines) })
+            .await
+            .unwrap();
+
+        // New requests don't wait for the old engine to be returned
+        assert_eq!(pool.stats().available_engines, 1);
+
+        in_flight.await.unwrap().unwrap();
+        assert_eq!(old_log.calls(), 1);
+        assert!(std::path::Path::new(&path).exists());
+        let _ = std::fs::remove_file(path);
+
+        speak_once(&pool).await;
+        assert_eq!(new_log.calls(), 1);
+        assert_eq!(pool.stats().active_requests, 0);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_reload_rejected() {
+        let (pool, _) = fake_pool(1, 100, Duration::ZERO);
+        let pool = Arc::new(pool);
+        let log = Arc::new(FakeEngineLog::default());
+
+        let slow_reload = {
+            let pool = pool.clone();
+            let engines = fake_engines(1, &log);
+            tokio::spawn(async move {
+                pool.reload_with(|_| async move {
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                    Ok(engines)
+                })
+                .await
+            })
+        };
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let engines = fake_engines(1, &log);
+        let second = pool.reload_with(|_| async move { Ok(engines) }).await;
+        assert!(matches!(second, Err(TtsError::ReloadInProgress)));
+
+        assert!(slow_reload.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_failed_reload_keeps_existing_engines() {
+        let (pool, old_log) = fake_pool(2, 100, Duration::ZERO);
+
+        let result = pool
+            .reload_with(|_| async { Err("voices file is corrupt".to_string()) })
+            .await;
+        assert!(matches!(result, Err(TtsError::TtsEngine(_))));
+        assert_eq!(pool.stats().pool_size, 2);
+
+        speak_once(&pool).await;
+        assert_eq!(old_log.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_pool_with_missing_model_files_fails_descriptively() {
+        let dir = tempfile::tempdir().unwrap();
+        let model = dir.path().join("kokoro-v1.0.onnx");
+        let voices = dir.path().join("voices-v1.0.bin");
+
+        let Err(error) = TTSPool::new(1, model.to_str().unwrap(), voices.to_str().unwrap()).await
+        else {
+            panic!("pool creation should fail");
+        };
+
+        assert!(matches!(
+            error.downcast_ref::<TtsError>(),
+            Some(TtsError::ModelFilesMissing(problems)) if problems.len() == 2
+        ));
+        let message = error.to_string();
+        assert!(message.contains(model.to_str().unwrap()), "{}", message);
+        assert!(message.contains("TTS_MODEL_DIR"), "{}", message);
+    }
+
+    #[tokio::test]
+    async fn test_pool_with_truncated_voices_file_says_to_download_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let model = dir.path().join("kokoro-v1.0.onnx");
+        let voices = dir.path().join("voices-v1.0.bin");
+        std::fs::write(&model, b"model").unwrap();
+        // The start of a real archive, cut off mid-download
+        let mut partial = b"PK\x03\x04".to_vec();
+        partial.extend_from_slice(&[0; 4096]);
+        std::fs::write(&voices, partial).unwrap();
+
+        let Err(error) = TTSPool::new(1, model.to_str().unwrap(), voices.to_str().unwrap()).await
+        else {
+            panic!("pool creation should fail");
+        };
+
+        assert!(matches!(
+            error.downcast_ref::<TtsError>(),
+            Some(TtsError::VoicesFileCorrupt(_))
+        ));
+        let message = error.to_string();
+        assert!(message.contains(voices.to_str().unwrap()), "{}", message);
+        assert!(
+            message.contains("download voices-v1.0.bin again"),
+            "{}",
+            message
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pool_starts_with_engines_that_loaded() {
+        let log = Arc::new(FakeEngineLog::default());
+        let pool = TTSPool::from_loader(3, |i| {
+            let log = log.clone();
+            async move {
+                if i == 1 {
+                    return Err("out of memory".into());
+                }
+                Ok(Box::new(FakeEngine {
+                    duration_ms: 100,
+                    delay: Duration::ZERO,
+                    log,
+                }) as Box<dyn SpeechEngine>)
+            }
+        })
+        .await
+        .unwrap();
+
+        let stats = pool.stats();
+        assert_eq!(stats.pool_size, 2);
+        assert_eq!(stats.available_engines, 2);
+        speak_once(&pool).await;
+        assert_eq!(log.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_reload_restores_the_configured_pool_size() {
+        let log = Arc::new(FakeEngineLog::default());
+        let pool = TTSPool::from_loader(3, |i| {
+            let engines = fake_engines(1, &log);
+            async move {
+                if i == 1 {
+                    return Err("out of memory".into());
+                }
+                Ok(engines.into_iter().next().unwrap())
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(pool.stats().pool_size, 2);
+
+        let size = pool
+            .reload_with(|pool_size| {
+                let engines = fake_engines(pool_size, &log);
+                async move { Ok(engines) }
+            })
+            .await
+            .unwrap();
+        assert_eq!(size, 3);
+        assert_eq!(pool.stats().pool_size, 3);
+    }
+
+    #[tokio::test]
+    async fn test_pool_fails_when_no_engine_loads() {
+        let Err(error) = TTSPool::from_loader(2, |_| async { Err("out of memory".into()) }).await
+        else {
+            panic!("pool creation should fail");
+        };
+        let message = error.to_string();
+        assert!(message.contains("No TTS engines"), "{}", message);
+        assert!(message.contains("out of memory"), "{}", message);
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_runs_each_engine_once() {
+        let logs: Vec<Arc<FakeEngineLog>> = (0..3).map(|_| Arc::default()).collect();
+        let engines = logs.iter().flat_map(|log| fake_engines(1, log)).collect();
+        let pool = TTSPool::from_engines(engines).unwrap();
+
+        pool.warm_up().await.unwrap();
+        // Temp file cleanup runs in the background
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        for log in &logs {
+            assert_eq!(log.calls(), 1);
+            // The warm-up clip is not left behind
+            assert!(!log.outputs()[0].exists());
+        }
+        // Warm-up isn't counted as traffic
+        assert_eq!(pool.stats().total_requests, 0);
+        assert_eq!(pool.stats().available_engines, 3);
+    }
+
+    fn self_test_pool(engine: impl SpeechEngine + 'static) -> TTSPool {
+        TTSPool::from_engines(vec![Box::new(engine)]).unwrap()
+    }
+
+    fn fake_engine(duration_ms: u32) -> FakeEngine {
+        FakeEngine {
+            duration_ms,
+            delay: Duration::ZERO,
+            log: Arc::default(),
+        }
+    }
+
+    /// Engine that writes bytes that aren't a WAV file
+    struct GarbageEngine;
+
+    impl SpeechEngine for GarbageEngine {
+        fn speak(
+            &self,
+            _text: &str,
+            output_path: &str,
+            _style: &str,
+            _speed: f32,
+            _mono: bool,
+        ) -> Result<(), Box<dyn Error>> {
+            std::fs::write(output_path, b"not a wav file")?;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_self_test_passes_with_plausible_audio() {
+        let log = Arc::new(FakeEngineLog::default());
+        let pool = self_test_pool(FakeEngine {
+            log: log.clone(),
+            ..fake_engine(2500)
+        });
+
+        let duration = pool.self_test().await.unwrap();
+        assert!((duration - 2500.0).abs() < 1.0);
+        assert_eq!(log.texts(), [SELF_TEST_TEXT]);
+        // Not counted as traffic, and the engine is handed back
+        assert_eq!(pool.stats().total_requests, 0);
+        assert_eq!(pool.stats().available_engines, 1);
+    }
+
+    #[tokio::test]
+    async fn test_self_test_fails_on_implausible_audio() {
+        for duration_ms in [0, 50, 60_000] {
+            let err = self_test_pool(fake_engine(duration_ms))
+                .self_test()
+                .await
+                .unwrap_err();
+            assert!(
+                err.to_string().contains("ms of audio"),
+                "{}: {}",
+                duration_ms,
+                err
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_self_test_fails_on_invalid_wav_or_engine_error() {
+        let err = self_test_pool(GarbageEngine).self_test().await.unwrap_err();
+        assert!(err.to_string().contains("not a valid WAV"), "{}", err);
+
+        let engine = FlakyEngine {
+            failing: Arc::new(std::sync::atomic::AtomicBool::new(true)),
+            inner: fake_engine(2500),
+        };
+        let err = self_test_pool(engine).self_test().await.unwrap_err();
+        assert!(err.to_string().contains("engine crashed"), "{}", err);
+    }
+
+    /// Engine that fails while `failing` is set and otherwise behaves like a fake
+    struct FlakyEngine {
+        failing: Arc<std::sync::atomic::AtomicBool>,
+        inner: FakeEngine,
+    }
+
+    impl SpeechEngine for FlakyEngine {
+        fn speak(
+            &self,
+            text: &str,
+            output_path: &str,
+            style: &str,
+            speed: f32,
+            mono: bool,
+        ) -> Result<(), Box<dyn Error>> {
+            if self.failing.load(Ordering::SeqCst) {
+                return Err("engine crashed".into());
+            }
+            self.inner.speak(text, output_path, style, speed, mono)
+        }
+    }
+
+    fn flaky_pool(cooldown: Duration) -> (TTSPool, Arc<std::sync::atomic::AtomicBool>) {
+        let failing = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let engine = FlakyEngine {
+            failing: failing.clone(),
+            inner: FakeEngine {
+                duration_ms: 100,
+                delay: Duration::ZERO,
+                log: Arc::default(),
+            },
+        };
+        let pool = TTSPool::from_engines(vec![Box::new(engine)])
+            .unwrap()
+            .with_circuit_breaker(BreakerConfig {
+                failure_threshold: 2,
+                window: Duration::from_secs(60),
+                cooldown,
+            });
+        (pool, failing)
+    }
+
+    #[test]
+    fn test_engine_error_recognizes_missing_voice() {
+        // Kokoro's message for a style missing from the voices file, verbatim
+        assert!(matches!(
+            engine_error("can not found from styles_map: af_sky"),
+            TtsError::VoiceUnavailable(voice) if voice == "af_sky"
+        ));
+
+        for message in [
+            "engine crashed",
+            "model file not found",
+            "Unknown voice af_sky",
+        ] {
+            assert!(
+                matches!(engine_error(message), TtsError::TtsEngine(_)),
+                "{}",
+                message
+            );
+        }
+    }
+
+    async fn try_speak(pool: &TTSPool) -> TtsResult<()> {
+        let path = temp_wav_path();
+        let tts = pool.acquire().await?;
+        let result = tts
+            .speak("hello", &path, "af_heart", 1.0, true)
+            .await
+            .map_err(|e| TtsError::TtsEngine(e.to_string()));
+        let _ = std::fs::remove_file(path);
+        result
+    }
+
+    #[tokio::test]
+    async fn test_breaker_opens_then_recovers_after_cooldown() {
+        let (pool, failing) = flaky_pool(Duration::from_millis(100));
+
+        for _ in 0..2 {
+            assert!(matches!(
+                try_speak(&pool).await,
+                Err(TtsError::TtsEngine(_))
+            ));
+        }
+        assert_eq!(pool.stats().breaker_state, BreakerState::Open);
+
+        // Open: requests fail fast without reaching an engine
+        assert!(matches!(
+            try_speak(&pool).await,
+            Err(TtsError::EnginesUnavailable {
+                retry_after_secs: 1
+            })
+        ));
+        assert_eq!(pool.stats().total_requests, 2);
+
+        // Half-open: the probe succeeds and closes the breaker
+        failing.store(false, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        try_speak(&pool).await.unwrap();
+
+        let stats = pool.stats();
+        assert_eq!(stats.breaker_state, BreakerState::Closed);
+        assert_eq!(stats.consecutive_failures, 0);
+        assert_eq!(stats.breaker_opened, 1);
+        try_speak(&pool).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_failed_probe_reopens_breaker() {
+        let (pool, _failing) = flaky_pool(Duration::from_millis(100));
+        for _ in 0..2 {
+            let _ = try_speak(&pool).await;
+        }
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        let probe = pool.acquire().await.unwrap();
+        assert_eq!(pool.stats().breaker_state, BreakerState::HalfOpen);
+
+        // Only one probe at a time
+        assert!(matches!(
+            pool.acquire().await,
+            Err(TtsError::EnginesUnavailable { .. })
+        ));
+
+        let path = temp_wav_path();
+        assert!(probe
+            .speak("hello", &path, "af_heart", 1.0, true)
+            .await
+            .is_err());
+        drop(probe);
+
+        assert_eq!(pool.stats().breaker_state, BreakerState::Open);
+        assert_eq!(pool.stats().breaker_opened, 2);
+        assert!(matches!(
+            try_speak(&pool).await,
+            Err(TtsError::EnginesUnavailable { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_probe_is_released() {
+        let (pool, failing) = flaky_pool(Duration::from_millis(100));
+
+        // Hold the only engine so the probe has to wait for it
+        let held = pool.acquire().await.unwrap();
+        let path = temp_wav_path();
+        for _ in 0..2 {
+            assert!(held
+                .speak("hello", &path, "af_heart", 1.0, true)
+                .await
+                .is_err());
+        }
+        assert_eq!(pool.stats().breaker_state, BreakerState::Open);
+
+        // The probe is admitted, then given up while waiting for an engine
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), pool.acquire())
+                .await
+                .is_err()
+        );
+        drop(held);
+
+        // The next request may probe instead
+        failing.store(false, Ordering::SeqCst);
+        try_speak(&pool).await.unwrap();
+        assert_eq!(pool.stats().breaker_state, BreakerState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_reload_requires_model_paths() {
+        let (pool, _) = fake_pool(1, 100, Duration::ZERO);
+        assert!(matches!(
+            pool.reload().await,
+            Err(TtsError::InvalidRequest(_))
+        ));
+    }
 }
@@ -1,5 +1,35 @@
 use std::env;
-use std::path::PathBuf;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::error::{Result, TtsError};
+use crate::kokoro::voice_config::Voice;
+
+/// First delay between model file checks while waiting for them to appear
+const MODEL_WAIT_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Longest delay between model file checks
+const MODEL_WAIT_MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Directory espeak-ng expects inside `PIPER_ESPEAKNG_DATA_DIRECTORY`
+const ESPEAK_DATA_DIR_NAME: &str = "espeak-ng-data";
+
+/// Signature every zip (and so NPZ) archive starts with
+const ZIP_LOCAL_HEADER: [u8; 4] = *b"PK\x03\x04";
+
+/// Signature of the record closing a zip archive
+const ZIP_END_RECORD: [u8; 4] = *b"PK\x05\x06";
+
+/// Size of the end record without its trailing comment
+const ZIP_END_RECORD_LEN: u64 = 22;
+
+/// The end record's comment is at most this long, bounding the search for it
+const ZIP_MAX_COMMENT_LEN: u64 = u16::MAX as u64;
+
+/// Data files espeak-ng needs to phonemize English
+const ESPEAK_DATA_FILES: [&str; 5] = ["phontab", "phonindex", "phondata", "intonations", "en_dict"];
 
 // Search multiple standard paths for models
 pub fn find_model_file(filename: &str) -> PathBuf {
@@ -126,3 +156,407 @@ pub fn get_voices_path() -> PathBuf {
 pub fn get_samples_dir() -> PathBuf {
     find_samples_dir()
 }
+
+/// Verify the model and voices files exist and are non-empty
+///
+/// The model loader fails with cryptic errors (or panics) on missing files,
+/// so this runs first and reports every problem path at once.
+pub fn check_model_files(model_path: &Path, voices_path: &Path) -> Result<()> {
+    let problems: Vec<String> = [model_path, voices_path]
+        .into_iter()
+        .filter_map(|path| {
+            let problem = match std::fs::metadata(path) {
+                Err(_) => "not found",
+                Ok(metadata) if !metadata.is_file() => "not a file",
+                Ok(metadata) if metadata.len() == 0 => "empty",
+                Ok(_) => return None,
+            };
+            Some(format!("{} ({})", path.display(), problem))
+        })
+        .collect();
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(TtsError::ModelFilesMissing(problems))
+    }
+}
+
+/// Identity of the model and voices files: their paths, sizes and
+/// modification times
+///
+/// Replacing either file changes it, so anything derived from one model (like
+/// cached audio) can tell it apart from another's.
+pub fn model_fingerprint(model_path: &Path, voices_path: &Path) -> String {
+    [model_path, voices_path]
+        .into_iter()
+        .map(|path| {
+            let (len, modified) = std::fs::metadata(path)
+                .map(|metadata| {
+                    let modified = metadata
+                        .modified()
+                        .ok()
+                        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                        .map(|since| since.as_nanos())
+                        .unwrap_or(0);
+                    (metadata.len(), modified)
+                })
+                .unwrap_or((0, 0));
+            format!("{}:{}:{}", path.display(), len, modified)
+        })
+        .collect::<Vec<_>>()
+        .join("\0")
+}
+
+/// Verify `parent` holds an `espeak-ng-data` directory espeak-ng can load
+///
+/// `parent` is the directory containing `espeak-ng-data`, as expected by
+/// `PIPER_ESPEAKNG_DATA_DIRECTORY`. Pointing at the data directory itself is a
+/// common mistake, so that gets its own message.
+pub fn check_espeak_data(parent: &Path) -> Result<()> {
+    let problem = |path: &Path, problem: &str| {
+        TtsError::EspeakDataInvalid(vec![format!("{} ({})", path.display(), problem)])
+    };
+
+    if !parent.is_dir() {
+        return Err(problem(parent, "not found"));
+    }
+
+    let data_dir = parent.join(ESPEAK_DATA_DIR_NAME);
+    if !data_dir.is_dir() {
+        if parent.join(ESPEAK_DATA_FILES[0]).is_file() {
+            return Err(problem(
+                parent,
+                "this is the espeak-ng-data directory itself, use its parent",
+            ));
+        }
+        return Err(problem(&data_dir, "not found"));
+    }
+
+    let problems: Vec<String> = ESPEAK_DATA_FILES
+        .iter()
+        .map(|name| data_dir.join(name))
+        .filter_map(|path| {
+            let problem = match std::fs::metadata(&path) {
+                Err(_) => "not found",
+                Ok(metadata) if !metadata.is_file() => "not a file",
+                Ok(metadata) if metadata.len() == 0 => "empty",
+                Ok(_) => return None,
+            };
+            Some(format!("{} ({})", path.display(), problem))
+        })
+        .collect();
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(TtsError::EspeakDataInvalid(problems))
+    }
+}
+
+/// Verify the voices file is a complete NPZ archive before an engine loads it
+///
+/// A truncated download otherwise fails deep inside the model loader with a
+/// message that doesn't point at the file. An archive ends with a record
+/// listing its entry count, so an interrupted download is missing it, and a
+/// complete file holds at least one entry per voice in [`Voice::all`].
+pub fn check_voices_file(voices_path: &Path) -> Result<()> {
+    let corrupt = |problem: &str| {
+        TtsError::VoicesFileCorrupt(format!("{} ({})", voices_path.display(), problem))
+    };
+
+    let mut file = File::open(voices_path)?;
+    let len = file.metadata()?.len();
+
+    let mut magic = [0; 4];
+    if file.read_exact(&mut magic).is_err() || magic != ZIP_LOCAL_HEADER {
+        return Err(corrupt("not a voices archive"));
+    }
+
+    let tail_len = len.min(ZIP_END_RECORD_LEN + ZIP_MAX_COMMENT_LEN);
+    let mut tail = vec![0; tail_len as usize];
+    file.seek(SeekFrom::End(-(tail_len as i64)))?;
+    file.read_exact(&mut tail)?;
+
+    let Some(end) = tail
+        .windows(ZIP_END_RECORD.len())
+        .rposition(|window| window == ZIP_END_RECORD)
+        .filter(|&end| end + ZIP_END_RECORD_LEN as usize <= tail.len())
+    else {
+        return Err(corrupt("truncated, the archive's end record is missing"));
+    };
+
+    let entries = u16::from_le_bytes([tail[end + 10], tail[end + 11]]) as usize;
+    let expected = Voice::all().len();
+    if entries < expected {
+        return Err(corrupt(&format!(
+            "holds {} entries, expected at least {}",
+            entries, expected
+        )));
+    }
+
+    Ok(())
+}
+
+/// Voices from [`Voice::all`] that the voices file doesn't contain
+///
+/// `voices-v1.0.bin` is an NPZ (zip) archive holding one `<voice id>.npy`
+/// array per voice. Entry names are stored uncompressed in the archive
+/// headers, so a byte search finds them without unpacking anything.
+pub fn missing_voices(voices_path: &Path) -> std::io::Result<Vec<Voice>> {
+    let archive = std::fs::read(voices_path)?;
+
+    Ok(Voice::all()
+        .into_iter()
+        .filter(|voice| {
+            let entry = format!("{}.npy", voice.id());
+            !archive
+                .windows(entry.len())
+                .any(|window| window == entry.as_bytes())
+        })
+        .collect())
+}
+
+/// Check the model files, retrying with backoff for up to `max_wait`
+///
+/// Gives volumes mounted after startup (e.g. in containers) time to appear.
+/// With a zero `max_wait` this is a single check.
+pub async fn wait_for_model_files(
+    model_path: &Path,
+    voices_path: &Path,
+    max_wait: Duration,
+) -> Result<()> {
+    let start = Instant::now();
+    let mut backoff = MODEL_WAIT_INITIAL_BACKOFF;
+
+    loop {
+        let result = check_model_files(model_path, voices_path);
+        let remaining = max_wait.saturating_sub(start.elapsed());
+        if result.is_ok() || remaining.is_zero() {
+            return result;
+        }
+
+        if let Err(e) = result {
+            tracing::warn!("{}; retrying in {:?}", e, backoff.min(remaining));
+        }
+        tokio::time::sleep(backoff.min(remaining)).await;
+        backoff = (backoff * 2).min(MODEL_WAIT_MAX_BACKOFF);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model_dir() -> (tempfile::TempDir, PathBuf, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let model = dir.path().join("kokoro-v1.0.onnx");
+        let voices = dir.path().join("voices-v1.0.bin");
+        (dir, model, voices)
+    }
+
+    #[test]
+    fn test_model_fingerprint_changes_with_files() {
+        let (_dir, model, voices) = model_dir();
+        std::fs::write(&model, b"model").unwrap();
+        std::fs::write(&voices, b"voices").unwrap();
+        let first = model_fingerprint(&model, &voices);
+        assert_eq!(first, model_fingerprint(&model, &voices));
+
+        std::fs::write(&model, b"a larger model").unwrap();
+        assert_ne!(first, model_fingerprint(&model, &voices));
+    }
+
+    #[test]
+    fn test_check_model_files_ok() {
+        let (_dir, model, voices) = model_dir();
+        std::fs::write(&model, b"model").unwrap();
+        std::fs::write(&voices, b"voices").unwrap();
+
+        assert!(check_model_files(&model, &voices).is_ok());
+    }
+
+    #[test]
+    fn test_check_model_files_reports_every_problem() {
+        let (dir, model, voices) = model_dir();
+        std::fs::write(&voices, b"").unwrap();
+
+        let Err(TtsError::ModelFilesMissing(problems)) = check_model_files(&model, &voices) else {
+            panic!("expected ModelFilesMissing");
+        };
+        assert_eq!(
+            problems,
+            vec![
+                format!("{} (not found)", model.display()),
+                format!("{} (empty)", voices.display()),
+            ]
+        );
+
+        let Err(error) = check_model_files(dir.path(), &voices) else {
+            panic!("expected an error");
+        };
+        let message = error.to_string();
+        assert!(message.contains("(not a file)"), "{}", message);
+        assert!(message.contains("TTS_MODEL_DIR"), "{}", message);
+    }
+
+    #[test]
+    fn test_missing_voices_finds_archive_entries() {
+        let (_dir, _model, voices) = model_dir();
+        let mut archive = b"PK\x03\x04".to_vec();
+        for voice in Voice::all() {
+            if voice != Voice::BritishMaleLewis {
+                archive.extend_from_slice(format!("{}.npy\0\0", voice.id()).as_bytes());
+            }
+        }
+        std::fs::write(&voices, archive).unwrap();
+
+        assert_eq!(
+            missing_voices(&voices).unwrap(),
+            vec![Voice::BritishMaleLewis]
+        );
+    }
+
+    /// A stand-in voices archive with `entries` entries, written with the
+    /// server's own zip writer
+    fn voices_archive(entries: usize) -> Vec<u8> {
+        let mut zip = crate::utils::zip::ZipStream::new();
+        let mut archive = Vec::new();
+        for voice in Voice::all().iter().cycle().take(entries) {
+            archive.extend(zip.entry(&format!("{}.npy", voice.id()), &[0; 64]).unwrap());
+        }
+        archive.extend(zip.finish().unwrap());
+        archive
+    }
+
+    #[test]
+    fn test_check_voices_file_ok() {
+        let (_dir, _model, voices) = model_dir();
+        std::fs::write(&voices, voices_archive(Voice::all().len())).unwrap();
+
+        assert!(check_voices_file(&voices).is_ok());
+    }
+
+    #[test]
+    fn test_check_voices_file_truncated() {
+        let (_dir, _model, voices) = model_dir();
+        let archive = voices_archive(Voice::all().len());
+        std::fs::write(&voices, &archive[..archive.len() / 2]).unwrap();
+
+        let Err(TtsError::VoicesFileCorrupt(problem)) = check_voices_file(&voices) else {
+            panic!("expected VoicesFileCorrupt");
+        };
+        assert!(problem.contains(voices.to_str().unwrap()), "{}", problem);
+        assert!(problem.contains("truncated"), "{}", problem);
+    }
+
+    #[test]
+    fn test_check_voices_file_wrong_contents() {
+        let (_dir, _model, voices) = model_dir();
+        std::fs::write(&voices, b"<html>Not Found</html>").unwrap();
+        let Err(TtsError::VoicesFileCorrupt(problem)) = check_voices_file(&voices) else {
+            panic!("expected VoicesFileCorrupt");
+        };
+        assert!(problem.contains("not a voices archive"), "{}", problem);
+
+        std::fs::write(&voices, voices_archive(3)).unwrap();
+        let Err(TtsError::VoicesFileCorrupt(problem)) = check_voices_file(&voices) else {
+            panic!("expected VoicesFileCorrupt");
+        };
+        assert!(problem.contains("holds 3 entries"), "{}", problem);
+    }
+
+    #[test]
+    fn test_missing_voices_unreadable_file() {
+        let (_dir, _model, voices) = model_dir();
+        assert!(missing_voices(&voices).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_wait_without_timeout_checks_once() {
+        let (_dir, model, voices) = model_dir();
+        let start = Instant::now();
+
+        let result = wait_for_model_files(&model, &voices, Duration::ZERO).await;
+        assert!(matches!(result, Err(TtsError::ModelFilesMissing(_))));
+        assert!(start.elapsed() < MODEL_WAIT_INITIAL_BACKOFF);
+    }
+
+    #[tokio::test]
+    async fn test_wait_picks_up_late_files() {
+        let (_dir, model, voices) = model_dir();
+        let (late_model, late_voices) = (model.clone(), voices.clone());
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            std::fs::write(late_model, b"model").unwrap();
+            std::fs::write(late_voices, b"voices").unwrap();
+        });
+
+        let result = wait_for_model_files(&model, &voices, Duration::from_secs(5)).await;
+        assert!(result.is_ok());
+    }
+
+    fn espeak_parent(files: &[&str]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = dir.path().join("espeak-ng-data");
+        std::fs::create_dir(&data_dir).unwrap();
+        for file in files {
+            std::fs::write(data_dir.join(file), b"data").unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_check_espeak_data_ok() {
+        let dir = espeak_parent(&ESPEAK_DATA_FILES);
+        assert!(check_espeak_data(dir.path()).is_ok());
+    }
+
+    #[test]
+    fn test_check_espeak_data_absent_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("nowhere");
+
+        let Err(TtsError::EspeakDataInvalid(problems)) = check_espeak_data(&missing) else {
+            panic!("expected EspeakDataInvalid");
+        };
+        assert_eq!(problems, vec![format!("{} (not found)", missing.display())]);
+
+        let Err(TtsError::EspeakDataInvalid(problems)) = check_espeak_data(dir.path()) else {
+            panic!("expected EspeakDataInvalid");
+        };
+        assert_eq!(
+            problems,
+            vec![format!(
+                "{} (not found)",
+                dir.path().join("espeak-ng-data").display()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_check_espeak_data_reports_missing_files() {
+        let dir = espeak_parent(&["phontab", "phonindex", "intonations"]);
+        std::fs::write(dir.path().join("espeak-ng-data/en_dict"), b"").unwrap();
+
+        let Err(TtsError::EspeakDataInvalid(problems)) = check_espeak_data(dir.path()) else {
+            panic!("expected EspeakDataInvalid");
+        };
+        let data_dir = dir.path().join("espeak-ng-data");
+        assert_eq!(
+            problems,
+            vec![
+                format!("{} (not found)", data_dir.join("phondata").display()),
+                format!("{} (empty)", data_dir.join("en_dict").display()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_espeak_data_pointed_at_data_directory() {
+        let dir = espeak_parent(&ESPEAK_DATA_FILES);
+
+        let error = check_espeak_data(&dir.path().join("espeak-ng-data")).unwrap_err();
+        assert!(error.to_string().contains("use its parent"), "{}", error);
+    }
+}
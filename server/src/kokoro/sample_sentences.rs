@@ -0,0 +1,168 @@
+/// Sentences the `/samples` voice previews are read from
+///
+/// Each language has its own default, so British voices aren't heard reading
+/// American phrasing. Individual voices can be given a sentence of their own.
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::voice_config::{Language, Voice};
+use super::SpeechEngine;
+
+/// Default sample for American English voices
+const AMERICAN_SAMPLE: &str = "Hello, I'm here to help you read any text on the web. Whether it's an article, a blog post, or a long document, I can read it aloud for you in a natural and clear voice. Just select the text you want to hear, and I'll take care of the rest.";
+
+/// Default sample for British English voices
+const BRITISH_SAMPLE: &str = "Hello, I'm here to help you read any text on the web. Whether it's an article, a blog post or a lengthy document, I can read it aloud for you in a natural, clear voice. Simply highlight the text you'd like to hear, and I'll sort out the rest.";
+
+/// Sample sentence per language, with optional per-voice overrides
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleSentences {
+    languages: HashMap<Language, String>,
+    voices: HashMap<Voice, String>,
+}
+
+impl Default for SampleSentences {
+    fn default() -> Self {
+        Self {
+            languages: HashMap::from([
+                (Language::AmericanEnglish, AMERICAN_SAMPLE.to_string()),
+                (Language::BritishEnglish, BRITISH_SAMPLE.to_string()),
+            ]),
+            voices: HashMap::new(),
+        }
+    }
+}
+
+impl SampleSentences {
+    /// The defaults with overrides from a JSON file
+    ///
+    /// The file is an object whose keys are language names as `/voices`
+    /// reports them (`AmericanEnglish`, `BritishEnglish`) or voice IDs, and
+    /// whose values are the sentences to read.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(&contents).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// The defaults with overrides from JSON, as described in [`Self::load`]
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let overrides: HashMap<String, String> =
+            serde_json::from_str(json).map_err(|e| format!("Invalid sample sentences: {}", e))?;
+
+        let mut sentences = Self::default();
+        for (key, sentence) in overrides {
+            let sentence = sentence.trim();
+            if sentence.is_empty() {
+                return Err(format!("Sample sentence for '{}' is empty", key));
+            }
+
+            if let Some(voice) = Voice::from_id(&key) {
+                sentences.voices.insert(voice, sentence.to_string());
+            } else if let Some(language) = language_from_name(&key) {
+                sentences.languages.insert(language, sentence.to_string());
+            } else {
+                return Err(format!(
+                    "Unknown sample sentence key '{}': expected a language or voice ID",
+                    key
+                ));
+            }
+        }
+
+        Ok(sentences)
+    }
+
+    /// Sentence `voice` reads: its own if set, else its language's
+    pub fn for_voice(&self, voice: Voice) -> &str {
+        self.voices
+            .get(&voice)
+            .or_else(|| self.languages.get(&voice.config().language))
+            .map(String::as_str)
+            .unwrap_or(AMERICAN_SAMPLE)
+    }
+}
+
+/// Language named as `/voices` reports it
+fn language_from_name(name: &str) -> Option<Language> {
+    [Language::AmericanEnglish, Language::BritishEnglish]
+        .into_iter()
+        .find(|language| format!("{:?}", language) == name)
+}
+
+/// Write `voice`'s sample into `samples_dir` as `<voice id>.wav`
+pub fn generate_sample(
+    engine: &dyn SpeechEngine,
+    sentences: &SampleSentences,
+    voice: Voice,
+    samples_dir: &Path,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let output_path = samples_dir.join(format!("{}.wav", voice.id()));
+    engine.speak(
+        sentences.for_voice(voice),
+        output_path
+            .to_str()
+            .ok_or("samples path is not valid UTF-8")?,
+        voice.id(),
+        1.0,
+        false,
+    )?;
+    Ok(output_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kokoro::fake::{FakeEngine, FakeEngineLog};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_regeneration_uses_language_sentence() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = Arc::new(FakeEngineLog::default());
+        let engine = FakeEngine {
+            duration_ms: 100,
+            delay: Duration::ZERO,
+            log: log.clone(),
+        };
+        let sentences = SampleSentences::default();
+
+        for voice in [Voice::BritishFemaleEmma, Voice::AmericanFemaleHeart] {
+            let path = generate_sample(&engine, &sentences, voice, dir.path()).unwrap();
+            assert!(path.ends_with(format!("{}.wav", voice.id())));
+            assert!(path.exists());
+        }
+
+        assert_eq!(log.voices(), vec!["bf_emma", "af_heart"]);
+        assert_eq!(log.texts(), vec![BRITISH_SAMPLE, AMERICAN_SAMPLE]);
+    }
+
+    #[test]
+    fn test_overrides_by_language_and_voice() {
+        let sentences = SampleSentences::parse(
+            r#"{"BritishEnglish": "Fancy a cuppa?", "bm_lewis": "  Cheerio.  "}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            sentences.for_voice(Voice::BritishFemaleLily),
+            "Fancy a cuppa?"
+        );
+        assert_eq!(sentences.for_voice(Voice::BritishMaleLewis), "Cheerio.");
+        assert_eq!(
+            sentences.for_voice(Voice::AmericanMaleAdam),
+            AMERICAN_SAMPLE
+        );
+    }
+
+    #[test]
+    fn test_invalid_overrides_are_rejected() {
+        assert!(SampleSentences::parse(r#"{"Klingon": "Qapla'"}"#).is_err());
+        assert!(SampleSentences::parse(r#"{"af_heart": " "}"#).is_err());
+        assert!(SampleSentences::parse("not json").is_err());
+        assert_eq!(
+            SampleSentences::parse("{}").unwrap(),
+            SampleSentences::default()
+        );
+    }
+}
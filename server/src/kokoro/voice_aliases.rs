@@ -0,0 +1,187 @@
+// Stable voice names for clients
+//
+// Kokoro voice IDs (`af_heart`, `bm_george`) are opaque and may change between
+// model releases. Clients can instead ask for a friendly name: each voice's
+// plain name ("heart", "george"), a role such as "narrator", or an OpenAI-style
+// name such as "shimmer". Operators can add or repoint aliases with
+// `TTS_VOICE_ALIASES`, e.g. `narrator=bm_lewis,assistant=af_sarah`.
+
+use serde::{Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+
+use super::voice_config::Voice;
+
+/// Role and compatibility aliases on top of each voice's plain name
+const BUILTIN_ALIASES: &[(&str, Voice)] = &[
+    ("default", Voice::AmericanFemaleHeart),
+    ("narrator", Voice::BritishMaleGeorge),
+    ("shimmer", Voice::AmericanFemaleSky),
+];
+
+/// Registry mapping friendly and legacy voice names to canonical voices
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoiceAliases {
+    aliases: HashMap<String, Voice>,
+}
+
+/// An `alias → voice ID` map, sorted by alias
+impl Serialize for VoiceAliases {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let aliases: BTreeMap<&str, &str> = self
+            .aliases
+            .iter()
+            .map(|(alias, voice)| (alias.as_str(), voice.id()))
+            .collect();
+        aliases.serialize(serializer)
+    }
+}
+
+impl Default for VoiceAliases {
+    fn default() -> Self {
+        let mut aliases: HashMap<String, Voice> = Voice::all()
+            .into_iter()
+            .map(|voice| (voice.config().name.to_lowercase(), voice))
+            .collect();
+        for (alias, voice) in BUILTIN_ALIASES {
+            aliases.insert(alias.to_string(), *voice);
+        }
+
+        Self { aliases }
+    }
+}
+
+impl VoiceAliases {
+    /// Built-in aliases plus overrides from `TTS_VOICE_ALIASES`
+    pub fn from_env() -> Result<Self, String> {
+        Self::parse(std::env::var("TTS_VOICE_ALIASES").ok().as_deref())
+    }
+
+    /// Built-in aliases plus comma-separated `alias=voice_id` overrides
+    pub fn parse(overrides: Option<&str>) -> Result<Self, String> {
+        let mut registry = Self::default();
+
+        for entry in overrides.unwrap_or("").split(',').map(str::trim) {
+            if entry.is_empty() {
+                continue;
+            }
+            let (alias, id) = entry.split_once('=').ok_or_else(|| {
+                format!("Invalid voice alias '{}': expected alias=voice_id", entry)
+            })?;
+            let alias = alias.trim().to_lowercase();
+            let id = id.trim();
+
+            if alias.is_empty() {
+                return Err(format!("Invalid voice alias '{}': alias is empty", entry));
+            }
+            if Voice::from_id(&alias).is_some() {
+                return Err(format!(
+                    "Invalid voice alias '{}': '{}' is already a voice ID",
+                    entry, alias
+                ));
+            }
+            let voice = Voice::from_id(id).ok_or_else(|| {
+                format!("Invalid voice alias '{}': unknown voice '{}'", entry, id)
+            })?;
+
+            registry.aliases.insert(alias, voice);
+        }
+
+        Ok(registry)
+    }
+
+    /// Resolve a canonical voice ID or alias (case-insensitive)
+    ///
+    /// Returns `None` for anything else, including voice mixes, which are
+    /// parsed by [`VoiceMix`](super::voice_config::VoiceMix).
+    pub fn resolve_voice(&self, alias: &str) -> Option<Voice> {
+        let alias = alias.trim().to_lowercase();
+        Voice::from_id(&alias).or_else(|| self.aliases.get(&alias).copied())
+    }
+
+    /// Number of registered aliases
+    pub fn count(&self) -> usize {
+        self.aliases.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_aliases() {
+        let aliases = VoiceAliases::default();
+        assert_eq!(
+            aliases.resolve_voice("heart"),
+            Some(Voice::AmericanFemaleHeart)
+        );
+        assert_eq!(
+            aliases.resolve_voice("alloy"),
+            Some(Voice::AmericanFemaleAlloy)
+        );
+        assert_eq!(
+            aliases.resolve_voice("Narrator"),
+            Some(Voice::BritishMaleGeorge)
+        );
+        assert_eq!(
+            aliases.resolve_voice("shimmer"),
+            Some(Voice::AmericanFemaleSky)
+        );
+        assert_eq!(aliases.count(), Voice::all().len() + BUILTIN_ALIASES.len());
+    }
+
+    #[test]
+    fn test_canonical_ids_resolve_to_themselves() {
+        let aliases = VoiceAliases::default();
+        for voice in Voice::all() {
+            assert_eq!(aliases.resolve_voice(voice.id()), Some(voice));
+        }
+        assert_eq!(
+            aliases.resolve_voice(" BF_LILY "),
+            Some(Voice::BritishFemaleLily)
+        );
+    }
+
+    #[test]
+    fn test_unknown_aliases() {
+        let aliases = VoiceAliases::default();
+        assert_eq!(aliases.resolve_voice("robot"), None);
+        assert_eq!(aliases.resolve_voice("af_sarah.4+af_nicole.6"), None);
+        assert_eq!(aliases.resolve_voice(""), None);
+    }
+
+    #[test]
+    fn test_config_overrides() {
+        let aliases =
+            VoiceAliases::parse(Some("narrator=bm_lewis, Assistant = af_sarah,")).unwrap();
+        assert_eq!(
+            aliases.resolve_voice("narrator"),
+            Some(Voice::BritishMaleLewis)
+        );
+        assert_eq!(
+            aliases.resolve_voice("assistant"),
+            Some(Voice::AmericanFemaleSarah)
+        );
+        // Built-ins that weren't overridden are kept
+        assert_eq!(
+            aliases.resolve_voice("default"),
+            Some(Voice::AmericanFemaleHeart)
+        );
+    }
+
+    #[test]
+    fn test_invalid_overrides() {
+        for overrides in [
+            "narrator",
+            "=af_heart",
+            "narrator=nobody",
+            "af_heart=bm_lewis",
+        ] {
+            assert!(
+                VoiceAliases::parse(Some(overrides)).is_err(),
+                "{} should be rejected",
+                overrides
+            );
+        }
+    }
+}
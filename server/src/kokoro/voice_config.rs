@@ -17,7 +17,8 @@
 //
 // ## Adding New Voices
 // To add support for more voices from Kokoro:
-// 1. Add the language variant to the Language enum below
+// 1. Add the language variant to the Language enum below (and its default
+//    sample sentence in sample_sentences.rs)
 // 2. Add the voice to the Voice enum (follow existing naming pattern)
 // 3. Add the voice configuration in the config() method
 // 4. Add the voice to the all() array
@@ -324,6 +325,11 @@ impl Voice {
         self.config().id
     }
 
+    /// Look up a voice by its ID (e.g. "af_heart")
+    pub fn from_id(id: &str) -> Option<Voice> {
+        Self::all().into_iter().find(|voice| voice.id() == id)
+    }
+
     /// Get all available voices as an array
     pub const fn all() -> [Voice; 28] {
         [
@@ -388,3 +394,209 @@ impl Voice {
             .collect()
     }
 }
+
+/// Most voices a single mix may blend
+pub const MAX_MIX_VOICES: usize = 4;
+
+/// A weighted blend of voices, written `af_heart:0.6+am_adam:0.4`
+///
+/// Kokoro voices are style vectors, so the engine blends them by weight.
+/// Weights are relative: they are normalized to sum to 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoiceMix {
+    parts: Vec<(Voice, f32)>,
+}
+
+impl VoiceMix {
+    /// Build a mix from voices and relative weights
+    ///
+    /// Needs 2 to [`MAX_MIX_VOICES`] distinct voices with positive weights.
+    pub fn new(parts: Vec<(Voice, f32)>) -> Result<Self, String> {
+        if parts.len() < 2 || parts.len() > MAX_MIX_VOICES {
+            return Err(format!(
+                "A voice mix needs 2 to {} voices, got {}",
+                MAX_MIX_VOICES,
+                parts.len()
+            ));
+        }
+        for (i, (voice, weight)) in parts.iter().enumerate() {
+            if !weight.is_finite() || *weight <= 0.0 {
+                return Err(format!(
+                    "Invalid weight {} for voice '{}': must be positive",
+                    weight,
+                    voice.id()
+                ));
+            }
+            if parts[..i].iter().any(|(other, _)| other == voice) {
+                return Err(format!("Voice '{}' appears twice in the mix", voice.id()));
+            }
+        }
+
+        let total: f32 = parts.iter().map(|(_, weight)| weight).sum();
+        let parts = parts
+            .into_iter()
+            .map(|(voice, weight)| (voice, weight / total))
+            .collect();
+        Ok(Self { parts })
+    }
+
+    /// Parse `voice[:weight]+voice[:weight]...`, resolving names with `resolve`
+    ///
+    /// Parts without a weight count as 1. Kokoro's own `af_sky.4` form, a
+    /// weight in tenths, is accepted too.
+    pub fn parse(spec: &str, resolve: impl Fn(&str) -> Option<Voice>) -> Result<Self, String> {
+        let parts = spec
+            .split('+')
+            .map(|part| {
+                let part = part.trim();
+                let (name, weight) = match part.split_once(':') {
+                    Some((name, weight)) => {
+                        let weight = weight.trim().parse::<f32>().map_err(|_| {
+                            format!("Invalid weight '{}' in voice mix part '{}'", weight, part)
+                        })?;
+                        (name, weight)
+                    }
+                    None => match part.split_once('.') {
+                        Some((name, tenths))
+                            if !tenths.is_empty() && tenths.bytes().all(|b| b.is_ascii_digit()) =>
+                        {
+                            (name, tenths.parse::<f32>().unwrap_or(0.0) / 10.0)
+                        }
+                        _ => (part, 1.0),
+                    },
+                };
+                let voice = resolve(name.trim())
+                    .ok_or_else(|| format!("Unknown voice '{}' in voice mix", name.trim()))?;
+                Ok((voice, weight))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Self::new(parts)
+    }
+
+    /// Voices and their normalized weights, in the order given
+    pub fn parts(&self) -> &[(Voice, f32)] {
+        &self.parts
+    }
+
+    /// The mix in the engine's style syntax (`af_heart.6+am_adam.4`)
+    ///
+    /// The engine takes weights in tenths, so they are rounded to tenths
+    /// that still sum to one; a voice rounded down to nothing is dropped.
+    pub fn style_name(&self) -> String {
+        let mut tenths: Vec<u32> = self
+            .parts
+            .iter()
+            .map(|(_, weight)| (weight * 10.0).floor() as u32)
+            .collect();
+
+        // Hand the leftover tenths to the largest remainders
+        let mut by_remainder: Vec<usize> = (0..self.parts.len()).collect();
+        by_remainder.sort_by(|&a, &b| {
+            let remainder = |i: usize| self.parts[i].1 * 10.0 - tenths[i] as f32;
+            remainder(b).total_cmp(&remainder(a))
+        });
+        let leftover = 10u32.saturating_sub(tenths.iter().sum());
+        for &i in by_remainder.iter().take(leftover as usize) {
+            tenths[i] += 1;
+        }
+
+        let kept: Vec<(Voice, u32)> = self
+            .parts
+            .iter()
+            .zip(tenths)
+            .filter(|(_, tenths)| *tenths > 0)
+            .map(|((voice, _), tenths)| (*voice, tenths))
+            .collect();
+        match kept.as_slice() {
+            [(voice, _)] => voice.id().to_string(),
+            _ => kept
+                .iter()
+                .map(|(voice, tenths)| format!("{}.{}", voice.id(), tenths))
+                .collect::<Vec<_>>()
+                .join("+"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_id_round_trips() {
+        for voice in Voice::all() {
+            assert_eq!(Voice::from_id(voice.id()), Some(voice));
+        }
+        assert_eq!(Voice::from_id("xx_nobody"), None);
+    }
+
+    fn parse_mix(spec: &str) -> Result<VoiceMix, String> {
+        VoiceMix::parse(spec, Voice::from_id)
+    }
+
+    #[test]
+    fn test_parse_voice_mix() {
+        let mix = parse_mix("af_heart:0.6+am_adam:0.4").unwrap();
+        assert_eq!(
+            mix.parts(),
+            &[
+                (Voice::AmericanFemaleHeart, 0.6),
+                (Voice::AmericanMaleAdam, 0.4)
+            ]
+        );
+        assert_eq!(mix.style_name(), "af_heart.6+am_adam.4");
+
+        // Kokoro's tenths syntax and unweighted parts
+        assert_eq!(
+            parse_mix("af_sky.4+af_nicole.6").unwrap().style_name(),
+            "af_sky.4+af_nicole.6"
+        );
+        assert_eq!(
+            parse_mix(" af_sky + af_nicole ").unwrap().style_name(),
+            "af_sky.5+af_nicole.5"
+        );
+    }
+
+    #[test]
+    fn test_voice_mix_weights_normalize() {
+        let mix = parse_mix("af_heart:3+am_adam:1").unwrap();
+        assert_eq!(mix.parts()[0].1, 0.75);
+        assert_eq!(mix.parts()[1].1, 0.25);
+        let total: f32 = mix.parts().iter().map(|(_, weight)| weight).sum();
+        assert!((total - 1.0).abs() < 1e-6);
+
+        // Rounded to tenths that still sum to ten
+        assert_eq!(mix.style_name(), "af_heart.8+am_adam.2");
+        assert_eq!(
+            parse_mix("af_sky:1+af_bella:1+af_nova:1")
+                .unwrap()
+                .style_name(),
+            "af_sky.4+af_bella.3+af_nova.3"
+        );
+        // A voice too faint to register is dropped
+        assert_eq!(
+            parse_mix("af_sky:0.97+af_bella:0.03").unwrap().style_name(),
+            "af_sky"
+        );
+    }
+
+    #[test]
+    fn test_invalid_voice_mixes() {
+        assert!(parse_mix("af_heart").unwrap_err().contains("2 to 4 voices"));
+        assert!(parse_mix("af_heart:0.5+xx_nobody:0.5")
+            .unwrap_err()
+            .contains("Unknown voice 'xx_nobody'"));
+        assert!(parse_mix("af_heart:abc+am_adam")
+            .unwrap_err()
+            .contains("Invalid weight"));
+        assert!(parse_mix("af_heart:0+am_adam:1")
+            .unwrap_err()
+            .contains("must be positive"));
+        assert!(parse_mix("af_heart:-1+am_adam:2").is_err());
+        assert!(parse_mix("af_heart+af_heart")
+            .unwrap_err()
+            .contains("twice"));
+        assert!(parse_mix("af_sky+af_bella+af_nova+af_river+af_kore").is_err());
+    }
+}
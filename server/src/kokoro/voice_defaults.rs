@@ -0,0 +1,153 @@
+// Operator-tuned defaults for individual voices
+//
+// Some voices sound best slightly slowed or pitched. Operators can give them a
+// default speed with `TTS_VOICE_SPEEDS` (e.g. `am_santa=0.9`) and a default
+// pitch with `TTS_VOICE_PITCHES` (e.g. `am_santa=0.95`), or the matching
+// `voice_speeds`/`voice_pitches` keys in the config file. Requests that give
+// a speed still get the speed they asked for.
+
+use std::collections::HashMap;
+
+use super::voice_aliases::VoiceAliases;
+use crate::config::constants::{MAX_PITCH, MAX_SPEED, MIN_PITCH, MIN_SPEED};
+
+/// Default speed and pitch for voices that have them configured
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VoiceDefaults {
+    speeds: HashMap<String, f32>,
+    pitches: HashMap<String, f32>,
+}
+
+impl VoiceDefaults {
+    /// Comma-separated `voice=speed` and `voice=pitch` entries; voices may be aliases
+    pub fn parse(
+        speeds: Option<&str>,
+        pitches: Option<&str>,
+        aliases: &VoiceAliases,
+    ) -> Result<Self, String> {
+        Ok(Self {
+            speeds: parse_entries(speeds, "speed", MIN_SPEED..=MAX_SPEED, aliases)?,
+            pitches: parse_entries(pitches, "pitch", MIN_PITCH..=MAX_PITCH, aliases)?,
+        })
+    }
+
+    /// Configured default speed for `voice`, by voice ID
+    pub fn speed(&self, voice: &str) -> Option<f32> {
+        self.speeds.get(voice).copied()
+    }
+
+    /// Configured default pitch factor for `voice`, by voice ID
+    pub fn pitch(&self, voice: &str) -> Option<f32> {
+        self.pitches.get(voice).copied()
+    }
+
+    /// Number of voices with a configured speed or pitch
+    pub fn count(&self) -> usize {
+        self.speeds
+            .keys()
+            .chain(
+                self.pitches
+                    .keys()
+                    .filter(|id| !self.speeds.contains_key(*id)),
+            )
+            .count()
+    }
+}
+
+fn parse_entries(
+    value: Option<&str>,
+    kind: &str,
+    range: std::ops::RangeInclusive<f32>,
+    aliases: &VoiceAliases,
+) -> Result<HashMap<String, f32>, String> {
+    let mut values = HashMap::new();
+
+    for entry in value.unwrap_or("").split(',').map(str::trim) {
+        if entry.is_empty() {
+            continue;
+        }
+        let (name, number) = entry.split_once('=').ok_or_else(|| {
+            format!(
+                "Invalid voice {} '{}': expected voice={}",
+                kind, entry, kind
+            )
+        })?;
+        let voice = aliases.resolve_voice(name).ok_or_else(|| {
+            format!(
+                "Invalid voice {} '{}': unknown voice '{}'",
+                kind,
+                entry,
+                name.trim()
+            )
+        })?;
+        let number = number
+            .trim()
+            .parse::<f32>()
+            .ok()
+            .filter(|number| range.contains(number))
+            .ok_or_else(|| {
+                format!(
+                    "Invalid voice {} '{}': expected a number from {} to {}",
+                    kind,
+                    entry,
+                    range.start(),
+                    range.end()
+                )
+            })?;
+
+        values.insert(voice.id().to_string(), number);
+    }
+
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_defaults_configured() {
+        let defaults = VoiceDefaults::parse(None, None, &VoiceAliases::default()).unwrap();
+        assert_eq!(defaults, VoiceDefaults::default());
+        assert_eq!(defaults.speed("am_santa"), None);
+        assert_eq!(defaults.pitch("am_santa"), None);
+        assert_eq!(defaults.count(), 0);
+    }
+
+    #[test]
+    fn test_parse_speeds_and_pitches() {
+        let defaults = VoiceDefaults::parse(
+            Some("am_santa=0.9, narrator=1.1"),
+            Some("am_santa=0.95"),
+            &VoiceAliases::default(),
+        )
+        .unwrap();
+
+        assert_eq!(defaults.speed("am_santa"), Some(0.9));
+        assert_eq!(defaults.pitch("am_santa"), Some(0.95));
+        // Aliases are stored under the voice ID
+        assert_eq!(defaults.speed("bm_george"), Some(1.1));
+        assert_eq!(defaults.pitch("bm_george"), None);
+        assert_eq!(defaults.count(), 2);
+    }
+
+    #[test]
+    fn test_invalid_entries_rejected() {
+        let aliases = VoiceAliases::default();
+        for (speeds, pitches) in [
+            (Some("am_santa"), None),
+            (Some("nobody=0.9"), None),
+            (Some("am_santa=fast"), None),
+            (Some("am_santa=9"), None),
+            (None, Some("am_santa=3.0")),
+            (None, Some("am_santa=NaN")),
+        ] {
+            assert!(
+                VoiceDefaults::parse(speeds, pitches, &aliases).is_err(),
+                "{:?} {:?}",
+                speeds,
+                pitches
+            );
+        }
+    }
+}
@@ -2,11 +2,16 @@
 pub mod audio;
 pub mod auth;
 pub mod chunking;
+pub mod concurrency;
 pub mod config;
+pub mod connection_limit;
 pub mod error;
+pub mod fair_queue;
 pub mod kokoro;
+pub mod local_socket;
 mod models; // Internal module, not exported
 pub mod rate_limit;
+pub mod request_timeout;
 pub mod server;
 pub mod services;
 pub mod text_processing;
@@ -0,0 +1,147 @@
+use axum::Router;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::connection_limit::serve_connection;
+
+/// Backoff after a failed `accept`, e.g. when out of file descriptors
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Serve `app` on a Unix domain socket at `path`
+///
+/// A socket file left behind by a previous run is replaced; any other file
+/// at `path` is an error rather than something to delete. Peers have no IP
+/// address, so handlers see no `ConnectInfo`.
+#[cfg(unix)]
+pub async fn serve(path: &Path, app: Router) -> std::io::Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+    use tokio::net::UnixListener;
+
+    if let Ok(metadata) = std::fs::symlink_metadata(path) {
+        if !metadata.file_type().is_socket() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("{} exists and is not a socket", path.display()),
+            ));
+        }
+        std::fs::remove_file(path)?;
+    }
+
+    let listener = UnixListener::bind(path)?;
+
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                tracing::error!("Failed to accept local connection: {}", e);
+                tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                continue;
+            }
+        };
+
+        let app = app.clone();
+        tokio::spawn(serve_connection(stream, None, app, false));
+    }
+}
+
+/// Serve `app` on a Windows named pipe such as `\\.\pipe\porua`
+///
+/// A new pipe instance is created before each connected one is handed off,
+/// so clients never find the pipe missing between connections.
+#[cfg(windows)]
+pub async fn serve(path: &Path, app: Router) -> std::io::Result<()> {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let mut server = ServerOptions::new()
+        .first_pipe_instance(true)
+        .create(path)?;
+
+    loop {
+        if let Err(e) = server.connect().await {
+            tracing::error!("Failed to accept local connection: {}", e);
+            tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+            continue;
+        }
+
+        let connected = std::mem::replace(&mut server, ServerOptions::new().create(path)?);
+        let app = app.clone();
+        tokio::spawn(serve_connection(connected, None, app, false));
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::kokoro::fake::fake_pool;
+    use crate::server::{create_router, AppState};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::UnixStream;
+
+    async fn start_server(path: &Path) {
+        let (pool, _) = fake_pool(1, 100, Duration::ZERO);
+        let app = create_router(AppState::for_tests(pool));
+        let socket = path.to_path_buf();
+        tokio::spawn(async move { serve(&socket, app).await });
+
+        // Wait until the server accepts, not just until the file exists
+        for _ in 0..100 {
+            if UnixStream::connect(path).await.is_ok() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("socket {} was never created", path.display());
+    }
+
+    async fn get_raw(path: &Path, uri: &str) -> String {
+        let mut stream = UnixStream::connect(path).await.unwrap();
+        let request = format!(
+            "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+            uri
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut response = Vec::new();
+        tokio::time::timeout(Duration::from_secs(2), stream.read_to_end(&mut response))
+            .await
+            .expect("response should not hang")
+            .unwrap();
+        String::from_utf8_lossy(&response).into_owned()
+    }
+
+    #[tokio::test]
+    async fn test_health_over_unix_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("porua.sock");
+        start_server(&path).await;
+
+        let response = get_raw(&path, "/health").await;
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response.contains("\"status\":\"ok\""), "{}", response);
+    }
+
+    #[tokio::test]
+    async fn test_stale_socket_is_replaced() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("porua.sock");
+        drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+        assert!(path.exists());
+
+        start_server(&path).await;
+        let response = get_raw(&path, "/health").await;
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+    }
+
+    #[tokio::test]
+    async fn test_refuses_to_replace_regular_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("porua.sock");
+        std::fs::write(&path, "keep me").unwrap();
+
+        let (pool, _) = fake_pool(1, 100, Duration::ZERO);
+        let app = create_router(AppState::for_tests(pool));
+        let err = serve(&path, app).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "keep me");
+    }
+}
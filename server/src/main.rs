@@ -2,26 +2,59 @@ mod audio;
 mod auth;
 mod chunking;
 mod cli;
+mod concurrency;
 mod config;
+mod connection_limit;
 mod error;
+mod fair_queue;
 mod kokoro;
+mod local_socket;
 mod models;
 mod rate_limit;
+mod request_timeout;
 mod server;
 mod services;
 mod text_processing;
 mod utils;
 
 use auth::load_api_keys;
-use kokoro::model_paths::{get_model_path, get_voices_path};
+use chunking::LongTokenPolicy;
+use concurrency::ConcurrencyLimiter;
+use config::constants::{
+    DEFAULT_AUDIO_CACHE_MAX_BYTES, DEFAULT_JOB_TTL_SECS, DEFAULT_MAX_AUDIO_DURATION_MS,
+    DEFAULT_MAX_CONNECTIONS, DEFAULT_MAX_NORMALIZED_LENGTH, DEFAULT_MAX_PENDING_JOBS,
+    DEFAULT_MIN_SPEED, DEFAULT_STREAM_KEEP_ALIVE_MS, MAX_ELLIPSIS_PAUSE_MS, MAX_SPEED,
+    MAX_TEXT_LENGTH, SAMPLES_CACHE_CONTROL, STALE_TEMP_FILE_AGE_SECS,
+};
+use config::cors::CorsConfig;
+use config::server_config::{
+    parse_flag, RateLimitMode, RateLimitSettings, ServerConfig, DEFAULT_CONFIG_FILE,
+};
+use config::trusted_proxies::TrustedProxies;
+use connection_limit::ConnectionLimiter;
+use fair_queue::FairQueue;
+use kokoro::model_paths::{
+    check_espeak_data, check_voices_file, get_model_path, get_voices_path, missing_voices,
+    wait_for_model_files,
+};
+use kokoro::voice_aliases::VoiceAliases;
 use kokoro::voice_config::Voice;
-use kokoro::{TTSPool, TTS};
-use rate_limit::{PerIpRateLimiter, PerKeyRateLimiter, RateLimitConfig, RateLimiterMode};
+use kokoro::voice_defaults::VoiceDefaults;
+use kokoro::{EngineSelection, TTSPool, TTS};
+use rate_limit::{PerIpRateLimiter, PerKeyRateLimiter, RateLimiterMode};
 use server::{create_router, AppState};
+use services::audio_cache::DiskCache;
+use services::batch::{BatchOptions, BatchOutput};
+use services::estimate::VoiceRates;
+use services::jobs::JobStore;
+use services::streaming::ChunkFailureMode;
 use std::env;
 use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use text_processing::normalization::NormalizationConfig;
+use text_processing::quotes::QuoteStyle;
 
 fn main() -> error::Result<()> {
     // Parse command line arguments FIRST before any initialization
@@ -89,10 +122,10 @@ async fn async_main(args: Vec<String>) -> error::Result<()> {
     // literal escape sequences from appearing in logs.
     // Override with LOG_ANSI environment variable: LOG_ANSI=true to force enable,
     // LOG_ANSI=false to force disable.
-    let use_ansi = env::var("LOG_ANSI")
-        .ok()
-        .and_then(|v| v.parse::<bool>().ok())
-        .unwrap_or_else(|| std::io::stdout().is_terminal());
+    let use_ansi = match env::var("LOG_ANSI") {
+        Ok(value) => parse_flag("LOG_ANSI", &value).map_err(error::TtsError::InvalidRequest)?,
+        Err(_) => std::io::stdout().is_terminal(),
+    };
 
     tracing_subscriber::fmt()
         .with_env_filter(
@@ -105,8 +138,7 @@ async fn async_main(args: Vec<String>) -> error::Result<()> {
         .compact() // Use compact formatting
         .init();
 
-    // Check if we should run in server mode
-    let server_mode = args.contains(&"--server".to_string());
+    let mode = run_mode(&args);
     let port = args
         .iter()
         .position(|arg| arg == "--port")
@@ -114,11 +146,10 @@ async fn async_main(args: Vec<String>) -> error::Result<()> {
         .and_then(|p| p.parse::<u16>().ok())
         .unwrap_or(3000);
 
-    // Get TTS pool size from environment or default to 2
-    let pool_size = env::var("TTS_POOL_SIZE")
-        .ok()
-        .and_then(|s| s.parse::<usize>().ok())
-        .unwrap_or(2);
+    // Settings from the optional config file, overridden by environment variables
+    let config = ServerConfig::load().map_err(error::TtsError::InvalidRequest)?;
+    let socket_path = local_socket_path(&args).or_else(|| config.unix_socket.clone());
+    let pool_size = config.pool_size;
 
     // Get model paths
     let model_path = get_model_path();
@@ -127,42 +158,189 @@ async fn async_main(args: Vec<String>) -> error::Result<()> {
     println!("Loading model from: {}", model_path.display());
     println!("Loading voices from: {}", voices_path.display());
 
-    if server_mode {
+    // Report missing files readably instead of via a cryptic model loader error
+    if let Err(e) = wait_for_model_files(&model_path, &voices_path, config.model_wait).await {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+    if let Err(e) = check_voices_file(&voices_path) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+
+    // espeak-ng only reads its data path from the environment, so a path
+    // from the config file is exported before any engine loads
+    if let Some(dir) = &config.espeak_data_dir {
+        println!("Loading eSpeak-ng data from: {}", dir.display());
+        env::set_var("PIPER_ESPEAKNG_DATA_DIRECTORY", dir);
+        if let Err(e) = check_espeak_data(dir) {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    if mode == RunMode::Check {
+        let passed = run_self_check(&model_path, &voices_path, &config.normalization).await;
+        std::process::exit(if passed { 0 } else { 1 });
+    }
+
+    if mode == RunMode::Server {
         // Server mode - initialize pool
         println!("Porua Server v{}", env!("CARGO_PKG_VERSION"));
-        println!("Starting TTS HTTP server on port {}...", port);
+        match &socket_path {
+            Some(path) => println!("Starting TTS HTTP server on {}...", path.display()),
+            None => println!("Starting TTS HTTP server on port {}...", port),
+        }
 
         // Load API keys
         let api_keys = load_api_keys();
 
+        // Invalid proxy ranges are a startup error rather than silently trusting nothing
+        let trusted_proxies =
+            TrustedProxies::from_env().map_err(error::TtsError::InvalidRequest)?;
+
         // Initialize rate limiter with dual-mode support
-        let rate_limiter = load_rate_limit_config(api_keys.is_enabled());
+        let rate_limiter = build_rate_limiter(
+            &config.rate_limit,
+            api_keys.is_enabled(),
+            trusted_proxies.clone(),
+            socket_path.is_none(),
+        )
+        .map_err(error::TtsError::InvalidRequest)?;
 
         println!("Initializing TTS pool with {} engines...", pool_size);
 
+        // Synthesis is CPU-bound, so engines beyond the core count mostly cost memory
+        if let Ok(cpus) = std::thread::available_parallelism() {
+            if pool_size > cpus.get() {
+                tracing::warn!(
+                    "Pool size {} exceeds the {} available CPUs; extra engines add memory use but little throughput",
+                    pool_size,
+                    cpus
+                );
+            }
+        }
+
         let tts_pool = TTSPool::new(
             pool_size,
             model_path.to_str().unwrap(),
             voices_path.to_str().unwrap(),
         )
-        .await?;
+        .await?
+        .with_circuit_breaker(config.circuit_breaker.clone())
+        .with_engine_selection(config.engine_selection);
+
+        // Take turns by API key when keys are checked, else by client address
+        let tts_pool = if config.fair_queue {
+            tts_pool.with_fair_queue(FairQueue::new(trusted_proxies, api_keys.is_enabled()))
+        } else {
+            tts_pool
+        };
+
+        // Engines that failed to load are left out; size everything else by what loaded
+        let requested_pool_size = pool_size;
+        let pool_size = tts_pool.stats().pool_size;
+        if pool_size < requested_pool_size {
+            println!(
+                "WARNING: only {} of {} engines loaded; continuing with a smaller pool",
+                pool_size, requested_pool_size
+            );
+        }
+
+        println!("\nWarm-up:");
+        if config.warmup {
+            match tts_pool.warm_up().await {
+                Ok(elapsed) => {
+                    tracing::info!("Warmed up {} engines in {:?}", pool_size, elapsed);
+                    println!(
+                        "  Warmed up {} engines in {} ms",
+                        pool_size,
+                        elapsed.as_millis()
+                    );
+                }
+                // A failed warm-up only costs first-request latency; keep starting
+                Err(e) => tracing::warn!("{}", e),
+            }
+        } else {
+            println!("  Status: DISABLED");
+        }
+        println!("  Configure: TTS_WARMUP (default: false)");
+
+        println!("\nStartup self-test:");
+        if config.startup_selftest {
+            match tts_pool.self_test().await {
+                Ok(duration_ms) => println!("  Passed ({:.0} ms of audio)", duration_ms),
+                // A broken model or eSpeak setup would fail every request; don't serve
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        } else {
+            println!("  Status: DISABLED");
+        }
+        println!("  Configure: TTS_STARTUP_SELFTEST (default: false)");
 
         let addr = format!("0.0.0.0:{}", port);
-        let listener = tokio::net::TcpListener::bind(&addr).await?;
+        let listener = match socket_path {
+            Some(_) => None,
+            None => Some(tokio::net::TcpListener::bind(&addr).await?),
+        };
 
-        println!("\nServer listening on http://{}", addr);
+        match &socket_path {
+            Some(path) => println!("\nServer listening on local socket {}", path.display()),
+            None => println!("\nServer listening on http://{}", addr),
+        }
         println!("\nAvailable endpoints:");
         println!("  POST   /tts          - Generate speech from text");
         println!("  POST   /tts/stream   - Generate speech with streaming response");
+        println!("  POST   /tts/file     - Stream speech for an uploaded text file");
+        println!("  POST   /tts/archive  - Download each chunk as a WAV in a zip");
+        println!("  POST   /tts/estimate - Estimate audio duration and compute time");
+        println!("  POST   /tts/phonemes - Show the phonemes text is read as");
+        println!("  POST   /tts/jobs     - Queue a background synthesis job");
+        println!("  DELETE /tts/jobs/{{id}} - Cancel a queued or running job");
         println!("  GET    /voices       - List available voices");
+        println!("  POST   /voices/mix/preview - Preview a voice mix");
         println!("  GET    /health       - Health check");
+        println!("  GET    /health/ready - Readiness check (engines, eSpeak-ng data)");
         println!("  GET    /stats        - Pool statistics");
+        if api_keys.is_enabled() {
+            println!("  POST   /admin/reload - Reload TTS engines from model files");
+            println!("  GET    /admin/config - Effective configuration (secrets redacted)");
+        }
+        println!("\nConfiguration:");
+        match &config.source {
+            Some(path) => println!("  Config file: {}", path.display()),
+            None => println!("  Config file: none ({} not found)", DEFAULT_CONFIG_FILE),
+        }
+        println!("  Configure: PORUA_CONFIG (environment variables override the file)");
         println!("\nPool configuration:");
         println!("  Pool size: {} engines", pool_size);
-        println!("  Set TTS_POOL_SIZE environment variable to change");
+        println!("  Set TTS_POOL_SIZE environment variable to change (max: TTS_MAX_POOL_SIZE)");
+        println!("\nCircuit Breaker:");
+        let breaker = &config.circuit_breaker;
+        if breaker.failure_threshold == 0 {
+            println!("  Status: DISABLED");
+        } else {
+            println!(
+                "  Opens after {} consecutive engine failures within {}s",
+                breaker.failure_threshold,
+                breaker.window.as_secs()
+            );
+            println!("  Cooldown: {}s", breaker.cooldown.as_secs());
+        }
+        println!("  Configure: CIRCUIT_BREAKER_FAILURES (default: 5, 0 = off), CIRCUIT_BREAKER_WINDOW_SECONDS (default: 60), CIRCUIT_BREAKER_COOLDOWN_SECONDS (default: 30)");
         println!("\nAuthentication:");
         if api_keys.is_enabled() {
             println!("  Status: ENABLED ({} key(s) configured)", api_keys.count());
+            if api_keys.max_text_length_count() > 0 {
+                println!(
+                    "  Max text length: {} key(s) with their own limit (default: {})",
+                    api_keys.max_text_length_count(),
+                    MAX_TEXT_LENGTH
+                );
+            }
             println!("  Use X-API-Key or Authorization: Bearer header");
         } else {
             println!("  Status: DISABLED (no key file found)");
@@ -183,8 +361,12 @@ async fn async_main(args: Vec<String>) -> error::Result<()> {
                         "  Configure: RATE_LIMIT_AUTHENTICATED_PER_SECOND, RATE_LIMIT_AUTHENTICATED_BURST_SIZE"
                     );
                 }
-                RateLimiterMode::PerIp(_) => {
+                RateLimiterMode::PerIp(ip_limiter) => {
                     println!("  Each IP address has independent rate limits");
+                    println!(
+                        "  Trusted proxies: {} (configure: TRUSTED_PROXIES)",
+                        ip_limiter.trusted_proxies().description()
+                    );
                     println!(
                         "  Configure: RATE_LIMIT_UNAUTHENTICATED_PER_SECOND, RATE_LIMIT_UNAUTHENTICATED_BURST_SIZE"
                     );
@@ -197,33 +379,546 @@ async fn async_main(args: Vec<String>) -> error::Result<()> {
             println!("  Set RATE_LIMIT_MODE=auto to enable protection");
         }
 
-        // Get request timeout from environment or default to 60 seconds
-        let request_timeout = load_request_timeout();
+        let request_timeout = config.request_timeout;
         println!("\nRequest Timeout:");
         println!("  Timeout: {} seconds", request_timeout.as_secs());
         println!("  Configure: REQUEST_TIMEOUT_SECONDS (default: 60)");
+        let max_request_timeout = config.max_request_timeout.max(request_timeout);
+        println!(
+            "  Longest requested timeout (X-Request-Timeout): {} seconds",
+            max_request_timeout.as_secs()
+        );
+        println!("  Configure: MAX_REQUEST_TIMEOUT_SECONDS (default: 300)");
+
+        let normalization = config.normalization;
+        println!("\nText Normalization:");
+        println!("  Fractions: {:?}", normalization.fraction_style);
+        println!("  Symbols: {:?}", normalization.symbol_style);
+        println!("  Emoji: {:?}", normalization.emoji_style);
+        println!(
+            "  Units: {}",
+            if normalization.units {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
+        println!(
+            "  Roman numerals: {}",
+            if normalization.roman_numerals {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
+        println!(
+            "  Time zones: {}",
+            if normalization.time_zones {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
+        println!(
+            "  All-caps words: {}",
+            if normalization.all_caps {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
+        println!("  Configure: TTS_FRACTION_STYLE (named, over)");
+        println!("  Configure: TTS_SYMBOL_STYLE (conservative, full, off)");
+        println!("  Configure: TTS_UNITS (default: false)");
+        println!("  Configure: TTS_ROMAN_NUMERALS (default: false)");
+        println!("  Configure: TTS_TIME_ZONES (default: false)");
+        println!(
+            "  Line-break hyphens: {}",
+            if normalization.dehyphenate {
+                "rejoined"
+            } else {
+                "kept"
+            }
+        );
+        println!("  Configure: TTS_ALL_CAPS (default: false)");
+        println!("  Configure: TTS_DEHYPHENATE (default: false)");
+        println!(
+            "  Decades: {}",
+            if normalization.decades {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
+        println!("  Configure: TTS_DECADES (default: false)");
+        println!(
+            "  Math operators: {}",
+            if normalization.math {
+                "read as words"
+            } else {
+                "kept"
+            }
+        );
+        println!("  Configure: TTS_MATH (default: false)");
+        println!(
+            "  Letters with numbers: {}",
+            match (normalization.alphanumerics, normalization.versions) {
+                (false, _) => "kept",
+                (true, false) => "read together (versions kept)",
+                (true, true) => "read together",
+            }
+        );
+        println!("  Configure: TTS_ALPHANUMERICS (default: false)");
+        println!("  Configure: TTS_VERSIONS (default: false)");
+        println!(
+            "  Ligatures and fullwidth forms: {}",
+            if normalization.fold_forms {
+                "folded to ASCII"
+            } else {
+                "kept"
+            }
+        );
+        println!("  Configure: TTS_FOLD_FORMS (default: false)");
+
+        let ellipsis_pause = config.ellipsis_pause;
+        match ellipsis_pause {
+            Some(pause) => println!(
+                "  Ellipsis: {}ms pause (not in multipart streams)",
+                pause.as_millis()
+            ),
+            None => println!("  Ellipsis: read as punctuation"),
+        }
+        println!(
+            "  Configure: TTS_ELLIPSIS_PAUSE_MS (default: 0 = off, max: {})",
+            MAX_ELLIPSIS_PAUSE_MS
+        );
+
+        let lists = config.lists;
+        match lists {
+            Some(config) => println!(
+                "  Lists: {}ms pause after each item, numbers {} (not in multipart streams)",
+                config.pause.as_millis(),
+                if config.speak_numbers {
+                    "spoken"
+                } else {
+                    "dropped"
+                }
+            ),
+            None => println!("  Lists: read like other text"),
+        }
+        println!(
+            "  Configure: TTS_LISTS (true, false), TTS_LIST_PAUSE_MS (default: 400, max: {}), \
+             TTS_LIST_NUMBERS (true, false)",
+            MAX_ELLIPSIS_PAUSE_MS
+        );
+
+        let asides = config.asides;
+        match asides {
+            Some(config) => println!(
+                "  Parenthetical asides: speed {:+}, gain {:+} dB (not in multipart streams)",
+                config.speed_delta, config.gain_db
+            ),
+            None => println!("  Parenthetical asides: read like other text"),
+        }
+        println!(
+            "  Configure: TTS_ASIDES (true, false), TTS_ASIDE_SPEED_DELTA (default: 0.1), \
+             TTS_ASIDE_GAIN_DB (default: -3)"
+        );
+
+        let say_punctuation = config.say_punctuation.clone();
+        println!(
+            "  Punctuation: {}",
+            if say_punctuation.is_some() {
+                "read aloud by name"
+            } else {
+                "heard as pauses and intonation"
+            }
+        );
+        println!(
+            "  Configure: TTS_SAY_PUNCTUATION (true, false), TTS_PUNCTUATION_NAMES (mark=name;...)"
+        );
+
+        let quotes = config.quotes;
+        match quotes {
+            QuoteStyle::Ignore => println!("  Quotes: read like other punctuation"),
+            QuoteStyle::Prosody(config) => println!(
+                "  Quotes: speed {:+}, pitch x{}, {}ms pause around them (not in multipart streams)",
+                config.speed_delta,
+                config.pitch_scale,
+                config.pause.as_millis()
+            ),
+            QuoteStyle::Spoken => println!("  Quotes: read as \"quote\" and \"unquote\""),
+        }
+        println!(
+            "  Configure: TTS_QUOTE_STYLE (ignore, prosody, spoken), TTS_QUOTE_SPEED_DELTA \
+             (default: 0), TTS_QUOTE_PITCH (default: 1.05), TTS_QUOTE_PAUSE_MS (default: 150)"
+        );
+
+        // Defaults to a few requests per engine that actually loaded
+        let max_in_flight = config
+            .max_in_flight_requests
+            .unwrap_or(pool_size * IN_FLIGHT_REQUESTS_PER_ENGINE);
+        let concurrency_limiter =
+            (max_in_flight > 0).then(|| ConcurrencyLimiter::new(max_in_flight));
+        println!("\nConcurrency Limit:");
+        match concurrency_limiter {
+            Some(ref limiter) => println!(
+                "  Max in-flight TTS requests: {} (excess requests get 503)",
+                limiter.max_in_flight()
+            ),
+            None => println!("  Status: DISABLED"),
+        }
+        println!(
+            "  Configure: MAX_IN_FLIGHT_REQUESTS (default: {}, 0 disables)",
+            pool_size * IN_FLIGHT_REQUESTS_PER_ENGINE
+        );
+        if config.fair_queue {
+            println!(
+                "  Fair queueing: ENABLED (engines rotate between {})",
+                if api_keys.is_enabled() {
+                    "API keys"
+                } else {
+                    "client IPs"
+                }
+            );
+        } else {
+            println!("  Fair queueing: DISABLED (engines go to waiters in arrival order)");
+        }
+        println!("  Configure: TTS_FAIR_QUEUE (default: false)");
+        println!(
+            "  Engine selection: {}",
+            match tts_pool.engine_selection() {
+                EngineSelection::FreeFirst => "free-first (idle engines before busy ones)",
+                EngineSelection::RoundRobin => "round-robin",
+            }
+        );
+        println!("  Configure: TTS_ENGINE_SELECTION (free-first, round-robin)");
+
+        let connection_limiter =
+            (config.max_connections > 0).then(|| ConnectionLimiter::new(config.max_connections));
+        println!("\nConnection Limit:");
+        match connection_limiter {
+            Some(ref limiter) => println!(
+                "  Max open connections: {} (excess connections get 503, /health still answers)",
+                limiter.max_connections()
+            ),
+            None => println!("  Status: DISABLED"),
+        }
+        println!(
+            "  Configure: MAX_CONNECTIONS (default: {}, 0 disables)",
+            DEFAULT_MAX_CONNECTIONS
+        );
+
+        let max_audio_duration = config.max_audio_duration;
+        println!("\nAudio Limits:");
+        println!(
+            "  Max audio duration: {} seconds",
+            max_audio_duration.as_secs()
+        );
+        println!(
+            "  Configure: MAX_AUDIO_DURATION_MS (default: {})",
+            DEFAULT_MAX_AUDIO_DURATION_MS
+        );
+        let max_normalized_length = config.max_normalized_length;
+        println!(
+            "  Max normalized text: {} bytes (numbers and symbols spelled out)",
+            max_normalized_length
+        );
+        println!(
+            "  Configure: MAX_NORMALIZED_LENGTH (default: {})",
+            DEFAULT_MAX_NORMALIZED_LENGTH
+        );
+
+        // Invalid origins are a startup error rather than a silently open policy
+        let cors = CorsConfig::from_env().map_err(error::TtsError::InvalidRequest)?;
+        println!("\nCORS:");
+        println!("  Allowed origins: {}", cors.origins_description());
+        println!(
+            "  Configure: CORS_ALLOWED_ORIGINS, CORS_ALLOWED_METHODS, CORS_ALLOWED_HEADERS (comma lists or *)"
+        );
+
+        println!("\nResponse Headers:");
+        if config.response_headers.is_empty() {
+            println!("  Custom headers: none");
+        } else {
+            println!(
+                "  Custom headers: {}",
+                config.response_headers.names().join(", ")
+            );
+        }
+        println!("  Samples: Cache-Control: {}", SAMPLES_CACHE_CONTROL);
+        println!(
+            "  Configure: RESPONSE_HEADERS (JSON object) or response_headers in the config file"
+        );
+
+        let temp_dir = utils::temp_file::init_temp_dir()?;
+        println!("\nTemp Files:");
+        println!("  Directory: {}", temp_dir.display());
+        match utils::temp_file::sweep_stale_files(
+            &temp_dir,
+            Duration::from_secs(STALE_TEMP_FILE_AGE_SECS),
+        ) {
+            Ok(0) => {}
+            Ok(removed) => println!("  Removed {} stale temp files", removed),
+            Err(e) => tracing::warn!("Failed to sweep temp directory: {}", e),
+        }
+        println!("  Configure: TTS_TEMP_DIR (default: system temp directory)");
+
+        let speed_clamp = config.speed_clamp;
+        let min_speed = config.min_speed;
+        println!("\nSpeed Validation:");
+        if speed_clamp {
+            println!(
+                "  Out-of-range speeds: CLAMPED to {}-{}",
+                min_speed, MAX_SPEED
+            );
+        } else {
+            println!(
+                "  Out-of-range speeds: REJECTED (must be {}-{})",
+                min_speed, MAX_SPEED
+            );
+        }
+        println!(
+            "  Configure: TTS_SPEED_CLAMP (default: false), TTS_MIN_SPEED (default: {})",
+            DEFAULT_MIN_SPEED
+        );
+
+        let long_tokens = config.long_tokens;
+        println!("\nLong Tokens:");
+        if long_tokens.max_length == 0 {
+            println!("  Status: DISABLED");
+        } else {
+            println!(
+                "  Words over {} characters without spaces: {}",
+                long_tokens.max_length,
+                match long_tokens.policy {
+                    LongTokenPolicy::Split => "SPLIT",
+                    LongTokenPolicy::Reject => "REJECTED",
+                }
+            );
+        }
+        println!("  Configure: TTS_MAX_TOKEN_LENGTH (default: 100, 0 = off), TTS_LONG_TOKEN_POLICY (split, reject)");
+
+        let chunk_retry = config.chunk_retry;
+        println!("\nStreaming Chunk Failures:");
+        println!("  Retries per chunk: {}", chunk_retry.retries);
+        println!(
+            "  After the last retry: {}",
+            match chunk_retry.failure_mode {
+                ChunkFailureMode::Skip => "send an error part and continue",
+                ChunkFailureMode::Abort => "send an error part and end the stream",
+            }
+        );
+        println!(
+            "  Configure: TTS_CHUNK_RETRIES (default: 2), TTS_CHUNK_FAILURE_MODE (skip, abort)"
+        );
+
+        let stream_keep_alive = config.stream_keep_alive;
+        println!("\nStreaming Keep-Alive:");
+        match stream_keep_alive {
+            Some(interval) => println!(
+                "  Empty part sent after {}ms without output",
+                interval.as_millis()
+            ),
+            None => println!("  Status: DISABLED"),
+        }
+        println!(
+            "  Configure: TTS_STREAM_KEEP_ALIVE_MS (default: {}, 0 = off)",
+            DEFAULT_STREAM_KEEP_ALIVE_MS
+        );
+
+        // Background jobs share the pool; one job per engine runs at a time
+        let jobs =
+            JobStore::new(config.job_ttl, pool_size).with_max_pending(config.max_pending_jobs);
+        println!("\nBackground Jobs:");
+        println!("  Running at once: {} (one per engine)", pool_size);
+        println!("  Queued or running at most: {}", jobs.max_pending());
+        println!("  Finished jobs kept for: {}s", jobs.ttl().as_secs());
+        println!(
+            "  Configure: TTS_JOB_TTL_SECONDS (default: {}), TTS_MAX_PENDING_JOBS (default: {})",
+            DEFAULT_JOB_TTL_SECS, DEFAULT_MAX_PENDING_JOBS
+        );
+        let expiring_jobs = jobs.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                let purged = expiring_jobs.purge_expired();
+                if purged > 0 {
+                    tracing::debug!("Purged {} expired jobs", purged);
+                }
+            }
+        });
+
+        // A typo in an override is a startup error rather than a silently missing alias
+        let voice_aliases = VoiceAliases::from_env().map_err(error::TtsError::InvalidRequest)?;
+        println!("\nVoice Aliases:");
+        println!("  Registered aliases: {}", voice_aliases.count());
+        println!("  Configure: TTS_VOICE_ALIASES (comma-separated alias=voice_id)");
+
+        // A fallback that can't be resolved would only fail later, mid-request
+        let fallback_voice =
+            resolve_fallback_voice(config.fallback_voice.as_deref(), &voice_aliases)
+                .map_err(error::TtsError::InvalidRequest)?;
+        println!("\nVoice Fallback:");
+        match &fallback_voice {
+            Some(voice) => println!(
+                "  Unavailable voices fall back to {} (X-TTS-Voice-Fallback header)",
+                voice
+            ),
+            None => println!("  Status: DISABLED (unavailable voices fail the request)"),
+        }
+        println!("  Configure: TTS_FALLBACK_VOICE (voice ID or alias)");
+
+        let voice_rates =
+            VoiceRates::from_env(&voice_aliases).map_err(error::TtsError::InvalidRequest)?;
+        println!("\nVoice Rates:");
+        println!("  Voices with a configured rate: {}", voice_rates.count());
+        println!("  Configure: TTS_VOICE_RATES (comma-separated voice=ms_per_char)");
+
+        let voice_defaults = VoiceDefaults::parse(
+            config.voice_speeds.as_deref(),
+            config.voice_pitches.as_deref(),
+            &voice_aliases,
+        )
+        .map_err(error::TtsError::InvalidRequest)?;
+        println!("\nVoice Defaults:");
+        println!(
+            "  Voices with a default speed or pitch: {}",
+            voice_defaults.count()
+        );
+        println!(
+            "  Configure: TTS_VOICE_SPEEDS (voice=speed,...), TTS_VOICE_PITCHES (voice=pitch,...)"
+        );
+
+        let concat_sample_rate = config.concat_sample_rate;
+        println!("\nChunking:");
+        println!(
+            "  /tts chunks text longer than {} bytes",
+            config.chunking_min_length
+        );
+        println!("  Configure: CHUNKING_MIN_LENGTH (default: 200)");
+        println!("\nChunk Concatenation:");
+        println!(
+            "  Mismatched sample rates: {}",
+            concat_sample_rate.description()
+        );
+        println!("  Configure: TTS_CONCAT_SAMPLE_RATE (strict, max, or a rate in Hz)");
+
+        let wav_metadata = config.wav_metadata;
+        println!("\nWAV Metadata:");
+        if wav_metadata {
+            println!("  Status: ENABLED (voice, speed and text snippet in a LIST/INFO chunk)");
+        } else {
+            println!("  Status: DISABLED (requests can opt in with \"metadata\": true)");
+        }
+        println!("  Configure: TTS_WAV_METADATA (default: false)");
+
+        let validation_headers = config.validation_headers;
+        println!("\nValidation Headers:");
+        if validation_headers {
+            println!("  Status: ENABLED (/tts sets X-TTS-Validation-Warnings)");
+        } else {
+            println!("  Status: DISABLED");
+        }
+        println!("  Configure: TTS_VALIDATION_HEADERS (default: false)");
+
+        println!("\nAudio Format:");
+        println!(
+            "  Default: {} (used when neither \"format\" nor Accept asks for one)",
+            config.default_audio_format.as_str()
+        );
+        println!("  Encoded: WAV only; MP3 and Ogg requests get 406 (or 400 for \"format\")");
+        println!("  Configure: TTS_DEFAULT_AUDIO_FORMAT (default: wav)");
+
+        // The cache directory is created if missing
+        let audio_cache = match &config.cache_dir {
+            Some(dir) => Some(DiskCache::new(dir, config.cache_max_bytes)?),
+            None => None,
+        };
+        println!("\nAudio Cache:");
+        match audio_cache {
+            Some(ref cache) => {
+                println!("  Status: ENABLED");
+                println!("  Directory: {}", cache.dir().display());
+                println!("  Max size: {} MB", cache.max_bytes() / (1024 * 1024));
+            }
+            None => println!("  Status: DISABLED"),
+        }
+        println!(
+            "  Configure: TTS_CACHE_DIR, TTS_CACHE_MAX_BYTES (default: {})",
+            DEFAULT_AUDIO_CACHE_MAX_BYTES
+        );
 
         let state = AppState {
             tts_pool: Arc::new(tts_pool),
             api_keys: api_keys.clone(),
             rate_limiter,
             request_timeout,
+            max_request_timeout,
+            normalization,
+            concurrency_limiter,
+            max_audio_duration,
+            cors,
+            generation_stats: Default::default(),
+            audio_cache: audio_cache.map(Arc::new),
+            speed_clamp,
+            voice_aliases: Arc::new(voice_aliases),
+            fallback_voice,
+            max_normalized_length,
+            wav_metadata,
+            concat_sample_rate,
+            chunking_min_length: config.chunking_min_length,
+            validation_headers,
+            long_tokens,
+            chunk_retry,
+            jobs,
+            ellipsis_pause,
+            espeak_data_dir: config.espeak_data_dir.clone(),
+            asides,
+            lists,
+            say_punctuation,
+            stream_keep_alive,
+            quotes,
+            voice_rates: Arc::new(voice_rates),
+            response_headers: Arc::new(config.response_headers.clone()),
+            min_speed,
+            voice_defaults: Arc::new(voice_defaults),
+            default_audio_format: config.default_audio_format,
+            config: Arc::new(config.clone()),
         };
 
         let app = create_router(state);
 
-        // Use into_make_service_with_connect_info to enable client IP extraction
-        axum::serve(
-            listener,
-            app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
-        )
-        .await?;
+        match (listener, connection_limiter) {
+            // Local socket for desktop integrations; no TCP port is opened
+            (None, _) => {
+                let path = socket_path.expect("no TCP listener without a socket path");
+                local_socket::serve(&path, app).await?
+            }
+            // Connection-capped accept loop; also provides ConnectInfo for client IPs
+            (Some(listener), Some(limiter)) => {
+                connection_limit::serve(listener, app, limiter).await?
+            }
+            // Use into_make_service_with_connect_info to enable client IP extraction
+            (Some(listener), None) => {
+                axum::serve(
+                    listener,
+                    app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+                )
+                .await?
+            }
+        }
     } else {
         // CLI mode - use single TTS instance
         println!("Initializing TTS engine for CLI mode...");
 
         let tts = TTS::new(model_path.to_str().unwrap(), voices_path.to_str().unwrap()).await?;
 
+        if let Some(output) = stdin_output(&args) {
+            return run_stdin_batch(&tts, output, config.normalization);
+        }
+
         let text = if args.len() > 1 {
             args[1..].join(" ")
         } else {
@@ -242,11 +937,14 @@ async fn async_main(args: Vec<String>) -> error::Result<()> {
         );
 
         // Normalize text for TTS (semantic + unicode normalization)
-        let normalized_text = text_processing::normalization::normalize_simple(&text);
+        let normalized_text = text_processing::normalization::normalize_simple_with_config(
+            &text,
+            &config.normalization,
+        );
 
         // Generate speech with selected voice and normal speed
         let output_path = "output.wav";
-        tts.speak(&normalized_text, output_path, voice.id(), 1.0)?;
+        tts.speak(&normalized_text, output_path, voice.id(), 1.0, false)?;
 
         println!("Speech saved to {}", output_path);
 
@@ -283,180 +981,351 @@ async fn async_main(args: Vec<String>) -> error::Result<()> {
     Ok(())
 }
 
-/// Load rate limit configuration based on environment variables and API key status
-fn load_rate_limit_config(api_keys_enabled: bool) -> Option<RateLimiterMode> {
-    // Parse RATE_LIMIT_MODE environment variable
-    let mode = env::var("RATE_LIMIT_MODE")
-        .unwrap_or_else(|_| "auto".to_string())
-        .to_lowercase();
-
-    match mode.as_str() {
-        "disabled" => None,
-        "per-key" => {
-            let config = load_authenticated_config();
-            Some(RateLimiterMode::PerKey(PerKeyRateLimiter::new(config)))
-        }
-        "per-ip" => {
-            let config = load_unauthenticated_config();
-            Some(RateLimiterMode::PerIp(PerIpRateLimiter::new(config)))
-        }
-        "auto" | _ => {
-            // Auto mode: choose based on API key status
-            if api_keys_enabled {
-                let config = load_authenticated_config();
-                Some(RateLimiterMode::PerKey(PerKeyRateLimiter::new(config)))
-            } else {
-                let config = load_unauthenticated_config();
-                Some(RateLimiterMode::PerIp(PerIpRateLimiter::new(config)))
-            }
-        }
+/// How to write CLI text read from stdin, if `-` is the only text argument
+///
+/// `--concat` joins the chunks into one WAV instead of numbered files.
+fn stdin_output(args: &[String]) -> Option<BatchOutput> {
+    let mut text_args = args.iter().skip(1).filter(|arg| *arg != "--concat");
+    if text_args.next().map(String::as_str) != Some("-") || text_args.next().is_some() {
+        return None;
+    }
+
+    if args.iter().any(|arg| arg == "--concat") {
+        Some(BatchOutput::Concatenated)
+    } else {
+        Some(BatchOutput::Files)
     }
 }
 
-/// Load configuration for authenticated (per-key) rate limiting
-fn load_authenticated_config() -> RateLimitConfig {
-    let per_second = env::var("RATE_LIMIT_AUTHENTICATED_PER_SECOND")
-        .or_else(|_| env::var("RATE_LIMIT_PER_SECOND"))
-        .ok()
-        .and_then(|v| v.parse().ok())
-        .unwrap_or(10);
-
-    let burst_size = env::var("RATE_LIMIT_AUTHENTICATED_BURST_SIZE")
-        .or_else(|_| env::var("RATE_LIMIT_BURST_SIZE"))
-        .ok()
-        .and_then(|v| v.parse().ok())
-        .unwrap_or(20);
-
-    RateLimitConfig {
-        per_second,
-        burst_size,
+/// Synthesize text piped to stdin, chunk by chunk, into the current directory
+fn run_stdin_batch(
+    tts: &TTS,
+    output: BatchOutput,
+    normalization: NormalizationConfig,
+) -> error::Result<()> {
+    let voice = Voice::BritishFemaleLily;
+    println!("Reading text from stdin (voice: {})...", voice.id());
+
+    let options = BatchOptions {
+        voice: voice.id().to_string(),
+        speed: 1.0,
+        normalization,
+        output,
+        output_dir: PathBuf::from("."),
+    };
+    let result = services::batch::synthesize_reader(tts, std::io::stdin().lock(), &options)?;
+
+    for path in &result.audio_files {
+        println!("Speech saved to {}", path.display());
     }
+    println!("Metadata saved to {}", result.metadata_file.display());
+    println!("\nTiming Summary:");
+    println!(
+        "  Total duration: {:.2}s",
+        result.total_duration_ms() / 1000.0
+    );
+    println!("  Number of chunks: {}", result.chunks.len());
+    Ok(())
+}
+
+/// What the binary was asked to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RunMode {
+    /// Serve the HTTP API (`--server`)
+    Server,
+    /// Validate models, voices and normalization, then exit (`--check`)
+    Check,
+    /// Synthesize the command-line text to output.wav
+    Cli,
 }
 
-/// Load configuration for unauthenticated (per-IP) rate limiting
-fn load_unauthenticated_config() -> RateLimitConfig {
-    let per_second = env::var("RATE_LIMIT_UNAUTHENTICATED_PER_SECOND")
-        .or_else(|_| env::var("RATE_LIMIT_PER_SECOND"))
-        .ok()
-        .and_then(|v| v.parse().ok())
-        .unwrap_or(5); // More restrictive default for unauthenticated
-
-    let burst_size = env::var("RATE_LIMIT_UNAUTHENTICATED_BURST_SIZE")
-        .or_else(|_| env::var("RATE_LIMIT_BURST_SIZE"))
-        .ok()
-        .and_then(|v| v.parse().ok())
-        .unwrap_or(10); // More restrictive default for unauthenticated
-
-    RateLimitConfig {
-        per_second,
-        burst_size,
+/// Pick the run mode from the command line; `--check` wins over `--server`
+fn run_mode(args: &[String]) -> RunMode {
+    if args.iter().any(|arg| arg == "--check") {
+        RunMode::Check
+    } else if args.iter().any(|arg| arg == "--server") {
+        RunMode::Server
+    } else {
+        RunMode::Cli
     }
 }
 
-/// Load request timeout configuration from environment variable
-fn load_request_timeout() -> Duration {
-    let timeout_seconds = env::var("REQUEST_TIMEOUT_SECONDS")
-        .ok()
-        .and_then(|v| v.parse::<u64>().ok())
-        .unwrap_or(60); // Default to 60 seconds
+/// Check models, voices and normalization without producing any audio
+///
+/// Prints one line per check and returns whether all of them passed.
+async fn run_self_check(
+    model_path: &Path,
+    voices_path: &Path,
+    normalization: &NormalizationConfig,
+) -> bool {
+    let mut passed = true;
+    let mut report = |name: &str, result: Result<String, String>| match result {
+        Ok(detail) => println!("  {}: OK ({})", name, detail),
+        Err(problem) => {
+            passed = false;
+            println!("  {}: FAILED ({})", name, problem);
+        }
+    };
 
-    Duration::from_secs(timeout_seconds)
+    println!("\nSelf-check:");
+
+    let start = Instant::now();
+    let models = TTS::new(model_path.to_str().unwrap(), voices_path.to_str().unwrap())
+        .await
+        .map(|_| format!("loaded in {} ms", start.elapsed().as_millis()))
+        .map_err(|e| e.to_string());
+    report("Models", models);
+
+    let total = Voice::all().len();
+    let voices = match missing_voices(voices_path) {
+        Ok(missing) if missing.is_empty() => Ok(format!("{}/{} present", total, total)),
+        Ok(missing) => Err(format!(
+            "missing from voices file: {}",
+            missing.iter().map(Voice::id).collect::<Vec<_>>().join(", ")
+        )),
+        Err(e) => Err(e.to_string()),
+    };
+    report("Voices", voices);
+
+    report("Normalization", normalization_self_test(normalization));
+
+    println!("Result: {}", if passed { "PASS" } else { "FAIL" });
+    passed
+}
+
+/// Normalize a known sentence and compare it with the expected speech
+fn normalization_self_test(config: &NormalizationConfig) -> Result<String, String> {
+    const INPUT: &str = "It costs $5.";
+    const EXPECTED: &str = "five dollars";
+
+    let normalized = text_processing::normalization::normalize_simple_with_config(INPUT, config);
+    if normalized.contains(EXPECTED) {
+        Ok(format!("\"{}\" -> \"{}\"", INPUT, normalized))
+    } else {
+        Err(format!(
+            "\"{}\" normalized to \"{}\", expected \"{}\"",
+            INPUT, normalized, EXPECTED
+        ))
+    }
+}
+
+/// Build the rate limiter for the configured mode and API key status
+///
+/// Without client IPs (local socket) per-IP limiting can't tell clients
+/// apart: asking for it is an error, and auto mode limits per key, or not
+/// at all without API keys.
+fn build_rate_limiter(
+    settings: &RateLimitSettings,
+    api_keys_enabled: bool,
+    trusted_proxies: TrustedProxies,
+    client_ips: bool,
+) -> Result<Option<RateLimiterMode>, String> {
+    let per_key =
+        || RateLimiterMode::PerKey(PerKeyRateLimiter::new(settings.authenticated.clone()));
+    let per_ip = || {
+        RateLimiterMode::PerIp(
+            PerIpRateLimiter::new(settings.unauthenticated.clone())
+                .with_trusted_proxies(trusted_proxies.clone()),
+        )
+    };
+
+    Ok(match settings.mode {
+        RateLimitMode::Disabled => None,
+        RateLimitMode::PerKey => Some(per_key()),
+        RateLimitMode::PerIp if !client_ips => {
+            return Err(
+                "Per-IP rate limiting needs client IPs, which a local socket doesn't have; \
+                 use RATE_LIMIT_MODE=per-key or disabled"
+                    .to_string(),
+            )
+        }
+        RateLimitMode::Auto if !client_ips => api_keys_enabled.then(per_key),
+        RateLimitMode::PerIp => Some(per_ip()),
+        // Auto mode: choose based on API key status
+        RateLimitMode::Auto if api_keys_enabled => Some(per_key()),
+        RateLimitMode::Auto => Some(per_ip()),
+    })
+}
+
+/// Default in-flight request allowance per pool engine
+const IN_FLIGHT_REQUESTS_PER_ENGINE: usize = 4;
+
+/// Local socket to listen on instead of TCP, from `--uds <path>`
+///
+/// A Unix domain socket path, or a named pipe such as `\\.\pipe\porua` on Windows.
+/// Takes precedence over `unix_socket` in the server config.
+fn local_socket_path(args: &[String]) -> Option<PathBuf> {
+    args.iter()
+        .position(|arg| arg == "--uds")
+        .and_then(|pos| args.get(pos + 1))
+        .filter(|path| !path.trim().is_empty())
+        .map(PathBuf::from)
+}
+
+/// Resolve the voice used when a requested one is unavailable (None = fail instead)
+///
+/// Aliases are resolved to the voice ID; unknown voices are an error.
+fn resolve_fallback_voice(
+    name: Option<&str>,
+    aliases: &VoiceAliases,
+) -> Result<Option<String>, String> {
+    let Some(name) = name else {
+        return Ok(None);
+    };
+    aliases
+        .resolve_voice(name)
+        .map(|voice| Some(voice.id().to_string()))
+        .ok_or_else(|| format!("Unknown TTS_FALLBACK_VOICE: {}", name))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_load_request_timeout_default() {
-        // Clear environment variable to test default
-        env::remove_var("REQUEST_TIMEOUT_SECONDS");
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
 
-        let timeout = load_request_timeout();
+    #[test]
+    fn test_run_mode_selection() {
+        assert_eq!(
+            run_mode(&args(&["porua_server", "--check"])),
+            RunMode::Check
+        );
         assert_eq!(
-            timeout,
-            Duration::from_secs(60),
-            "Default timeout should be 60 seconds"
+            run_mode(&args(&["porua_server", "--server", "--check"])),
+            RunMode::Check
         );
+        assert_eq!(
+            run_mode(&args(&["porua_server", "--server", "--port", "8080"])),
+            RunMode::Server
+        );
+        assert_eq!(run_mode(&args(&["porua_server", "Hello"])), RunMode::Cli);
+        assert_eq!(run_mode(&args(&["porua_server"])), RunMode::Cli);
     }
 
     #[test]
-    fn test_load_request_timeout_custom() {
-        // Set custom timeout
-        env::set_var("REQUEST_TIMEOUT_SECONDS", "120");
-
-        let timeout = load_request_timeout();
+    fn test_stdin_output() {
         assert_eq!(
-            timeout,
-            Duration::from_secs(120),
-            "Custom timeout should be 120 seconds"
+            stdin_output(&args(&["porua_server", "-"])),
+            Some(BatchOutput::Files)
         );
-
-        // Cleanup
-        env::remove_var("REQUEST_TIMEOUT_SECONDS");
+        assert_eq!(
+            stdin_output(&args(&["porua_server", "--concat", "-"])),
+            Some(BatchOutput::Concatenated)
+        );
+        assert_eq!(stdin_output(&args(&["porua_server"])), None);
+        assert_eq!(stdin_output(&args(&["porua_server", "Hello"])), None);
+        assert_eq!(stdin_output(&args(&["porua_server", "-", "Hello"])), None);
     }
 
     #[test]
-    fn test_load_request_timeout_invalid_falls_back_to_default() {
-        // Set invalid timeout value
-        env::set_var("REQUEST_TIMEOUT_SECONDS", "invalid");
+    fn test_normalization_self_test_passes_with_default_config() {
+        assert!(normalization_self_test(&NormalizationConfig::default()).is_ok());
+    }
 
-        let timeout = load_request_timeout();
-        assert_eq!(
-            timeout,
-            Duration::from_secs(60),
-            "Invalid timeout should fall back to 60 seconds"
-        );
+    #[test]
+    fn test_build_rate_limiter_modes() {
+        let settings = |mode| RateLimitSettings {
+            mode,
+            ..ServerConfig::default().rate_limit
+        };
+        let build = |mode, api_keys_enabled| {
+            build_rate_limiter(
+                &settings(mode),
+                api_keys_enabled,
+                TrustedProxies::default(),
+                true,
+            )
+            .unwrap()
+        };
 
-        // Cleanup
-        env::remove_var("REQUEST_TIMEOUT_SECONDS");
+        assert!(build(RateLimitMode::Disabled, true).is_none());
+        assert!(matches!(
+            build(RateLimitMode::Auto, true),
+            Some(RateLimiterMode::PerKey(_))
+        ));
+        assert!(matches!(
+            build(RateLimitMode::Auto, false),
+            Some(RateLimiterMode::PerIp(_))
+        ));
+        assert!(matches!(
+            build(RateLimitMode::PerIp, true),
+            Some(RateLimiterMode::PerIp(_))
+        ));
+
+        // Each mode uses the limits for its kind of client
+        let limiter = build(RateLimitMode::PerKey, false).unwrap();
+        assert_eq!(limiter.config().per_second, 10);
+        let limiter = build(RateLimitMode::PerIp, false).unwrap();
+        assert_eq!(limiter.config().per_second, 5);
     }
 
     #[test]
-    fn test_load_request_timeout_negative_falls_back_to_default() {
-        // Set negative timeout value
-        env::set_var("REQUEST_TIMEOUT_SECONDS", "-1");
-
-        let timeout = load_request_timeout();
-        assert_eq!(
-            timeout,
-            Duration::from_secs(60),
-            "Negative timeout should fall back to 60 seconds"
-        );
+    fn test_build_rate_limiter_without_client_ips() {
+        let settings = |mode| RateLimitSettings {
+            mode,
+            ..ServerConfig::default().rate_limit
+        };
+        let build = |mode, api_keys_enabled| {
+            build_rate_limiter(
+                &settings(mode),
+                api_keys_enabled,
+                TrustedProxies::default(),
+                false,
+            )
+        };
 
-        // Cleanup
-        env::remove_var("REQUEST_TIMEOUT_SECONDS");
+        // Asking for per-IP limiting is a startup error
+        assert!(build(RateLimitMode::PerIp, true).is_err());
+        assert!(build(RateLimitMode::PerIp, false).is_err());
+
+        // Auto mode limits per key, or not at all without keys
+        assert!(build(RateLimitMode::Auto, false).unwrap().is_none());
+        assert!(matches!(
+            build(RateLimitMode::Auto, true),
+            Ok(Some(RateLimiterMode::PerKey(_)))
+        ));
+        assert!(matches!(
+            build(RateLimitMode::PerKey, false),
+            Ok(Some(RateLimiterMode::PerKey(_)))
+        ));
     }
 
     #[test]
-    fn test_load_request_timeout_zero_is_valid() {
-        // Set zero timeout (edge case - effectively disables timeout)
-        env::set_var("REQUEST_TIMEOUT_SECONDS", "0");
-
-        let timeout = load_request_timeout();
+    fn test_local_socket_path() {
         assert_eq!(
-            timeout,
-            Duration::from_secs(0),
-            "Zero timeout should be accepted"
+            local_socket_path(&args(&["porua_server", "--server"])),
+            None
+        );
+        assert_eq!(
+            local_socket_path(&args(&[
+                "porua_server",
+                "--server",
+                "--uds",
+                "/tmp/porua.sock"
+            ])),
+            Some(PathBuf::from("/tmp/porua.sock"))
+        );
+        assert_eq!(
+            local_socket_path(&args(&["porua_server", "--uds", " "])),
+            None
         );
-
-        // Cleanup
-        env::remove_var("REQUEST_TIMEOUT_SECONDS");
     }
 
     #[test]
-    fn test_load_request_timeout_large_value() {
-        // Test large timeout value (e.g., 1 hour)
-        env::set_var("REQUEST_TIMEOUT_SECONDS", "3600");
+    fn test_resolve_fallback_voice() {
+        let aliases = VoiceAliases::default();
 
-        let timeout = load_request_timeout();
+        assert_eq!(resolve_fallback_voice(None, &aliases), Ok(None));
         assert_eq!(
-            timeout,
-            Duration::from_secs(3600),
-            "Large timeout should be accepted"
+            resolve_fallback_voice(Some("af_heart"), &aliases),
+            Ok(Some("af_heart".to_string()))
         );
-
-        // Cleanup
-        env::remove_var("REQUEST_TIMEOUT_SECONDS");
+        assert_eq!(
+            resolve_fallback_voice(Some("Narrator"), &aliases),
+            Ok(Some("bm_george".to_string()))
+        );
+        assert!(resolve_fallback_voice(Some("nobody"), &aliases).is_err());
     }
 }
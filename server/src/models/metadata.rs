@@ -1,10 +1,50 @@
 use serde::Serialize;
 
+use crate::config::constants::SUPPORTED_METADATA_VERSIONS;
+use crate::error::{Result, TtsError};
+
+/// Metadata schema version negotiated with the client
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MetadataVersion {
+    /// Timing and phrases only
+    V1,
+    /// Current schema with original text, offsets, validation and debug info
+    #[default]
+    V2,
+}
+
+impl MetadataVersion {
+    /// Parse a requested version; None selects the default
+    pub fn parse(requested: Option<&str>) -> Result<Self> {
+        match requested.map(str::trim) {
+            None | Some("") => Ok(Self::default()),
+            Some("1" | "1.0") => Ok(Self::V1),
+            Some("2" | "2.0") => Ok(Self::V2),
+            Some(other) => Err(TtsError::InvalidRequest(format!(
+                "Unsupported metadata version: {} (supported: {})",
+                other,
+                SUPPORTED_METADATA_VERSIONS.join(", ")
+            ))),
+        }
+    }
+
+    /// Version string as sent to clients
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::V1 => "1.0",
+            Self::V2 => "2.0",
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Clone)]
 pub struct PhraseMetadata {
     /// Normalized text (what the TTS engine spoke)
     pub text: String,
-    /// Original text from input (before normalization)
+    /// Original text from input (before normalization), with its casing
+    ///
+    /// Absent when normalization left the phrase unchanged, so `text` is the
+    /// original wording.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub original_text: Option<String>,
     #[serde(skip_serializing)]
@@ -12,10 +52,10 @@ pub struct PhraseMetadata {
     pub words: Vec<String>,
     pub start_ms: f64,
     pub duration_ms: f64,
-    /// Character offset start in the full text
+    /// Byte offset of the phrase start in the chunk's normalized `text`
     #[serde(skip_serializing_if = "Option::is_none")]
     pub char_offset_start: Option<usize>,
-    /// Character offset end in the full text
+    /// Byte offset of the phrase end in the chunk's normalized `text`
     #[serde(skip_serializing_if = "Option::is_none")]
     pub char_offset_end: Option<usize>,
 }
@@ -73,4 +113,92 @@ pub struct ChunkMetadata {
     /// Debug information
     #[serde(skip_serializing_if = "Option::is_none")]
     pub debug_info: Option<DebugInfo>,
+    /// Peak amplitude envelope of the audio, when the request asks for one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub waveform: Option<Vec<f32>>,
+}
+
+/// Progress part sent after each completed chunk when a stream opts in
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct StreamProgress {
+    /// Always "progress", so clients can skip parts they don't understand
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// Chunks delivered so far, including this one
+    pub completed: usize,
+    pub total: usize,
+}
+
+impl StreamProgress {
+    pub fn new(completed: usize, total: usize) -> Self {
+        Self {
+            kind: "progress",
+            completed,
+            total,
+        }
+    }
+}
+
+/// Part sent in place of a chunk that could not be generated
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct StreamError {
+    /// Always "error"
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// Index of the chunk that failed
+    pub chunk_index: usize,
+    pub message: String,
+}
+
+impl StreamError {
+    pub fn new(chunk_index: usize, message: String) -> Self {
+        Self {
+            kind: "error",
+            chunk_index,
+            message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_progress_serialization() {
+        let json = serde_json::to_value(StreamProgress::new(3, 10)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "type": "progress", "completed": 3, "total": 10 })
+        );
+    }
+
+    #[test]
+    fn test_metadata_version_parse() {
+        assert_eq!(MetadataVersion::parse(None).unwrap(), MetadataVersion::V2);
+        assert_eq!(
+            MetadataVersion::parse(Some("1.0")).unwrap(),
+            MetadataVersion::V1
+        );
+        assert_eq!(
+            MetadataVersion::parse(Some(" 2 ")).unwrap(),
+            MetadataVersion::V2
+        );
+    }
+
+    #[test]
+    fn test_metadata_version_rejects_unsupported() {
+        let err = MetadataVersion::parse(Some("3.0")).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Unsupported metadata version: 3.0"));
+        assert!(err.to_string().contains("1.0, 2.0"));
+    }
+
+    #[test]
+    fn test_metadata_version_strings_are_supported() {
+        for version in [MetadataVersion::V1, MetadataVersion::V2] {
+            assert!(SUPPORTED_METADATA_VERSIONS.contains(&version.as_str()));
+        }
+    }
 }
@@ -3,7 +3,14 @@ pub mod requests;
 pub mod responses;
 
 pub use metadata::{
-    ChunkMetadata, DebugInfo, PhraseMetadata, ValidationError, ValidationResult, ValidationWarning,
+    ChunkMetadata, DebugInfo, MetadataVersion, PhraseMetadata, StreamError, StreamProgress,
+    ValidationError, ValidationResult, ValidationWarning,
+};
+pub use requests::{StreamQuery, TTSRequest, TtsQuery, VoiceMixPreviewRequest};
+pub use responses::{
+    ArchiveChunk, ArchiveManifest, AuthConfigSection, ChunkBoundary, CircuitBreakerStats,
+    ConfigResponse, EstimateResponse, HealthResponse, JobResponse, PhonemesResponse,
+    PoolStatsResponse, ReadinessCheck, ReadinessResponse, ReloadResponse, RuntimeConfigSection,
+    TimingSource, TtsDebugInfo, TtsJsonResponse, TtsMetadataResponse, VoiceInfo,
+    VoiceMixPreviewResponse, VoiceStats, VoiceWeight, VoicesResponse,
 };
-pub use requests::TTSRequest;
-pub use responses::{HealthResponse, PoolStatsResponse, VoiceInfo, VoicesResponse};
@@ -1,20 +1,162 @@
 use serde::Deserialize;
 
-#[derive(Debug, Deserialize)]
+use crate::chunking::ChunkStrategy;
+use crate::config::constants::DEFAULT_MAX_PHRASE_WORDS;
+use crate::utils::accept::AudioFormat;
+
+#[derive(Debug, Clone, Deserialize)]
 pub struct TTSRequest {
     pub text: String,
     #[serde(default = "default_voice")]
     pub voice: String,
-    #[serde(default = "default_speed")]
-    pub speed: f32,
+    /// Speaking speed (None = the voice's default speed, else 1.0)
+    #[serde(default)]
+    pub speed: Option<f32>,
+    /// Pitch factor, 1.0 = unchanged (None = 1.0)
+    ///
+    /// Set from the voice's configured default pitch; requests can't ask for one.
+    #[serde(skip)]
+    pub pitch: Option<f32>,
     #[serde(default = "default_enable_chunking")]
     pub enable_chunking: bool,
+    /// Where chunks may be cut: `size` (default) or `sentence`, which never
+    /// splits a sentence unless it is far over the chunk size
+    #[serde(default)]
+    pub chunk_strategy: ChunkStrategy,
+    /// Output sample rate in Hz (None = engine native rate)
+    #[serde(default)]
+    pub sample_rate: Option<u32>,
+    /// Output bits per sample: 8, 16 or 24 (None = the engine's native 16)
+    #[serde(default)]
+    pub bit_depth: Option<u16>,
+    /// Emit single-channel audio instead of duplicated stereo
+    #[serde(default)]
+    pub mono: bool,
+    /// Normalize the text before synthesis; false sends it to the engine as-is
+    #[serde(default = "default_normalize")]
+    pub normalize: bool,
+    /// Download filename for the audio (sanitized; generated when absent)
+    #[serde(default)]
+    pub filename: Option<String>,
+    /// Embed voice, speed and a text snippet as WAV INFO metadata
+    /// (None = server default)
+    #[serde(default)]
+    pub metadata: Option<bool>,
+    /// Audio encoding: `wav`, `mp3` or `ogg` (only `wav` is encoded so far);
+    /// takes precedence over `Accept`
+    #[serde(default)]
+    pub format: Option<AudioFormat>,
+    /// Longest phrase in the timing metadata, in words (None = 8)
+    ///
+    /// Only changes how metadata is broken up, never the synthesized audio.
+    #[serde(default)]
+    pub max_phrase_words: Option<usize>,
+    /// Blend of voices with relative weights; replaces `voice` when set
+    #[serde(default)]
+    pub voice_mix: Option<Vec<VoiceMixPart>>,
+    /// Shortest phrase in the timing metadata, in ms (None = no minimum)
+    ///
+    /// Shorter phrases get time from their neighbors, or are merged into one.
+    #[serde(default)]
+    pub min_phrase_ms: Option<u32>,
+    /// Peak values in each metadata block's waveform envelope (None = no envelope)
+    #[serde(default)]
+    pub waveform_points: Option<usize>,
+    /// Return only the timing metadata as JSON, without the audio
+    ///
+    /// The audio is still synthesized, since the timings are measured from it.
+    #[serde(default)]
+    pub metadata_only: bool,
+}
+
+/// One voice of a `voice_mix`
+#[derive(Debug, Clone, Deserialize)]
+pub struct VoiceMixPart {
+    pub voice: String,
+    #[serde(default = "default_mix_weight")]
+    pub weight: f32,
+}
+
+impl Default for TTSRequest {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            voice: default_voice(),
+            speed: None,
+            pitch: None,
+            enable_chunking: default_enable_chunking(),
+            chunk_strategy: ChunkStrategy::default(),
+            sample_rate: None,
+            bit_depth: None,
+            mono: false,
+            normalize: default_normalize(),
+            filename: None,
+            metadata: None,
+            format: None,
+            max_phrase_words: None,
+            voice_mix: None,
+            min_phrase_ms: None,
+            waveform_points: None,
+            metadata_only: false,
+        }
+    }
+}
+
+impl TTSRequest {
+    /// Speaking speed, defaulting to 1.0 when unset
+    pub fn speed(&self) -> f32 {
+        self.speed.unwrap_or_else(default_speed)
+    }
+
+    /// Pitch factor, defaulting to 1.0 (unchanged) when unset
+    pub fn pitch(&self) -> f32 {
+        self.pitch.unwrap_or(1.0)
+    }
+
+    /// Metadata phrase word limit, defaulting to 8 when unset
+    pub fn max_phrase_words(&self) -> usize {
+        self.max_phrase_words.unwrap_or(DEFAULT_MAX_PHRASE_WORDS)
+    }
+}
+
+/// Body of `/voices/mix/preview`: a mix in either of the `/tts` forms
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct VoiceMixPreviewRequest {
+    /// A mix written as `"af_heart:0.6+am_adam:0.4"`
+    #[serde(default)]
+    pub voice: String,
+    /// Blend of voices with relative weights; replaces `voice` when set
+    #[serde(default)]
+    pub voice_mix: Option<Vec<VoiceMixPart>>,
+}
+
+/// Query parameters accepted by `/tts`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TtsQuery {
+    /// Return normalization details alongside the audio (multipart response)
+    #[serde(default)]
+    pub debug: bool,
+}
+
+/// Query parameters accepted by `/tts/stream`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StreamQuery {
+    /// Respond with one concatenated WAV instead of multipart
+    #[serde(default)]
+    pub merge: bool,
+    /// Send a progress part after each completed chunk
+    #[serde(default)]
+    pub progress: bool,
 }
 
 fn default_enable_chunking() -> bool {
     true
 }
 
+fn default_normalize() -> bool {
+    true
+}
+
 fn default_voice() -> String {
     "bf_lily".to_string()
 }
@@ -23,6 +165,10 @@ fn default_speed() -> f32 {
     1.0
 }
 
+fn default_mix_weight() -> f32 {
+    1.0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -40,7 +186,7 @@ mod tests {
 
         assert_eq!(req.text, "Hello world");
         assert_eq!(req.voice, "af_bella");
-        assert_eq!(req.speed, 1.5);
+        assert_eq!(req.speed, Some(1.5));
         assert!(!req.enable_chunking);
     }
 
@@ -52,8 +198,44 @@ mod tests {
 
         assert_eq!(req.text, "Hello");
         assert_eq!(req.voice, "bf_lily"); // default
-        assert_eq!(req.speed, 1.0); // default
+        assert_eq!(req.speed, None); // voice default
+        assert_eq!(req.speed(), 1.0);
+        assert_eq!(req.pitch(), 1.0);
         assert!(req.enable_chunking); // default
+        assert_eq!(req.sample_rate, None); // default
+        assert!(!req.mono); // default
+    }
+
+    #[test]
+    fn test_tts_request_chunk_strategy() {
+        let req: TTSRequest = serde_json::from_str(r#"{"text": "Test"}"#).unwrap();
+        assert_eq!(req.chunk_strategy, ChunkStrategy::Size);
+
+        let json = r#"{"text": "Test", "chunk_strategy": "sentence"}"#;
+        let req: TTSRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.chunk_strategy, ChunkStrategy::Sentence);
+
+        let json = r#"{"text": "Test", "chunk_strategy": "paragraph"}"#;
+        assert!(serde_json::from_str::<TTSRequest>(json).is_err());
+    }
+
+    #[test]
+    fn test_tts_request_mono() {
+        let json = r#"{"text": "Test", "mono": true}"#;
+
+        let req: TTSRequest = serde_json::from_str(json).unwrap();
+
+        assert!(req.mono);
+    }
+
+    #[test]
+    fn test_tts_request_sample_rate() {
+        let json = r#"{"text": "Test", "sample_rate": 16000}"#;
+
+        let req: TTSRequest = serde_json::from_str(json).unwrap();
+
+        assert_eq!(req.sample_rate, Some(16000));
+        assert_eq!(req.bit_depth, None);
     }
 
     #[test]
@@ -77,7 +259,17 @@ mod tests {
 
         let req: TTSRequest = serde_json::from_str(json).unwrap();
 
-        assert_eq!(req.speed, 1.0);
+        assert_eq!(req.speed, None);
+        assert_eq!(req.speed(), 1.0);
+    }
+
+    #[test]
+    fn test_tts_request_pitch_is_not_settable() {
+        let json = r#"{"text": "Test", "pitch": 0.9}"#;
+
+        let req: TTSRequest = serde_json::from_str(json).unwrap();
+
+        assert_eq!(req.pitch, None);
     }
 
     #[test]
@@ -135,7 +327,7 @@ mod tests {
         for speed in test_cases {
             let json = format!(r#"{{"text": "Test", "speed": {}}}"#, speed);
             let req: TTSRequest = serde_json::from_str(&json).unwrap();
-            assert_eq!(req.speed, speed);
+            assert_eq!(req.speed, Some(speed));
         }
     }
 
@@ -1,4 +1,12 @@
 use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::config::server_config::ServerConfig;
+use crate::kokoro::voice_aliases::VoiceAliases;
+use crate::models::ChunkMetadata;
+use crate::services::jobs::JobStatus;
+use crate::text_processing::normalization::{NormalizationChange, NormalizationInfo};
+use crate::utils::accept::AudioFormat;
 
 #[allow(dead_code)]
 #[derive(Debug, Serialize)]
@@ -29,12 +37,223 @@ pub struct HealthResponse {
     pub version: String,
 }
 
+/// Body of `/health/ready`: `ready` only when every check passes
+#[derive(Debug, Serialize)]
+pub struct ReadinessResponse {
+    pub status: String,
+    pub checks: Vec<ReadinessCheck>,
+}
+
+/// One dependency `/health/ready` looked at
+#[derive(Debug, Serialize)]
+pub struct ReadinessCheck {
+    pub name: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct PoolStatsResponse {
     pub pool_size: usize,
     pub active_requests: usize,
     pub available_engines: usize,
     pub total_requests: usize,
+    pub uptime_seconds: u64,
+    pub total_generations: u64,
+    pub total_audio_seconds: f64,
+    pub average_generation_ms: f64,
+    pub circuit_breaker: CircuitBreakerStats,
+    /// Usage of each voice since startup, by voice ID
+    pub by_voice: BTreeMap<String, VoiceStats>,
+}
+
+/// One voice's entry in the `by_voice` section of `/stats`
+#[derive(Debug, Serialize, PartialEq)]
+pub struct VoiceStats {
+    /// Syntheses with this voice (one per chunk of a chunked request)
+    pub generations: u64,
+    pub audio_seconds: f64,
+}
+
+/// Circuit breaker section of `/stats`
+#[derive(Debug, Serialize)]
+pub struct CircuitBreakerStats {
+    /// `closed`, `open` or `half-open`
+    pub state: String,
+    pub consecutive_failures: u32,
+    pub times_opened: u64,
+}
+
+/// `/tts` response body for clients that ask for JSON
+#[derive(Debug, Serialize)]
+pub struct TtsJsonResponse {
+    /// The WAV file, as standard padded base64
+    pub audio_base64: String,
+    /// Phrase timings for the whole clip
+    pub metadata: ChunkMetadata,
+}
+
+/// `/tts` response body for `metadata_only` requests
+#[derive(Debug, Serialize)]
+pub struct TtsMetadataResponse {
+    /// Where the phrase timings come from
+    pub timing: TimingSource,
+    /// Phrase timings for the whole clip
+    pub metadata: ChunkMetadata,
+}
+
+/// How the durations in a metadata-only response were measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimingSource {
+    /// Measured from audio that was synthesized and then discarded, so the
+    /// timings match what a normal request for the same text would play
+    Synthesized,
+}
+
+/// Normalization details returned by `/tts?debug=true`
+#[derive(Debug, Serialize)]
+pub struct TtsDebugInfo {
+    pub original: String,
+    pub normalized: String,
+    /// Original byte position for each byte of `normalized`
+    pub char_mapping: Vec<usize>,
+    pub info: NormalizationInfo,
+    pub changes: Vec<NormalizationChange>,
+    pub chunks: Vec<ChunkBoundary>,
+}
+
+/// A piece of the request synthesized on its own
+#[derive(Debug, Serialize)]
+pub struct ChunkBoundary {
+    pub index: usize,
+    pub text: String,
+    /// Normalized text sent to the engine
+    pub normalized: String,
+    pub speed: f32,
+    /// Byte range of `text` in the request text, if it appears verbatim
+    pub start: Option<usize>,
+    pub end: Option<usize>,
+}
+
+/// `manifest.json` of a `/tts/archive` zip
+#[derive(Debug, Serialize)]
+pub struct ArchiveManifest {
+    pub voice: String,
+    /// Length of all chunks played back to back
+    pub total_duration_ms: f64,
+    pub chunks: Vec<ArchiveChunk>,
+}
+
+/// One WAV file of a `/tts/archive` zip
+#[derive(Debug, Serialize)]
+pub struct ArchiveChunk {
+    pub index: usize,
+    /// Name of the chunk's entry in the zip
+    pub file: String,
+    pub text: String,
+    /// Length of the file, including any pause after the chunk
+    pub duration_ms: f64,
+    /// Where the chunk starts when the files are played in order
+    pub start_ms: f64,
+}
+
+/// Result of `/tts/estimate`
+#[derive(Debug, Serialize)]
+pub struct EstimateResponse {
+    /// Characters of normalized text that would be synthesized
+    pub characters: usize,
+    pub chunk_count: usize,
+    pub estimated_duration_ms: f64,
+    /// Wall-clock synthesis time with chunks spread over the engine pool
+    pub estimated_compute_ms: f64,
+    /// Whether the estimate uses rates observed on this server
+    pub calibrated: bool,
+}
+
+/// Result of `/tts/phonemes`
+#[derive(Debug, Serialize)]
+pub struct PhonemesResponse {
+    /// Text as it would be sent to the engine
+    pub normalized: String,
+    /// Phonemes the engine reads for `normalized`
+    pub phonemes: String,
+}
+
+/// Result of `/voices/mix/preview`
+#[derive(Debug, Serialize)]
+pub struct VoiceMixPreviewResponse {
+    /// The mix in the engine's style syntax, as it was synthesized
+    pub voice: String,
+    /// Each voice with its weight normalized so the weights sum to 1
+    pub weights: Vec<VoiceWeight>,
+    /// A short sample of the mix as a WAV file, in standard padded base64
+    pub audio_base64: String,
+}
+
+/// One voice of a resolved mix
+#[derive(Debug, Serialize)]
+pub struct VoiceWeight {
+    pub voice: String,
+    pub weight: f32,
+}
+
+/// Status of a `/tts/jobs` synthesis job
+#[derive(Debug, Serialize)]
+pub struct JobResponse {
+    pub job_id: String,
+    /// `queued`, `running`, `done` or `failed`
+    pub status: JobStatus,
+    pub chunks_done: usize,
+    pub chunks_total: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReloadResponse {
+    pub status: String,
+    pub pool_size: usize,
+    pub duration_ms: u64,
+}
+
+/// Effective server configuration returned by `/admin/config`
+///
+/// Secrets are never included: API keys only appear as a count, and the
+/// values of response headers that look like credentials are hidden.
+#[derive(Debug, Serialize)]
+pub struct ConfigResponse {
+    pub version: String,
+    /// Settings as resolved from the config file, environment and defaults
+    pub settings: ServerConfig,
+    /// Voice names accepted in requests, by the voice ID each resolves to
+    pub voice_aliases: VoiceAliases,
+    pub auth: AuthConfigSection,
+    pub runtime: RuntimeConfigSection,
+}
+
+/// Authentication section of `/admin/config`
+#[derive(Debug, Serialize)]
+pub struct AuthConfigSection {
+    pub enabled: bool,
+    pub key_count: usize,
+}
+
+/// What the running server made of its settings, in `/admin/config`
+#[derive(Debug, Serialize)]
+pub struct RuntimeConfigSection {
+    /// Engines actually loaded
+    pub pool_size: usize,
+    /// `per-api-key` or `per-ip-address` (None = not rate limited)
+    pub rate_limit_mode: Option<String>,
+    /// None when in-flight requests aren't capped
+    pub max_in_flight_requests: Option<usize>,
+    pub max_text_length: usize,
+    pub supported_formats: Vec<AudioFormat>,
+    pub cors_allowed_origins: String,
+    /// Voice ID of the fallback voice, after alias resolution
+    pub fallback_voice: Option<String>,
 }
 
 #[cfg(test)]
@@ -154,6 +373,14 @@ mod tests {
         assert!(json.contains("\"version\":\"0.1.0\""));
     }
 
+    fn closed_breaker() -> CircuitBreakerStats {
+        CircuitBreakerStats {
+            state: "closed".to_string(),
+            consecutive_failures: 0,
+            times_opened: 0,
+        }
+    }
+
     #[test]
     fn test_pool_stats_response_serialization() {
         let response = PoolStatsResponse {
@@ -161,6 +388,12 @@ mod tests {
             active_requests: 2,
             available_engines: 2,
             total_requests: 150,
+            uptime_seconds: 3600,
+            total_generations: 148,
+            total_audio_seconds: 912.5,
+            average_generation_ms: 420.0,
+            circuit_breaker: closed_breaker(),
+            by_voice: BTreeMap::new(),
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -169,6 +402,13 @@ mod tests {
         assert!(json.contains("\"active_requests\":2"));
         assert!(json.contains("\"available_engines\":2"));
         assert!(json.contains("\"total_requests\":150"));
+        assert!(json.contains("\"uptime_seconds\":3600"));
+        assert!(json.contains("\"total_generations\":148"));
+        assert!(json.contains("\"total_audio_seconds\":912.5"));
+        assert!(json.contains("\"average_generation_ms\":420.0"));
+        assert!(json.contains(
+            "\"circuit_breaker\":{\"state\":\"closed\",\"consecutive_failures\":0,\"times_opened\":0}"
+        ));
     }
 
     #[test]
@@ -178,6 +418,12 @@ mod tests {
             active_requests: 0,
             available_engines: 0,
             total_requests: 0,
+            uptime_seconds: 0,
+            total_generations: 0,
+            total_audio_seconds: 0.0,
+            average_generation_ms: 0.0,
+            circuit_breaker: closed_breaker(),
+            by_voice: BTreeMap::new(),
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -193,6 +439,12 @@ mod tests {
             active_requests: 50,
             available_engines: 50,
             total_requests: 1000000,
+            uptime_seconds: 86400 * 30,
+            total_generations: 999000,
+            total_audio_seconds: 3_600_000.0,
+            average_generation_ms: 350.0,
+            circuit_breaker: closed_breaker(),
+            by_voice: BTreeMap::new(),
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -248,6 +500,24 @@ mod tests {
         assert_eq!(parsed["gender"], "Female");
     }
 
+    #[test]
+    fn test_job_response_serialization() {
+        let response = JobResponse {
+            job_id: "abc".to_string(),
+            status: JobStatus::Running,
+            chunks_done: 2,
+            chunks_total: 5,
+            error: None,
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+
+        assert_eq!(
+            json,
+            "{\"job_id\":\"abc\",\"status\":\"running\",\"chunks_done\":2,\"chunks_total\":5}"
+        );
+    }
+
     #[test]
     fn test_tts_response_skip_none_error() {
         let response = TTSResponse {
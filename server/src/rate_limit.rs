@@ -16,7 +16,9 @@ use std::net::IpAddr;
 use std::num::NonZeroU32;
 use std::sync::Arc;
 
+use crate::config::trusted_proxies::TrustedProxies;
 use crate::utils::header_utils::{extract_api_key, extract_client_ip};
+use crate::utils::redact::key_fingerprint;
 
 /// Type alias for the in-memory rate limiter
 type InMemoryRateLimiter = RateLimiter<NotKeyed, InMemoryState, DefaultClock>;
@@ -28,7 +30,7 @@ struct ErrorResponse {
 }
 
 /// Configuration for rate limiting
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct RateLimitConfig {
     /// Requests per second allowed
     pub per_second: u32,
@@ -117,6 +119,8 @@ pub struct PerIpRateLimiter {
     limiters: Arc<DashMap<IpAddr, Arc<InMemoryRateLimiter>>>,
     /// Configuration for new rate limiters
     config: RateLimitConfig,
+    /// Proxies allowed to report the client IP via forwarding headers
+    trusted_proxies: TrustedProxies,
     /// Clock for rate limiting
     clock: DefaultClock,
 }
@@ -127,10 +131,22 @@ impl PerIpRateLimiter {
         Self {
             limiters: Arc::new(DashMap::new()),
             config,
+            trusted_proxies: TrustedProxies::default(),
             clock: DefaultClock::default(),
         }
     }
 
+    /// Use the given proxies instead of the loopback default
+    pub fn with_trusted_proxies(mut self, trusted_proxies: TrustedProxies) -> Self {
+        self.trusted_proxies = trusted_proxies;
+        self
+    }
+
+    /// Proxies whose forwarding headers are believed
+    pub fn trusted_proxies(&self) -> &TrustedProxies {
+        &self.trusted_proxies
+    }
+
     /// Get or create a rate limiter for the given IP address
     fn get_or_create_limiter(&self, ip: IpAddr) -> Arc<InMemoryRateLimiter> {
         self.limiters
@@ -226,9 +242,9 @@ pub async fn rate_limit_middleware(
                     tracing::warn!(
                         "Rate limit exceeded for API key: {} (retry after {} seconds)",
                         if api_key == "anonymous" {
-                            "unauthenticated"
+                            "unauthenticated".to_string()
                         } else {
-                            &api_key
+                            key_fingerprint(&api_key)
                         },
                         retry_after
                     );
@@ -238,7 +254,7 @@ pub async fn rate_limit_middleware(
         }
         RateLimiterMode::PerIp(ip_limiter) => {
             // Extract IP address from request
-            match extract_client_ip(&request) {
+            match extract_client_ip(&request, ip_limiter.trusted_proxies()) {
                 Ok(ip) => {
                     // Check rate limit for this IP
                     match ip_limiter.check_rate_limit(ip) {
@@ -0,0 +1,199 @@
+use axum::{
+    extract::{Request, State},
+    http::HeaderMap,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::time::Duration;
+
+use crate::config::constants::REQUEST_TIMEOUT_HEADER;
+use crate::error::TtsError;
+
+/// Per-request timeouts: a server default that clients may override with
+/// `X-Request-Timeout`, up to a configured maximum
+///
+/// Long audiobook chapters can legitimately take longer than a typical
+/// request, so raising the global timeout for everyone isn't needed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RequestTimeouts {
+    pub default: Duration,
+    pub max: Duration,
+}
+
+impl RequestTimeouts {
+    /// `max` is raised to `default` if lower, so the hint can't shorten the ceiling
+    pub fn new(default: Duration, max: Duration) -> Self {
+        Self {
+            default,
+            max: max.max(default),
+        }
+    }
+
+    /// Timeout for a request, from its `X-Request-Timeout` header if present
+    ///
+    /// The header is in seconds (fractions allowed) and is clamped to `max`.
+    /// Values that aren't a positive number are rejected.
+    pub fn for_request(&self, headers: &HeaderMap) -> Result<Duration, TtsError> {
+        let Some(value) = headers.get(REQUEST_TIMEOUT_HEADER) else {
+            return Ok(self.default);
+        };
+
+        let invalid = || {
+            TtsError::InvalidRequest(format!(
+                "X-Request-Timeout must be a positive number of seconds, got {:?}",
+                String::from_utf8_lossy(value.as_bytes())
+            ))
+        };
+        let seconds: f64 = value
+            .to_str()
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .ok_or_else(invalid)?;
+        if !seconds.is_finite() || seconds <= 0.0 {
+            return Err(invalid());
+        }
+
+        Ok(Duration::from_secs_f64(seconds.min(self.max.as_secs_f64())))
+    }
+}
+
+/// Middleware to cut off requests that run past their timeout with a 408
+///
+/// Like the timeout layer it replaces, this only bounds the time until the
+/// response starts; streamed bodies aren't cut off midway.
+pub async fn request_timeout_middleware(
+    State(timeouts): State<RequestTimeouts>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let timeout = match timeouts.for_request(request.headers()) {
+        Ok(timeout) => timeout,
+        Err(e) => return e.into_response(),
+    };
+
+    match tokio::time::timeout(timeout, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => {
+            tracing::warn!("Request timed out after {:?}", timeout);
+            TtsError::RequestTimeout(timeout).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::StatusCode, middleware, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn timeouts() -> RequestTimeouts {
+        RequestTimeouts::new(Duration::from_millis(100), Duration::from_millis(500))
+    }
+
+    fn headers(timeout: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_TIMEOUT_HEADER, timeout.parse().unwrap());
+        headers
+    }
+
+    /// A route that takes 300ms: longer than the default, under the max
+    fn slow_app(timeouts: RequestTimeouts) -> Router {
+        Router::new()
+            .route(
+                "/slow",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_millis(300)).await;
+                    "done"
+                }),
+            )
+            .layer(middleware::from_fn_with_state(
+                timeouts,
+                request_timeout_middleware,
+            ))
+    }
+
+    async fn status_with_timeout(app: Router, timeout: Option<&str>) -> StatusCode {
+        let mut request = Request::builder().uri("/slow");
+        if let Some(timeout) = timeout {
+            request = request.header(REQUEST_TIMEOUT_HEADER, timeout);
+        }
+        let response = app
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        response.status()
+    }
+
+    #[test]
+    fn test_timeout_from_header() {
+        let timeouts = timeouts();
+        assert_eq!(
+            timeouts.for_request(&HeaderMap::new()).unwrap(),
+            Duration::from_millis(100)
+        );
+        assert_eq!(
+            timeouts.for_request(&headers("0.25")).unwrap(),
+            Duration::from_millis(250)
+        );
+        // Shorter than the default is allowed, longer than the max is clamped
+        assert_eq!(
+            timeouts.for_request(&headers("0.05")).unwrap(),
+            Duration::from_millis(50)
+        );
+        assert_eq!(
+            timeouts.for_request(&headers("3600")).unwrap(),
+            Duration::from_millis(500)
+        );
+
+        for bad in ["soon", "0", "-5", "NaN", "inf"] {
+            assert!(
+                matches!(
+                    timeouts.for_request(&headers(bad)),
+                    Err(TtsError::InvalidRequest(_))
+                ),
+                "{:?}",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_max_is_never_below_default() {
+        let timeouts = RequestTimeouts::new(Duration::from_secs(60), Duration::from_secs(10));
+        assert_eq!(timeouts.max, Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn test_default_timeout_cuts_off_slow_request() {
+        assert_eq!(
+            status_with_timeout(slow_app(timeouts()), None).await,
+            StatusCode::REQUEST_TIMEOUT
+        );
+    }
+
+    #[tokio::test]
+    async fn test_allowed_longer_timeout_is_not_cut_off() {
+        assert_eq!(
+            status_with_timeout(slow_app(timeouts()), Some("0.45")).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn test_timeout_over_max_is_clamped_and_cut_off() {
+        // Clamped to the 150ms max, too short for the 300ms route
+        let strict = RequestTimeouts::new(Duration::from_millis(50), Duration::from_millis(150));
+        assert_eq!(
+            status_with_timeout(slow_app(strict), Some("30")).await,
+            StatusCode::REQUEST_TIMEOUT
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_header_is_rejected() {
+        assert_eq!(
+            status_with_timeout(slow_app(timeouts()), Some("later")).await,
+            StatusCode::BAD_REQUEST
+        );
+    }
+}
@@ -1,24 +1,78 @@
 use axum::{
-    extract::State,
+    body::Body,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
     middleware,
-    response::Response,
+    response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
+use std::future::Future;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
-use tower_http::cors::{Any, CorsLayer};
+use tokio_stream::StreamExt;
+use tokio_util::io::ReaderStream;
 use tower_http::services::ServeDir;
-use tower_http::timeout::TimeoutLayer;
 
 use crate::audio;
+use crate::audio::wav_utils::SampleRateMismatch;
 use crate::auth::ApiKeys;
-use crate::chunking::{chunk_text, ChunkingConfig};
-use crate::config::constants::MAX_TEXT_LENGTH;
+use crate::chunking::{chunk_text, ChunkingConfig, LongTokenConfig};
+use crate::concurrency::ConcurrencyLimiter;
+use crate::config::constants::{
+    DEFAULT_MAX_PHRASE_WORDS, MAX_PITCH, MAX_TEXT_LENGTH, MAX_UPLOAD_BODY_BYTES,
+    METADATA_VERSION_HEADER, MIN_PITCH, MIX_PREVIEW_TEXT, MULTIPART_BOUNDARY,
+    SUPPORTED_AUDIO_FORMATS, VALIDATION_WARNINGS_HEADER, VOICE_FALLBACK_HEADER,
+};
+use crate::config::cors::CorsConfig;
+use crate::config::response_headers::{self, ResponseHeaders};
+use crate::config::server_config::ServerConfig;
 use crate::error::{Result, TtsError};
-use crate::kokoro::{model_paths::get_samples_dir, voice_config::Voice, TTSPool};
-use crate::models::{HealthResponse, PoolStatsResponse, TTSRequest, VoiceInfo, VoicesResponse};
+use crate::fair_queue;
+use crate::kokoro::{
+    circuit_breaker::BreakerState,
+    engine_error,
+    model_paths::{check_espeak_data, get_samples_dir},
+    voice_aliases::VoiceAliases,
+    voice_config::{Voice, VoiceMix},
+    voice_defaults::VoiceDefaults,
+    TTSPool, TTS,
+};
+use crate::models::requests::VoiceMixPart;
+use crate::models::{
+    AuthConfigSection, ChunkBoundary, CircuitBreakerStats, ConfigResponse, EstimateResponse,
+    HealthResponse, JobResponse, MetadataVersion, PhonemesResponse, PoolStatsResponse,
+    ReadinessCheck, ReadinessResponse, ReloadResponse, RuntimeConfigSection, StreamQuery,
+    TTSRequest, TimingSource, TtsDebugInfo, TtsJsonResponse, TtsMetadataResponse, TtsQuery,
+    VoiceInfo, VoiceMixPreviewRequest, VoiceMixPreviewResponse, VoiceStats, VoiceWeight,
+    VoicesResponse,
+};
 use crate::rate_limit::RateLimiterMode;
+use crate::request_timeout::RequestTimeouts;
+use crate::services::archive::ArchiveBuilder;
+use crate::services::audio_cache::DiskCache;
+use crate::services::estimate::{EstimateModel, VoiceRates};
+use crate::services::generation_stats::GenerationStats;
+use crate::services::jobs::{JobAudioError, JobCancelError, JobStore};
+use crate::services::metadata_builder;
+use crate::services::streaming::{
+    create_audio_part, create_boundary_end, create_json_part, ChunkRetryConfig,
+};
+use crate::text_processing::asides::{self, AsideConfig};
+use crate::text_processing::lists::{self, ListConfig};
+use crate::text_processing::normalization::{self, NormalizationConfig, NormalizationResult};
+use crate::text_processing::punctuation::PunctuationNames;
+use crate::text_processing::quotes::{self, QuoteStyle};
+use crate::text_processing::sentence_splitting;
+use crate::text_processing::speed_markup::{self, SpeechSegment};
+use crate::utils::accept::{self, AudioFormat};
+use crate::utils::base64;
+use crate::utils::filename;
+use crate::utils::form_data::{self, FormPart};
+use crate::utils::header_utils::extract_api_key;
+use crate::utils::redact;
+use crate::utils::speed::resolve_speed;
 use crate::utils::temp_file::TempFile;
 
 // Shared application state
@@ -28,138 +82,1179 @@ pub struct AppState {
     pub api_keys: ApiKeys,
     pub rate_limiter: Option<RateLimiterMode>,
     pub request_timeout: Duration,
+    /// Longest timeout a request may ask for with `X-Request-Timeout`
+    pub max_request_timeout: Duration,
+    pub normalization: NormalizationConfig,
+    pub concurrency_limiter: Option<ConcurrencyLimiter>,
+    pub max_audio_duration: Duration,
+    pub cors: CorsConfig,
+    pub generation_stats: Arc<GenerationStats>,
+    pub audio_cache: Option<Arc<DiskCache>>,
+    pub speed_clamp: bool,
+    pub voice_aliases: Arc<VoiceAliases>,
+    pub wav_metadata: bool,
+    pub concat_sample_rate: SampleRateMismatch,
+    /// Text longer than this (in bytes) is chunked when chunking is enabled
+    pub chunking_min_length: usize,
+    /// Validate phrase timings on `/tts` and report warnings in a header
+    pub validation_headers: bool,
+    /// Splitting or rejection of overlong tokens
+    pub long_tokens: LongTokenConfig,
+    /// Retries and failure handling for streamed chunks
+    pub chunk_retry: ChunkRetryConfig,
+    /// Background synthesis jobs from `/tts/jobs`
+    pub jobs: JobStore,
+    /// Silence inserted after an ellipsis (None = read as punctuation)
+    pub ellipsis_pause: Option<Duration>,
+    /// Directory containing `espeak-ng-data`, checked by `/health/ready`
+    /// (None = espeak-ng's built-in path)
+    pub espeak_data_dir: Option<PathBuf>,
+    /// Speed and gain for parenthetical asides (None = read like other text)
+    pub asides: Option<AsideConfig>,
+    /// Pauses and numbering for list items (None = read like other text)
+    pub lists: Option<ListConfig>,
+    /// Voice `/tts` retries with when the requested one is unavailable
+    /// (None = fail instead)
+    pub fallback_voice: Option<String>,
+    /// Longest normalized text accepted, in bytes
+    pub max_normalized_length: usize,
+    /// Names punctuation is read aloud by (None = punctuation is only heard
+    /// as pauses and intonation)
+    pub say_punctuation: Option<PunctuationNames>,
+    /// Quiet time after which multipart streams send a keep-alive part
+    /// (None = never)
+    pub stream_keep_alive: Option<Duration>,
+    /// How quotation marks are read
+    pub quotes: QuoteStyle,
+    /// Configured speaking rates of voices, for estimates and stream offsets
+    pub voice_rates: Arc<VoiceRates>,
+    /// Static headers added to every response
+    pub response_headers: Arc<ResponseHeaders>,
+    /// Slowest accepted speed; slower requests are rejected (or clamped)
+    pub min_speed: f32,
+    /// Operator-configured default speed and pitch of voices
+    pub voice_defaults: Arc<VoiceDefaults>,
+    /// Format produced when neither `format` nor `Accept` asks for one
+    pub default_audio_format: AudioFormat,
+    /// Resolved settings, as reported by `/admin/config`
+    pub config: Arc<ServerConfig>,
+}
+
+#[cfg(test)]
+impl AppState {
+    /// State with default settings around the given pool (no auth, no limits)
+    pub fn for_tests(tts_pool: TTSPool) -> Self {
+        Self {
+            tts_pool: Arc::new(tts_pool),
+            api_keys: ApiKeys::empty(),
+            rate_limiter: None,
+            request_timeout: Duration::from_secs(60),
+            max_request_timeout: Duration::from_secs(
+                crate::config::constants::DEFAULT_MAX_REQUEST_TIMEOUT_SECS,
+            ),
+            normalization: NormalizationConfig::default(),
+            concurrency_limiter: None,
+            max_audio_duration: Duration::from_millis(
+                crate::config::constants::DEFAULT_MAX_AUDIO_DURATION_MS,
+            ),
+            cors: CorsConfig::default(),
+            generation_stats: Arc::new(GenerationStats::new()),
+            audio_cache: None,
+            speed_clamp: false,
+            voice_aliases: Arc::new(VoiceAliases::default()),
+            wav_metadata: false,
+            concat_sample_rate: SampleRateMismatch::Strict,
+            chunking_min_length: crate::config::constants::DEFAULT_CHUNKING_MIN_LENGTH,
+            validation_headers: false,
+            long_tokens: LongTokenConfig::default(),
+            chunk_retry: ChunkRetryConfig::default(),
+            jobs: JobStore::new(
+                Duration::from_secs(crate::config::constants::DEFAULT_JOB_TTL_SECS),
+                1,
+            ),
+            ellipsis_pause: None,
+            espeak_data_dir: None,
+            asides: None,
+            lists: None,
+            fallback_voice: None,
+            max_normalized_length: crate::config::constants::DEFAULT_MAX_NORMALIZED_LENGTH,
+            say_punctuation: None,
+            stream_keep_alive: None,
+            quotes: QuoteStyle::Ignore,
+            voice_rates: Default::default(),
+            response_headers: Default::default(),
+            min_speed: crate::config::constants::DEFAULT_MIN_SPEED,
+            voice_defaults: Default::default(),
+            default_audio_format: AudioFormat::Wav,
+            config: Default::default(),
+        }
+    }
 }
 
 // HTTP Handlers
 
 /// Generate TTS audio from text
+///
+/// With `?debug=true` the audio is returned as multipart, preceded by a JSON
+/// part describing how the text was normalized and chunked. Clients that
+/// accept JSON but not WAV get the audio base64-encoded in a JSON body.
 async fn generate_tts(
     State(state): State<AppState>,
-    Json(req): Json<TTSRequest>,
-) -> Result<Vec<u8>> {
+    Query(query): Query<TtsQuery>,
+    headers: HeaderMap,
+    Json(mut req): Json<TTSRequest>,
+) -> Result<Response> {
     tracing::debug!(
         "TTS request - text_len={}, voice='{}', speed={}, chunking={}",
         req.text.len(),
         req.voice,
-        req.speed,
+        req.speed(),
         req.enable_chunking
     );
 
+    let max_text_length = key_max_text_length(&state, &headers);
+    let requested = req.clone();
+    let segments = validate_tts_request(&state, &mut req, max_text_length)?;
+
+    // Every supported format is WAV for now, so this only turns away clients
+    // that can't take it
+    select_audio_format(&headers, req.format, state.default_audio_format)?;
+
+    if query.debug {
+        let pieces = plan_pieces(
+            &req,
+            segments,
+            state.chunking_min_length,
+            state.ellipsis_pause.is_some(),
+        );
+        return generate_tts_debug(state, req, pieces).await;
+    }
+
+    let json = req.metadata_only || accepts_json(&headers);
+    with_voice_fallback(
+        &state,
+        requested,
+        req,
+        segments,
+        max_text_length,
+        |req, segments| respond_tts(state.clone(), req, segments, json),
+    )
+    .await
+}
+
+/// Generate the `/tts` response for a validated request: base64 JSON when
+/// `json` is set, else WAV
+async fn respond_tts(
+    state: AppState,
+    req: TTSRequest,
+    segments: Vec<SpeechSegment>,
+    json: bool,
+) -> Result<Response> {
+    // Determine if we should use chunking (enabled and text is long enough)
+    let use_chunking = should_chunk(&req, &req.text, state.chunking_min_length);
+
+    if json {
+        return generate_tts_json(state, req, segments).await;
+    }
+
+    let disposition = filename::content_disposition(req.filename.as_deref(), &req.voice, "wav");
+
+    // Added after generation, so cached audio is shared with untagged requests
+    let metadata = req
+        .metadata
+        .unwrap_or(state.wav_metadata)
+        .then(|| audio::wav_metadata::request_info(&req.text, &req.voice, req.speed()));
+    let validation_text = state
+        .validation_headers
+        .then(|| prepare_text(&state, &req, &spoken_text(&segments)));
+    let finish = |audio_data: Vec<u8>| -> Result<Response> {
+        let warnings = match validation_text {
+            Some(ref norm_result) => Some(count_validation_warnings(&audio_data, norm_result)?),
+            None => None,
+        };
+        let mut response = wav_response(match &metadata {
+            Some(info) => audio::wav_metadata::embed_info(&audio_data, info)?,
+            None => audio_data,
+        });
+        if let Some(warnings) = warnings {
+            response
+                .headers_mut()
+                .insert(VALIDATION_WARNINGS_HEADER, warnings.into());
+        }
+        Ok(response)
+    };
+
+    let mut response = if is_segmented(&req, &segments) || pauses_at_ellipses(&state, &req.text) {
+        // Inline {{speed:N}} markup, asides or ellipsis pauses: synthesize each
+        // segment separately, at its own speed
+        finish(generate_tts_segmented(state, req, segments).await?)?
+    } else if use_chunking {
+        finish(generate_tts_chunked(state, req).await?)?
+    } else if req.sample_rate.is_some()
+        || req.bit_depth.is_some()
+        || metadata.is_some()
+        || validation_text.is_some()
+    {
+        // Resampling, re-quantizing, metadata and validation need the whole
+        // clip in memory
+        finish(generate_tts_single(state, req).await?)?
+    } else {
+        stream_tts_single(state, req).await?
+    };
+
+    response
+        .headers_mut()
+        .insert(header::CONTENT_DISPOSITION, disposition);
+    Ok(response)
+}
+
+/// Run `generate` on a validated request, retrying once with the fallback
+/// voice when the engine reports a voice unavailable
+///
+/// The retry starts again from `requested`, the request as the client sent it,
+/// so the fallback voice gets its own default speed and pitch rather than the
+/// unavailable voice's. The fallback's response carries an
+/// `X-TTS-Voice-Fallback` header naming the voice actually used. Without a
+/// configured fallback, errors are returned as they are.
+async fn with_voice_fallback<F, Fut>(
+    state: &AppState,
+    requested: TTSRequest,
+    req: TTSRequest,
+    segments: Vec<SpeechSegment>,
+    max_text_length: usize,
+    generate: F,
+) -> Result<Response>
+where
+    F: Fn(TTSRequest, Vec<SpeechSegment>) -> Fut,
+    Fut: Future<Output = Result<Response>>,
+{
+    let Some(fallback) = state
+        .fallback_voice
+        .clone()
+        .filter(|fallback| *fallback != req.voice)
+    else {
+        return generate(req, segments).await;
+    };
+
+    let voice = req.voice.clone();
+    match generate(req, segments).await {
+        Err(TtsError::VoiceUnavailable(missing)) => {
+            tracing::warn!(
+                "Voice '{}' unavailable ('{}' is not in the voices file); falling back to '{}'",
+                voice,
+                missing,
+                fallback
+            );
+            let mut retry = TTSRequest {
+                voice: fallback.clone(),
+                voice_mix: None,
+                ..requested
+            };
+            let segments = validate_tts_request(state, &mut retry, max_text_length)?;
+            let mut response = generate(retry, segments).await?;
+            if let Ok(value) = HeaderValue::from_str(&fallback) {
+                response.headers_mut().insert(VOICE_FALLBACK_HEADER, value);
+            }
+            Ok(response)
+        }
+        result => result,
+    }
+}
+
+/// Longest text the request's API key may send for synthesis
+///
+/// Keys without their own limit, and requests without a key, get
+/// `MAX_TEXT_LENGTH`.
+fn key_max_text_length(state: &AppState, headers: &HeaderMap) -> usize {
+    extract_api_key(headers)
+        .and_then(|key| state.api_keys.max_text_length(&key))
+        .unwrap_or(MAX_TEXT_LENGTH)
+}
+
+/// Validate a `/tts` request, returning its speed-marked segments
+///
+/// Text longer than `max_text_length` is rejected, and out-of-range speeds
+/// are clamped in place when clamping is enabled.
+fn validate_tts_request(
+    state: &AppState,
+    req: &mut TTSRequest,
+    max_text_length: usize,
+) -> Result<Vec<SpeechSegment>> {
+    resolve_request_voice(state, req)?;
+    apply_voice_defaults(state, req);
+
     // Validate text is not empty
     if req.text.trim().is_empty() {
         return Err(TtsError::EmptyText);
     }
 
     // Validate text length to prevent DoS
-    if req.text.len() > MAX_TEXT_LENGTH {
+    if req.text.len() > max_text_length {
         return Err(TtsError::InvalidRequest(format!(
             "Text too long: {} chars (max {})",
             req.text.len(),
-            MAX_TEXT_LENGTH
+            max_text_length
         )));
     }
 
-    // Validate speed is reasonable
-    if req.speed <= 0.0 || req.speed > 3.0 {
-        return Err(TtsError::InvalidSpeed(req.speed));
+    // Break up (or refuse) tokens too long for the engine
+    req.text = state.long_tokens.apply(&req.text)?;
+
+    // Validate speed is reasonable (or clamp it, if configured)
+    req.speed = Some(resolve_speed(
+        req.speed(),
+        state.min_speed,
+        state.speed_clamp,
+    )?);
+    req.max_phrase_words = Some(metadata_builder::validate_max_phrase_words(
+        req.max_phrase_words(),
+    )?);
+    req.min_phrase_ms = metadata_builder::validate_min_phrase_ms(req.min_phrase_ms)?;
+    req.waveform_points = metadata_builder::validate_waveform_points(req.waveform_points)?;
+
+    // Validate requested output sample rate and bit depth
+    audio::resample::validate_sample_rate(req.sample_rate)?;
+    audio::wav_utils::validate_bit_depth(req.bit_depth)?;
+
+    if let Some(format) = req.format {
+        ensure_supported_format(format)?;
     }
 
-    // Determine if we should use chunking (enabled and text is long enough)
-    // Lower threshold allows faster perceived latency for streaming
-    let use_chunking = req.enable_chunking && req.text.len() > 200;
+    // Split out inline {{speed:N}} markup and make sure something is left to say
+    let mut segments = speed_markup::parse_speed_markup(
+        &req.text,
+        req.speed(),
+        state.min_speed,
+        state.speed_clamp,
+    )?;
+    if let Some(config) = &state.lists {
+        segments = lists::split_list_items(segments, config);
+    }
+    if let Some(config) = &state.asides {
+        segments = asides::split_asides(segments, config, state.min_speed);
+    }
+    if let QuoteStyle::Prosody(config) = &state.quotes {
+        segments = quotes::split_quotes(segments, config, state.min_speed);
+    }
+    check_normalized_text(state, req, &segments)?;
+
+    Ok(segments)
+}
+
+/// Replace a friendly or legacy voice name with its canonical voice ID
+///
+/// A voice mix (`voice_mix`, or a `voice` containing `+`) is validated and
+/// replaced by the engine's blend syntax. Other unrecognized voices are left
+/// alone for the engine to accept or reject.
+pub(crate) fn resolve_request_voice(state: &AppState, req: &mut TTSRequest) -> Result<()> {
+    if let Some(mix) = parse_voice_mix(state, &req.voice, req.voice_mix.as_deref())? {
+        req.voice = mix.style_name();
+    } else if let Some(voice) = state.voice_aliases.resolve_voice(&req.voice) {
+        req.voice = voice.id().to_string();
+    }
+    Ok(())
+}
+
+/// The voice mix a request asks for, or None when it names a single voice
+fn parse_voice_mix(
+    state: &AppState,
+    voice: &str,
+    voice_mix: Option<&[VoiceMixPart]>,
+) -> Result<Option<VoiceMix>> {
+    let resolve = |name: &str| state.voice_aliases.resolve_voice(name);
+
+    let mix = if let Some(parts) = voice_mix {
+        parts
+            .iter()
+            .map(|part| {
+                resolve(&part.voice)
+                    .map(|voice| (voice, part.weight))
+                    .ok_or_else(|| format!("Unknown voice '{}' in voice mix", part.voice))
+            })
+            .collect::<std::result::Result<Vec<_>, String>>()
+            .and_then(VoiceMix::new)
+    } else if voice.contains('+') {
+        VoiceMix::parse(voice, resolve)
+    } else {
+        return Ok(None);
+    };
+    mix.map(Some).map_err(TtsError::InvalidRequest)
+}
+
+/// Fill in the voice's configured default speed where the request left it
+/// unset, and its default pitch
+///
+/// Expects the voice to be resolved to its canonical ID already; voices
+/// without configured defaults keep speed and pitch 1.0.
+pub(crate) fn apply_voice_defaults(state: &AppState, req: &mut TTSRequest) {
+    req.speed = req.speed.or(state.voice_defaults.speed(&req.voice));
+    req.pitch = state.voice_defaults.pitch(&req.voice);
+}
+
+/// Whether `text` from this request is long enough to be chunked
+fn should_chunk(req: &TTSRequest, text: &str, min_length: usize) -> bool {
+    req.enable_chunking && text.len() > min_length
+}
 
-    if use_chunking {
-        generate_tts_chunked(state, req).await
+/// Split a validated request into the pieces that are synthesized separately
+///
+/// Text longer than `min_length` is chunked when the request allows it, and
+/// pieces are split after each ellipsis when `pause_at_ellipses` is set.
+fn plan_pieces(
+    req: &TTSRequest,
+    segments: Vec<SpeechSegment>,
+    min_length: usize,
+    pause_at_ellipses: bool,
+) -> Vec<SpeechSegment> {
+    let pieces = if is_segmented(req, &segments) {
+        segment_pieces(req, segments, min_length)
+    } else if should_chunk(req, &req.text, min_length) {
+        chunk_pieces(req)
     } else {
-        generate_tts_single(state, req).await
+        vec![SpeechSegment {
+            text: req.text.clone(),
+            speed: req.speed(),
+            gain_db: 0.0,
+            pitch_scale: 1.0,
+            pause_after: None,
+        }]
+    };
+
+    if pause_at_ellipses {
+        split_pieces_at_ellipses(pieces)
+    } else {
+        pieces
+    }
+}
+
+/// Break pieces after each ellipsis, so silence can be inserted there
+fn split_pieces_at_ellipses(pieces: Vec<SpeechSegment>) -> Vec<SpeechSegment> {
+    pieces
+        .into_iter()
+        .flat_map(|piece| {
+            let parts = sentence_splitting::split_after_ellipses(&piece.text);
+            let last = parts.len().saturating_sub(1);
+            parts
+                .into_iter()
+                .enumerate()
+                .map(move |(i, text)| SpeechSegment {
+                    text,
+                    speed: piece.speed,
+                    gain_db: piece.gain_db,
+                    pitch_scale: piece.pitch_scale,
+                    pause_after: piece.pause_after.filter(|_| i == last),
+                })
+        })
+        .collect()
+}
+
+/// Silence to insert after each piece
+///
+/// Every piece but the last is followed by its own pause (after a list
+/// item), or by the configured ellipsis pause if it trails off in one.
+fn piece_pauses(state: &AppState, pieces: &[SpeechSegment]) -> Vec<Option<Duration>> {
+    pieces
+        .iter()
+        .enumerate()
+        .map(|(i, piece)| {
+            if i + 1 == pieces.len() {
+                return None;
+            }
+            piece.pause_after.or_else(|| {
+                state
+                    .ellipsis_pause
+                    .filter(|_| sentence_splitting::ends_with_ellipsis(&piece.text))
+            })
+        })
+        .collect()
+}
+
+/// Whether the segments must be synthesized one by one, rather than the
+/// request text as a whole
+///
+/// That is the case with inline speed markup, with asides split out at
+/// their own speed or gain, and with list items.
+fn is_segmented(req: &TTSRequest, segments: &[SpeechSegment]) -> bool {
+    speed_markup::has_speed_markup(&req.text)
+        || segments.len() > 1
+        || segments.iter().any(|segment| {
+            segment.speed != req.speed()
+                || segment.gain_db != 0.0
+                || segment.pitch_scale != 1.0
+                || segment.pause_after.is_some()
+        })
+}
+
+/// The request's pitch scaled for a piece, kept within the accepted range
+fn piece_pitch(req: &TTSRequest, piece: &SpeechSegment) -> f32 {
+    (req.pitch() * piece.pitch_scale).clamp(MIN_PITCH, MAX_PITCH)
+}
+
+/// Whether the request text has an ellipsis to pause at
+fn pauses_at_ellipses(state: &AppState, text: &str) -> bool {
+    state.ellipsis_pause.is_some() && sentence_splitting::split_after_ellipses(text).len() > 1
+}
+
+/// Estimate a request's audio duration and synthesis time without generating it
+///
+/// The text is validated, normalized and chunked exactly as `/tts` would.
+/// Compute time assumes the chunks are spread across the whole engine pool.
+async fn estimate_tts(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(mut req): Json<TTSRequest>,
+) -> Result<Json<EstimateResponse>> {
+    let segments = validate_tts_request(&state, &mut req, key_max_text_length(&state, &headers))?;
+    let pieces = plan_pieces(
+        &req,
+        segments,
+        state.chunking_min_length,
+        state.ellipsis_pause.is_some(),
+    );
+    let model = EstimateModel::for_voice(&state.generation_stats, &state.voice_rates, &req.voice);
+
+    let mut characters = 0;
+    let mut estimated_duration_ms: f64 = piece_pauses(&state, &pieces)
+        .iter()
+        .flatten()
+        .map(|pause| pause.as_secs_f64() * 1000.0)
+        .sum();
+    let mut total_generation_ms = 0.0;
+    for piece in &pieces {
+        let chars = prepare_text(&state, &req, &piece.text)
+            .normalized
+            .chars()
+            .count();
+        let audio_ms = model.audio_ms(chars, piece.speed);
+
+        characters += chars;
+        estimated_duration_ms += audio_ms;
+        total_generation_ms += model.generation_ms(audio_ms);
+    }
+
+    let parallelism = pieces.len().min(state.tts_pool.stats().pool_size).max(1);
+
+    Ok(Json(EstimateResponse {
+        characters,
+        chunk_count: pieces.len(),
+        estimated_duration_ms,
+        estimated_compute_ms: total_generation_ms / parallelism as f64,
+        calibrated: model.calibrated,
+    }))
+}
+
+/// Show the phonemes the engine would read for a request, without generating audio
+///
+/// The text is validated and normalized exactly as `/tts` would, so the
+/// result reflects what synthesis actually receives.
+async fn phonemize_tts(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(mut req): Json<TTSRequest>,
+) -> Result<Json<PhonemesResponse>> {
+    let segments = validate_tts_request(&state, &mut req, key_max_text_length(&state, &headers))?;
+    let normalized = segments
+        .iter()
+        .map(|segment| prepare_text(&state, &req, &segment.text).normalized)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    // espeak alone does the work, so this never waits for a busy engine
+    let text = normalized.clone();
+    let phonemes = tokio::task::spawn_blocking(move || {
+        TTS::phonemize(&text).map_err(|e| TtsError::TtsEngine(e.to_string()))
+    })
+    .await??;
+
+    Ok(Json(PhonemesResponse {
+        normalized,
+        phonemes,
+    }))
+}
+
+/// Reject input that normalizes to too much text, or to nothing to say (only
+/// emoji, punctuation or symbols)
+fn check_normalized_text(
+    state: &AppState,
+    req: &TTSRequest,
+    segments: &[SpeechSegment],
+) -> Result<()> {
+    let normalized: Vec<String> = segments
+        .iter()
+        .map(|segment| prepare_text(state, req, &segment.text).normalized)
+        .collect();
+    check_normalized_length(state, normalized.iter().map(String::len).sum())?;
+
+    if normalized
+        .iter()
+        .any(|text| normalization::has_speakable_content(text))
+    {
+        Ok(())
+    } else {
+        Err(TtsError::InvalidRequest(
+            "No speakable content in text".to_string(),
+        ))
+    }
+}
+
+/// Reject text whose normalized form is longer than the configured maximum
+///
+/// The raw text is limited separately, but spelling out numbers and symbols
+/// can multiply its length.
+pub(crate) fn check_normalized_length(state: &AppState, length: usize) -> Result<()> {
+    if length > state.max_normalized_length {
+        return Err(TtsError::InvalidRequest(format!(
+            "Normalized text too long: {} chars (max {}); numbers, currency and symbols \
+             grow when spelled out",
+            length, state.max_normalized_length
+        )));
     }
+    Ok(())
+}
+
+/// Build a response carrying a complete in-memory WAV
+fn wav_response(audio_data: Vec<u8>) -> Response {
+    ([(header::CONTENT_TYPE, "audio/wav")], audio_data).into_response()
+}
+
+/// Generate TTS for a single chunk of text and stream the WAV from disk
+///
+/// The body is sent with chunked transfer as it is read from the temp file,
+/// so the whole clip is never buffered in memory.
+async fn stream_tts_single(state: AppState, req: TTSRequest) -> Result<Response> {
+    let normalized_text = normalize_request_text(&state, &req);
+
+    let cache_key = cache_key(&state, &normalized_text, &req);
+    if let Some(audio_data) = cached_audio(&state, cache_key.as_deref()).await {
+        return Ok(wav_response(audio_data));
+    }
+
+    let temp_file = synthesize_to_file(&state, &req, normalized_text).await?;
+
+    if let (Some(cache), Some(key)) = (&state.audio_cache, &cache_key) {
+        // Small clips only need one read; the streamed body re-reads from disk
+        match temp_file.read().await {
+            Ok(audio_data) => store_in_cache(cache, key, &audio_data).await,
+            Err(e) => tracing::warn!("Failed to read audio for cache: {}", e),
+        }
+    }
+
+    let file = temp_file.open().await?;
+    let file_len = file.metadata().await?.len();
+
+    let stream = ReaderStream::new(file).map(move |chunk| {
+        // Keep the temp file alive until the body has been fully streamed
+        let _ = temp_file.path();
+        chunk
+    });
+
+    Response::builder()
+        .header(header::CONTENT_TYPE, "audio/wav")
+        .header(header::CONTENT_LENGTH, file_len)
+        .body(Body::from_stream(stream))
+        .map_err(|e| TtsError::Unknown(e.to_string()))
 }
 
 /// Generate TTS for a single chunk of text
 async fn generate_tts_single(state: AppState, req: TTSRequest) -> Result<Vec<u8>> {
+    let normalized_text = normalize_request_text(&state, &req);
+
+    // Check the on-disk cache before generating
+    let cache_key = cache_key(&state, &normalized_text, &req);
+    if let Some(audio_data) = cached_audio(&state, cache_key.as_deref()).await {
+        return Ok(audio_data);
+    }
+
+    let temp_file = synthesize_to_file(&state, &req, normalized_text).await?;
+
+    // Read generated audio file
+    let mut audio_data = temp_file.read().await?;
+
+    // TempFile will automatically clean up when it goes out of scope
+
+    // Resample to the requested output rate (header is rewritten accordingly)
+    if let Some(sample_rate) = req.sample_rate {
+        audio_data = audio::resample::resample_wav(&audio_data, sample_rate)?;
+    }
+    if let Some(bits) = req.bit_depth {
+        audio_data = audio::wav_utils::convert_bit_depth(&audio_data, bits)?;
+    }
+
+    if let (Some(cache), Some(key)) = (&state.audio_cache, &cache_key) {
+        store_in_cache(cache, key, &audio_data).await;
+    }
+
+    Ok(audio_data)
+}
+
+/// Disk cache key for a request, or None when the cache is disabled
+fn cache_key(state: &AppState, normalized_text: &str, req: &TTSRequest) -> Option<String> {
+    state
+        .audio_cache
+        .as_ref()
+        .map(|_| DiskCache::key(&state.tts_pool.model_id(), normalized_text, req))
+}
+
+/// Look up previously generated audio in the disk cache
+async fn cached_audio(state: &AppState, key: Option<&str>) -> Option<Vec<u8>> {
+    let (cache, key) = (state.audio_cache.as_ref()?, key?);
+    let audio_data = cache.get(key).await?;
+    tracing::debug!("Audio cache hit for {}", key);
+    Some(audio_data)
+}
+
+/// Store generated audio in the disk cache; failures only cost a future miss
+async fn store_in_cache(cache: &DiskCache, key: &str, audio_data: &[u8]) {
+    if let Err(e) = cache.put(key, audio_data).await {
+        tracing::warn!("Failed to write audio cache entry {}: {}", key, e);
+    }
+}
+
+/// Normalize text from a request, unless the request opted out of normalization
+pub(crate) fn prepare_text(state: &AppState, req: &TTSRequest, text: &str) -> NormalizationResult {
+    if req.normalize {
+        normalization::normalize_for_tts_with_config(text, &state.normalization)
+    } else {
+        NormalizationResult::unchanged(text)
+    }
+}
+
+/// Normalize request text for TTS (semantic + unicode normalization)
+fn normalize_request_text(state: &AppState, req: &TTSRequest) -> String {
+    let text = &req.text;
+    let mut normalized_text = prepare_text(state, req, text).normalized;
+    if state.quotes == QuoteStyle::Spoken {
+        normalized_text = quotes::speak(&normalized_text);
+    }
+    if let Some(names) = &state.say_punctuation {
+        normalized_text = names.speak(&normalized_text);
+    }
+
+    // Log sizes and hashes only; the raw text is user content (trace shows it)
+    redact::log_text("Original text", text);
+    redact::log_text("Normalized text", &normalized_text);
+
+    normalized_text
+}
+
+/// Synthesize a single chunk of already-normalized text into a temp WAV file
+///
+/// The generated clip is checked against the configured maximum duration.
+async fn synthesize_to_file(
+    state: &AppState,
+    req: &TTSRequest,
+    normalized_text: String,
+) -> Result<TempFile> {
     // Acquire a TTS engine from the pool
     let tts = state.tts_pool.acquire().await.map_err(|e| {
         tracing::error!("Failed to acquire TTS engine: {}", e);
-        TtsError::TtsEngine(e.to_string())
+        e
     })?;
 
     // Generate unique temporary file
     let temp_file = TempFile::new();
     let temp_path = temp_file.as_str().to_string();
 
-    // Normalize text for TTS (semantic + unicode normalization)
-    let normalized_text = crate::text_processing::normalization::normalize_simple(&req.text);
-
-    // Debug logging to verify normalization
-    tracing::info!("Original text: {:?}", &req.text);
-    tracing::info!("Normalized text: {:?}", &normalized_text);
-
+    let chars = normalized_text.chars().count();
     let voice = req.voice.clone();
-    let speed = req.speed;
+    let speed = req.speed();
+    let pitch = req.pitch();
+    let mono = req.mono;
+
+    // Pitch shifting shortens the audio, so synthesize that much slower first
+    let engine_speed = speed / pitch;
 
     // Move TTS generation to blocking thread pool
+    let generation_start = std::time::Instant::now();
     let generation_result = tokio::task::spawn_blocking(move || {
-        futures::executor::block_on(tts.speak(&normalized_text, &temp_path, &voice, speed))
-            .map_err(|e| TtsError::TtsEngine(e.to_string()))
+        futures::executor::block_on(tts.speak(
+            &normalized_text,
+            &temp_path,
+            &voice,
+            engine_speed,
+            mono,
+        ))
+        .map_err(engine_error)
     })
     .await?;
 
     // Handle generation result
     generation_result?;
+    if pitch != 1.0 {
+        let audio_data = temp_file.read().await?;
+        let shifted = audio::resample::shift_pitch_wav(&audio_data, pitch)?;
+        temp_file.write(shifted).await?;
+    }
+    let generation_time = generation_start.elapsed();
 
-    // Read generated audio file
-    let audio_data = tokio::fs::read(temp_file.path()).await?;
+    // Reject runaway output before doing any more work on it
+    let duration_ms = audio::duration::calculate_file(temp_file.path())?;
+    check_audio_duration(duration_ms, state.max_audio_duration)?;
 
-    // TempFile will automatically clean up when it goes out of scope
+    state
+        .generation_stats
+        .record(chars, speed, duration_ms, generation_time);
+    state
+        .generation_stats
+        .record_voice(&req.voice, chars, speed, duration_ms);
 
-    Ok(audio_data)
+    Ok(temp_file)
 }
 
 /// Generate TTS with text chunking and parallel processing
 async fn generate_tts_chunked(state: AppState, req: TTSRequest) -> Result<Vec<u8>> {
-    // Split text into chunks
-    let config = ChunkingConfig::default();
-    let chunks = chunk_text(&req.text, &config);
+    let chunks = chunk_pieces(&req);
 
     tracing::debug!(
         "Split text into {} chunks for parallel processing",
         chunks.len()
     );
 
+    generate_tts_pieces(state, req, chunks).await
+}
+
+/// Generate TTS for speed-marked segments, each at its own speed
+///
+/// Long segments are chunked like any other text when chunking is enabled,
+/// and split after ellipses when those are read as pauses.
+async fn generate_tts_segmented(
+    state: AppState,
+    req: TTSRequest,
+    segments: Vec<SpeechSegment>,
+) -> Result<Vec<u8>> {
+    let pieces = plan_pieces(
+        &req,
+        segments,
+        state.chunking_min_length,
+        state.ellipsis_pause.is_some(),
+    );
+
+    tracing::debug!("Split text into {} pieces", pieces.len());
+
+    generate_tts_pieces(state, req, pieces).await
+}
+
+/// Chunking settings for a request, honouring its `chunk_strategy`
+fn chunking_config(req: &TTSRequest) -> ChunkingConfig {
+    ChunkingConfig {
+        strategy: req.chunk_strategy,
+        ..Default::default()
+    }
+}
+
+/// Split the request text into chunks at the request's speed
+fn chunk_pieces(req: &TTSRequest) -> Vec<SpeechSegment> {
+    chunk_text(&req.text, &chunking_config(req))
+        .into_iter()
+        .map(|text| SpeechSegment {
+            text,
+            speed: req.speed(),
+            gain_db: 0.0,
+            pitch_scale: 1.0,
+            pause_after: None,
+        })
+        .collect()
+}
+
+/// Split speed-marked segments further where they are longer than `min_length`
+fn segment_pieces(
+    req: &TTSRequest,
+    segments: Vec<SpeechSegment>,
+    min_length: usize,
+) -> Vec<SpeechSegment> {
+    let config = chunking_config(req);
+    let mut pieces = Vec::new();
+
+    for segment in segments {
+        if should_chunk(req, &segment.text, min_length) {
+            let chunks = chunk_text(&segment.text, &config);
+            let last = chunks.len().saturating_sub(1);
+            pieces.extend(
+                chunks
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, text)| SpeechSegment {
+                        text,
+                        speed: segment.speed,
+                        gain_db: segment.gain_db,
+                        pitch_scale: segment.pitch_scale,
+                        pause_after: segment.pause_after.filter(|_| i == last),
+                    }),
+            );
+        } else {
+            pieces.push(segment);
+        }
+    }
+
+    pieces
+}
+
+/// Generate TTS and return it as multipart after a JSON part with the
+/// normalization result and chunk boundaries
+///
+/// The audio is synthesized from exactly the pieces reported as chunks.
+async fn generate_tts_debug(
+    state: AppState,
+    req: TTSRequest,
+    pieces: Vec<SpeechSegment>,
+) -> Result<Response> {
+    let result = prepare_text(&state, &req, &req.text);
+
+    let mut search_from = 0;
+    let chunks = pieces
+        .iter()
+        .enumerate()
+        .map(|(index, piece)| {
+            let start = req.text[search_from..]
+                .find(&piece.text)
+                .map(|pos| search_from + pos);
+            let end = start.map(|start| start + piece.text.len());
+            if let Some(end) = end {
+                search_from = end;
+            }
+
+            ChunkBoundary {
+                index,
+                text: piece.text.clone(),
+                normalized: prepare_text(&state, &req, &piece.text).normalized,
+                speed: piece.speed,
+                start,
+                end,
+            }
+        })
+        .collect();
+
+    let debug_info = TtsDebugInfo {
+        info: normalization::get_normalization_info(&result),
+        changes: normalization::describe_changes(&result),
+        original: result.original,
+        normalized: result.normalized,
+        char_mapping: result.char_mapping,
+        chunks,
+    };
+
+    let audio_data = generate_tts_pieces(state, req, pieces).await?;
+
+    let mut body = Vec::new();
+    body.extend_from_slice(&create_json_part(&debug_info)?);
+    body.extend_from_slice(&create_audio_part(audio_data));
+    body.extend_from_slice(create_boundary_end().as_bytes());
+
+    Ok((
+        [(
+            header::CONTENT_TYPE,
+            format!("multipart/mixed; boundary={}", MULTIPART_BOUNDARY),
+        )],
+        body,
+    )
+        .into_response())
+}
+
+/// Generate each piece in parallel at its own speed and concatenate the audio
+async fn generate_tts_pieces(
+    state: AppState,
+    req: TTSRequest,
+    pieces: Vec<SpeechSegment>,
+) -> Result<Vec<u8>> {
+    generate_tts_pieces_with_progress(state, req, pieces, || {}).await
+}
+
+/// Like `generate_tts_pieces`, calling `on_piece` as each piece completes
+async fn generate_tts_pieces_with_progress(
+    state: AppState,
+    req: TTSRequest,
+    pieces: Vec<SpeechSegment>,
+    on_piece: impl Fn(),
+) -> Result<Vec<u8>> {
+    let max_audio_duration = state.max_audio_duration;
+
+    let pauses = piece_pauses(&state, &pieces);
+    let gains: Vec<f32> = pieces.iter().map(|piece| piece.gain_db).collect();
+
     // Generate audio for each chunk in parallel
     let mut tasks = Vec::new();
+    let mut aborts = AbortOnDrop(Vec::new());
 
-    for (i, chunk) in chunks.into_iter().enumerate() {
+    for (i, piece) in pieces.into_iter().enumerate() {
+        let pitch = piece_pitch(&req, &piece);
         let chunk_req = TTSRequest {
-            text: chunk,
-            voice: req.voice.clone(),
-            speed: req.speed,
+            text: piece.text,
+            speed: Some(piece.speed),
+            pitch: Some(pitch),
             enable_chunking: false, // Don't recursively chunk
+            ..req.clone()
         };
         let state_clone = state.clone();
 
-        let task = tokio::spawn(async move {
+        let task = tokio::spawn(fair_queue::inherit_client(async move {
             tracing::debug!("Processing chunk {}", i);
             generate_tts_single(state_clone, chunk_req).await
-        });
+        }));
 
+        aborts.0.push(task.abort_handle());
         tasks.push(task);
     }
 
-    // Wait for all chunks to complete
+    // Wait for all chunks to complete, tracking the combined duration
     let mut audio_chunks = Vec::new();
+    let mut total_duration_ms = 0.0;
     for (i, task) in tasks.into_iter().enumerate() {
-        let audio_data = task.await??;
+        let mut audio_data = task.await??;
         tracing::debug!("Chunk {} completed", i);
+        on_piece();
+
+        // Applied after generation, so cached audio stays at its natural volume
+        if gains[i] != 0.0 {
+            audio_data = audio::wav_utils::apply_gain(&audio_data, gains[i])?;
+        }
+        if let Some(pause) = pauses[i] {
+            audio_data = audio::wav_utils::append_silence(&audio_data, pause)?;
+        }
+
+        total_duration_ms += audio::duration::calculate(&audio_data)?;
+        check_audio_duration(total_duration_ms, max_audio_duration)?;
+
         audio_chunks.push(audio_data);
     }
 
     // Concatenate all audio chunks
     tracing::debug!("Concatenating {} audio chunks", audio_chunks.len());
-    let combined_audio = audio::wav_utils::concatenate(audio_chunks)?;
+    let combined_audio =
+        audio::wav_utils::concatenate_with(audio_chunks, state.concat_sample_rate)?;
     Ok(combined_audio)
 }
 
+/// Aborts a request's chunk tasks if it is dropped before they finish,
+/// so a cancelled request stops taking engines from the pool
+struct AbortOnDrop(Vec<tokio::task::AbortHandle>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        for task in &self.0 {
+            task.abort();
+        }
+    }
+}
+
+/// Generate each piece as its own WAV and stream them back as a zip
+///
+/// Entries are numbered in reading order and followed by `manifest.json`.
+/// Files include any pause after their chunk, so played back to back they
+/// sound like `/tts`. The first chunk is generated before responding, so a
+/// request that fails outright still gets an error status.
+async fn generate_tts_archive(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(mut req): Json<TTSRequest>,
+) -> Result<Response> {
+    let segments = validate_tts_request(&state, &mut req, key_max_text_length(&state, &headers))?;
+    let pieces = plan_pieces(
+        &req,
+        segments,
+        state.chunking_min_length,
+        state.ellipsis_pause.is_some(),
+    );
+    let disposition = filename::content_disposition(req.filename.as_deref(), &req.voice, "zip");
+    let max_audio_duration = state.max_audio_duration;
+
+    // Generate audio for each chunk in parallel
+    let pauses = piece_pauses(&state, &pieces);
+    let mut chunks = Vec::new();
+    for (piece, pause) in pieces.into_iter().zip(pauses) {
+        let chunk_req = TTSRequest {
+            text: piece.text.clone(),
+            speed: Some(piece.speed),
+            pitch: Some(piece_pitch(&req, &piece)),
+            enable_chunking: false,
+            ..req.clone()
+        };
+        let task = tokio::spawn(fair_queue::inherit_client(generate_tts_single(
+            state.clone(),
+            chunk_req,
+        )));
+        chunks.push((piece, pause, task));
+    }
+
+    let mut archive = ArchiveBuilder::new(&req.voice);
+    let mut chunks = chunks.into_iter();
+    let first = match chunks.next() {
+        Some(chunk) => next_archive_entry(&mut archive, chunk, max_audio_duration).await?,
+        None => Vec::new(),
+    };
+
+    let (tx, rx) = tokio::sync::mpsc::channel::<std::result::Result<Vec<u8>, std::io::Error>>(4);
+    tokio::spawn(async move {
+        if tx.send(Ok(first)).await.is_err() {
+            return;
+        }
+        for chunk in chunks {
+            let entry = next_archive_entry(&mut archive, chunk, max_audio_duration).await;
+            let failed = entry.is_err();
+            if let Err(ref e) = entry {
+                tracing::error!("Archive chunk failed: {}", e);
+            }
+            // Stop once the client is gone or the archive can't be completed
+            if tx.send(entry.map_err(std::io::Error::other)).await.is_err() || failed {
+                return;
+            }
+        }
+        let _ = tx
+            .send(archive.finish().map_err(std::io::Error::other))
+            .await;
+    });
+
+    Response::builder()
+        .header(header::CONTENT_TYPE, "application/zip")
+        .header(header::CONTENT_DISPOSITION, disposition)
+        .body(Body::from_stream(
+            tokio_stream::wrappers::ReceiverStream::new(rx),
+        ))
+        .map_err(|e| TtsError::Unknown(e.to_string()))
+}
+
+/// Wait for a chunk of `/tts/archive` and add it to the archive, returning
+/// its zip entry
+async fn next_archive_entry(
+    archive: &mut ArchiveBuilder,
+    (piece, pause, task): (
+        SpeechSegment,
+        Option<Duration>,
+        tokio::task::JoinHandle<Result<Vec<u8>>>,
+    ),
+    max_audio_duration: Duration,
+) -> Result<Vec<u8>> {
+    let mut audio_data = task.await??;
+
+    // Applied after generation, so cached audio stays at its natural volume
+    if piece.gain_db != 0.0 {
+        audio_data = audio::wav_utils::apply_gain(&audio_data, piece.gain_db)?;
+    }
+    if let Some(pause) = pause {
+        audio_data = audio::wav_utils::append_silence(&audio_data, pause)?;
+    }
+
+    let entry = archive.add_chunk(&piece.text, &audio_data)?;
+    check_audio_duration(archive.total_duration_ms(), max_audio_duration)?;
+    Ok(entry)
+}
+
+/// Reject generated audio longer than the configured maximum
+fn check_audio_duration(duration_ms: f64, max_duration: Duration) -> Result<()> {
+    let max_ms = max_duration.as_millis() as f64;
+    if duration_ms > max_ms {
+        tracing::warn!(
+            "Generated audio exceeds limit: {:.0}ms (max {:.0}ms)",
+            duration_ms,
+            max_ms
+        );
+        return Err(TtsError::InvalidRequest(format!(
+            "Generated audio too long: {:.0} ms (max {:.0} ms)",
+            duration_ms, max_ms
+        )));
+    }
+    Ok(())
+}
+
 /// List all available voices
 async fn list_voices() -> Json<VoicesResponse> {
     let voices = Voice::all()
@@ -180,6 +1275,41 @@ async fn list_voices() -> Json<VoicesResponse> {
     Json(VoicesResponse { voices })
 }
 
+/// Resolve a voice mix and synthesize a short sample of it
+///
+/// Lets a UI preview a blend while its weights are being adjusted.
+async fn preview_voice_mix(
+    State(state): State<AppState>,
+    Json(req): Json<VoiceMixPreviewRequest>,
+) -> Result<Json<VoiceMixPreviewResponse>> {
+    let mix = parse_voice_mix(&state, &req.voice, req.voice_mix.as_deref())?.ok_or_else(|| {
+        TtsError::InvalidRequest(
+            "Send a voice mix as `voice_mix`, or as a `voice` joining voices with '+'".to_string(),
+        )
+    })?;
+
+    let sample_req = TTSRequest {
+        text: MIX_PREVIEW_TEXT.to_string(),
+        voice: mix.style_name(),
+        ..Default::default()
+    };
+    let temp_file = synthesize_to_file(&state, &sample_req, MIX_PREVIEW_TEXT.to_string()).await?;
+    let audio_data = temp_file.read().await?;
+
+    Ok(Json(VoiceMixPreviewResponse {
+        voice: sample_req.voice,
+        weights: mix
+            .parts()
+            .iter()
+            .map(|(voice, weight)| VoiceWeight {
+                voice: voice.id().to_string(),
+                weight: *weight,
+            })
+            .collect(),
+        audio_base64: base64::encode(&audio_data),
+    }))
+}
+
 /// Health check endpoint
 async fn health_check() -> Json<HealthResponse> {
     Json(HealthResponse {
@@ -188,40 +1318,604 @@ async fn health_check() -> Json<HealthResponse> {
     })
 }
 
-/// Pool statistics endpoint
+/// Readiness probe: 503 until every dependency synthesis needs is usable
+///
+/// Unlike `/health`, this fails while the circuit breaker is open or the
+/// configured espeak-ng data is missing.
+async fn readiness_check(State(state): State<AppState>) -> Response {
+    let checks = vec![engines_check(&state), espeak_data_check(&state)];
+    let ready = checks.iter().all(|check| check.ok);
+
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    let body = ReadinessResponse {
+        status: if ready { "ready" } else { "not_ready" }.to_string(),
+        checks,
+    };
+    (status, Json(body)).into_response()
+}
+
+fn engines_check(state: &AppState) -> ReadinessCheck {
+    let stats = state.tts_pool.stats();
+    let detail = if stats.breaker_state == BreakerState::Open {
+        "circuit breaker is open after repeated engine failures".to_string()
+    } else {
+        format!("{} engines loaded", stats.pool_size)
+    };
+    ReadinessCheck {
+        name: "engines".to_string(),
+        ok: stats.breaker_state != BreakerState::Open,
+        detail: Some(detail),
+    }
+}
+
+fn espeak_data_check(state: &AppState) -> ReadinessCheck {
+    let (ok, detail) = match &state.espeak_data_dir {
+        Some(dir) => match check_espeak_data(dir) {
+            Ok(()) => (true, dir.display().to_string()),
+            Err(e) => (false, e.to_string()),
+        },
+        None => (true, "using espeak-ng's built-in data path".to_string()),
+    };
+    ReadinessCheck {
+        name: "espeak_data".to_string(),
+        ok,
+        detail: Some(detail),
+    }
+}
+
+/// Pool statistics endpoint
 async fn pool_stats(State(state): State<AppState>) -> Json<PoolStatsResponse> {
     let stats = state.tts_pool.stats();
+    let generation = &state.generation_stats;
     Json(PoolStatsResponse {
         pool_size: stats.pool_size,
         active_requests: stats.active_requests,
         available_engines: stats.available_engines,
         total_requests: stats.total_requests,
+        uptime_seconds: generation.uptime().as_secs(),
+        total_generations: generation.total_generations(),
+        total_audio_seconds: generation.total_audio_seconds(),
+        average_generation_ms: generation.average_generation_ms(),
+        circuit_breaker: CircuitBreakerStats {
+            state: stats.breaker_state.as_str().to_string(),
+            consecutive_failures: stats.consecutive_failures,
+            times_opened: stats.breaker_opened,
+        },
+        by_voice: generation
+            .by_voice()
+            .into_iter()
+            .map(|(voice, counts)| {
+                let stats = VoiceStats {
+                    generations: counts.generations,
+                    audio_seconds: counts.audio_ms as f64 / 1000.0,
+                };
+                (voice, stats)
+            })
+            .collect(),
+    })
+}
+
+/// Reload the TTS engines from the model and voices files
+///
+/// The new engines are swapped in once they have all loaded; requests already
+/// running finish on the old engines.
+async fn reload_pool(State(state): State<AppState>) -> Result<Json<ReloadResponse>> {
+    let start = std::time::Instant::now();
+    let pool_size = state.tts_pool.reload().await?;
+
+    Ok(Json(ReloadResponse {
+        status: "ok".to_string(),
+        pool_size,
+        duration_ms: start.elapsed().as_millis() as u64,
+    }))
+}
+
+/// Report the effective configuration, as resolved from the config file,
+/// environment and defaults
+///
+/// API keys are counted, never listed, and credential-like response header
+/// values are redacted.
+async fn admin_config(State(state): State<AppState>) -> Json<ConfigResponse> {
+    Json(ConfigResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        settings: (*state.config).clone(),
+        voice_aliases: (*state.voice_aliases).clone(),
+        auth: AuthConfigSection {
+            enabled: state.api_keys.is_enabled(),
+            key_count: state.api_keys.count(),
+        },
+        runtime: RuntimeConfigSection {
+            pool_size: state.tts_pool.stats().pool_size,
+            rate_limit_mode: state
+                .rate_limiter
+                .as_ref()
+                .map(|limiter| limiter.mode_description().to_lowercase()),
+            max_in_flight_requests: state
+                .concurrency_limiter
+                .as_ref()
+                .map(|limiter| limiter.max_in_flight()),
+            max_text_length: MAX_TEXT_LENGTH,
+            supported_formats: SUPPORTED_AUDIO_FORMATS.to_vec(),
+            cors_allowed_origins: state.cors.origins_description(),
+            fallback_voice: state.fallback_voice.clone(),
+        },
     })
 }
 
 /// Generate TTS audio with multipart streaming response
+///
+/// With `?merge=true`, or an `Accept` header asking for `audio/wav` only, the
+/// chunks are joined into a single WAV for clients that can't read multipart.
 async fn generate_tts_stream(
     State(state): State<AppState>,
+    Query(query): Query<StreamQuery>,
+    headers: HeaderMap,
     Json(req): Json<TTSRequest>,
 ) -> Result<Response> {
-    crate::services::streaming::generate_tts_stream(state, req).await
+    stream_tts_request(state, query, &headers, req).await
+}
+
+/// Stream the audio for a text file uploaded as `multipart/form-data`
+///
+/// The `file` part holds the text; optional `voice` and `speed` fields are
+/// used as in `/tts/stream`, and so are its query parameters.
+async fn generate_tts_file(
+    State(state): State<AppState>,
+    Query(query): Query<StreamQuery>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<Response> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let boundary = form_data::boundary(content_type).ok_or_else(|| {
+        TtsError::UnsupportedMediaType(format!(
+            "expected multipart/form-data, got \"{}\"",
+            content_type
+        ))
+    })?;
+
+    let body = axum::body::to_bytes(body, MAX_UPLOAD_BODY_BYTES)
+        .await
+        .map_err(|_| {
+            TtsError::PayloadTooLarge(format!(
+                "request body exceeds {} bytes",
+                MAX_UPLOAD_BODY_BYTES
+            ))
+        })?;
+    let parts = form_data::parse(&body, &boundary)
+        .map_err(|e| TtsError::InvalidRequest(format!("Malformed form data: {}", e)))?;
+
+    let req = file_upload_request(&parts, key_max_text_length(&state, &headers))?;
+    stream_tts_request(state, query, &headers, req).await
+}
+
+/// Build a TTS request from the fields of a `/tts/file` upload
+fn file_upload_request(parts: &[FormPart], max_text_length: usize) -> Result<TTSRequest> {
+    let field = |name: &str| parts.iter().find(|part| part.name == name);
+    let text_field = |name: &str| -> Result<Option<String>> {
+        field(name)
+            .map(|part| {
+                std::str::from_utf8(&part.data)
+                    .map(|value| value.trim().to_string())
+                    .map_err(|_| TtsError::InvalidRequest(format!("{} is not valid UTF-8", name)))
+            })
+            .transpose()
+    };
+
+    let file =
+        field("file").ok_or_else(|| TtsError::InvalidRequest("Missing file field".to_string()))?;
+
+    // Browsers and curl label .txt uploads text/plain; no type is taken as text too
+    let content_type = file.content_type.as_deref().unwrap_or("text/plain");
+    if !content_type
+        .trim_start()
+        .to_ascii_lowercase()
+        .starts_with("text/")
+    {
+        return Err(TtsError::UnsupportedMediaType(format!(
+            "file must be text, got \"{}\"",
+            content_type
+        )));
+    }
+    if file.data.len() > max_text_length {
+        return Err(TtsError::PayloadTooLarge(format!(
+            "file is {} bytes, the limit is {}",
+            file.data.len(),
+            max_text_length
+        )));
+    }
+    let text = std::str::from_utf8(&file.data)
+        .map_err(|_| TtsError::InvalidRequest("file is not valid UTF-8 text".to_string()))?;
+
+    let mut req = TTSRequest {
+        text: text.trim_start_matches('\u{feff}').to_string(),
+        ..TTSRequest::default()
+    };
+    if let Some(voice) = text_field("voice")?.filter(|v| !v.is_empty()) {
+        req.voice = voice;
+    }
+    if let Some(speed) = text_field("speed")?.filter(|v| !v.is_empty()) {
+        req.speed = Some(
+            speed
+                .parse()
+                .map_err(|_| TtsError::InvalidRequest(format!("Invalid speed: {}", speed)))?,
+        );
+    }
+    Ok(req)
+}
+
+/// Respond with multipart or merged audio for `/tts/stream` and `/tts/file`
+async fn stream_tts_request(
+    state: AppState,
+    query: StreamQuery,
+    headers: &HeaderMap,
+    req: TTSRequest,
+) -> Result<Response> {
+    select_audio_format(headers, req.format, state.default_audio_format)?;
+
+    let max_text_length = key_max_text_length(&state, headers);
+    if query.merge || accepts_only_wav(headers) {
+        return generate_tts_merged(state, req, max_text_length).await;
+    }
+
+    let requested = headers
+        .get(METADATA_VERSION_HEADER)
+        .map(|v| v.to_str().unwrap_or("(non-ASCII)"));
+    let metadata_version = MetadataVersion::parse(requested)?;
+
+    crate::services::streaming::generate_tts_stream(
+        state,
+        req,
+        metadata_version,
+        query.progress,
+        max_text_length,
+    )
+    .await
+}
+
+/// Raw `Accept` header value, if present and readable
+fn accept_header(headers: &HeaderMap) -> Option<&str> {
+    headers.get(header::ACCEPT).and_then(|v| v.to_str().ok())
+}
+
+/// Media types listed in the `Accept` header, lowercased and without parameters
+///
+/// Types refused with `q=0` are left out.
+fn accepted_media_types(headers: &HeaderMap) -> Vec<String> {
+    accept_header(headers)
+        .map(accept::parse_accept)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|range| range.quality > 0.0)
+        .map(|range| range.media_type)
+        .collect()
+}
+
+/// Choose the audio encoding: the `format` field if given, else `Accept`
+///
+/// Falls back to the server default when `Accept` is absent, `*/*` or names
+/// no audio type; audio types that can't be produced get a 406.
+fn select_audio_format(
+    headers: &HeaderMap,
+    explicit: Option<AudioFormat>,
+    default: AudioFormat,
+) -> Result<AudioFormat> {
+    if let Some(format) = explicit {
+        ensure_supported_format(format)?;
+        return Ok(format);
+    }
+
+    let accept = accept_header(headers);
+    accept::negotiate_audio_format(accept, SUPPORTED_AUDIO_FORMATS, default).ok_or_else(|| {
+        TtsError::NotAcceptable(format!(
+            "cannot produce any of \"{}\" (supported: {})",
+            accept.unwrap_or_default(),
+            supported_formats(AudioFormat::mime_type)
+        ))
+    })
+}
+
+/// Reject an explicitly requested format the server can't encode
+fn ensure_supported_format(format: AudioFormat) -> Result<()> {
+    if SUPPORTED_AUDIO_FORMATS.contains(&format) {
+        Ok(())
+    } else {
+        Err(TtsError::InvalidRequest(format!(
+            "Unsupported format: {} (supported: {})",
+            format.as_str(),
+            supported_formats(AudioFormat::as_str)
+        )))
+    }
+}
+
+fn supported_formats(name: fn(&AudioFormat) -> &'static str) -> String {
+    SUPPORTED_AUDIO_FORMATS
+        .iter()
+        .map(name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Whether the `Accept` header lists `audio/wav` and no multipart type
+fn accepts_only_wav(headers: &HeaderMap) -> bool {
+    let media_types = accepted_media_types(headers);
+
+    media_types.iter().any(|t| t == "audio/wav")
+        && !media_types.iter().any(|t| t.starts_with("multipart/"))
+}
+
+/// Whether the `Accept` header lists `application/json` and not `audio/wav`
+fn accepts_json(headers: &HeaderMap) -> bool {
+    let media_types = accepted_media_types(headers);
+
+    media_types.iter().any(|t| t == "application/json")
+        && !media_types.iter().any(|t| t == "audio/wav")
+}
+
+/// The text actually spoken, without any speed markup tags
+fn spoken_text(segments: &[SpeechSegment]) -> String {
+    segments
+        .iter()
+        .map(|segment| segment.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Validate the phrase timings for generated audio, returning the warning count
+///
+/// Each warning is logged; clients only see the count.
+fn count_validation_warnings(
+    audio_data: &[u8],
+    norm_result: &NormalizationResult,
+) -> Result<usize> {
+    let metadata = metadata_builder::build_metadata_for_version(
+        audio_data,
+        norm_result.clone(),
+        0,
+        0.0,
+        MetadataVersion::V2,
+        DEFAULT_MAX_PHRASE_WORDS,
+    )?;
+
+    let warnings = metadata
+        .validation
+        .map(|validation| validation.warnings)
+        .unwrap_or_default();
+    for warning in &warnings {
+        tracing::warn!(
+            "Validation warning ({}) at phrase {}: {}",
+            warning.warning_type,
+            warning.phrase_index,
+            warning.message
+        );
+    }
+
+    Ok(warnings.len())
+}
+
+/// Generate a `/tts` request as base64 WAV plus phrase metadata in one JSON body
+///
+/// The audio is generated in memory exactly as the WAV response would be.
+/// `metadata_only` requests get the metadata alone and the audio is dropped.
+async fn generate_tts_json(
+    state: AppState,
+    req: TTSRequest,
+    segments: Vec<SpeechSegment>,
+) -> Result<Response> {
+    // Metadata describes the spoken text, without any speed markup tags
+    let norm_result = prepare_text(&state, &req, &spoken_text(&segments));
+
+    let metadata_only = req.metadata_only;
+    let info = (!metadata_only && req.metadata.unwrap_or(state.wav_metadata))
+        .then(|| audio::wav_metadata::request_info(&req.text, &req.voice, req.speed()));
+    let max_phrase_words = req.max_phrase_words();
+    let min_phrase_ms = req.min_phrase_ms;
+    let waveform_points = req.waveform_points;
+
+    let pieces = plan_pieces(
+        &req,
+        segments,
+        state.chunking_min_length,
+        state.ellipsis_pause.is_some(),
+    );
+    let mut audio_data = generate_tts_pieces(state, req, pieces).await?;
+    if let Some(info) = info {
+        audio_data = audio::wav_metadata::embed_info(&audio_data, &info)?;
+    }
+
+    let mut metadata = metadata_builder::build_metadata_for_version(
+        &audio_data,
+        norm_result,
+        0,
+        0.0,
+        MetadataVersion::default(),
+        max_phrase_words,
+    )?;
+    metadata_builder::apply_min_phrase_duration(&mut metadata, min_phrase_ms);
+    metadata_builder::attach_waveform(&mut metadata, &audio_data, waveform_points)?;
+
+    if metadata_only {
+        return Ok(Json(TtsMetadataResponse {
+            timing: TimingSource::Synthesized,
+            metadata,
+        })
+        .into_response());
+    }
+
+    Ok(Json(TtsJsonResponse {
+        audio_base64: base64::encode(&audio_data),
+        metadata,
+    })
+    .into_response())
+}
+
+/// Generate a streaming request as one concatenated WAV
+///
+/// The text is chunked and synthesized in parallel as for multipart
+/// streaming; only the response format differs.
+async fn generate_tts_merged(
+    state: AppState,
+    mut req: TTSRequest,
+    max_text_length: usize,
+) -> Result<Response> {
+    let segments = validate_tts_request(&state, &mut req, max_text_length)?;
+    let mut pieces = if is_segmented(&req, &segments) {
+        segment_pieces(&req, segments, state.chunking_min_length)
+    } else {
+        chunk_pieces(&req)
+    };
+    if state.ellipsis_pause.is_some() {
+        pieces = split_pieces_at_ellipses(pieces);
+    }
+
+    tracing::debug!("Merging {} streamed chunks into one WAV", pieces.len());
+
+    Ok(wav_response(generate_tts_pieces(state, req, pieces).await?))
+}
+
+/// Queue a `/tts` request for background synthesis
+///
+/// The request is validated up front, so bad input still fails with 400.
+/// Responds 202 with the job's status and its URL in `Location`.
+async fn submit_tts_job(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(mut req): Json<TTSRequest>,
+) -> Result<Response> {
+    let segments = validate_tts_request(&state, &mut req, key_max_text_length(&state, &headers))?;
+    let pieces = plan_pieces(
+        &req,
+        segments,
+        state.chunking_min_length,
+        state.ellipsis_pause.is_some(),
+    );
+    let id = state
+        .jobs
+        .create(pieces.len())
+        .ok_or(TtsError::JobQueueFull)?;
+
+    tracing::info!("Queued TTS job {} ({} chunks)", id, pieces.len());
+    tokio::spawn(fair_queue::inherit_client(run_tts_job(
+        state.clone(),
+        id.clone(),
+        req,
+        pieces,
+    )));
+
+    let status = state
+        .jobs
+        .status(&id)
+        .ok_or_else(|| TtsError::JobNotFound(id.clone()))?;
+    Ok((
+        StatusCode::ACCEPTED,
+        [(header::LOCATION, format!("/tts/jobs/{}", id))],
+        Json(status),
+    )
+        .into_response())
+}
+
+/// Synthesize a queued job once a slot is free and store the result
+///
+/// Cancelling the job drops its synthesis: chunks not yet started are aborted
+/// and their temp files removed.
+async fn run_tts_job(state: AppState, id: String, req: TTSRequest, pieces: Vec<SpeechSegment>) {
+    let Some(cancel) = state.jobs.cancellation(&id) else {
+        return;
+    };
+    let _slot = tokio::select! {
+        biased;
+        _ = cancel.cancelled() => return,
+        slot = state.jobs.start(&id) => slot,
+    };
+
+    let info = req
+        .metadata
+        .unwrap_or(state.wav_metadata)
+        .then(|| audio::wav_metadata::request_info(&req.text, &req.voice, req.speed()));
+    let jobs = state.jobs.clone();
+    let synthesis = generate_tts_pieces_with_progress(state, req, pieces, || jobs.chunk_done(&id));
+    let result = tokio::select! {
+        biased;
+        _ = cancel.cancelled() => {
+            tracing::info!("TTS job {} cancelled", id);
+            return;
+        }
+        result = synthesis => result,
+    };
+    let result = result.and_then(|audio_data| match info {
+        Some(info) => audio::wav_metadata::embed_info(&audio_data, &info),
+        None => Ok(audio_data),
+    });
+
+    match &result {
+        Ok(_) => tracing::info!("TTS job {} finished", id),
+        Err(e) => tracing::warn!("TTS job {} failed: {}", id, e),
+    }
+    jobs.finish(&id, result.map_err(|e| e.to_string()));
+}
+
+/// Status and progress of a background job
+async fn tts_job_status(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<JobResponse>> {
+    state
+        .jobs
+        .status(&id)
+        .map(Json)
+        .ok_or(TtsError::JobNotFound(id))
+}
+
+/// Cancel a queued or running background job
+async fn cancel_tts_job(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<JobResponse>> {
+    match state.jobs.cancel(&id) {
+        Ok(status) => Ok(Json(status)),
+        Err(JobCancelError::NotFound) => Err(TtsError::JobNotFound(id)),
+        Err(JobCancelError::Finished(status)) => Err(TtsError::JobFinished(format!(
+            "job {} is {}",
+            id,
+            status.as_str()
+        ))),
+    }
+}
+
+/// The finished audio of a background job
+async fn tts_job_audio(State(state): State<AppState>, Path(id): Path<String>) -> Result<Response> {
+    match state.jobs.audio(&id) {
+        Ok(audio_data) => Ok(([(header::CONTENT_TYPE, "audio/wav")], audio_data).into_response()),
+        Err(JobAudioError::NotFound) => Err(TtsError::JobNotFound(id)),
+        Err(JobAudioError::NotFinished(status)) => Err(TtsError::JobUnavailable(format!(
+            "job {} is {}",
+            id,
+            status.as_str()
+        ))),
+        Err(JobAudioError::Failed(error)) => Err(TtsError::JobUnavailable(format!(
+            "job {} failed: {}",
+            id, error
+        ))),
+    }
 }
 
 /// Create and configure the HTTP server router
 pub fn create_router(state: AppState) -> Router<()> {
-    // Configure CORS to allow all origins (adjust as needed for production)
+    // Configure CORS from settings (defaults to allowing any origin)
     // Expose headers needed for streaming responses (multipart/mixed with chunked encoding)
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods(Any)
-        .allow_headers(Any)
-        .expose_headers(Any); // Expose all response headers for streaming compatibility
+    let cors = state.cors.layer();
 
     // Clone api_keys for middleware
     let api_keys_for_middleware = state.api_keys.clone();
 
-    // Get timeout duration from state
-    let timeout_duration = state.request_timeout;
+    // Timeouts from state; requests may ask for longer, up to the max
+    let timeouts = RequestTimeouts::new(state.request_timeout, state.max_request_timeout);
 
     // Create static file service for audio samples
     // Samples directory is resolved using smart path resolution (similar to models)
@@ -229,14 +1923,54 @@ pub fn create_router(state: AppState) -> Router<()> {
     let samples_dir = get_samples_dir();
     tracing::debug!("Serving samples from: {:?}", samples_dir);
     let samples_service = ServeDir::new(samples_dir).append_index_html_on_directories(false);
+    let samples = Router::new()
+        .nest_service("/samples", samples_service)
+        .layer(middleware::map_response(
+            response_headers::samples_cache_headers,
+        ));
+    let response_headers = state.response_headers.clone();
 
     let mut router = Router::new()
         .route("/tts", post(generate_tts))
         .route("/tts/stream", post(generate_tts_stream))
+        .route("/tts/archive", post(generate_tts_archive))
+        .route("/tts/file", post(generate_tts_file))
+        .route("/voices/mix/preview", post(preview_voice_mix));
+
+    // Cap in-flight generation requests (route_layer only covers the routes above)
+    if let Some(limiter) = state.concurrency_limiter.clone() {
+        router = router.route_layer(middleware::from_fn_with_state(
+            limiter,
+            crate::concurrency::concurrency_limit_middleware,
+        ));
+    }
+
+    router = router
+        .route("/tts/estimate", post(estimate_tts))
+        .route("/tts/phonemes", post(phonemize_tts))
+        .route("/tts/jobs", post(submit_tts_job))
+        .route("/tts/jobs/:id", get(tts_job_status).delete(cancel_tts_job))
+        .route("/tts/jobs/:id/audio", get(tts_job_audio))
         .route("/voices", get(list_voices))
         .route("/health", get(health_check))
+        .route("/health/ready", get(readiness_check))
         .route("/stats", get(pool_stats))
-        .nest_service("/samples", samples_service);
+        .merge(samples);
+
+    // Admin endpoints are only exposed when API key authentication is enabled
+    if state.api_keys.is_enabled() {
+        router = router
+            .route("/admin/reload", post(reload_pool))
+            .route("/admin/config", get(admin_config));
+    }
+
+    // Queue engine acquisitions per client (inside auth, so keys are checked)
+    if let Some(queue) = state.tts_pool.fair_queue() {
+        router = router.layer(middleware::from_fn_with_state(
+            queue,
+            crate::fair_queue::client_key_middleware,
+        ));
+    }
 
     // Apply rate limiting only if API keys are enabled
     if let Some(rate_limiter) = state.rate_limiter.clone() {
@@ -252,11 +1986,26 @@ pub fn create_router(state: AppState) -> Router<()> {
         crate::auth::auth_middleware,
     ));
 
-    // Apply timeout layer to prevent long-running requests from exhausting resources
-    router
+    // Apply timeout layer to prevent long-running requests from exhausting resources.
+    // It only bounds the time until the response starts, so `/tts/stream`, which
+    // responds before synthesizing, is never cut off once parts are flowing.
+    let router = router
         .with_state(state)
         .layer(cors)
-        .layer(TimeoutLayer::new(timeout_duration))
+        .layer(middleware::from_fn_with_state(
+            timeouts,
+            crate::request_timeout::request_timeout_middleware,
+        ));
+
+    // Outermost, so configured headers also reach errors from the layers above
+    if response_headers.is_empty() {
+        router
+    } else {
+        router.layer(middleware::from_fn_with_state(
+            response_headers,
+            response_headers::response_headers_middleware,
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -283,9 +2032,11 @@ mod tests {
         }
 
         // Validate speed is reasonable
-        if req.speed <= 0.0 || req.speed > 3.0 {
-            return Err(TtsError::InvalidSpeed(req.speed));
-        }
+        resolve_speed(
+            req.speed(),
+            crate::config::constants::DEFAULT_MIN_SPEED,
+            false,
+        )?;
 
         Ok(())
     }
@@ -295,8 +2046,9 @@ mod tests {
         let req = TTSRequest {
             text: "".to_string(),
             voice: "af_heart".to_string(),
-            speed: 1.0,
+            speed: Some(1.0),
             enable_chunking: false,
+            ..Default::default()
         };
 
         let result = validate_tts_request(&req);
@@ -313,8 +2065,9 @@ mod tests {
         let req = TTSRequest {
             text: "   \n\t  ".to_string(),
             voice: "af_heart".to_string(),
-            speed: 1.0,
+            speed: Some(1.0),
             enable_chunking: false,
+            ..Default::default()
         };
 
         let result = validate_tts_request(&req);
@@ -334,8 +2087,9 @@ mod tests {
         let req = TTSRequest {
             text: long_text,
             voice: "af_heart".to_string(),
-            speed: 1.0,
+            speed: Some(1.0),
             enable_chunking: false,
+            ..Default::default()
         };
 
         let result = validate_tts_request(&req);
@@ -359,8 +2113,9 @@ mod tests {
         let req = TTSRequest {
             text,
             voice: "af_heart".to_string(),
-            speed: 1.0,
+            speed: Some(1.0),
             enable_chunking: false,
+            ..Default::default()
         };
 
         let result = validate_tts_request(&req);
@@ -375,8 +2130,9 @@ mod tests {
         let req = TTSRequest {
             text,
             voice: "af_heart".to_string(),
-            speed: 1.0,
+            speed: Some(1.0),
             enable_chunking: false,
+            ..Default::default()
         };
 
         let result = validate_tts_request(&req);
@@ -400,8 +2156,9 @@ mod tests {
             let req = TTSRequest {
                 text,
                 voice: "af_heart".to_string(),
-                speed: 1.0,
+                speed: Some(1.0),
                 enable_chunking: false,
+                ..Default::default()
             };
 
             let result = validate_tts_request(&req);
@@ -436,8 +2193,9 @@ mod tests {
         let req = TTSRequest {
             text: long_text,
             voice: "af_heart".to_string(),
-            speed: 1.0,
+            speed: Some(1.0),
             enable_chunking: true, // Chunking enabled
+            ..Default::default()
         };
 
         let result = validate_tts_request(&req);
@@ -457,6 +2215,9 @@ mod tests {
         let test_cases = vec![
             (0.0, false),  // Zero speed
             (-1.0, false), // Negative speed
+            (0.01, false), // Positive, but pathologically slow
+            (0.1, false),  // Below the default minimum
+            (0.25, true),  // Default minimum speed
             (0.5, true),   // Valid low speed
             (1.0, true),   // Normal speed
             (2.0, true),   // Valid high speed
@@ -469,8 +2230,9 @@ mod tests {
             let req = TTSRequest {
                 text: "Test text".to_string(),
                 voice: "af_heart".to_string(),
-                speed,
+                speed: Some(speed),
                 enable_chunking: false,
+                ..Default::default()
             };
 
             let result = validate_tts_request(&req);
@@ -480,7 +2242,7 @@ mod tests {
             } else {
                 assert!(result.is_err(), "Speed {} should be invalid", speed);
                 match result.unwrap_err() {
-                    TtsError::InvalidSpeed(_) => {} // Expected
+                    TtsError::InvalidSpeed { .. } => {} // Expected
                     other => panic!(
                         "Expected InvalidSpeed error for speed {}, got: {:?}",
                         speed, other
@@ -600,4 +2362,2677 @@ mod tests {
             assert_eq!(timeout.as_secs(), timeout.as_secs());
         }
     }
+
+    // ===== Audio Duration Guard Tests =====
+
+    fn state_with_max_duration(
+        clip_ms: u32,
+        max_ms: u64,
+    ) -> (AppState, std::sync::Arc<crate::kokoro::fake::FakeEngineLog>) {
+        let (pool, log) = crate::kokoro::fake::fake_pool(2, clip_ms, Duration::ZERO);
+        let state = AppState {
+            max_audio_duration: Duration::from_millis(max_ms),
+            ..AppState::for_tests(pool)
+        };
+        (state, log)
+    }
+
+    #[test]
+    fn test_check_audio_duration() {
+        assert!(check_audio_duration(999.0, Duration::from_secs(1)).is_ok());
+        assert!(check_audio_duration(1000.0, Duration::from_secs(1)).is_ok());
+        assert!(matches!(
+            check_audio_duration(1001.0, Duration::from_secs(1)),
+            Err(TtsError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_normalized_length_is_capped() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let state = AppState {
+            max_normalized_length: 20_000,
+            ..AppState::for_tests(pool)
+        };
+
+        // Under the raw limit, but over three times as long once spelled out
+        let mut exploding = TTSRequest {
+            text: "$1 ".repeat(3_000),
+            ..Default::default()
+        };
+        assert!(exploding.text.len() < MAX_TEXT_LENGTH);
+        match super::validate_tts_request(&state, &mut exploding, MAX_TEXT_LENGTH) {
+            Err(TtsError::InvalidRequest(msg)) => {
+                assert!(msg.contains("Normalized text too long"), "{}", msg)
+            }
+            other => panic!("Expected InvalidRequest, got: {:?}", other),
+        }
+
+        let mut ordinary = TTSRequest {
+            text: "It costs $5 today. ".repeat(450),
+            ..Default::default()
+        };
+        assert!(super::validate_tts_request(&state, &mut ordinary, MAX_TEXT_LENGTH).is_ok());
+    }
+
+    #[test]
+    fn test_full_length_text_fits_default_normalized_limit() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let state = AppState {
+            max_normalized_length: crate::config::constants::DEFAULT_MAX_NORMALIZED_LENGTH,
+            ..AppState::for_tests(pool)
+        };
+
+        // Numbers, money and dates all grow when spelled out
+        let sentence = "On May 5, 2024 the 3 of us paid $12.50 for 2 tickets to Dr. Smith's talk. ";
+        let mut req = TTSRequest {
+            text: sentence.repeat(MAX_TEXT_LENGTH / sentence.len() + 1)[..MAX_TEXT_LENGTH]
+                .to_string(),
+            ..Default::default()
+        };
+        assert!(super::validate_tts_request(&state, &mut req, MAX_TEXT_LENGTH).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_single_rejects_over_long_audio() {
+        let (state, _) = state_with_max_duration(2000, 1000);
+        let req = TTSRequest {
+            text: "Hello".to_string(),
+            ..Default::default()
+        };
+
+        match generate_tts_single(state, req).await {
+            Err(TtsError::InvalidRequest(msg)) => assert!(msg.contains("too long")),
+            other => panic!("Expected InvalidRequest, got: {:?}", other.map(|a| a.len())),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_single_accepts_audio_within_limit() {
+        let (state, _) = state_with_max_duration(500, 1000);
+        let req = TTSRequest {
+            text: "Hello".to_string(),
+            ..Default::default()
+        };
+
+        let audio = generate_tts_single(state, req).await.unwrap();
+        let duration = audio::duration::calculate(&audio).unwrap();
+        assert!((duration - 500.0).abs() < 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_chunked_rejects_cumulative_over_long_audio() {
+        // Each chunk is under the limit on its own, but together they exceed it
+        let (state, log) = state_with_max_duration(600, 1000);
+        let req = TTSRequest {
+            text: "This sentence is long enough to make the chunker split the input. ".repeat(6),
+            enable_chunking: true,
+            ..Default::default()
+        };
+
+        let result = generate_tts_chunked(state, req).await;
+        assert!(matches!(result, Err(TtsError::InvalidRequest(_))));
+        assert!(log.calls() >= 2);
+    }
+
+    #[tokio::test]
+    async fn test_chunking_follows_configured_min_length() {
+        // About 270 bytes: chunked at the default threshold, not at 1000
+        let text = "This sentence is long enough to make the chunker split the input. ".repeat(4);
+
+        for (min_length, expect_chunked) in [(200, true), (1000, false)] {
+            let (pool, log) = crate::kokoro::fake::fake_pool(2, 100, Duration::ZERO);
+            let state = AppState {
+                chunking_min_length: min_length,
+                ..AppState::for_tests(pool)
+            };
+            let req = TTSRequest {
+                text: text.clone(),
+                enable_chunking: true,
+                ..Default::default()
+            };
+
+            let response = generate_tts(
+                State(state),
+                Query(TtsQuery::default()),
+                HeaderMap::new(),
+                Json(req),
+            )
+            .await
+            .unwrap();
+            axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+
+            assert_eq!(log.calls() > 1, expect_chunked, "min_length {}", min_length);
+        }
+    }
+
+    async fn validation_header(validation_headers: bool, text: &str) -> Option<String> {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 2000, Duration::ZERO);
+        let state = AppState {
+            validation_headers,
+            ..AppState::for_tests(pool)
+        };
+        let req = TTSRequest {
+            text: text.to_string(),
+            normalize: false,
+            ..Default::default()
+        };
+
+        let response = generate_tts(
+            State(state),
+            Query(TtsQuery::default()),
+            HeaderMap::new(),
+            Json(req),
+        )
+        .await
+        .unwrap();
+        response
+            .headers()
+            .get(VALIDATION_WARNINGS_HEADER)
+            .map(|value| value.to_str().unwrap().to_string())
+    }
+
+    #[tokio::test]
+    async fn test_validation_header_counts_gap_warnings() {
+        // Two runs of whitespace leave phrases more than 50 chars apart
+        let gap = " ".repeat(60);
+        let text = format!("Hello there.{gap}Goodbye now.{gap}See you soon.");
+
+        assert_eq!(validation_header(true, &text).await.as_deref(), Some("2"));
+        assert_eq!(
+            validation_header(true, "Hello there. Goodbye now.")
+                .await
+                .as_deref(),
+            Some("0")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validation_header_off_by_default() {
+        let text = format!("Hello there.{}Goodbye now.", " ".repeat(60));
+        assert_eq!(validation_header(false, &text).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_long_token_split_or_rejected() {
+        let token = "x".repeat(1000);
+        let req = || TTSRequest {
+            text: token.clone(),
+            enable_chunking: false,
+            ..Default::default()
+        };
+
+        // Split: the engine only ever sees pieces within the limit
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let state = AppState::for_tests(pool);
+        generate_tts(
+            State(state),
+            Query(TtsQuery::default()),
+            HeaderMap::new(),
+            Json(req()),
+        )
+        .await
+        .unwrap();
+        let spoken = &log.texts()[0];
+        assert!(crate::chunking::longest_token(spoken) <= 100);
+        assert_eq!(spoken.replace(' ', ""), token);
+
+        // Reject: 400 before any synthesis
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let state = AppState {
+            long_tokens: LongTokenConfig {
+                max_length: 100,
+                policy: crate::chunking::LongTokenPolicy::Reject,
+            },
+            ..AppState::for_tests(pool)
+        };
+        let result = generate_tts(
+            State(state),
+            Query(TtsQuery::default()),
+            HeaderMap::new(),
+            Json(req()),
+        )
+        .await;
+        assert!(matches!(result, Err(TtsError::InvalidRequest(_))));
+        assert_eq!(log.calls(), 0);
+    }
+
+    #[test]
+    fn test_plan_pieces_uses_min_length() {
+        let req = TTSRequest {
+            text: "One sentence here. Another sentence there. ".repeat(6),
+            enable_chunking: true,
+            ..Default::default()
+        };
+        let whole = || {
+            vec![SpeechSegment {
+                text: req.text.clone(),
+                speed: 1.0,
+                gain_db: 0.0,
+                pitch_scale: 1.0,
+                pause_after: None,
+            }]
+        };
+
+        assert!(plan_pieces(&req, whole(), 100, false).len() > 1);
+        assert_eq!(plan_pieces(&req, whole(), req.text.len(), false).len(), 1);
+    }
+
+    #[test]
+    fn test_plan_pieces_follows_chunk_strategy() {
+        let long = "This sentence runs on for quite a while, with a clause here, another one \
+            there, and a third for good measure, then a fourth that nobody asked for, and a \
+            fifth that mostly repeats the fourth, before it finally reaches its end.";
+        let mut req = TTSRequest {
+            text: format!("Opening line. {} Closing line.", long).repeat(2),
+            enable_chunking: true,
+            ..Default::default()
+        };
+        let whole = |req: &TTSRequest| {
+            vec![SpeechSegment {
+                text: req.text.clone(),
+                speed: 1.0,
+                gain_db: 0.0,
+                pitch_scale: 1.0,
+                pause_after: None,
+            }]
+        };
+
+        let pieces = plan_pieces(&req, whole(&req), 100, false);
+        assert!(pieces.iter().all(|piece| !piece.text.contains(long)));
+
+        req.chunk_strategy = crate::chunking::ChunkStrategy::Sentence;
+        let pieces = plan_pieces(&req, whole(&req), 100, false);
+        assert_eq!(
+            pieces
+                .iter()
+                .filter(|piece| piece.text.contains(long))
+                .count(),
+            2
+        );
+    }
+
+    // ===== Streamed Response Tests =====
+
+    #[tokio::test]
+    async fn test_stream_single_body_matches_file() {
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 750, Duration::ZERO);
+        let state = AppState::for_tests(pool);
+        let req = TTSRequest {
+            text: "Hello world".to_string(),
+            ..Default::default()
+        };
+
+        let response = stream_tts_single(state, req).await.unwrap();
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "audio/wav"
+        );
+
+        // The temp file stays on disk while the body is pending
+        let path = log.outputs()[0].clone();
+        let file_contents = std::fs::read(&path).unwrap();
+        assert_eq!(
+            response.headers().get(header::CONTENT_LENGTH).unwrap(),
+            &file_contents.len().to_string()
+        );
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body.as_ref(), file_contents.as_slice());
+
+        // Cleanup runs once the body is done
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!path.exists());
+    }
+
+    // ===== Admin Endpoint Tests =====
+
+    async fn post_reload(app: Router, api_key: Option<&str>) -> axum::http::StatusCode {
+        use tower::ServiceExt;
+
+        let mut request = axum::http::Request::builder()
+            .method("POST")
+            .uri("/admin/reload");
+        if let Some(key) = api_key {
+            request = request.header("x-api-key", key);
+        }
+
+        app.oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_admin_reload_not_exposed_without_auth() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let app = create_router(AppState::for_tests(pool));
+
+        assert_eq!(
+            post_reload(app, None).await,
+            axum::http::StatusCode::NOT_FOUND
+        );
+    }
+
+    #[tokio::test]
+    async fn test_admin_reload_requires_api_key() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let state = AppState {
+            api_keys: ApiKeys::from_keys(["admin-key".to_string()].into_iter().collect()),
+            ..AppState::for_tests(pool)
+        };
+        let app = create_router(state);
+
+        assert_eq!(
+            post_reload(app.clone(), None).await,
+            axum::http::StatusCode::UNAUTHORIZED
+        );
+
+        // Fake pools have no model files to reload from
+        assert_eq!(
+            post_reload(app, Some("admin-key")).await,
+            axum::http::StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[tokio::test]
+    async fn test_admin_config_reports_settings_without_keys() {
+        use tower::ServiceExt;
+
+        let (pool, _) = crate::kokoro::fake::fake_pool(3, 100, Duration::ZERO);
+        let config = ServerConfig::resolve(
+            crate::config::server_config::FileConfig::default(),
+            |name| match name {
+                "REQUEST_TIMEOUT_SECONDS" => Some("45".to_string()),
+                "TTS_UNITS" => Some("true".to_string()),
+                "TTS_QUOTE_STYLE" => Some("spoken".to_string()),
+                "RESPONSE_HEADERS" => Some(
+                    r#"{"X-Frame-Options": "DENY", "X-Upstream-Token": "upstream-secret"}"#
+                        .to_string(),
+                ),
+                _ => None,
+            },
+        )
+        .unwrap();
+        let state = AppState {
+            api_keys: ApiKeys::from_keys(
+                ["admin-key".to_string(), "other-secret-key".to_string()]
+                    .into_iter()
+                    .collect(),
+            ),
+            voice_aliases: Arc::new(VoiceAliases::parse(Some("host=am_adam")).unwrap()),
+            config: Arc::new(config),
+            ..AppState::for_tests(pool)
+        };
+        let app = create_router(state);
+
+        let get_config = |key: Option<&'static str>| {
+            let app = app.clone();
+            async move {
+                let mut request = axum::http::Request::builder().uri("/admin/config");
+                if let Some(key) = key {
+                    request = request.header("x-api-key", key);
+                }
+                app.oneshot(request.body(Body::empty()).unwrap())
+                    .await
+                    .unwrap()
+            }
+        };
+
+        assert_eq!(
+            get_config(None).await.status(),
+            axum::http::StatusCode::UNAUTHORIZED
+        );
+
+        let response = get_config(Some("admin-key")).await;
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(json["auth"]["enabled"], true);
+        assert_eq!(json["auth"]["key_count"], 2);
+        assert_eq!(json["runtime"]["pool_size"], 3);
+        assert!(json["runtime"]["rate_limit_mode"].is_null());
+        assert_eq!(
+            json["runtime"]["supported_formats"],
+            serde_json::json!(["wav"])
+        );
+
+        let settings = &json["settings"];
+        assert_eq!(settings["request_timeout_ms"], 45_000);
+        assert_eq!(settings["default_audio_format"], "wav");
+        assert_eq!(settings["normalization"]["units"], true);
+        assert_eq!(settings["normalization"]["math"], false);
+        assert_eq!(settings["quotes"], "spoken");
+        assert!(settings["lists"].is_null());
+        assert_eq!(settings["response_headers"]["x-frame-options"], "DENY");
+        assert_eq!(
+            settings["response_headers"]["x-upstream-token"],
+            "[redacted]"
+        );
+
+        assert_eq!(json["voice_aliases"]["host"], "am_adam");
+        assert_eq!(json["voice_aliases"]["narrator"], "bm_george");
+
+        // No key material or secret header values anywhere in the body
+        let body = String::from_utf8_lossy(&body);
+        assert!(!body.contains("admin-key"));
+        assert!(!body.contains("other-secret-key"));
+        assert!(!body.contains("upstream-secret"));
+    }
+
+    #[tokio::test]
+    async fn test_configured_response_headers_on_every_response() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let headers = ResponseHeaders::parse(
+            &[
+                ("X-Frame-Options".to_string(), "DENY".to_string()),
+                ("Access-Control-Max-Age".to_string(), "600".to_string()),
+            ]
+            .into_iter()
+            .collect(),
+        )
+        .unwrap();
+        let app = create_router(AppState {
+            response_headers: Arc::new(headers),
+            ..AppState::for_tests(pool)
+        });
+
+        for (method, uri, body) in [
+            ("GET", "/health", None),
+            ("POST", "/tts", Some(r#"{"text": "Hello"}"#)),
+            ("POST", "/tts", Some(r#"{"text": "   "}"#)),
+            ("GET", "/tts/jobs/missing", None),
+        ] {
+            let response = send(&app, method, uri, body).await;
+            assert_eq!(response.headers()["x-frame-options"], "DENY", "{}", uri);
+            assert_eq!(response.headers()["access-control-max-age"], "600");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_no_extra_response_headers_by_default() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let app = create_router(AppState::for_tests(pool));
+
+        let response = send(&app, "GET", "/health", None).await;
+        assert!(!response.headers().contains_key("x-frame-options"));
+    }
+
+    #[tokio::test]
+    async fn test_admin_config_not_exposed_without_auth() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let app = create_router(AppState::for_tests(pool));
+
+        let response = send(&app, "GET", "/admin/config", None).await;
+        assert_eq!(response.status(), axum::http::StatusCode::NOT_FOUND);
+    }
+
+    // ===== Job Tests =====
+
+    async fn send(app: &Router, method: &str, uri: &str, body: Option<&str>) -> Response {
+        use tower::ServiceExt;
+
+        let request = axum::http::Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(body.map(|b| Body::from(b.to_string())).unwrap_or_default())
+            .unwrap();
+        app.clone().oneshot(request).await.unwrap()
+    }
+
+    async fn json_body(response: Response) -> serde_json::Value {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    /// Poll a job until it leaves `queued`/`running`
+    async fn wait_for_job(app: &Router, id: &str) -> serde_json::Value {
+        for _ in 0..200 {
+            let status =
+                json_body(send(app, "GET", &format!("/tts/jobs/{}", id), None).await).await;
+            if status["status"] != "queued" && status["status"] != "running" {
+                return status;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("job {} never finished", id);
+    }
+
+    #[tokio::test]
+    async fn test_job_submit_poll_fetch() {
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 500, Duration::from_millis(20));
+        let app = create_router(AppState::for_tests(pool));
+        let text = "This sentence is long enough to make the chunker split the input. ".repeat(8);
+
+        let response = send(
+            &app,
+            "POST",
+            "/tts/jobs",
+            Some(&serde_json::json!({ "text": text }).to_string()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let location = response.headers()[header::LOCATION]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let submitted = json_body(response).await;
+        let id = submitted["job_id"].as_str().unwrap().to_string();
+        assert_eq!(location, format!("/tts/jobs/{}", id));
+        let chunks_total = submitted["chunks_total"].as_u64().unwrap();
+        assert!(chunks_total > 1);
+
+        // Audio isn't available until the job is done
+        let early = send(&app, "GET", &format!("/tts/jobs/{}/audio", id), None).await;
+        assert_eq!(early.status(), StatusCode::CONFLICT);
+
+        let status = wait_for_job(&app, &id).await;
+        assert_eq!(status["status"], "done");
+        assert_eq!(status["chunks_done"], chunks_total);
+
+        let response = send(&app, "GET", &format!("/tts/jobs/{}/audio", id), None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "audio/wav");
+        let audio = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let duration = audio::duration::calculate(&audio).unwrap();
+        assert!((duration - 500.0 * chunks_total as f64).abs() < 1.0);
+        assert_eq!(log.calls(), chunks_total as usize);
+    }
+
+    #[tokio::test]
+    async fn test_job_rejects_invalid_request_up_front() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let app = create_router(AppState::for_tests(pool));
+
+        let response = send(&app, "POST", "/tts/jobs", Some(r#"{"text": "   "}"#)).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_job_submission_rejected_when_queue_full() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::from_millis(200));
+        let state = AppState {
+            jobs: JobStore::new(Duration::from_secs(60), 1).with_max_pending(1),
+            ..AppState::for_tests(pool)
+        };
+        let app = create_router(state);
+
+        let response = send(&app, "POST", "/tts/jobs", Some(r#"{"text": "Hello"}"#)).await;
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let id = json_body(response).await["job_id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        let response = send(&app, "POST", "/tts/jobs", Some(r#"{"text": "Hello"}"#)).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert!(response.headers().contains_key(header::RETRY_AFTER));
+
+        // Room frees up once the job finishes
+        assert_eq!(wait_for_job(&app, &id).await["status"], "done");
+        let response = send(&app, "POST", "/tts/jobs", Some(r#"{"text": "Hello"}"#)).await;
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+    }
+
+    #[tokio::test]
+    async fn test_job_expires_after_ttl() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let state = AppState {
+            jobs: JobStore::new(Duration::from_millis(50), 1),
+            ..AppState::for_tests(pool)
+        };
+        let app = create_router(state);
+
+        let response = send(&app, "POST", "/tts/jobs", Some(r#"{"text": "Hello"}"#)).await;
+        let id = json_body(response).await["job_id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert_eq!(wait_for_job(&app, &id).await["status"], "done");
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let status = send(&app, "GET", &format!("/tts/jobs/{}", id), None).await;
+        assert_eq!(status.status(), StatusCode::NOT_FOUND);
+        let audio = send(&app, "GET", &format!("/tts/jobs/{}/audio", id), None).await;
+        assert_eq!(audio.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_job_cancel_stops_engine_acquisitions() {
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 500, Duration::from_millis(100));
+        let app = create_router(AppState::for_tests(pool));
+        let text = "This sentence is long enough to make the chunker split the input. ".repeat(8);
+
+        let response = send(
+            &app,
+            "POST",
+            "/tts/jobs",
+            Some(&serde_json::json!({ "text": text }).to_string()),
+        )
+        .await;
+        let submitted = json_body(response).await;
+        let id = submitted["job_id"].as_str().unwrap().to_string();
+        let chunks_total = submitted["chunks_total"].as_u64().unwrap() as usize;
+        assert!(chunks_total > 2);
+
+        // Let the first chunk get an engine
+        while log.calls() == 0 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+
+        let response = send(&app, "DELETE", &format!("/tts/jobs/{}", id), None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(json_body(response).await["status"], "cancelled");
+        let calls_at_cancel = log.calls();
+
+        // The chunk in progress runs out, but nothing else takes an engine
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert_eq!(log.calls(), calls_at_cancel);
+        assert!(calls_at_cancel < chunks_total);
+
+        let status = json_body(send(&app, "GET", &format!("/tts/jobs/{}", id), None).await).await;
+        assert_eq!(status["status"], "cancelled");
+        let audio = send(&app, "GET", &format!("/tts/jobs/{}/audio", id), None).await;
+        assert_eq!(audio.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_job_cancel_unknown_or_finished() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let app = create_router(AppState::for_tests(pool));
+
+        let response = send(&app, "DELETE", "/tts/jobs/missing", None).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = send(&app, "POST", "/tts/jobs", Some(r#"{"text": "Hello"}"#)).await;
+        let id = json_body(response).await["job_id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert_eq!(wait_for_job(&app, &id).await["status"], "done");
+
+        let response = send(&app, "DELETE", &format!("/tts/jobs/{}", id), None).await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let status = json_body(send(&app, "GET", &format!("/tts/jobs/{}", id), None).await).await;
+        assert_eq!(status["status"], "done");
+    }
+
+    // ===== Archive Tests =====
+
+    #[tokio::test]
+    async fn test_archive_holds_each_chunk_and_a_manifest() {
+        let (pool, log) = crate::kokoro::fake::fake_pool(2, 500, Duration::ZERO);
+        let app = create_router(AppState::for_tests(pool));
+        let text = "This sentence is long enough to make the chunker split the input. ".repeat(8);
+
+        let response = send(
+            &app,
+            "POST",
+            "/tts/archive",
+            Some(&serde_json::json!({ "text": text, "filename": "story" }).to_string()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/zip");
+        assert_eq!(
+            response.headers()[header::CONTENT_DISPOSITION],
+            "attachment; filename=\"story.zip\""
+        );
+
+        let archive = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let entries = crate::utils::zip::read_stored_entries(&archive).unwrap();
+        let chunk_count = log.calls();
+        assert!(chunk_count > 1);
+        assert_eq!(entries.len(), chunk_count + 1);
+
+        let (name, manifest) = entries.last().unwrap();
+        assert_eq!(name, "manifest.json");
+        let manifest: serde_json::Value = serde_json::from_slice(manifest).unwrap();
+        let chunks = manifest["chunks"].as_array().unwrap();
+        assert_eq!(chunks.len(), chunk_count);
+        assert_eq!(manifest["total_duration_ms"], 500.0 * chunk_count as f64);
+
+        for (i, chunk) in chunks.iter().enumerate() {
+            let (name, audio) = &entries[i];
+            assert_eq!(chunk["file"], name.as_str());
+            assert_eq!(chunk["index"], i);
+            assert_eq!(chunk["start_ms"], 500.0 * i as f64);
+            assert_eq!(
+                chunk["duration_ms"],
+                audio::duration::calculate(audio).unwrap()
+            );
+            assert!(text.contains(chunk["text"].as_str().unwrap().trim()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_archive_rejects_invalid_request_up_front() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let app = create_router(AppState::for_tests(pool));
+
+        let response = send(&app, "POST", "/tts/archive", Some(r#"{"text": "   "}"#)).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    // ===== Request Timeout Tests =====
+
+    #[tokio::test]
+    async fn test_request_timeout_header_extends_up_to_max() {
+        use tower::ServiceExt;
+
+        // Synthesis takes 300ms: over the 100ms default, under the 500ms max.
+        // A timed-out request's engine stays busy, so each request gets its own.
+        let (pool, _) = crate::kokoro::fake::fake_pool(3, 100, Duration::from_millis(300));
+        let app = create_router(AppState {
+            request_timeout: Duration::from_millis(100),
+            max_request_timeout: Duration::from_millis(500),
+            ..AppState::for_tests(pool)
+        });
+
+        let status = |timeout: Option<&'static str>| {
+            let app = app.clone();
+            async move {
+                let mut request = axum::http::Request::builder()
+                    .method("POST")
+                    .uri("/tts")
+                    .header(header::CONTENT_TYPE, "application/json");
+                if let Some(timeout) = timeout {
+                    request =
+                        request.header(crate::config::constants::REQUEST_TIMEOUT_HEADER, timeout);
+                }
+                let request = request
+                    .body(Body::from(r#"{"text": "Hello world"}"#))
+                    .unwrap();
+                app.oneshot(request).await.unwrap().status()
+            }
+        };
+
+        assert_eq!(status(None).await, StatusCode::REQUEST_TIMEOUT);
+        assert_eq!(status(Some("0.45")).await, StatusCode::OK);
+        // Asking for more than the max gets the max, which is still enough here
+        assert_eq!(status(Some("3600")).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout_beyond_max_is_cut_off() {
+        use tower::ServiceExt;
+
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::from_millis(300));
+        let app = create_router(AppState {
+            request_timeout: Duration::from_millis(50),
+            max_request_timeout: Duration::from_millis(150),
+            ..AppState::for_tests(pool)
+        });
+
+        let request = axum::http::Request::builder()
+            .method("POST")
+            .uri("/tts")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(crate::config::constants::REQUEST_TIMEOUT_HEADER, "30")
+            .body(Body::from(r#"{"text": "Hello world"}"#))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::REQUEST_TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn test_stream_outlasting_request_timeout_still_completes() {
+        // Each chunk takes 300ms, three times the timeout
+        let (pool, _) = crate::kokoro::fake::fake_pool(2, 100, Duration::from_millis(300));
+        let app = create_router(AppState {
+            request_timeout: Duration::from_millis(100),
+            max_request_timeout: Duration::from_millis(100),
+            ..AppState::for_tests(pool)
+        });
+
+        let response = send(
+            &app,
+            "POST",
+            "/tts/stream",
+            Some(r#"{"text": "Hello world"}"#),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8_lossy(&body);
+        assert!(body.contains("audio/wav"));
+        assert!(body.ends_with(&crate::services::streaming::create_boundary_end()));
+
+        // The same synthesis as a plain request is stuck past the timeout
+        let response = send(&app, "POST", "/tts", Some(r#"{"text": "Hello world"}"#)).await;
+        assert_eq!(response.status(), StatusCode::REQUEST_TIMEOUT);
+    }
+
+    // ===== Format Negotiation Tests =====
+
+    async fn post_with_accept(uri: &str, accept: Option<&str>, body: &str) -> Response {
+        use tower::ServiceExt;
+
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let app = create_router(AppState::for_tests(pool));
+        let mut request = axum::http::Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header(header::CONTENT_TYPE, "application/json");
+        if let Some(accept) = accept {
+            request = request.header(header::ACCEPT, accept);
+        }
+        app.oneshot(request.body(Body::from(body.to_string())).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_tts_accept_negotiation() {
+        let body = r#"{"text": "Hello"}"#;
+        for accept in [None, Some("*/*"), Some("audio/*"), Some("audio/wav")] {
+            let response = post_with_accept("/tts", accept, body).await;
+            assert_eq!(response.status(), StatusCode::OK, "Accept: {:?}", accept);
+            assert_eq!(response.headers()[header::CONTENT_TYPE], "audio/wav");
+        }
+
+        for accept in ["audio/mpeg", "audio/ogg", "audio/ogg, audio/mpeg;q=0.5"] {
+            let response = post_with_accept("/tts", Some(accept), body).await;
+            assert_eq!(
+                response.status(),
+                StatusCode::NOT_ACCEPTABLE,
+                "Accept: {}",
+                accept
+            );
+        }
+
+        // A wildcard fallback still gets WAV
+        let response = post_with_accept("/tts", Some("audio/mpeg, */*;q=0.1"), body).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_explicit_format_wins_over_accept() {
+        let response = post_with_accept(
+            "/tts",
+            Some("audio/mpeg"),
+            r#"{"text": "Hello", "format": "wav"}"#,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "audio/wav");
+
+        // A format without an encoder is a bad request, whatever Accept says
+        let response =
+            post_with_accept("/tts", Some("*/*"), r#"{"text": "Hello", "format": "mp3"}"#).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_stream_accept_negotiation() {
+        let body = r#"{"text": "Hello"}"#;
+        let response =
+            post_with_accept("/tts/stream", Some("multipart/mixed, audio/wav"), body).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = post_with_accept("/tts/stream", Some("audio/ogg"), body).await;
+        assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+
+        let response =
+            post_with_accept("/tts/stream", None, r#"{"text": "Hello", "format": "ogg"}"#).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_max_phrase_words_validated() {
+        for (value, status) in [
+            (0, StatusCode::BAD_REQUEST),
+            (51, StatusCode::BAD_REQUEST),
+            (3, StatusCode::OK),
+        ] {
+            let body = format!(
+                r#"{{"text": "Hello there", "max_phrase_words": {}}}"#,
+                value
+            );
+            for uri in ["/tts", "/tts/stream"] {
+                let response = post_with_accept(uri, None, &body).await;
+                assert_eq!(response.status(), status, "{} with {}", uri, value);
+            }
+        }
+    }
+
+    // ===== Stats Tests =====
+
+    #[tokio::test]
+    async fn test_stats_track_generated_audio() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 1500, Duration::ZERO);
+        let state = AppState::for_tests(pool);
+
+        let Json(before) = pool_stats(State(state.clone())).await;
+        assert_eq!(before.total_generations, 0);
+        assert_eq!(before.total_audio_seconds, 0.0);
+
+        let req = TTSRequest {
+            text: "Hello".to_string(),
+            ..Default::default()
+        };
+        generate_tts_single(state.clone(), req).await.unwrap();
+
+        let Json(after) = pool_stats(State(state)).await;
+        assert_eq!(after.total_generations, 1);
+        assert!((after.total_audio_seconds - 1.5).abs() < 0.01);
+        assert!(after.average_generation_ms >= 0.0);
+        assert_eq!(after.total_requests, 1);
+    }
+
+    #[tokio::test]
+    async fn test_stats_break_down_by_voice() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 1500, Duration::ZERO);
+        let state = AppState::for_tests(pool);
+
+        for voice in ["af_heart", "bm_george", "af_heart"] {
+            let req = TTSRequest {
+                text: "Hello".to_string(),
+                voice: voice.to_string(),
+                ..Default::default()
+            };
+            generate_tts_single(state.clone(), req).await.unwrap();
+        }
+
+        let Json(stats) = pool_stats(State(state)).await;
+        assert_eq!(stats.by_voice.len(), 2);
+        assert_eq!(
+            stats.by_voice["af_heart"],
+            VoiceStats {
+                generations: 2,
+                audio_seconds: 3.0,
+            }
+        );
+        assert_eq!(
+            stats.by_voice["bm_george"],
+            VoiceStats {
+                generations: 1,
+                audio_seconds: 1.5,
+            }
+        );
+    }
+
+    // ===== Disk Cache Tests =====
+
+    #[tokio::test]
+    async fn test_disk_cache_skips_generation_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let req = TTSRequest {
+            text: "Hello".to_string(),
+            ..Default::default()
+        };
+
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 500, Duration::ZERO);
+        let state = AppState {
+            audio_cache: Some(Arc::new(DiskCache::new(dir.path(), 1024 * 1024).unwrap())),
+            ..AppState::for_tests(pool)
+        };
+        let first = generate_tts_single(state.clone(), req.clone())
+            .await
+            .unwrap();
+        let second = generate_tts_single(state, req.clone()).await.unwrap();
+        assert_eq!(log.calls(), 1);
+        assert_eq!(first, second);
+
+        // Simulated restart: new pool and cache instance over the same directory
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 500, Duration::ZERO);
+        let state = AppState {
+            audio_cache: Some(Arc::new(DiskCache::new(dir.path(), 1024 * 1024).unwrap())),
+            ..AppState::for_tests(pool)
+        };
+        let response = stream_tts_single(state, req).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(log.calls(), 0);
+        assert_eq!(body.as_ref(), first.as_slice());
+    }
+
+    #[tokio::test]
+    async fn test_disk_cache_misses_after_model_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let req = TTSRequest {
+            text: "Hello".to_string(),
+            ..Default::default()
+        };
+        let state_with_model = |model_id: &str| {
+            let (pool, log) = crate::kokoro::fake::fake_pool(1, 500, Duration::ZERO);
+            let state = AppState {
+                audio_cache: Some(Arc::new(DiskCache::new(dir.path(), 1024 * 1024).unwrap())),
+                ..AppState::for_tests(pool.with_model_id(model_id))
+            };
+            (state, log)
+        };
+
+        let (state, log) = state_with_model("model-a");
+        generate_tts_single(state, req.clone()).await.unwrap();
+        assert_eq!(log.calls(), 1);
+
+        // Same cache directory, new model files
+        let (state, log) = state_with_model("model-b");
+        generate_tts_single(state, req.clone()).await.unwrap();
+        assert_eq!(log.calls(), 1);
+    }
+
+    // ===== Metadata Version Tests =====
+
+    #[tokio::test]
+    async fn test_stream_rejects_unsupported_metadata_version() {
+        use tower::ServiceExt;
+
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let app = create_router(AppState::for_tests(pool));
+
+        let request = axum::http::Request::builder()
+            .method("POST")
+            .uri("/tts/stream")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(METADATA_VERSION_HEADER, "9.0")
+            .body(Body::from(r#"{"text": "Hello"}"#))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(String::from_utf8_lossy(&body).contains("Unsupported metadata version"));
+        assert_eq!(log.calls(), 0);
+    }
+
+    // ===== Speed Markup Tests =====
+
+    #[tokio::test]
+    async fn test_speed_markup_segments_use_their_own_speed() {
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 1000, Duration::ZERO);
+        let state = AppState::for_tests(pool);
+
+        let segment_duration = |speed: f32| {
+            let state = state.clone();
+            async move {
+                let req = TTSRequest {
+                    text: "Segment".to_string(),
+                    speed: Some(speed),
+                    ..Default::default()
+                };
+                let audio = generate_tts_single(state, req).await.unwrap();
+                audio::duration::calculate(&audio).unwrap()
+            }
+        };
+        let slow = segment_duration(0.5).await;
+        let normal = segment_duration(1.0).await;
+        assert!((slow - 2.0 * normal).abs() < 1.0);
+
+        let req = TTSRequest {
+            text: "{{speed:0.5}}Say this slowly.{{/speed}} Then carry on.".to_string(),
+            ..Default::default()
+        };
+        let response = generate_tts(
+            State(state),
+            Query(TtsQuery::default()),
+            HeaderMap::new(),
+            Json(req),
+        )
+        .await
+        .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        let combined = audio::duration::calculate(&body).unwrap();
+        assert!(
+            (combined - (slow + normal)).abs() < 1.0,
+            "Expected {}ms, got {}ms",
+            slow + normal,
+            combined
+        );
+        assert_eq!(log.calls(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_speed_markup_rejects_invalid_markup() {
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let req = TTSRequest {
+            text: "{{speed:0.5}}never closed".to_string(),
+            ..Default::default()
+        };
+
+        let result = generate_tts(
+            State(AppState::for_tests(pool)),
+            Query(TtsQuery::default()),
+            HeaderMap::new(),
+            Json(req),
+        )
+        .await;
+        assert!(matches!(result, Err(TtsError::InvalidRequest(_))));
+        assert_eq!(log.calls(), 0);
+    }
+
+    // ===== Speakable Content Tests =====
+
+    async fn tts_text(text: &str) -> (Result<Response>, usize) {
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let req = TTSRequest {
+            text: text.to_string(),
+            ..Default::default()
+        };
+        let result = generate_tts(
+            State(AppState::for_tests(pool)),
+            Query(TtsQuery::default()),
+            HeaderMap::new(),
+            Json(req),
+        )
+        .await;
+        (result, log.calls())
+    }
+
+    #[tokio::test]
+    async fn test_rejects_emoji_only_input() {
+        let (result, calls) = tts_text("\u{1F600} \u{1F389}\u{1F44D}").await;
+        assert!(matches!(result, Err(TtsError::InvalidRequest(msg)) if msg.contains("speakable")));
+        assert_eq!(calls, 0);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_punctuation_only_input() {
+        let (result, calls) = tts_text("?!... --- ***").await;
+        assert!(matches!(result, Err(TtsError::InvalidRequest(_))));
+        assert_eq!(calls, 0);
+    }
+
+    #[tokio::test]
+    async fn test_accepts_mixed_emoji_and_text() {
+        let (result, calls) = tts_text("Great job \u{1F389}!").await;
+        assert!(result.is_ok());
+        assert_eq!(calls, 1);
+    }
+
+    // ===== Speed Clamp Tests =====
+
+    async fn tts_at_speed(speed: f32, speed_clamp: bool) -> Result<Response> {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 1000, Duration::ZERO);
+        let state = AppState {
+            speed_clamp,
+            ..AppState::for_tests(pool)
+        };
+        let req = TTSRequest {
+            text: "Hello".to_string(),
+            speed: Some(speed),
+            ..Default::default()
+        };
+        generate_tts(
+            State(state),
+            Query(TtsQuery::default()),
+            HeaderMap::new(),
+            Json(req),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_speed_rejected_without_clamp() {
+        let result = tts_at_speed(3.01, false).await;
+        assert!(matches!(result, Err(TtsError::InvalidSpeed { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_speed_below_minimum_rejected() {
+        let result = tts_at_speed(0.1, false).await;
+        assert!(matches!(
+            result,
+            Err(TtsError::InvalidSpeed { speed, min }) if speed == 0.1 && min == 0.25
+        ));
+        assert!(tts_at_speed(0.5, false).await.is_ok());
+
+        // With clamping, the minimum is where slow speeds end up
+        let response = tts_at_speed(0.1, true).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let duration = audio::duration::calculate(&body).unwrap();
+        assert!((duration - 1000.0 / 0.25).abs() < 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_min_speed_is_configurable() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 1000, Duration::ZERO);
+        let state = AppState {
+            min_speed: 0.5,
+            ..AppState::for_tests(pool)
+        };
+        let at_speed = |speed: f32| TTSRequest {
+            text: "Hello {{speed:0.4}}there{{/speed}}".to_string(),
+            speed: Some(speed),
+            ..Default::default()
+        };
+
+        for speed in [0.4, 1.0] {
+            let result = generate_tts(
+                State(state.clone()),
+                Query(TtsQuery::default()),
+                HeaderMap::new(),
+                Json(at_speed(speed)),
+            )
+            .await;
+            assert!(matches!(
+                result,
+                Err(TtsError::InvalidSpeed {
+                    speed: 0.4,
+                    min: 0.5
+                })
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_speed_clamped_when_enabled() {
+        let response = tts_at_speed(3.01, true).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        // Fake engine output scales with speed: 1000ms / 3.0
+        let duration = audio::duration::calculate(&body).unwrap();
+        assert!((duration - 1000.0 / 3.0).abs() < 1.0);
+    }
+
+    // ===== Per-Key Text Length Tests =====
+
+    async fn tts_with_key(state: &AppState, key: &str, text_len: usize) -> Result<Response> {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", key.parse().unwrap());
+        let req = TTSRequest {
+            text: "Hello there. ".repeat(text_len / 13),
+            ..Default::default()
+        };
+        generate_tts(
+            State(state.clone()),
+            Query(TtsQuery::default()),
+            headers,
+            Json(req),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_max_text_length_per_key() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(2, 100, Duration::ZERO);
+        let keys = ["free-key", "paid-key", "plain-key"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let state = AppState {
+            api_keys: ApiKeys::from_keys(keys)
+                .with_max_text_length("free-key", 260)
+                .with_max_text_length("paid-key", MAX_TEXT_LENGTH * 2),
+            ..AppState::for_tests(pool)
+        };
+
+        // A restricted key is turned away at a length the others accept
+        match tts_with_key(&state, "free-key", 520).await {
+            Err(TtsError::InvalidRequest(msg)) => {
+                assert!(msg.contains("Text too long"));
+                assert!(msg.contains("max 260"), "{}", msg);
+            }
+            other => panic!("Expected Text too long, got {:?}", other.map(|_| ())),
+        }
+        assert!(tts_with_key(&state, "free-key", 260).await.is_ok());
+        assert!(tts_with_key(&state, "plain-key", 520).await.is_ok());
+
+        // A permissive key may go past the global limit
+        let long = MAX_TEXT_LENGTH + 1300;
+        assert!(tts_with_key(&state, "paid-key", long).await.is_ok());
+        match tts_with_key(&state, "plain-key", long).await {
+            Err(TtsError::InvalidRequest(msg)) => {
+                assert!(msg.contains(&format!("max {}", MAX_TEXT_LENGTH)));
+            }
+            other => panic!("Expected Text too long, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_max_text_length_per_key_on_other_routes() {
+        use tower::ServiceExt;
+
+        let (pool, _) = crate::kokoro::fake::fake_pool(2, 100, Duration::ZERO);
+        let keys = ["free-key", "plain-key"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let app = create_router(AppState {
+            api_keys: ApiKeys::from_keys(keys).with_max_text_length("free-key", 260),
+            ..AppState::for_tests(pool)
+        });
+        let post = |uri: &'static str, key: &'static str| {
+            let body = serde_json::json!({ "text": "Hello there. ".repeat(40) });
+            let request = axum::http::Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header(header::CONTENT_TYPE, "application/json")
+                .header("x-api-key", key)
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            app.clone().oneshot(request)
+        };
+
+        for uri in ["/tts/estimate", "/tts/stream", "/tts/stream?merge=true"] {
+            let response = post(uri, "free-key").await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", uri);
+            let body = json_body(response).await;
+            assert!(
+                body["error"].as_str().unwrap().contains("max 260"),
+                "{}",
+                uri
+            );
+
+            let response = post(uri, "plain-key").await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "{}", uri);
+        }
+    }
+
+    // ===== Voice Default Tests =====
+
+    async fn tts_with_voice(
+        voice: &str,
+        speed: Option<f32>,
+        voice_defaults: VoiceDefaults,
+    ) -> (Result<Vec<u8>>, Vec<f32>) {
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 1000, Duration::ZERO);
+        let req = TTSRequest {
+            text: "Hello".to_string(),
+            voice: voice.to_string(),
+            speed,
+            ..Default::default()
+        };
+        let state = AppState {
+            voice_defaults: Arc::new(voice_defaults),
+            ..AppState::for_tests(pool)
+        };
+        let result = async {
+            let response = generate_tts(
+                State(state),
+                Query(TtsQuery::default()),
+                HeaderMap::new(),
+                Json(req),
+            )
+            .await?;
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            Ok(body.to_vec())
+        }
+        .await;
+        (result, log.speeds())
+    }
+
+    fn voice_defaults(speeds: &str, pitches: &str) -> VoiceDefaults {
+        VoiceDefaults::parse(Some(speeds), Some(pitches), &VoiceAliases::default()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_voice_default_speed_used_when_request_speed_unset() {
+        let defaults = voice_defaults("am_santa=0.9", "");
+        let (result, speeds) = tts_with_voice("am_santa", None, defaults).await;
+        let duration = audio::duration::calculate(&result.unwrap()).unwrap();
+
+        assert_eq!(speeds, vec![0.9]);
+        assert!((duration - 1000.0 / 0.9).abs() < 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_voice_default_pitch_keeps_the_speed() {
+        let defaults = voice_defaults("am_santa=0.9", "am_santa=0.95");
+        let (result, speeds) = tts_with_voice("am_santa", None, defaults).await;
+        let duration = audio::duration::calculate(&result.unwrap()).unwrap();
+
+        // The engine runs slower still to make room for the pitch shift...
+        assert_eq!(speeds.len(), 1);
+        assert!((speeds[0] - 0.9 / 0.95).abs() < 1e-6);
+        // ...and the shifted audio lasts as long as speaking at 0.9
+        assert!((duration - 1000.0 / 0.9).abs() < 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_explicit_speed_overrides_voice_default() {
+        let defaults = voice_defaults("am_santa=0.9", "");
+        let (result, speeds) = tts_with_voice("am_santa", Some(1.5), defaults).await;
+        let duration = audio::duration::calculate(&result.unwrap()).unwrap();
+
+        assert_eq!(speeds, vec![1.5]);
+        assert!((duration - 1000.0 / 1.5).abs() < 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_voice_without_defaults_speaks_at_normal_speed() {
+        let defaults = voice_defaults("am_santa=0.9", "am_santa=0.95");
+        let (result, speeds) = tts_with_voice("bf_lily", None, defaults).await;
+        let duration = audio::duration::calculate(&result.unwrap()).unwrap();
+
+        assert_eq!(speeds, vec![1.0]);
+        assert!((duration - 1000.0).abs() < 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_voice_defaults_apply_to_aliases() {
+        let defaults = voice_defaults("am_santa=0.9", "");
+        let (result, speeds) = tts_with_voice("santa", None, defaults).await;
+
+        assert!(result.is_ok());
+        assert_eq!(speeds, vec![0.9]);
+    }
+
+    // ===== JSON Response Tests =====
+
+    async fn tts_with_accept(accept: &str, text: &str) -> Response {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 300, Duration::ZERO);
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, accept.parse().unwrap());
+        let req = TTSRequest {
+            text: text.to_string(),
+            ..Default::default()
+        };
+        generate_tts(
+            State(AppState::for_tests(pool)),
+            Query(TtsQuery::default()),
+            headers,
+            Json(req),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_json_response_carries_base64_wav() {
+        let response = tts_with_accept("application/json", "It costs $5.").await;
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/json"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        let audio = base64::decode(json["audio_base64"].as_str().unwrap()).unwrap();
+        let duration = audio::duration::calculate(&audio).unwrap();
+        assert!((duration - 300.0).abs() < 1.0);
+        assert_eq!(json["metadata"]["duration_ms"].as_f64().unwrap(), duration);
+        assert_eq!(json["metadata"]["text"], "It costs five dollars.");
+
+        // Same bytes as the plain WAV response for the same request
+        let wav = tts_with_accept("audio/wav", "It costs $5.").await;
+        let wav = axum::body::to_bytes(wav.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(audio, wav.to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_json_response_strips_speed_markup_from_metadata() {
+        let response = tts_with_accept(
+            "application/json",
+            "{{speed:0.5}}Slowly.{{/speed}} Then normal.",
+        )
+        .await;
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(json["metadata"]["text"], "Slowly. Then normal.");
+        assert!(base64::decode(json["audio_base64"].as_str().unwrap()).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_metadata_only_returns_timings_without_audio() {
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 300, Duration::ZERO);
+        let req = TTSRequest {
+            text: "It costs $5.".to_string(),
+            metadata_only: true,
+            ..Default::default()
+        };
+        // No JSON Accept header: the flag alone selects the JSON body
+        let response = generate_tts(
+            State(AppState::for_tests(pool)),
+            Query(TtsQuery::default()),
+            HeaderMap::new(),
+            Json(req),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/json"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        let mut keys: Vec<_> = json.as_object().unwrap().keys().cloned().collect();
+        keys.sort();
+        assert_eq!(keys, ["metadata", "timing"]);
+        assert_eq!(json["timing"], "synthesized");
+        assert_eq!(json["metadata"]["text"], "It costs five dollars.");
+        assert!((json["metadata"]["duration_ms"].as_f64().unwrap() - 300.0).abs() < 1.0);
+        // The audio was still synthesized to measure it
+        assert_eq!(log.calls(), 1);
+    }
+
+    #[test]
+    fn test_accepts_json() {
+        let accept = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::ACCEPT, value.parse().unwrap());
+            accepts_json(&headers)
+        };
+
+        assert!(accept("application/json"));
+        assert!(accept("application/json; charset=utf-8, */*"));
+        assert!(!accept("audio/wav, application/json"));
+        assert!(!accept("*/*"));
+        assert!(!accepts_json(&HeaderMap::new()));
+    }
+
+    // ===== Debug Flag Tests =====
+
+    async fn tts_body(debug: bool) -> (String, Vec<u8>) {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let req = TTSRequest {
+            text: "It costs $100.".to_string(),
+            ..Default::default()
+        };
+        let response = generate_tts(
+            State(AppState::for_tests(pool)),
+            Query(TtsQuery { debug }),
+            HeaderMap::new(),
+            Json(req),
+        )
+        .await
+        .unwrap();
+
+        let content_type = response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (content_type, body.to_vec())
+    }
+
+    #[tokio::test]
+    async fn test_debug_flag_adds_normalization_part() {
+        let (content_type, body) = tts_body(true).await;
+        assert!(content_type.starts_with("multipart/mixed"));
+
+        let body = String::from_utf8_lossy(&body);
+        let json_start = body.find("Content-Type: application/json\r\n\r\n").unwrap();
+        let json_line = body[json_start..].lines().nth(2).unwrap();
+        let debug: serde_json::Value = serde_json::from_str(json_line).unwrap();
+
+        assert_eq!(debug["original"], "It costs $100.");
+        assert_eq!(debug["normalized"], "It costs one hundred dollars.");
+        assert_eq!(debug["changes"][0]["original"], "$100");
+        assert_eq!(debug["changes"][0]["normalized"], "one hundred dollars");
+        assert_eq!(debug["chunks"][0]["start"], 0);
+        assert_eq!(debug["chunks"][0]["end"], 14);
+        assert!(body.contains("Content-Type: audio/wav"));
+    }
+
+    #[tokio::test]
+    async fn test_no_debug_part_without_flag() {
+        let (content_type, body) = tts_body(false).await;
+        assert_eq!(content_type, "audio/wav");
+        assert!(body.starts_with(b"RIFF"));
+        assert!(!String::from_utf8_lossy(&body).contains("application/json"));
+    }
+
+    // ===== Estimate Tests =====
+
+    async fn estimate(state: AppState, text: String) -> EstimateResponse {
+        let req = TTSRequest {
+            text,
+            ..Default::default()
+        };
+        let Json(response) = estimate_tts(State(state), HeaderMap::new(), Json(req))
+            .await
+            .unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn test_estimate_scales_with_input_length() {
+        let (pool, log) = crate::kokoro::fake::fake_pool(2, 100, Duration::ZERO);
+        let state = AppState::for_tests(pool);
+        let sentence = "The quick brown fox jumps over the lazy dog. ";
+
+        let short = estimate(state.clone(), sentence.repeat(2)).await;
+        let long = estimate(state, sentence.repeat(8)).await;
+
+        let ratio = long.estimated_duration_ms / short.estimated_duration_ms;
+        assert!((ratio - 4.0).abs() < 0.1, "ratio was {}", ratio);
+        assert!(long.characters > 3 * short.characters);
+        assert!(long.chunk_count > short.chunk_count);
+        assert!(long.estimated_compute_ms > short.estimated_compute_ms);
+        assert!(!long.calibrated);
+
+        // Nothing was synthesized
+        assert_eq!(log.calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_follows_voice_rates() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let state = AppState {
+            voice_rates: Arc::new(
+                VoiceRates::parse(Some("bm_george=90,af_sky=50"), &VoiceAliases::default())
+                    .unwrap(),
+            ),
+            ..AppState::for_tests(pool)
+        };
+        let estimate_voice = |voice: &str| {
+            let req = TTSRequest {
+                text: "The quick brown fox jumps over the lazy dog.".to_string(),
+                voice: voice.to_string(),
+                ..Default::default()
+            };
+            estimate_tts(State(state.clone()), HeaderMap::new(), Json(req))
+        };
+
+        let Json(slow) = estimate_voice("narrator").await.unwrap();
+        let Json(fast) = estimate_voice("af_sky").await.unwrap();
+        assert_eq!(slow.characters, fast.characters);
+        assert!(slow.estimated_duration_ms > fast.estimated_duration_ms);
+        assert!(slow.estimated_compute_ms > fast.estimated_compute_ms);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_uses_normalized_text_and_validates() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let state = AppState::for_tests(pool);
+
+        let response = estimate(state.clone(), "$100".to_string()).await;
+        assert_eq!(response.characters, "one hundred dollars".len());
+
+        let req = TTSRequest {
+            text: "   ".to_string(),
+            ..Default::default()
+        };
+        let result = estimate_tts(State(state), HeaderMap::new(), Json(req)).await;
+        assert!(matches!(result, Err(TtsError::EmptyText)));
+    }
+
+    // ===== Ellipsis Pause Tests =====
+
+    async fn tts_duration_ms(state: AppState, text: &str) -> f64 {
+        let req = TTSRequest {
+            text: text.to_string(),
+            ..Default::default()
+        };
+        let response = generate_tts(
+            State(state),
+            Query(TtsQuery::default()),
+            HeaderMap::new(),
+            Json(req),
+        )
+        .await
+        .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        audio::duration::calculate(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_ellipsis_pause_lengthens_audio() {
+        let text = "Well... I suppose so.";
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let mut state = AppState::for_tests(pool);
+
+        let literal = tts_duration_ms(state.clone(), text).await;
+        assert_eq!(log.texts(), vec![text]);
+
+        state.ellipsis_pause = Some(Duration::from_millis(500));
+        let paused = tts_duration_ms(state, text).await;
+        assert_eq!(&log.texts()[1..], ["Well...", "I suppose so."]);
+
+        // Two 100ms clips around the pause, against one clip before
+        assert!(paused > literal + 500.0, "{} vs {}", paused, literal);
+        assert!((paused - 700.0).abs() < 1.0, "{}", paused);
+    }
+
+    #[tokio::test]
+    async fn test_trailing_ellipsis_adds_no_pause() {
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let mut state = AppState::for_tests(pool);
+        state.ellipsis_pause = Some(Duration::from_millis(500));
+
+        let duration = tts_duration_ms(state, "And then…").await;
+        assert_eq!(log.calls(), 1);
+        assert!((duration - 100.0).abs() < 1.0, "{}", duration);
+    }
+
+    /// Engine that doesn't have `missing` loaded, and reports it like Kokoro
+    struct MissingVoiceEngine {
+        missing: &'static str,
+        inner: crate::kokoro::fake::FakeEngine,
+    }
+
+    impl crate::kokoro::SpeechEngine for MissingVoiceEngine {
+        fn speak(
+            &self,
+            text: &str,
+            output_path: &str,
+            style: &str,
+            speed: f32,
+            mono: bool,
+        ) -> std::result::Result<(), Box<dyn std::error::Error>> {
+            if style == self.missing {
+                return Err(format!("can not found from styles_map: {}", style).into());
+            }
+            self.inner.speak(text, output_path, style, speed, mono)
+        }
+    }
+
+    async fn tts_with_missing_voice(
+        fallback_voice: Option<&str>,
+        speed: Option<f32>,
+        voice_defaults: VoiceDefaults,
+    ) -> (Result<Response>, Vec<f32>) {
+        let log = Arc::new(crate::kokoro::fake::FakeEngineLog::default());
+        let engine = MissingVoiceEngine {
+            missing: "af_sky",
+            inner: crate::kokoro::fake::FakeEngine {
+                duration_ms: 100,
+                delay: Duration::ZERO,
+                log: log.clone(),
+            },
+        };
+        let state = AppState {
+            fallback_voice: fallback_voice.map(str::to_string),
+            voice_defaults: Arc::new(voice_defaults),
+            ..AppState::for_tests(TTSPool::from_engines(vec![Box::new(engine)]).unwrap())
+        };
+        let req = TTSRequest {
+            text: "Hello there.".to_string(),
+            voice: "af_sky".to_string(),
+            speed,
+            ..Default::default()
+        };
+        let result = generate_tts(
+            State(state),
+            Query(TtsQuery::default()),
+            HeaderMap::new(),
+            Json(req),
+        )
+        .await;
+        (result, log.speeds())
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_voice_falls_back() {
+        let (result, _) = tts_with_missing_voice(Some("af_heart"), None, Default::default()).await;
+        let response = result.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(VOICE_FALLBACK_HEADER).unwrap(),
+            "af_heart"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_voice_fails_without_fallback() {
+        let (result, _) = tts_with_missing_voice(None, None, Default::default()).await;
+        let err = result.unwrap_err();
+        assert!(
+            matches!(&err, TtsError::VoiceUnavailable(voice) if voice == "af_sky"),
+            "{:?}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fallback_uses_its_own_voice_defaults() {
+        let defaults = voice_defaults("af_sky=0.8, af_heart=1.2", "");
+
+        // Unset speed: the fallback's default, not the missing voice's
+        let (result, speeds) =
+            tts_with_missing_voice(Some("af_heart"), None, defaults.clone()).await;
+        assert!(result.is_ok());
+        assert_eq!(speeds, vec![1.2]);
+
+        // A requested speed is kept
+        let (result, speeds) = tts_with_missing_voice(Some("af_heart"), Some(1.5), defaults).await;
+        assert!(result.is_ok());
+        assert_eq!(speeds, vec![1.5]);
+    }
+
+    #[tokio::test]
+    async fn test_list_items_are_spoken_with_pauses() {
+        let text = "- eggs\n- milk\n- bread";
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let mut state = AppState::for_tests(pool);
+
+        // Off by default: the whole text in one go
+        tts_duration_ms(state.clone(), text).await;
+        assert_eq!(log.texts(), vec![text]);
+
+        state.lists = Some(ListConfig {
+            pause: Duration::from_millis(500),
+            speak_numbers: true,
+        });
+        let duration = tts_duration_ms(state.clone(), text).await;
+        assert_eq!(&log.texts()[1..], ["eggs", "milk", "bread"]);
+
+        // Three 100ms clips with a pause between each, none after the last
+        assert!((duration - 1300.0).abs() < 1.0, "{}", duration);
+
+        tts_duration_ms(state, "1. Whisk the eggs\n2. Add the milk").await;
+        assert_eq!(
+            &log.texts()[4..],
+            ["One, Whisk the eggs", "Two, Add the milk"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_say_punctuation_names_marks_for_the_engine() {
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let mut state = AppState::for_tests(pool);
+
+        // Off by default: punctuation reaches the engine as is
+        tts_duration_ms(state.clone(), "Hello, world.").await;
+        assert_eq!(log.texts(), vec!["Hello, world."]);
+
+        state.say_punctuation = Some(PunctuationNames::default());
+        tts_duration_ms(state, "Hello, world.").await;
+        assert_eq!(log.texts()[1], "Hello comma world period");
+    }
+
+    #[tokio::test]
+    async fn test_quote_styles() {
+        let text = "He said “Stop right there” and ran.";
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let mut state = AppState::for_tests(pool);
+
+        state.quotes = QuoteStyle::Spoken;
+        tts_duration_ms(state.clone(), text).await;
+        assert_eq!(
+            log.texts(),
+            vec!["He said quote Stop right there unquote and ran."]
+        );
+
+        state.quotes = QuoteStyle::Prosody(quotes::QuoteProsody {
+            speed_delta: 0.0,
+            pitch_scale: 1.25,
+            pause: Duration::from_millis(100),
+        });
+        let duration = tts_duration_ms(state, text).await;
+
+        let mut spoken: Vec<(String, f32)> = log.texts()[1..]
+            .iter()
+            .cloned()
+            .zip(log.speeds()[1..].iter().copied())
+            .collect();
+        spoken.sort_by(|a, b| a.0.cmp(&b.0));
+        // The engine runs slower to make room for the quotation's pitch shift
+        assert_eq!(
+            spoken,
+            vec![
+                ("\"Stop right there\"".to_string(), 0.8),
+                ("He said".to_string(), 1.0),
+                ("and ran.".to_string(), 1.0),
+            ]
+        );
+        // Three 100ms pieces, with a pause before and after the quotation
+        assert!((duration - 500.0).abs() < 2.0, "{}", duration);
+    }
+
+    #[tokio::test]
+    async fn test_parenthetical_asides_use_their_own_speed() {
+        let text = "He left (as it happens) early.";
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let mut state = AppState::for_tests(pool);
+
+        // Off by default: the whole text in one go
+        tts_duration_ms(state.clone(), text).await;
+        assert_eq!(log.texts(), vec![text]);
+
+        state.asides = Some(AsideConfig {
+            speed_delta: 0.2,
+            gain_db: -6.0,
+        });
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, "application/json".parse().unwrap());
+        let req = TTSRequest {
+            text: text.to_string(),
+            ..Default::default()
+        };
+        let response = generate_tts(State(state), Query(TtsQuery::default()), headers, Json(req))
+            .await
+            .unwrap();
+        let json = json_body(response).await;
+
+        let mut spoken: Vec<(String, f32)> = log.texts()[1..]
+            .iter()
+            .cloned()
+            .zip(log.speeds()[1..].iter().copied())
+            .collect();
+        spoken.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            spoken,
+            vec![
+                ("(as it happens)".to_string(), 1.2),
+                ("He left".to_string(), 1.0),
+                ("early.".to_string(), 1.0),
+            ]
+        );
+
+        // The parentheses are kept in the metadata
+        assert_eq!(json["metadata"]["text"], text);
+        let phrases = json["metadata"]["phrases"].as_array().unwrap();
+        assert!(phrases
+            .iter()
+            .any(|phrase| phrase["text"].as_str().unwrap().contains("(as it happens)")));
+    }
+
+    // ===== Phonemes Tests =====
+
+    #[tokio::test]
+    #[ignore = "needs the espeak-ng data Kokoro phonemizes with"]
+    async fn test_phonemes_for_normalized_text_without_audio() {
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let state = AppState::for_tests(pool);
+
+        let req = TTSRequest {
+            text: "Hello, it costs $5.".to_string(),
+            ..Default::default()
+        };
+        let Json(response) = phonemize_tts(State(state), HeaderMap::new(), Json(req))
+            .await
+            .unwrap();
+        assert_eq!(response.normalized, "Hello, it costs five dollars.");
+        assert!(!response.phonemes.is_empty());
+        assert_eq!(log.calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_phonemes_reject_empty_text() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let req = TTSRequest {
+            text: "".to_string(),
+            ..Default::default()
+        };
+        let result = phonemize_tts(
+            State(AppState::for_tests(pool)),
+            HeaderMap::new(),
+            Json(req),
+        )
+        .await;
+        assert!(matches!(result, Err(TtsError::EmptyText)));
+    }
+
+    #[tokio::test]
+    async fn test_phonemes_do_not_wait_for_busy_engines() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let state = AppState::for_tests(pool);
+        let _busy = state.tts_pool.acquire().await.unwrap();
+
+        let req = TTSRequest {
+            text: "Hello world.".to_string(),
+            ..Default::default()
+        };
+        // Whether espeak is available or not, the answer comes without an engine
+        let answered = tokio::time::timeout(
+            Duration::from_secs(5),
+            phonemize_tts(State(state), HeaderMap::new(), Json(req)),
+        )
+        .await;
+        assert!(answered.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs the espeak-ng data Kokoro phonemizes with"]
+    async fn test_phonemes_route() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let app = create_router(AppState::for_tests(pool));
+
+        let response = send(
+            &app,
+            "POST",
+            "/tts/phonemes",
+            Some(r#"{"text": "Hello world."}"#),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = json_body(response).await;
+        assert!(!json["phonemes"].as_str().unwrap().is_empty());
+    }
+
+    // ===== Normalization Opt-Out Tests =====
+
+    #[tokio::test]
+    async fn test_normalize_false_sends_raw_text() {
+        for (normalize, expected) in [
+            (true, "It costs one hundred dollars."),
+            (false, "It costs $100."),
+        ] {
+            let (pool, log) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+            let req = TTSRequest {
+                text: "It costs $100.".to_string(),
+                normalize,
+                ..Default::default()
+            };
+            let response = generate_tts(
+                State(AppState::for_tests(pool)),
+                Query(TtsQuery::default()),
+                HeaderMap::new(),
+                Json(req),
+            )
+            .await
+            .unwrap();
+            let _ = axum::body::to_bytes(response.into_body(), usize::MAX).await;
+
+            assert_eq!(log.texts(), vec![expected.to_string()]);
+        }
+    }
+
+    // ===== Voice Alias Tests =====
+
+    async fn spoken_voice(
+        state: AppState,
+        log: &crate::kokoro::fake::FakeEngineLog,
+        voice: &str,
+    ) -> String {
+        let req = TTSRequest {
+            text: "Hello world".to_string(),
+            voice: voice.to_string(),
+            ..Default::default()
+        };
+        let response = generate_tts(
+            State(state),
+            Query(TtsQuery::default()),
+            HeaderMap::new(),
+            Json(req),
+        )
+        .await
+        .unwrap();
+        let _ = axum::body::to_bytes(response.into_body(), usize::MAX).await;
+
+        log.voices().last().cloned().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_voice_aliases_resolved_before_synthesis() {
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let state = AppState::for_tests(pool);
+
+        assert_eq!(
+            spoken_voice(state.clone(), &log, "narrator").await,
+            "bm_george"
+        );
+        assert_eq!(spoken_voice(state.clone(), &log, "Alloy").await, "af_alloy");
+        assert_eq!(
+            spoken_voice(state.clone(), &log, "bf_lily").await,
+            "bf_lily"
+        );
+        // Kokoro's own mix syntax reaches the engine unchanged
+        assert_eq!(
+            spoken_voice(state, &log, "af_sarah.4+af_nicole.6").await,
+            "af_sarah.4+af_nicole.6"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_voice_alias_overrides_apply() {
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let mut state = AppState::for_tests(pool);
+        state.voice_aliases = Arc::new(VoiceAliases::parse(Some("narrator=bm_lewis")).unwrap());
+
+        assert_eq!(spoken_voice(state, &log, "narrator").await, "bm_lewis");
+    }
+
+    #[tokio::test]
+    async fn test_voice_mix_is_blended_for_the_engine() {
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let state = AppState::for_tests(pool);
+
+        assert_eq!(
+            spoken_voice(state.clone(), &log, "af_heart:0.6+am_adam:0.4").await,
+            "af_heart.6+am_adam.4"
+        );
+        // Aliases work inside mixes, and weights are relative
+        assert_eq!(
+            spoken_voice(state.clone(), &log, "alloy:3 + narrator:1").await,
+            "af_alloy.8+bm_george.2"
+        );
+
+        let mut req = TTSRequest {
+            text: "Hello world".to_string(),
+            voice_mix: Some(vec![
+                VoiceMixPart {
+                    voice: "af_heart".to_string(),
+                    weight: 1.0,
+                },
+                VoiceMixPart {
+                    voice: "am_adam".to_string(),
+                    weight: 1.0,
+                },
+            ]),
+            ..Default::default()
+        };
+        super::validate_tts_request(&state, &mut req, MAX_TEXT_LENGTH).unwrap();
+        assert_eq!(req.voice, "af_heart.5+am_adam.5");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_voice_mix_is_rejected() {
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let state = AppState::for_tests(pool);
+
+        for voice in ["af_heart:0.5+xx_nobody:0.5", "af_heart:-1+am_adam:2"] {
+            let mut req = TTSRequest {
+                text: "Hello world".to_string(),
+                voice: voice.to_string(),
+                ..Default::default()
+            };
+            let result = super::validate_tts_request(&state, &mut req, MAX_TEXT_LENGTH);
+            assert!(
+                matches!(result, Err(TtsError::InvalidRequest(_))),
+                "{}",
+                voice
+            );
+        }
+        assert_eq!(log.calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_voice_mix_preview_returns_normalized_weights_and_sample() {
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let app = create_router(AppState::for_tests(pool));
+
+        let response = send(
+            &app,
+            "POST",
+            "/voices/mix/preview",
+            Some(r#"{"voice_mix": [{"voice": "heart", "weight": 3}, {"voice": "am_adam"}]}"#),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = json_body(response).await;
+
+        assert_eq!(
+            json["weights"],
+            serde_json::json!([
+                { "voice": "af_heart", "weight": 0.75 },
+                { "voice": "am_adam", "weight": 0.25 }
+            ])
+        );
+        let audio = base64::decode(json["audio_base64"].as_str().unwrap()).unwrap();
+        assert_eq!(&audio[..4], b"RIFF");
+
+        // The fixed sentence is spoken with the blended style
+        assert_eq!(log.texts(), vec![MIX_PREVIEW_TEXT.to_string()]);
+        assert_eq!(
+            log.voices(),
+            vec![json["voice"].as_str().unwrap().to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_voice_mix_preview_rejects_unknown_voices() {
+        let (pool, log) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let app = create_router(AppState::for_tests(pool));
+
+        for body in [
+            r#"{"voice": "af_heart:0.5+xx_nobody:0.5"}"#,
+            r#"{"voice_mix": [{"voice": "af_heart"}, {"voice": "xx_nobody"}]}"#,
+            r#"{"voice": "af_heart"}"#,
+        ] {
+            let response = send(&app, "POST", "/voices/mix/preview", Some(body)).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", body);
+        }
+        let response = send(
+            &app,
+            "POST",
+            "/voices/mix/preview",
+            Some(r#"{"voice": "af_heart+xx_nobody"}"#),
+        )
+        .await;
+        let json = json_body(response).await;
+        assert!(json["error"].as_str().unwrap().contains("xx_nobody"));
+        assert_eq!(log.calls(), 0);
+    }
+
+    // ===== Download Filename Tests =====
+
+    async fn disposition_for(filename: Option<&str>) -> String {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let req = TTSRequest {
+            text: "Hello world".to_string(),
+            voice: "af_heart".to_string(),
+            filename: filename.map(str::to_string),
+            ..Default::default()
+        };
+        let response = generate_tts(
+            State(AppState::for_tests(pool)),
+            Query(TtsQuery::default()),
+            HeaderMap::new(),
+            Json(req),
+        )
+        .await
+        .unwrap();
+
+        response.headers()[header::CONTENT_DISPOSITION]
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_requested_filename_is_sanitized_and_reflected() {
+        assert_eq!(
+            disposition_for(Some("../secret/chapter 1.wav")).await,
+            "attachment; filename=\"chapter 1.wav\""
+        );
+        assert_eq!(
+            disposition_for(Some("a\"\r\nX-Evil: 1")).await,
+            "attachment; filename=\"a___X-Evil_ 1.wav\""
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_filename_gets_default() {
+        let header = disposition_for(None).await;
+        assert!(
+            header.starts_with("attachment; filename=\"speech_"),
+            "{}",
+            header
+        );
+        assert!(header.ends_with("_af_heart.wav\""), "{}", header);
+    }
+
+    // ===== WAV Metadata Tests =====
+
+    async fn generated_wav(state: AppState, metadata: Option<bool>) -> Vec<u8> {
+        let req = TTSRequest {
+            text: "Hello world".to_string(),
+            voice: "af_heart".to_string(),
+            speed: Some(1.5),
+            metadata,
+            ..Default::default()
+        };
+        let response = generate_tts(
+            State(state),
+            Query(TtsQuery::default()),
+            HeaderMap::new(),
+            Json(req),
+        )
+        .await
+        .unwrap();
+
+        axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap()
+            .to_vec()
+    }
+
+    #[tokio::test]
+    async fn test_metadata_embedded_when_requested() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let wav = generated_wav(AppState::for_tests(pool), Some(true)).await;
+
+        let info = audio::wav_metadata::read_info(&wav).unwrap();
+        assert!(info.contains(&(*b"INAM", "Hello world".to_string())));
+        assert!(info.contains(&(*b"IART", "af_heart".to_string())));
+        assert!(info.contains(&(*b"ICMT", "voice=af_heart speed=1.5".to_string())));
+        assert!(hound::WavReader::new(std::io::Cursor::new(&wav)).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_metadata_follows_server_default() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let mut state = AppState::for_tests(pool);
+
+        let wav = generated_wav(state.clone(), None).await;
+        assert!(audio::wav_metadata::read_info(&wav).unwrap().is_empty());
+
+        state.wav_metadata = true;
+        let wav = generated_wav(state.clone(), None).await;
+        assert!(!audio::wav_metadata::read_info(&wav).unwrap().is_empty());
+
+        // A request can still opt out
+        let wav = generated_wav(state, Some(false)).await;
+        assert!(audio::wav_metadata::read_info(&wav).unwrap().is_empty());
+    }
+
+    // ===== Merged Stream Tests =====
+
+    async fn post_stream(app: Router, uri: &str, accept: Option<&str>, body: &str) -> Response {
+        use tower::ServiceExt;
+
+        let mut request = axum::http::Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header(header::CONTENT_TYPE, "application/json");
+        if let Some(accept) = accept {
+            request = request.header(header::ACCEPT, accept);
+        }
+        app.oneshot(request.body(Body::from(body.to_string())).unwrap())
+            .await
+            .unwrap()
+    }
+
+    fn long_text_body() -> String {
+        let text = "This sentence is long enough to help fill a chunk of text. ".repeat(12);
+        serde_json::json!({ "text": text }).to_string()
+    }
+
+    #[tokio::test]
+    async fn test_stream_merge_returns_single_wav() {
+        let (pool, log) = crate::kokoro::fake::fake_pool(2, 400, Duration::ZERO);
+        let app = create_router(AppState::for_tests(pool));
+
+        let response = post_stream(app, "/tts/stream?merge=true", None, &long_text_body()).await;
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "audio/wav");
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let duration = audio::duration::calculate(&body).unwrap();
+
+        // One WAV as long as every chunk put together
+        let chunks = log.calls();
+        assert!(chunks > 1, "expected several chunks, got {}", chunks);
+        assert!(
+            (duration - 400.0 * chunks as f64).abs() < 1.0,
+            "duration {} for {} chunks",
+            duration,
+            chunks
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_accept_wav_selects_merge() {
+        for (accept, merged) in [
+            (Some("audio/wav"), true),
+            (Some("audio/wav;q=1.0, */*;q=0.1"), true),
+            (Some("multipart/mixed, audio/wav"), false),
+            (None, false),
+        ] {
+            let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+            let app = create_router(AppState::for_tests(pool));
+
+            let response =
+                post_stream(app, "/tts/stream", accept, r#"{"text": "Hello world"}"#).await;
+            let content_type = response.headers()[header::CONTENT_TYPE].to_str().unwrap();
+            assert_eq!(content_type == "audio/wav", merged, "Accept: {:?}", accept);
+        }
+    }
+
+    /// Post a `multipart/form-data` body with one file part and the given fields
+    async fn upload_file(file_type: &str, contents: &str, fields: &[(&str, &str)]) -> Response {
+        use tower::ServiceExt;
+
+        let mut body = String::new();
+        for (name, value) in fields {
+            body.push_str(&format!(
+                "--form-boundary\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                name, value
+            ));
+        }
+        body.push_str(&format!(
+            "--form-boundary\r\n\
+             Content-Disposition: form-data; name=\"file\"; filename=\"chapter.txt\"\r\n\
+             Content-Type: {}\r\n\r\n{}\r\n--form-boundary--\r\n",
+            file_type, contents
+        ));
+
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let app = create_router(AppState::for_tests(pool));
+        let request = axum::http::Request::builder()
+            .method("POST")
+            .uri("/tts/file")
+            .header(
+                header::CONTENT_TYPE,
+                "multipart/form-data; boundary=form-boundary",
+            )
+            .body(Body::from(body))
+            .unwrap();
+        app.oneshot(request).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_tts_file_streams_multipart_audio() {
+        let response = upload_file(
+            "text/plain; charset=utf-8",
+            "Hello world. This is a test.",
+            &[("voice", "am_adam"), ("speed", "1.2")],
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let content_type = response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(content_type.starts_with("multipart/mixed"));
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8_lossy(&body);
+        assert!(body.contains("Content-Type: audio/wav"));
+        assert!(body.contains("This is a test."));
+    }
+
+    #[tokio::test]
+    async fn test_tts_file_rejects_non_text_upload() {
+        let response = upload_file("image/png", "not really a png", &[]).await;
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[tokio::test]
+    async fn test_tts_file_rejects_oversized_file() {
+        let response = upload_file("text/plain", &"a".repeat(MAX_TEXT_LENGTH + 1), &[]).await;
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_tts_file_requires_form_data() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let app = create_router(AppState::for_tests(pool));
+        let response = send(&app, "POST", "/tts/file", Some(r#"{"text": "Hello"}"#)).await;
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[test]
+    fn test_file_upload_request_reads_fields() {
+        let part = |name: &str, content_type: Option<&str>, data: &str| FormPart {
+            name: name.to_string(),
+            filename: None,
+            content_type: content_type.map(str::to_string),
+            data: bytes::Bytes::from(data.to_string()),
+        };
+
+        let req = file_upload_request(
+            &[
+                part("file", None, "\u{feff}Once upon a time."),
+                part("voice", None, " bf_emma "),
+            ],
+            MAX_TEXT_LENGTH,
+        )
+        .unwrap();
+        assert_eq!(req.text, "Once upon a time.");
+        assert_eq!(req.voice, "bf_emma");
+        assert_eq!(req.speed, None);
+
+        assert!(matches!(
+            file_upload_request(&[part("voice", None, "bf_emma")], MAX_TEXT_LENGTH),
+            Err(TtsError::InvalidRequest(_))
+        ));
+        assert!(matches!(
+            file_upload_request(
+                &[part("file", None, "Hi"), part("speed", None, "fast")],
+                MAX_TEXT_LENGTH
+            ),
+            Err(TtsError::InvalidRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_readiness_reports_espeak_data() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let app = create_router(AppState::for_tests(pool));
+        let response = send(&app, "GET", "/health/ready", None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = json_body(response).await;
+        assert_eq!(body["status"], "ready");
+        assert_eq!(body["checks"][1]["name"], "espeak_data");
+        assert_eq!(body["checks"][1]["ok"], true);
+
+        let dir = tempfile::tempdir().unwrap();
+        let (pool, _) = crate::kokoro::fake::fake_pool(1, 100, Duration::ZERO);
+        let app = create_router(AppState {
+            espeak_data_dir: Some(dir.path().to_path_buf()),
+            ..AppState::for_tests(pool)
+        });
+        let response = send(&app, "GET", "/health/ready", None).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = json_body(response).await;
+        assert_eq!(body["status"], "not_ready");
+        assert_eq!(body["checks"][0]["ok"], true);
+        assert_eq!(body["checks"][1]["ok"], false);
+        let detail = body["checks"][1]["detail"].as_str().unwrap();
+        assert!(detail.contains("espeak-ng-data (not found)"), "{}", detail);
+    }
+
+    #[tokio::test]
+    async fn test_bit_depth_sets_wav_bits_per_sample() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(2, 100, Duration::ZERO);
+        let app = create_router(AppState {
+            chunking_min_length: 20,
+            ..AppState::for_tests(pool)
+        });
+
+        // Single and chunked requests each go through their own output path
+        for text in [
+            "Hello there.",
+            "The first sentence is here. And a second one.",
+        ] {
+            for bits in [8, 16, 24] {
+                let body = serde_json::json!({ "text": text, "bit_depth": bits }).to_string();
+                let response = send(&app, "POST", "/tts", Some(&body)).await;
+                assert_eq!(response.status(), StatusCode::OK);
+                let audio = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+
+                let reader = hound::WavReader::new(std::io::Cursor::new(audio.to_vec())).unwrap();
+                assert_eq!(reader.spec().bits_per_sample, bits, "{:?}", text);
+                assert!(crate::audio::duration::calculate(&audio).unwrap() >= 100.0);
+            }
+        }
+
+        let body = serde_json::json!({ "text": "Hello there.", "bit_depth": 12 }).to_string();
+        let response = send(&app, "POST", "/tts", Some(&body)).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_mono_wav_header() {
+        let (pool, _) = crate::kokoro::fake::fake_pool(2, 100, Duration::ZERO);
+        let app = create_router(AppState {
+            chunking_min_length: 20,
+            ..AppState::for_tests(pool)
+        });
+
+        for text in [
+            "Hello there.",
+            "The first sentence is here. And a second one.",
+        ] {
+            for bits in [16u16, 24] {
+                let body = serde_json::json!({ "text": text, "mono": true, "bit_depth": bits })
+                    .to_string();
+                let response = send(&app, "POST", "/tts", Some(&body)).await;
+                assert_eq!(response.status(), StatusCode::OK);
+                let audio = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+
+                // Read the fmt chunk directly rather than trusting a decoder
+                let fmt = audio.windows(4).position(|id| id == b"fmt ").unwrap() + 8;
+                let u16_at = |at: usize| u16::from_le_bytes([audio[at], audio[at + 1]]);
+                let u32_at = |at: usize| {
+                    u32::from_le_bytes([audio[at], audio[at + 1], audio[at + 2], audio[at + 3]])
+                };
+                let channels = u16_at(fmt + 2);
+                let sample_rate = u32_at(fmt + 4);
+                let byte_rate = u32_at(fmt + 8);
+                let block_align = u16_at(fmt + 12);
+                let bits_per_sample = u16_at(fmt + 14);
+
+                assert_eq!(channels, 1, "{:?}", text);
+                assert_eq!(bits_per_sample, bits);
+                assert_eq!(block_align, bits / 8);
+                assert_eq!(byte_rate, sample_rate * u32::from(bits / 8));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_post_processing_is_deterministic() {
+        // The engine's output is fixed, so chunking, pauses, pool scheduling,
+        // resampling and pitch shifting must add no variation of their own
+        let text = "The first sentence is here. Then a second one... \
+                    And the third sentence closes the paragraph.";
+        let app = create_router(AppState {
+            chunking_min_length: 20,
+            ellipsis_pause: Some(Duration::from_millis(300)),
+            ..AppState::for_tests(crate::kokoro::fake::tone_pool(3))
+        });
+        let body = serde_json::json!({
+            "text": text,
+            "voice": "am_adam",
+            "speed": 1.2,
+            "pitch": 1.3,
+            "sample_rate": 16000
+        })
+        .to_string();
+
+        let mut outputs = Vec::new();
+        for _ in 0..3 {
+            let response = send(&app, "POST", "/tts", Some(&body)).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let audio = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            outputs.push(audio);
+        }
+
+        // Not silence, so processing had real samples to get wrong
+        let reader = hound::WavReader::new(std::io::Cursor::new(outputs[0].to_vec())).unwrap();
+        assert_eq!(reader.spec().sample_rate, 16000);
+        assert!(reader.into_samples::<i16>().any(|s| s.unwrap() != 0));
+
+        assert_eq!(outputs[0], outputs[1]);
+        assert_eq!(outputs[0], outputs[2]);
+    }
 }
@@ -0,0 +1,119 @@
+/// Zip archives holding a request's chunks as separate WAV files
+///
+/// Chunks are added in reading order as they are generated, each returning
+/// the bytes of its zip entry so the archive can be streamed as it grows.
+/// Finishing appends `manifest.json`, which lists every chunk's file, text,
+/// duration and offset.
+use crate::audio;
+use crate::error::{Result, TtsError};
+use crate::models::{ArchiveChunk, ArchiveManifest};
+use crate::utils::zip::ZipStream;
+
+/// Name of the manifest entry, always the last file in the archive
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+pub struct ArchiveBuilder {
+    zip: ZipStream,
+    manifest: ArchiveManifest,
+}
+
+impl ArchiveBuilder {
+    pub fn new(voice: &str) -> Self {
+        Self {
+            zip: ZipStream::new(),
+            manifest: ArchiveManifest {
+                voice: voice.to_string(),
+                total_duration_ms: 0.0,
+                chunks: Vec::new(),
+            },
+        }
+    }
+
+    /// Zip entry for the next chunk's audio
+    pub fn add_chunk(&mut self, text: &str, audio_data: &[u8]) -> Result<Vec<u8>> {
+        let index = self.manifest.chunks.len();
+        let file = chunk_file_name(index);
+        let duration_ms = audio::duration::calculate(audio_data)?;
+
+        let entry = self
+            .zip
+            .entry(&file, audio_data)
+            .map_err(TtsError::Unknown)?;
+
+        self.manifest.chunks.push(ArchiveChunk {
+            index,
+            file,
+            text: text.to_string(),
+            duration_ms,
+            start_ms: self.manifest.total_duration_ms,
+        });
+        self.manifest.total_duration_ms += duration_ms;
+        Ok(entry)
+    }
+
+    /// Length of the chunks added so far
+    pub fn total_duration_ms(&self) -> f64 {
+        self.manifest.total_duration_ms
+    }
+
+    /// Closing bytes of the archive: the manifest entry and zip directory
+    pub fn finish(mut self) -> Result<Vec<u8>> {
+        let manifest = serde_json::to_vec_pretty(&self.manifest)?;
+        let mut bytes = self
+            .zip
+            .entry(MANIFEST_FILE, &manifest)
+            .map_err(TtsError::Unknown)?;
+        bytes.extend(self.zip.finish().map_err(TtsError::Unknown)?);
+        Ok(bytes)
+    }
+}
+
+/// `chunk_001.wav` for the first chunk, so files sort in reading order
+fn chunk_file_name(index: usize) -> String {
+    format!("chunk_{:03}.wav", index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::zip::read_stored_entries;
+    use hound::{SampleFormat, WavSpec};
+
+    fn wav(ms: usize) -> Vec<u8> {
+        let spec = WavSpec {
+            channels: 1,
+            sample_rate: 24000,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+        audio::wav_utils::write_normalized_samples(&vec![0.25; ms * 24], spec).unwrap()
+    }
+
+    #[test]
+    fn test_archive_has_chunks_then_manifest() {
+        let mut builder = ArchiveBuilder::new("af_heart");
+        let mut archive = builder.add_chunk("First.", &wav(500)).unwrap();
+        archive.extend(builder.add_chunk("Second.", &wav(250)).unwrap());
+        assert_eq!(builder.total_duration_ms(), 750.0);
+        archive.extend(builder.finish().unwrap());
+
+        let entries = read_stored_entries(&archive).unwrap();
+        let names: Vec<&str> = entries.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["chunk_001.wav", "chunk_002.wav", MANIFEST_FILE]);
+        assert_eq!(entries[1].1, wav(250));
+
+        let manifest: serde_json::Value = serde_json::from_slice(&entries[2].1).unwrap();
+        assert_eq!(manifest["voice"], "af_heart");
+        assert_eq!(manifest["total_duration_ms"], 750.0);
+        assert_eq!(manifest["chunks"][1]["file"], "chunk_002.wav");
+        assert_eq!(manifest["chunks"][1]["text"], "Second.");
+        assert_eq!(manifest["chunks"][1]["duration_ms"], 250.0);
+        assert_eq!(manifest["chunks"][1]["start_ms"], 500.0);
+    }
+
+    #[test]
+    fn test_chunk_file_names_sort_in_order() {
+        assert_eq!(chunk_file_name(0), "chunk_001.wav");
+        assert_eq!(chunk_file_name(41), "chunk_042.wav");
+    }
+}
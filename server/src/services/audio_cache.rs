@@ -0,0 +1,253 @@
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use uuid::Uuid;
+
+use crate::models::TTSRequest;
+use crate::utils::hash::fnv1a_128;
+
+/// Persistent cache of generated WAV files, surviving server restarts
+///
+/// Entries are stored as `<hash>.wav` in the cache directory. Hits refresh the
+/// file's modification time, and once the directory grows beyond `max_bytes`
+/// the least recently used entries (oldest mtime) are evicted.
+pub struct DiskCache {
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+impl DiskCache {
+    /// Open (creating if needed) a cache directory capped at `max_bytes`
+    pub fn new(dir: impl Into<PathBuf>, max_bytes: u64) -> std::io::Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir, max_bytes })
+    }
+
+    /// Cache directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Size cap in bytes
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    /// Cache key for a request whose text has already been normalized
+    ///
+    /// Everything that changes the generated audio is part of the key: the
+    /// model (see [`TTSPool::model_id`]), the spoken text, voice, speed,
+    /// pitch and output format. Audio cached before the model files were
+    /// replaced is never served for the new model.
+    ///
+    /// [`TTSPool::model_id`]: crate::kokoro::TTSPool::model_id
+    pub fn key(model_id: &str, normalized_text: &str, req: &TTSRequest) -> String {
+        let material = format!(
+            "{}\0{}\0{}\0{}\0{}\0{}\0{}\0{}",
+            model_id,
+            normalized_text,
+            req.voice,
+            req.speed(),
+            req.pitch(),
+            req.sample_rate.unwrap_or(0),
+            req.bit_depth.unwrap_or(16),
+            req.mono
+        );
+        format!("{:032x}", fnv1a_128(material.as_bytes()))
+    }
+
+    /// Look up cached audio, marking the entry as recently used
+    pub async fn get(&self, key: &str) -> Option<Vec<u8>> {
+        let path = self.entry_path(key);
+        let data = tokio::fs::read(&path).await.ok()?;
+
+        if let Err(e) = touch(&path) {
+            tracing::debug!("Failed to refresh cache entry {:?}: {}", path, e);
+        }
+
+        Some(data)
+    }
+
+    /// Store audio under `key`, evicting old entries if over the size cap
+    pub async fn put(&self, key: &str, data: &[u8]) -> std::io::Result<()> {
+        // Write to a unique temp name first so readers never see a partial file
+        let staging = self.dir.join(format!("{}.{}.tmp", key, Uuid::new_v4()));
+        tokio::fs::write(&staging, data).await?;
+        tokio::fs::rename(&staging, self.entry_path(key)).await?;
+
+        let dir = self.dir.clone();
+        let max_bytes = self.max_bytes;
+        let removed = tokio::task::spawn_blocking(move || evict(&dir, max_bytes)).await??;
+        if removed > 0 {
+            tracing::debug!("Evicted {} audio cache entries", removed);
+        }
+
+        Ok(())
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.wav", key))
+    }
+}
+
+/// Remove the least recently used entries until the cache fits in `max_bytes`
+///
+/// Returns the number of entries removed.
+fn evict(dir: &Path, max_bytes: u64) -> std::io::Result<usize> {
+    let mut entries: Vec<(SystemTime, u64, PathBuf)> = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("wav") {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+        entries.push((modified, metadata.len(), path));
+    }
+
+    let mut total: u64 = entries.iter().map(|(_, len, _)| len).sum();
+    entries.sort_by_key(|(modified, _, _)| *modified);
+
+    let mut removed = 0;
+    for (_, len, path) in entries {
+        if total <= max_bytes {
+            break;
+        }
+        if std::fs::remove_file(&path).is_ok() {
+            total -= len;
+            removed += 1;
+        }
+    }
+
+    Ok(removed)
+}
+
+/// Set a file's modification time to now
+fn touch(path: &Path) -> std::io::Result<()> {
+    std::fs::File::options()
+        .write(true)
+        .open(path)?
+        .set_modified(SystemTime::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const MODEL: &str = "kokoro-v1.0.onnx:325532387:1";
+
+    fn request(voice: &str, speed: f32) -> TTSRequest {
+        TTSRequest {
+            text: "Hello".to_string(),
+            voice: voice.to_string(),
+            speed: Some(speed),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_key_depends_on_all_inputs() {
+        let base = DiskCache::key(MODEL, "hello", &request("bf_lily", 1.0));
+
+        assert_eq!(
+            base,
+            DiskCache::key(MODEL, "hello", &request("bf_lily", 1.0))
+        );
+        assert_ne!(
+            base,
+            DiskCache::key(MODEL, "hello!", &request("bf_lily", 1.0))
+        );
+        assert_ne!(
+            base,
+            DiskCache::key(MODEL, "hello", &request("af_heart", 1.0))
+        );
+        assert_ne!(
+            base,
+            DiskCache::key(MODEL, "hello", &request("bf_lily", 1.2))
+        );
+
+        let mono = TTSRequest {
+            mono: true,
+            ..request("bf_lily", 1.0)
+        };
+        assert_ne!(base, DiskCache::key(MODEL, "hello", &mono));
+
+        let resampled = TTSRequest {
+            sample_rate: Some(16000),
+            ..request("bf_lily", 1.0)
+        };
+        assert_ne!(base, DiskCache::key(MODEL, "hello", &resampled));
+
+        let requantized = TTSRequest {
+            bit_depth: Some(24),
+            ..request("bf_lily", 1.0)
+        };
+        assert_ne!(base, DiskCache::key(MODEL, "hello", &requantized));
+
+        let other_model = "kokoro-v1.0.onnx:325532387:2";
+        assert_ne!(
+            base,
+            DiskCache::key(other_model, "hello", &request("bf_lily", 1.0))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cache_hit_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = DiskCache::key(MODEL, "hello", &request("bf_lily", 1.0));
+
+        {
+            let cache = DiskCache::new(dir.path(), 1024 * 1024).unwrap();
+            assert!(cache.get(&key).await.is_none());
+            cache.put(&key, b"RIFF audio").await.unwrap();
+        }
+
+        // A fresh instance pointing at the same directory sees the entry
+        let cache = DiskCache::new(dir.path(), 1024 * 1024).unwrap();
+        assert_eq!(cache.get(&key).await.unwrap(), b"RIFF audio");
+    }
+
+    #[tokio::test]
+    async fn test_eviction_removes_least_recently_used() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::new(dir.path(), 250).unwrap();
+
+        cache.put("first", &[0u8; 100]).await.unwrap();
+        cache.put("second", &[0u8; 100]).await.unwrap();
+
+        // Make "first" clearly older, then use it so "second" becomes the LRU entry
+        let an_hour_ago = SystemTime::now() - Duration::from_secs(3600);
+        for key in ["first", "second"] {
+            std::fs::File::options()
+                .write(true)
+                .open(cache.entry_path(key))
+                .unwrap()
+                .set_modified(an_hour_ago)
+                .unwrap();
+        }
+        assert!(cache.get("first").await.is_some());
+
+        cache.put("third", &[0u8; 100]).await.unwrap();
+
+        assert!(cache.get("first").await.is_some());
+        assert!(cache.get("second").await.is_none());
+        assert!(cache.get("third").await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_put_leaves_no_staging_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::new(dir.path(), 1024).unwrap();
+        cache.put("entry", b"data").await.unwrap();
+
+        let names: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        assert_eq!(names, vec!["entry.wav".to_string()]);
+    }
+}
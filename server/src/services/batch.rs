@@ -0,0 +1,278 @@
+/// Synthesis of long text read from a stream, for CLI `-` mode
+///
+/// Text is read a paragraph at a time (paragraphs end at blank lines), so
+/// synthesis starts before the input ends. Each paragraph is chunked like
+/// `/tts` text, and every chunk is written as `output_001.wav`,
+/// `output_002.wav`, ... or, when concatenating, collected into one
+/// `output.wav`. `output.json` holds the metadata of every chunk, with
+/// offsets as if the files were played back to back.
+use std::io::BufRead;
+use std::path::PathBuf;
+
+use crate::audio;
+use crate::chunking::{chunk_text, ChunkingConfig};
+use crate::error::{Result, TtsError};
+use crate::kokoro::{engine_error, SpeechEngine};
+use crate::models::ChunkMetadata;
+use crate::services::metadata_builder;
+use crate::text_processing::normalization::{self, NormalizationConfig};
+
+/// Metadata of every chunk, written next to the audio
+pub const METADATA_FILE: &str = "output.json";
+
+/// The single WAV written when concatenating
+pub const CONCATENATED_FILE: &str = "output.wav";
+
+/// How the synthesized chunks are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchOutput {
+    /// One numbered WAV per chunk, written as soon as it is synthesized
+    Files,
+    /// All chunks joined into one WAV at the end
+    Concatenated,
+}
+
+pub struct BatchOptions {
+    pub voice: String,
+    pub speed: f32,
+    pub normalization: NormalizationConfig,
+    pub output: BatchOutput,
+    /// Directory the audio and metadata are written to
+    pub output_dir: PathBuf,
+}
+
+/// What a batch run wrote
+#[derive(Debug)]
+pub struct BatchResult {
+    /// Audio files, in reading order
+    pub audio_files: Vec<PathBuf>,
+    pub metadata_file: PathBuf,
+    pub chunks: Vec<ChunkMetadata>,
+}
+
+impl BatchResult {
+    /// Length of all chunks played back to back
+    pub fn total_duration_ms(&self) -> f64 {
+        self.chunks.iter().map(|chunk| chunk.duration_ms).sum()
+    }
+}
+
+/// Synthesize everything `reader` yields, writing audio and metadata files
+pub fn synthesize_reader(
+    engine: &dyn SpeechEngine,
+    reader: impl BufRead,
+    options: &BatchOptions,
+) -> Result<BatchResult> {
+    let mut batch = Batch {
+        engine,
+        options,
+        chunks: Vec::new(),
+        audio_files: Vec::new(),
+        wavs: Vec::new(),
+        offset_ms: 0.0,
+    };
+
+    // Hard-wrapped lines are joined; a blank line ends the paragraph
+    let mut paragraph = String::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            batch.add_paragraph(&paragraph)?;
+            paragraph.clear();
+        } else {
+            if !paragraph.is_empty() {
+                paragraph.push(' ');
+            }
+            paragraph.push_str(line.trim());
+        }
+    }
+    batch.add_paragraph(&paragraph)?;
+
+    batch.finish()
+}
+
+struct Batch<'a> {
+    engine: &'a dyn SpeechEngine,
+    options: &'a BatchOptions,
+    chunks: Vec<ChunkMetadata>,
+    audio_files: Vec<PathBuf>,
+    /// Chunk audio held for concatenation
+    wavs: Vec<Vec<u8>>,
+    offset_ms: f64,
+}
+
+impl Batch<'_> {
+    fn add_paragraph(&mut self, paragraph: &str) -> Result<()> {
+        if paragraph.trim().is_empty() {
+            return Ok(());
+        }
+
+        for chunk in chunk_text(paragraph, &ChunkingConfig::default()) {
+            let normalized =
+                normalization::normalize_simple_with_config(&chunk, &self.options.normalization);
+            if !normalization::has_speakable_content(&normalized) {
+                continue;
+            }
+            self.add_chunk(&chunk, &normalized)?;
+        }
+        Ok(())
+    }
+
+    fn add_chunk(&mut self, text: &str, normalized: &str) -> Result<()> {
+        let index = self.chunks.len();
+        let path = self.options.output_dir.join(chunk_file_name(index));
+
+        self.engine
+            .speak(
+                normalized,
+                path.to_str().unwrap_or(""),
+                &self.options.voice,
+                self.options.speed,
+                false,
+            )
+            .map_err(engine_error)?;
+        let audio_bytes = std::fs::read(&path)?;
+
+        let metadata = metadata_builder::build_metadata(&audio_bytes, text, index, self.offset_ms)?;
+        self.offset_ms += metadata.duration_ms;
+        self.chunks.push(metadata);
+
+        match self.options.output {
+            BatchOutput::Files => self.audio_files.push(path),
+            BatchOutput::Concatenated => {
+                // Only the joined file is kept
+                std::fs::remove_file(&path)?;
+                self.wavs.push(audio_bytes);
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<BatchResult> {
+        if self.chunks.is_empty() {
+            return Err(TtsError::EmptyText);
+        }
+
+        if self.options.output == BatchOutput::Concatenated {
+            let path = self.options.output_dir.join(CONCATENATED_FILE);
+            std::fs::write(&path, audio::wav_utils::concatenate(self.wavs)?)?;
+            self.audio_files.push(path);
+        }
+
+        let metadata_file = self.options.output_dir.join(METADATA_FILE);
+        std::fs::write(&metadata_file, serde_json::to_string_pretty(&self.chunks)?)?;
+
+        Ok(BatchResult {
+            audio_files: self.audio_files,
+            metadata_file,
+            chunks: self.chunks,
+        })
+    }
+}
+
+/// `output_001.wav` for the first chunk, so files sort in reading order
+fn chunk_file_name(index: usize) -> String {
+    format!("output_{:03}.wav", index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kokoro::fake::{FakeEngine, FakeEngineLog};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    const INPUT: &str = "The first paragraph has one sentence.\n\n\
+        The second paragraph is wrapped\nacross two lines.\n";
+
+    fn run(
+        output: BatchOutput,
+        input: &str,
+        dir: &std::path::Path,
+    ) -> (Result<BatchResult>, Arc<FakeEngineLog>) {
+        let log = Arc::new(FakeEngineLog::default());
+        let engine = FakeEngine {
+            duration_ms: 500,
+            delay: Duration::ZERO,
+            log: log.clone(),
+        };
+        let options = BatchOptions {
+            voice: "bf_lily".to_string(),
+            speed: 1.0,
+            normalization: NormalizationConfig::default(),
+            output,
+            output_dir: dir.to_path_buf(),
+        };
+        (synthesize_reader(&engine, input.as_bytes(), &options), log)
+    }
+
+    #[test]
+    fn test_writes_one_file_per_chunk() {
+        let dir = tempfile::tempdir().unwrap();
+        let (result, log) = run(BatchOutput::Files, INPUT, dir.path());
+        let result = result.unwrap();
+
+        assert_eq!(
+            log.texts(),
+            vec![
+                "The first paragraph has one sentence.",
+                "The second paragraph is wrapped across two lines.",
+            ]
+        );
+        let names: Vec<_> = result
+            .audio_files
+            .iter()
+            .map(|path| path.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["output_001.wav", "output_002.wav"]);
+        for path in &result.audio_files {
+            let duration = audio::duration::calculate_file(path).unwrap();
+            assert!((duration - 500.0).abs() < 1.0);
+        }
+
+        let json: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&result.metadata_file).unwrap()).unwrap();
+        let chunks = json.as_array().unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1]["chunk_index"], 1);
+        assert_eq!(
+            chunks[1]["text"],
+            "The second paragraph is wrapped across two lines."
+        );
+        assert!((chunks[1]["start_offset_ms"].as_f64().unwrap() - 500.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_concatenates_into_one_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let (result, log) = run(BatchOutput::Concatenated, INPUT, dir.path());
+        let result = result.unwrap();
+
+        assert_eq!(log.calls(), 2);
+        assert_eq!(result.audio_files.len(), 1);
+        assert!(result.audio_files[0].ends_with(CONCATENATED_FILE));
+        let duration = audio::duration::calculate_file(&result.audio_files[0]).unwrap();
+        assert!((duration - result.total_duration_ms()).abs() < 1.0);
+        assert!((duration - 1000.0).abs() < 1.0);
+        assert!(!dir.path().join("output_001.wav").exists());
+    }
+
+    #[test]
+    fn test_long_paragraph_is_chunked() {
+        let sentence = "This sentence is repeated to make a long paragraph. ";
+        let dir = tempfile::tempdir().unwrap();
+        let (result, log) = run(BatchOutput::Files, &sentence.repeat(10), dir.path());
+        let result = result.unwrap();
+
+        assert!(log.calls() > 1);
+        assert_eq!(result.chunks.len(), log.calls());
+    }
+
+    #[test]
+    fn test_empty_input_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let (result, log) = run(BatchOutput::Files, "\n  \n", dir.path());
+        assert!(matches!(result, Err(TtsError::EmptyText)));
+        assert_eq!(log.calls(), 0);
+    }
+}
@@ -0,0 +1,237 @@
+use crate::config::constants::{
+    DEFAULT_AUDIO_MS_PER_CHAR, DEFAULT_GENERATION_MS_PER_AUDIO_MS, ESTIMATE_CALIBRATION_MIN_CHARS,
+};
+use crate::kokoro::voice_aliases::VoiceAliases;
+use crate::services::generation_stats::GenerationStats;
+use std::collections::HashMap;
+
+/// Coefficients for estimating a request's audio length and synthesis time
+///
+/// Starts from static defaults and switches to rates observed by
+/// `GenerationStats` once enough text has been synthesized to trust them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EstimateModel {
+    /// Audio per character at speed 1.0, in milliseconds
+    pub audio_ms_per_char: f64,
+    /// Synthesis time per millisecond of audio
+    pub generation_ms_per_audio_ms: f64,
+    /// Whether the coefficients come from observed generations
+    pub calibrated: bool,
+}
+
+impl Default for EstimateModel {
+    fn default() -> Self {
+        Self {
+            audio_ms_per_char: DEFAULT_AUDIO_MS_PER_CHAR,
+            generation_ms_per_audio_ms: DEFAULT_GENERATION_MS_PER_AUDIO_MS,
+            calibrated: false,
+        }
+    }
+}
+
+impl EstimateModel {
+    /// Model calibrated from the server's generations so far, if there are enough
+    pub fn from_stats(stats: &GenerationStats) -> Self {
+        if stats.total_chars() < ESTIMATE_CALIBRATION_MIN_CHARS {
+            return Self::default();
+        }
+
+        match (
+            stats.audio_ms_per_char(),
+            stats.generation_ms_per_audio_ms(),
+        ) {
+            (Some(audio_ms_per_char), Some(generation_ms_per_audio_ms)) => Self {
+                audio_ms_per_char,
+                generation_ms_per_audio_ms,
+                calibrated: true,
+            },
+            _ => Self::default(),
+        }
+    }
+
+    /// Model for one voice, which may speak faster or slower than average
+    ///
+    /// The voice's own observed rate wins once it has enough generations,
+    /// then a rate configured in `rates`, then the server-wide model.
+    pub fn for_voice(stats: &GenerationStats, rates: &VoiceRates, voice: &str) -> Self {
+        let mut model = Self::from_stats(stats);
+
+        let observed = stats
+            .voice(voice)
+            .filter(|counts| counts.chars >= ESTIMATE_CALIBRATION_MIN_CHARS)
+            .and_then(|counts| counts.audio_ms_per_char());
+        if let Some(audio_ms_per_char) = observed {
+            model.audio_ms_per_char = audio_ms_per_char;
+            model.calibrated = true;
+        } else if let Some(audio_ms_per_char) = rates.get(voice) {
+            model.audio_ms_per_char = audio_ms_per_char;
+        }
+
+        model
+    }
+
+    /// Estimated audio for `chars` characters spoken at `speed`, in milliseconds
+    pub fn audio_ms(&self, chars: usize, speed: f32) -> f64 {
+        chars as f64 * self.audio_ms_per_char / speed as f64
+    }
+
+    /// Estimated time to synthesize `audio_ms` of audio on one engine
+    pub fn generation_ms(&self, audio_ms: f64) -> f64 {
+        audio_ms * self.generation_ms_per_audio_ms
+    }
+}
+
+/// Static speaking rates for voices that are noticeably faster or slower
+/// than average, used until a voice's own rate has been observed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VoiceRates {
+    /// Audio per character at speed 1.0, in milliseconds, by voice ID
+    rates: HashMap<String, f64>,
+}
+
+impl VoiceRates {
+    /// Rates from `TTS_VOICE_RATES`
+    pub fn from_env(aliases: &VoiceAliases) -> Result<Self, String> {
+        Self::parse(std::env::var("TTS_VOICE_RATES").ok().as_deref(), aliases)
+    }
+
+    /// Comma-separated `voice=ms_per_char` entries; voices may be aliases
+    pub fn parse(value: Option<&str>, aliases: &VoiceAliases) -> Result<Self, String> {
+        let mut rates = HashMap::new();
+
+        for entry in value.unwrap_or("").split(',').map(str::trim) {
+            if entry.is_empty() {
+                continue;
+            }
+            let (name, rate) = entry.split_once('=').ok_or_else(|| {
+                format!("Invalid voice rate '{}': expected voice=ms_per_char", entry)
+            })?;
+            let voice = aliases.resolve_voice(name).ok_or_else(|| {
+                format!(
+                    "Invalid voice rate '{}': unknown voice '{}'",
+                    entry,
+                    name.trim()
+                )
+            })?;
+            let rate = rate
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|rate| rate.is_finite() && *rate > 0.0)
+                .ok_or_else(|| {
+                    format!(
+                        "Invalid voice rate '{}': expected a positive number of milliseconds per character",
+                        entry
+                    )
+                })?;
+
+            rates.insert(voice.id().to_string(), rate);
+        }
+
+        Ok(Self { rates })
+    }
+
+    /// Configured audio per character for `voice`, in milliseconds
+    pub fn get(&self, voice: &str) -> Option<f64> {
+        self.rates.get(voice).copied()
+    }
+
+    /// Number of voices with a configured rate
+    pub fn count(&self) -> usize {
+        self.rates.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_uncalibrated_uses_defaults() {
+        let stats = GenerationStats::new();
+        stats.record(100, 1.0, 10_000.0, Duration::from_secs(1));
+
+        let model = EstimateModel::from_stats(&stats);
+        assert_eq!(model, EstimateModel::default());
+        assert!(!model.calibrated);
+    }
+
+    #[test]
+    fn test_calibrates_from_observed_generations() {
+        let stats = GenerationStats::new();
+        stats.record(1000, 1.0, 50_000.0, Duration::from_secs(10));
+
+        let model = EstimateModel::from_stats(&stats);
+        assert!(model.calibrated);
+        assert_eq!(model.audio_ms_per_char, 50.0);
+        assert_eq!(model.generation_ms_per_audio_ms, 0.2);
+    }
+
+    #[test]
+    fn test_estimates_scale_with_length_and_speed() {
+        let model = EstimateModel::default();
+        assert_eq!(model.audio_ms(200, 1.0), 2.0 * model.audio_ms(100, 1.0));
+        assert_eq!(model.audio_ms(100, 2.0), model.audio_ms(100, 1.0) / 2.0);
+        assert_eq!(
+            model.generation_ms(1000.0),
+            DEFAULT_GENERATION_MS_PER_AUDIO_MS * 1000.0
+        );
+    }
+
+    #[test]
+    fn test_slow_voice_estimates_longer_than_fast_voice() {
+        let stats = GenerationStats::new();
+        let rates =
+            VoiceRates::parse(Some("bm_george=90, af_sky=50"), &VoiceAliases::default()).unwrap();
+
+        let slow = EstimateModel::for_voice(&stats, &rates, "bm_george");
+        let fast = EstimateModel::for_voice(&stats, &rates, "af_sky");
+        assert!(slow.audio_ms(120, 1.0) > fast.audio_ms(120, 1.0));
+        assert_eq!(slow.audio_ms_per_char, 90.0);
+
+        // Voices without a rate use the server-wide model
+        assert_eq!(
+            EstimateModel::for_voice(&stats, &rates, "af_heart"),
+            EstimateModel::default()
+        );
+    }
+
+    #[test]
+    fn test_observed_voice_rate_overrides_configured_rate() {
+        let stats = GenerationStats::new();
+        stats.record_voice("af_sky", 1000, 1.0, 80_000.0);
+        stats.record_voice("bm_george", 1000, 1.0, 40_000.0);
+        // Too little to trust yet
+        stats.record_voice("af_heart", 100, 1.0, 20_000.0);
+        let rates =
+            VoiceRates::parse(Some("af_sky=50,af_heart=70"), &VoiceAliases::default()).unwrap();
+
+        let sky = EstimateModel::for_voice(&stats, &rates, "af_sky");
+        let george = EstimateModel::for_voice(&stats, &rates, "bm_george");
+        assert_eq!(sky.audio_ms_per_char, 80.0);
+        assert!(sky.calibrated);
+        assert!(sky.audio_ms(120, 1.0) > george.audio_ms(120, 1.0));
+
+        let heart = EstimateModel::for_voice(&stats, &rates, "af_heart");
+        assert_eq!(heart.audio_ms_per_char, 70.0);
+        assert!(!heart.calibrated);
+    }
+
+    #[test]
+    fn test_parse_voice_rates() {
+        let aliases = VoiceAliases::default();
+
+        let rates = VoiceRates::parse(Some("narrator=85, af_sky = 55.5,"), &aliases).unwrap();
+        assert_eq!(rates.count(), 2);
+        assert_eq!(rates.get("bm_george"), Some(85.0));
+        assert_eq!(rates.get("af_sky"), Some(55.5));
+        assert_eq!(rates.get("narrator"), None);
+
+        assert_eq!(VoiceRates::parse(None, &aliases).unwrap().count(), 0);
+        assert!(VoiceRates::parse(Some("af_sky"), &aliases).is_err());
+        assert!(VoiceRates::parse(Some("nobody=60"), &aliases).is_err());
+        assert!(VoiceRates::parse(Some("af_sky=fast"), &aliases).is_err());
+        assert!(VoiceRates::parse(Some("af_sky=0"), &aliases).is_err());
+    }
+}
@@ -0,0 +1,230 @@
+use dashmap::DashMap;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Lifetime counters for the work done by the server
+///
+/// Shared through `AppState` and updated after every successful synthesis.
+pub struct GenerationStats {
+    started_at: Instant,
+    generations: AtomicU64,
+    chars: AtomicU64,
+    audio_ms: AtomicU64,
+    /// Audio scaled to what it would have been at speed 1.0
+    unit_speed_audio_ms: AtomicU64,
+    generation_ms: AtomicU64,
+    /// Syntheses, characters and audio milliseconds per voice ID
+    by_voice: DashMap<String, VoiceCounts>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct VoiceCounts {
+    pub generations: u64,
+    pub chars: u64,
+    pub audio_ms: u64,
+    /// Audio scaled to what it would have been at speed 1.0
+    pub unit_speed_audio_ms: u64,
+}
+
+impl VoiceCounts {
+    /// Observed audio per character at speed 1.0, in milliseconds
+    pub fn audio_ms_per_char(&self) -> Option<f64> {
+        if self.chars == 0 {
+            return None;
+        }
+        Some(self.unit_speed_audio_ms as f64 / self.chars as f64)
+    }
+}
+
+impl Default for GenerationStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GenerationStats {
+    /// Start counting from now
+    pub fn new() -> Self {
+        Self {
+            started_at: Instant::now(),
+            generations: AtomicU64::new(0),
+            chars: AtomicU64::new(0),
+            audio_ms: AtomicU64::new(0),
+            unit_speed_audio_ms: AtomicU64::new(0),
+            generation_ms: AtomicU64::new(0),
+            by_voice: DashMap::new(),
+        }
+    }
+
+    /// Record one synthesis of `chars` characters at `speed`, producing
+    /// `audio_ms` of audio in `elapsed`
+    pub fn record(&self, chars: usize, speed: f32, audio_ms: f64, elapsed: Duration) {
+        let audio_ms = audio_ms.max(0.0);
+        self.generations.fetch_add(1, Ordering::Relaxed);
+        self.chars.fetch_add(chars as u64, Ordering::Relaxed);
+        self.audio_ms
+            .fetch_add(audio_ms.round() as u64, Ordering::Relaxed);
+        self.unit_speed_audio_ms
+            .fetch_add((audio_ms * speed as f64).round() as u64, Ordering::Relaxed);
+        self.generation_ms
+            .fetch_add(elapsed.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    /// Record one synthesis of `chars` characters at `speed` with `voice`,
+    /// producing `audio_ms` of audio
+    pub fn record_voice(&self, voice: &str, chars: usize, speed: f32, audio_ms: f64) {
+        let audio_ms = audio_ms.max(0.0);
+        let mut counts = self.by_voice.entry(voice.to_string()).or_default();
+        counts.generations += 1;
+        counts.chars += chars as u64;
+        counts.audio_ms += audio_ms.round() as u64;
+        counts.unit_speed_audio_ms += (audio_ms * speed as f64).round() as u64;
+    }
+
+    /// Counts for one voice, if it has been used
+    pub fn voice(&self, voice: &str) -> Option<VoiceCounts> {
+        self.by_voice.get(voice).map(|counts| *counts)
+    }
+
+    /// Counts for each voice used so far, by voice ID
+    pub fn by_voice(&self) -> BTreeMap<String, VoiceCounts> {
+        self.by_voice
+            .iter()
+            .map(|entry| (entry.key().clone(), *entry.value()))
+            .collect()
+    }
+
+    /// Time since the server started
+    pub fn uptime(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// Number of successful syntheses
+    pub fn total_generations(&self) -> u64 {
+        self.generations.load(Ordering::Relaxed)
+    }
+
+    /// Total audio generated, in seconds
+    pub fn total_audio_seconds(&self) -> f64 {
+        self.audio_ms.load(Ordering::Relaxed) as f64 / 1000.0
+    }
+
+    /// Mean synthesis time per generation, in milliseconds (0 before the first)
+    pub fn average_generation_ms(&self) -> f64 {
+        let generations = self.total_generations();
+        if generations == 0 {
+            return 0.0;
+        }
+        self.generation_ms.load(Ordering::Relaxed) as f64 / generations as f64
+    }
+
+    /// Total characters synthesized
+    pub fn total_chars(&self) -> u64 {
+        self.chars.load(Ordering::Relaxed)
+    }
+
+    /// Observed audio per character at speed 1.0, in milliseconds
+    pub fn audio_ms_per_char(&self) -> Option<f64> {
+        let chars = self.total_chars();
+        if chars == 0 {
+            return None;
+        }
+        Some(self.unit_speed_audio_ms.load(Ordering::Relaxed) as f64 / chars as f64)
+    }
+
+    /// Observed synthesis time per millisecond of audio (real-time factor)
+    pub fn generation_ms_per_audio_ms(&self) -> Option<f64> {
+        let audio_ms = self.audio_ms.load(Ordering::Relaxed);
+        if audio_ms == 0 {
+            return None;
+        }
+        Some(self.generation_ms.load(Ordering::Relaxed) as f64 / audio_ms as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_stats_are_zero() {
+        let stats = GenerationStats::new();
+        assert_eq!(stats.total_generations(), 0);
+        assert_eq!(stats.total_audio_seconds(), 0.0);
+        assert_eq!(stats.average_generation_ms(), 0.0);
+    }
+
+    #[test]
+    fn test_record_accumulates() {
+        let stats = GenerationStats::new();
+        stats.record(20, 1.0, 1500.0, Duration::from_millis(100));
+        stats.record(30, 1.0, 2500.0, Duration::from_millis(300));
+
+        assert_eq!(stats.total_generations(), 2);
+        assert_eq!(stats.total_chars(), 50);
+        assert_eq!(stats.total_audio_seconds(), 4.0);
+        assert_eq!(stats.average_generation_ms(), 200.0);
+    }
+
+    #[test]
+    fn test_rates_scale_to_unit_speed() {
+        let stats = GenerationStats::new();
+        assert_eq!(stats.audio_ms_per_char(), None);
+        assert_eq!(stats.generation_ms_per_audio_ms(), None);
+
+        // 100 chars at double speed took 3s; at speed 1.0 they'd take 6s
+        stats.record(100, 2.0, 3000.0, Duration::from_millis(600));
+
+        assert_eq!(stats.audio_ms_per_char(), Some(60.0));
+        assert_eq!(stats.generation_ms_per_audio_ms(), Some(0.2));
+    }
+
+    #[test]
+    fn test_record_voice_counts_each_voice() {
+        let stats = GenerationStats::new();
+        assert!(stats.by_voice().is_empty());
+
+        stats.record_voice("af_heart", 20, 1.0, 1200.0);
+        stats.record_voice("bm_george", 10, 1.0, 500.0);
+        stats.record_voice("af_heart", 10, 2.0, 800.4);
+
+        let by_voice = stats.by_voice();
+        assert_eq!(
+            by_voice["af_heart"],
+            VoiceCounts {
+                generations: 2,
+                chars: 30,
+                audio_ms: 2000,
+                unit_speed_audio_ms: 2801,
+            }
+        );
+        assert_eq!(by_voice["bm_george"].generations, 1);
+        assert_eq!(stats.voice("bm_george"), Some(by_voice["bm_george"]));
+        assert_eq!(stats.voice("af_sky"), None);
+    }
+
+    #[test]
+    fn test_voice_rate_scales_to_unit_speed() {
+        let stats = GenerationStats::new();
+        stats.record_voice("af_heart", 100, 2.0, 3000.0);
+        stats.record_voice("bm_george", 100, 1.0, 9000.0);
+
+        assert_eq!(
+            stats.voice("af_heart").unwrap().audio_ms_per_char(),
+            Some(60.0)
+        );
+        assert_eq!(
+            stats.voice("bm_george").unwrap().audio_ms_per_char(),
+            Some(90.0)
+        );
+        assert_eq!(VoiceCounts::default().audio_ms_per_char(), None);
+    }
+
+    #[test]
+    fn test_uptime_advances() {
+        let stats = GenerationStats::new();
+        std::thread::sleep(Duration::from_millis(10));
+        assert!(stats.uptime() >= Duration::from_millis(10));
+    }
+}
@@ -0,0 +1,415 @@
+use bytes::Bytes;
+use dashmap::DashMap;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::config::constants::DEFAULT_MAX_PENDING_JOBS;
+use crate::models::JobResponse;
+
+/// Where a background synthesis job is in its life
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    /// Waiting for a free slot
+    Queued,
+    Running,
+    /// Audio is ready to fetch
+    Done,
+    Failed,
+    /// Stopped by the client before it finished
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Done => "done",
+            JobStatus::Failed => "failed",
+            JobStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+struct Job {
+    status: JobStatus,
+    chunks_total: usize,
+    chunks_done: usize,
+    audio: Option<Bytes>,
+    error: Option<String>,
+    finished_at: Option<Instant>,
+    /// Tells the job's synthesis to stop
+    cancel: CancellationToken,
+}
+
+/// Why a job's audio can't be fetched
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobAudioError {
+    /// Unknown ID, or the job expired
+    NotFound,
+    /// Still queued or running
+    NotFinished(JobStatus),
+    Failed(String),
+}
+
+/// Why a job can't be cancelled
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobCancelError {
+    /// Unknown ID, or the job expired
+    NotFound,
+    /// Already done, failed or cancelled
+    Finished(JobStatus),
+}
+
+/// In-memory state of `/tts/jobs` synthesis jobs
+///
+/// Finished jobs (and their audio) are dropped `ttl` after they finish.
+/// Expired jobs are purged whenever a job is submitted, and periodically by
+/// the server. At most `max_running` jobs synthesize at once; the rest wait
+/// as `queued`, up to `max_pending` queued and running jobs in all.
+#[derive(Clone)]
+pub struct JobStore {
+    jobs: Arc<DashMap<String, Job>>,
+    ttl: Duration,
+    slots: Arc<Semaphore>,
+    max_pending: usize,
+}
+
+impl JobStore {
+    pub fn new(ttl: Duration, max_running: usize) -> Self {
+        Self {
+            jobs: Arc::new(DashMap::new()),
+            ttl,
+            slots: Arc::new(Semaphore::new(max_running.max(1))),
+            max_pending: DEFAULT_MAX_PENDING_JOBS,
+        }
+    }
+
+    /// Limit how many jobs may be queued or running at once
+    pub fn with_max_pending(mut self, max_pending: usize) -> Self {
+        self.max_pending = max_pending.max(1);
+        self
+    }
+
+    /// How long finished jobs are kept
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Most jobs that may be queued or running at once
+    pub fn max_pending(&self) -> usize {
+        self.max_pending
+    }
+
+    /// Register a queued job of `chunks_total` chunks, returning its ID
+    ///
+    /// Returns None when `max_pending` jobs are already queued or running.
+    pub fn create(&self, chunks_total: usize) -> Option<String> {
+        self.purge_expired();
+        let pending = self
+            .jobs
+            .iter()
+            .filter(|job| job.finished_at.is_none())
+            .count();
+        if pending >= self.max_pending {
+            return None;
+        }
+
+        let id = Uuid::new_v4().to_string();
+        self.jobs.insert(
+            id.clone(),
+            Job {
+                status: JobStatus::Queued,
+                chunks_total,
+                chunks_done: 0,
+                audio: None,
+                error: None,
+                finished_at: None,
+                cancel: CancellationToken::new(),
+            },
+        );
+        Some(id)
+    }
+
+    /// Token that is cancelled when the job is, or None for an unknown job
+    pub fn cancellation(&self, id: &str) -> Option<CancellationToken> {
+        self.jobs.get(id).map(|job| job.cancel.clone())
+    }
+
+    /// Mark a queued or running job cancelled and signal its synthesis to stop
+    ///
+    /// Like any finished job, it expires `ttl` from now.
+    pub fn cancel(&self, id: &str) -> std::result::Result<JobResponse, JobCancelError> {
+        {
+            let mut job = self
+                .jobs
+                .get_mut(id)
+                .filter(|job| !self.is_expired(job))
+                .ok_or(JobCancelError::NotFound)?;
+            if job.finished_at.is_some() {
+                return Err(JobCancelError::Finished(job.status));
+            }
+
+            job.status = JobStatus::Cancelled;
+            job.finished_at = Some(Instant::now());
+            job.cancel.cancel();
+        }
+        self.status(id).ok_or(JobCancelError::NotFound)
+    }
+
+    /// Wait for a free slot, then mark the job running
+    ///
+    /// The job holds its slot until the returned permit is dropped.
+    pub async fn start(&self, id: &str) -> OwnedSemaphorePermit {
+        let permit = self
+            .slots
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("job semaphore is never closed");
+        if let Some(mut job) = self.jobs.get_mut(id) {
+            if job.status == JobStatus::Queued {
+                job.status = JobStatus::Running;
+            }
+        }
+        permit
+    }
+
+    /// Count one more chunk as synthesized
+    pub fn chunk_done(&self, id: &str) {
+        if let Some(mut job) = self.jobs.get_mut(id) {
+            job.chunks_done = (job.chunks_done + 1).min(job.chunks_total);
+        }
+    }
+
+    /// Record the outcome of a job; its TTL starts now
+    ///
+    /// Ignored for a job that was cancelled in the meantime.
+    pub fn finish(&self, id: &str, result: std::result::Result<Vec<u8>, String>) {
+        if let Some(mut job) = self.jobs.get_mut(id) {
+            if job.status == JobStatus::Cancelled {
+                return;
+            }
+            match result {
+                Ok(audio) => {
+                    job.status = JobStatus::Done;
+                    job.chunks_done = job.chunks_total;
+                    job.audio = Some(Bytes::from(audio));
+                }
+                Err(error) => {
+                    job.status = JobStatus::Failed;
+                    job.error = Some(error);
+                }
+            }
+            job.finished_at = Some(Instant::now());
+        }
+    }
+
+    /// Current status of a job, or None if it is unknown or expired
+    pub fn status(&self, id: &str) -> Option<JobResponse> {
+        let job = self.jobs.get(id).filter(|job| !self.is_expired(job))?;
+        Some(JobResponse {
+            job_id: id.to_string(),
+            status: job.status,
+            chunks_done: job.chunks_done,
+            chunks_total: job.chunks_total,
+            error: job.error.clone(),
+        })
+    }
+
+    /// The finished audio of a job
+    pub fn audio(&self, id: &str) -> std::result::Result<Bytes, JobAudioError> {
+        let job = self
+            .jobs
+            .get(id)
+            .filter(|job| !self.is_expired(job))
+            .ok_or(JobAudioError::NotFound)?;
+        match (&job.audio, &job.error) {
+            (Some(audio), _) => Ok(audio.clone()),
+            (None, Some(error)) => Err(JobAudioError::Failed(error.clone())),
+            (None, None) => Err(JobAudioError::NotFinished(job.status)),
+        }
+    }
+
+    /// Drop jobs that finished more than `ttl` ago, returning how many
+    pub fn purge_expired(&self) -> usize {
+        let before = self.jobs.len();
+        self.jobs.retain(|_, job| !self.is_expired(job));
+        before - self.jobs.len()
+    }
+
+    /// Number of jobs held, including expired ones not yet purged
+    #[cfg(test)]
+    pub fn job_count(&self) -> usize {
+        self.jobs.len()
+    }
+
+    fn is_expired(&self, job: &Job) -> bool {
+        job.finished_at
+            .map(|finished| finished.elapsed() > self.ttl)
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_job_lifecycle() {
+        let store = JobStore::new(Duration::from_secs(60), 1);
+        let id = store.create(3).unwrap();
+        assert_eq!(store.status(&id).unwrap().status, JobStatus::Queued);
+        assert_eq!(
+            store.audio(&id),
+            Err(JobAudioError::NotFinished(JobStatus::Queued))
+        );
+
+        let _permit = store.start(&id).await;
+        store.chunk_done(&id);
+        let status = store.status(&id).unwrap();
+        assert_eq!(status.status, JobStatus::Running);
+        assert_eq!((status.chunks_done, status.chunks_total), (1, 3));
+
+        store.finish(&id, Ok(vec![1, 2, 3]));
+        let status = store.status(&id).unwrap();
+        assert_eq!(status.status, JobStatus::Done);
+        assert_eq!(status.chunks_done, 3);
+        assert_eq!(store.audio(&id).unwrap().as_ref(), &[1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_job_reports_error() {
+        let store = JobStore::new(Duration::from_secs(60), 1);
+        let id = store.create(1).unwrap();
+        store.finish(&id, Err("engine crashed".to_string()));
+
+        let status = store.status(&id).unwrap();
+        assert_eq!(status.status, JobStatus::Failed);
+        assert_eq!(status.error.as_deref(), Some("engine crashed"));
+        assert_eq!(
+            store.audio(&id),
+            Err(JobAudioError::Failed("engine crashed".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_unknown_job() {
+        let store = JobStore::new(Duration::from_secs(60), 1);
+        assert!(store.status("missing").is_none());
+        assert_eq!(store.audio("missing"), Err(JobAudioError::NotFound));
+    }
+
+    #[test]
+    fn test_finished_jobs_expire() {
+        let store = JobStore::new(Duration::from_millis(20), 1);
+        let finished = store.create(1).unwrap();
+        let pending = store.create(1).unwrap();
+        store.finish(&finished, Ok(vec![0]));
+
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(store.status(&finished).is_none());
+        assert_eq!(store.audio(&finished), Err(JobAudioError::NotFound));
+
+        // Unfinished jobs never expire
+        assert_eq!(store.purge_expired(), 1);
+        assert_eq!(store.job_count(), 1);
+        assert!(store.status(&pending).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_running_job() {
+        let store = JobStore::new(Duration::from_secs(60), 1);
+        let id = store.create(3).unwrap();
+        let token = store.cancellation(&id).unwrap();
+        let _permit = store.start(&id).await;
+        store.chunk_done(&id);
+
+        let status = store.cancel(&id).unwrap();
+        assert_eq!(status.status, JobStatus::Cancelled);
+        assert_eq!(status.chunks_done, 1);
+        assert!(token.is_cancelled());
+
+        // The job's own outcome arriving late doesn't revive it
+        store.finish(&id, Ok(vec![1, 2, 3]));
+        assert_eq!(store.status(&id).unwrap().status, JobStatus::Cancelled);
+        assert_eq!(
+            store.audio(&id),
+            Err(JobAudioError::NotFinished(JobStatus::Cancelled))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancel_queued_job_never_runs() {
+        let store = JobStore::new(Duration::from_secs(60), 1);
+        let id = store.create(1).unwrap();
+        store.cancel(&id).unwrap();
+
+        let _permit = store.start(&id).await;
+        assert_eq!(store.status(&id).unwrap().status, JobStatus::Cancelled);
+    }
+
+    #[test]
+    fn test_cancel_unknown_or_finished_job() {
+        let store = JobStore::new(Duration::from_secs(60), 1);
+        assert_eq!(
+            store.cancel("missing").unwrap_err(),
+            JobCancelError::NotFound
+        );
+        assert!(store.cancellation("missing").is_none());
+
+        let done = store.create(1).unwrap();
+        store.finish(&done, Ok(vec![0]));
+        assert_eq!(
+            store.cancel(&done).unwrap_err(),
+            JobCancelError::Finished(JobStatus::Done)
+        );
+
+        let cancelled = store.create(1).unwrap();
+        store.cancel(&cancelled).unwrap();
+        assert_eq!(
+            store.cancel(&cancelled).unwrap_err(),
+            JobCancelError::Finished(JobStatus::Cancelled)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_jobs_wait_for_a_free_slot() {
+        let store = JobStore::new(Duration::from_secs(60), 1);
+        let first = store.create(1).unwrap();
+        let second = store.create(1).unwrap();
+
+        let permit = store.start(&first).await;
+        let waiting = {
+            let store = store.clone();
+            let second = second.clone();
+            tokio::spawn(async move { drop(store.start(&second).await) })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(store.status(&second).unwrap().status, JobStatus::Queued);
+
+        drop(permit);
+        waiting.await.unwrap();
+        assert_eq!(store.status(&second).unwrap().status, JobStatus::Running);
+    }
+
+    #[test]
+    fn test_pending_jobs_are_capped() {
+        let store = JobStore::new(Duration::from_secs(60), 1).with_max_pending(2);
+        let first = store.create(1).unwrap();
+        let second = store.create(1).unwrap();
+        assert!(store.create(1).is_none());
+
+        // Finished and cancelled jobs don't count
+        store.finish(&first, Ok(vec![0]));
+        store.cancel(&second).unwrap();
+        assert!(store.create(1).is_some());
+        assert!(store.create(1).is_some());
+        assert!(store.create(1).is_none());
+    }
+}
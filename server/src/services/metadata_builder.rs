@@ -1,9 +1,15 @@
 use crate::audio;
-use crate::error::Result;
+use crate::audio::segmentation::SegmentationConfig;
+use crate::config::constants::{
+    DEFAULT_MAX_PHRASE_WORDS, MAX_MIN_PHRASE_MS, MAX_PHRASE_WORDS, MAX_WAVEFORM_POINTS,
+    MIN_PHRASE_WORDS,
+};
+use crate::error::{Result, TtsError};
 use crate::models::{
-    ChunkMetadata, DebugInfo, PhraseMetadata, ValidationError, ValidationResult, ValidationWarning,
+    ChunkMetadata, DebugInfo, MetadataVersion, PhraseMetadata, ValidationError, ValidationResult,
+    ValidationWarning,
 };
-use crate::text_processing::normalization;
+use crate::text_processing::normalization::{self, NormalizationResult};
 
 /// Build metadata from audio bytes and text with enhanced features
 pub fn build_metadata(
@@ -12,10 +18,221 @@ pub fn build_metadata(
     chunk_index: usize,
     start_offset_ms: f64,
 ) -> Result<ChunkMetadata> {
-    build_metadata_with_options(audio_bytes, text, chunk_index, start_offset_ms, true, true)
+    build_metadata_with_options(
+        audio_bytes,
+        text,
+        chunk_index,
+        start_offset_ms,
+        true,
+        true,
+        DEFAULT_MAX_PHRASE_WORDS,
+    )
+}
+
+/// Validate a requested metadata phrase word limit
+pub fn validate_max_phrase_words(max_phrase_words: usize) -> Result<usize> {
+    if (MIN_PHRASE_WORDS..=MAX_PHRASE_WORDS).contains(&max_phrase_words) {
+        Ok(max_phrase_words)
+    } else {
+        Err(TtsError::InvalidRequest(format!(
+            "Invalid max_phrase_words: {} (must be {}-{})",
+            max_phrase_words, MIN_PHRASE_WORDS, MAX_PHRASE_WORDS
+        )))
+    }
+}
+
+/// Validate a requested minimum phrase duration
+pub fn validate_min_phrase_ms(min_phrase_ms: Option<u32>) -> Result<Option<u32>> {
+    match min_phrase_ms {
+        Some(ms) if ms > MAX_MIN_PHRASE_MS => Err(TtsError::InvalidRequest(format!(
+            "Invalid min_phrase_ms: {} (must be 0-{})",
+            ms, MAX_MIN_PHRASE_MS
+        ))),
+        _ => Ok(min_phrase_ms),
+    }
+}
+
+/// Give every phrase at least `min_phrase_ms`, keeping the total duration
+///
+/// Time for short phrases is taken from longer ones in proportion to how
+/// far they are above the minimum. When the chunk is too short for every
+/// phrase to reach it, the shortest phrases are first merged into a
+/// neighbor, and each merge is reported as a validation warning.
+pub fn apply_min_phrase_duration(metadata: &mut ChunkMetadata, min_phrase_ms: Option<u32>) {
+    let floor = match min_phrase_ms {
+        Some(ms) if ms > 0 => ms as f64,
+        _ => return,
+    };
+    let phrases = &mut metadata.phrases;
+    if phrases.is_empty() {
+        return;
+    }
+    let total: f64 = phrases.iter().map(|p| p.duration_ms).sum();
+
+    while phrases.len() > 1 && phrases.len() as f64 * floor > total {
+        let (shortest, _) = phrases
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.duration_ms.total_cmp(&b.duration_ms))
+            .unwrap();
+        let neighbor = if shortest == 0 {
+            1
+        } else if shortest == phrases.len() - 1
+            || phrases[shortest - 1].duration_ms <= phrases[shortest + 1].duration_ms
+        {
+            shortest - 1
+        } else {
+            shortest + 1
+        };
+
+        let index = shortest.min(neighbor);
+        let short_ms = phrases[shortest].duration_ms;
+        let second = phrases.remove(index + 1);
+        let first = phrases.remove(index);
+        phrases.insert(index, merge_phrases(&metadata.text, first, second));
+
+        if let Some(validation) = &mut metadata.validation {
+            validation.warnings.push(ValidationWarning {
+                phrase_index: index,
+                warning_type: "short_phrase_merged".to_string(),
+                message: format!(
+                    "Merged a {:.0}ms phrase into its neighbor to reach the {:.0}ms minimum",
+                    short_ms, floor
+                ),
+            });
+        }
+    }
+
+    let deficit: f64 = phrases
+        .iter()
+        .map(|p| (floor - p.duration_ms).max(0.0))
+        .sum();
+    let excess: f64 = phrases
+        .iter()
+        .map(|p| (p.duration_ms - floor).max(0.0))
+        .sum();
+    if deficit > 0.0 && excess >= deficit {
+        for phrase in phrases.iter_mut() {
+            phrase.duration_ms = if phrase.duration_ms < floor {
+                floor
+            } else {
+                phrase.duration_ms - deficit * (phrase.duration_ms - floor) / excess
+            };
+        }
+    }
+
+    let mut start_ms = phrases[0].start_ms;
+    for phrase in phrases.iter_mut() {
+        phrase.start_ms = start_ms;
+        start_ms += phrase.duration_ms;
+    }
+}
+
+/// Join two adjacent phrases, taking the merged text from the chunk text
+/// when the offsets allow so punctuation between them is kept
+fn merge_phrases(
+    chunk_text: &str,
+    first: PhraseMetadata,
+    second: PhraseMetadata,
+) -> PhraseMetadata {
+    let spoken = |p: &PhraseMetadata| p.original_text.clone().unwrap_or_else(|| p.text.clone());
+    let text = match (first.char_offset_start, second.char_offset_end) {
+        (Some(start), Some(end)) if chunk_text.get(start..end).is_some() => {
+            chunk_text[start..end].to_string()
+        }
+        _ => format!("{} {}", first.text, second.text),
+    };
+    let original_text = (first.original_text.is_some() || second.original_text.is_some())
+        .then(|| format!("{} {}", spoken(&first), spoken(&second)));
+
+    PhraseMetadata {
+        text,
+        original_text,
+        words: [first.words, second.words].concat(),
+        start_ms: first.start_ms,
+        duration_ms: first.duration_ms + second.duration_ms,
+        char_offset_start: first.char_offset_start,
+        char_offset_end: second.char_offset_end,
+    }
+}
+
+/// Validate a requested waveform envelope resolution
+pub fn validate_waveform_points(points: Option<usize>) -> Result<Option<usize>> {
+    match points {
+        Some(points) if !(1..=MAX_WAVEFORM_POINTS).contains(&points) => {
+            Err(TtsError::InvalidRequest(format!(
+                "Invalid waveform_points: {} (must be 1-{})",
+                points, MAX_WAVEFORM_POINTS
+            )))
+        }
+        _ => Ok(points),
+    }
+}
+
+/// Add a `points`-value peak envelope of `audio_bytes` to the metadata
+///
+/// Does nothing when `points` is None, so callers can pass the request's
+/// setting straight through.
+pub fn attach_waveform(
+    metadata: &mut ChunkMetadata,
+    audio_bytes: &[u8],
+    points: Option<usize>,
+) -> Result<()> {
+    if let Some(points) = points {
+        metadata.waveform = Some(audio::waveform::peak_envelope(audio_bytes, points)?);
+    }
+    Ok(())
+}
+
+/// Build metadata for already-normalized text in the shape of the requested
+/// schema version
+///
+/// `norm_result` maps the spoken text back to the user's original wording.
+/// Version 1.0 predates normalization tracking and validation, so those
+/// fields are left out entirely.
+pub fn build_metadata_for_version(
+    audio_bytes: &[u8],
+    norm_result: NormalizationResult,
+    chunk_index: usize,
+    start_offset_ms: f64,
+    version: MetadataVersion,
+    max_phrase_words: usize,
+) -> Result<ChunkMetadata> {
+    match version {
+        MetadataVersion::V2 => build_metadata_from_normalized(
+            audio_bytes,
+            norm_result,
+            chunk_index,
+            start_offset_ms,
+            true,
+            true,
+            max_phrase_words,
+        ),
+        MetadataVersion::V1 => {
+            let mut metadata = build_metadata_from_normalized(
+                audio_bytes,
+                norm_result,
+                chunk_index,
+                start_offset_ms,
+                false,
+                false,
+                max_phrase_words,
+            )?;
+            metadata.version = None;
+            metadata.original_text = None;
+            for phrase in &mut metadata.phrases {
+                phrase.original_text = None;
+                phrase.char_offset_start = None;
+                phrase.char_offset_end = None;
+            }
+            Ok(metadata)
+        }
+    }
 }
 
-/// Build metadata with options for validation and debug info
+/// Build metadata with options for validation, debug info and phrase length
+///
+/// Phrases are broken up after at most `max_phrase_words` words.
 pub fn build_metadata_with_options(
     audio_bytes: &[u8],
     text: &str,
@@ -23,16 +240,43 @@ pub fn build_metadata_with_options(
     start_offset_ms: f64,
     include_validation: bool,
     include_debug: bool,
+    max_phrase_words: usize,
 ) -> Result<ChunkMetadata> {
     // Normalize text for TTS while preserving original
     let norm_result = normalization::normalize_for_tts(text);
+    build_metadata_from_normalized(
+        audio_bytes,
+        norm_result,
+        chunk_index,
+        start_offset_ms,
+        include_validation,
+        include_debug,
+        max_phrase_words,
+    )
+}
+
+/// Build metadata from a normalization result (spoken text plus its original)
+fn build_metadata_from_normalized(
+    audio_bytes: &[u8],
+    norm_result: NormalizationResult,
+    chunk_index: usize,
+    start_offset_ms: f64,
+    include_validation: bool,
+    include_debug: bool,
+    max_phrase_words: usize,
+) -> Result<ChunkMetadata> {
     let normalization_info = normalization::get_normalization_info(&norm_result);
 
     // Calculate duration
     let duration_ms = audio::duration::calculate(audio_bytes)?;
 
     // Segment normalized text into phrases
-    let phrase_texts = audio::segmentation::segment_phrases(&norm_result.normalized);
+    let segmentation = SegmentationConfig {
+        max_phrase_words,
+        ..SegmentationConfig::default()
+    };
+    let phrase_texts =
+        audio::segmentation::segment_phrases_with_config(&norm_result.normalized, &segmentation);
 
     // Calculate character-weighted durations for each phrase
     let total_chars: usize = phrase_texts.iter().map(|p| p.len()).sum();
@@ -109,7 +353,7 @@ pub fn build_metadata_with_options(
 
     // Create metadata
     Ok(ChunkMetadata {
-        version: Some("2.0".to_string()),
+        version: Some(MetadataVersion::V2.as_str().to_string()),
         chunk_index,
         text: norm_result.normalized.clone(),
         original_text: if norm_result.original != norm_result.normalized {
@@ -122,6 +366,7 @@ pub fn build_metadata_with_options(
         start_offset_ms,
         validation,
         debug_info,
+        waveform: None,
     })
 }
 
@@ -226,8 +471,11 @@ mod tests {
     use std::io::Cursor;
 
     fn create_test_wav_with_duration(duration_ms: f64) -> Vec<u8> {
+        create_test_wav_with_channels(duration_ms, 1)
+    }
+
+    fn create_test_wav_with_channels(duration_ms: f64, channels: u16) -> Vec<u8> {
         let sample_rate = 24000;
-        let channels = 1;
         let num_samples = ((duration_ms / 1000.0) * sample_rate as f64) as u32;
 
         let spec = WavSpec {
@@ -265,6 +513,18 @@ mod tests {
         assert!(!metadata.phrases.is_empty());
     }
 
+    #[test]
+    fn test_build_metadata_mono_and_stereo_durations_match() {
+        let text = "Hello world. How are you?";
+        let mono = build_metadata(&create_test_wav_with_channels(1500.0, 1), text, 0, 0.0).unwrap();
+        let stereo =
+            build_metadata(&create_test_wav_with_channels(1500.0, 2), text, 0, 0.0).unwrap();
+
+        assert!((mono.duration_ms - 1500.0).abs() < 10.0);
+        assert!((stereo.duration_ms - mono.duration_ms).abs() < 1.0);
+        assert_eq!(mono.phrases.len(), stereo.phrases.len());
+    }
+
     #[test]
     fn test_build_metadata_multiple_phrases() {
         let text = "Hello world. How are you?";
@@ -368,6 +628,47 @@ mod tests {
         assert_ne!(metadata.text, text); // Normalized version should be different
     }
 
+    #[test]
+    fn test_build_metadata_for_version_shapes_differ() {
+        let text = "It costs $100.";
+        let audio_bytes = create_test_wav_with_duration(1000.0);
+
+        let norm_result = normalization::normalize_for_tts(text);
+
+        let v1 = build_metadata_for_version(
+            &audio_bytes,
+            norm_result.clone(),
+            0,
+            0.0,
+            MetadataVersion::V1,
+            DEFAULT_MAX_PHRASE_WORDS,
+        )
+        .unwrap();
+        let v2 = build_metadata_for_version(
+            &audio_bytes,
+            norm_result,
+            0,
+            0.0,
+            MetadataVersion::V2,
+            DEFAULT_MAX_PHRASE_WORDS,
+        )
+        .unwrap();
+
+        let v1 = serde_json::to_value(&v1).unwrap();
+        let v2 = serde_json::to_value(&v2).unwrap();
+
+        for field in ["version", "original_text", "validation", "debug_info"] {
+            assert!(v1.get(field).is_none(), "1.0 should omit {}", field);
+            assert!(v2.get(field).is_some(), "2.0 should include {}", field);
+        }
+        assert!(v1["phrases"][0].get("char_offset_start").is_none());
+        assert!(v2["phrases"][0].get("char_offset_start").is_some());
+
+        // Shared fields are identical
+        assert_eq!(v1["text"], v2["text"]);
+        assert_eq!(v1["duration_ms"], v2["duration_ms"]);
+    }
+
     #[test]
     fn test_build_metadata_version() {
         let text = "Test";
@@ -407,8 +708,16 @@ mod tests {
         let text = "Test";
         let audio_bytes = create_test_wav_with_duration(500.0);
 
-        let metadata =
-            build_metadata_with_options(&audio_bytes, text, 0, 0.0, false, true).unwrap();
+        let metadata = build_metadata_with_options(
+            &audio_bytes,
+            text,
+            0,
+            0.0,
+            false,
+            true,
+            DEFAULT_MAX_PHRASE_WORDS,
+        )
+        .unwrap();
 
         assert!(metadata.validation.is_none());
         assert!(metadata.debug_info.is_some());
@@ -419,13 +728,97 @@ mod tests {
         let text = "Test";
         let audio_bytes = create_test_wav_with_duration(500.0);
 
-        let metadata =
-            build_metadata_with_options(&audio_bytes, text, 0, 0.0, true, false).unwrap();
+        let metadata = build_metadata_with_options(
+            &audio_bytes,
+            text,
+            0,
+            0.0,
+            true,
+            false,
+            DEFAULT_MAX_PHRASE_WORDS,
+        )
+        .unwrap();
 
         assert!(metadata.validation.is_some());
         assert!(metadata.debug_info.is_none());
     }
 
+    #[test]
+    fn test_smaller_phrase_limit_yields_more_phrases() {
+        let text = "The quick brown fox jumps over the lazy dog and keeps running far away";
+        let audio_bytes = create_test_wav_with_duration(4000.0);
+        let phrases = |max_phrase_words| {
+            build_metadata_with_options(&audio_bytes, text, 0, 0.0, true, false, max_phrase_words)
+                .unwrap()
+                .phrases
+        };
+
+        let default = phrases(DEFAULT_MAX_PHRASE_WORDS);
+        let short = phrases(3);
+        assert!(short.len() > default.len());
+        assert!(short.iter().all(|phrase| phrase.words.len() <= 3));
+
+        // Same audio either way: the phrases still cover the whole clip
+        let total = |phrases: &[PhraseMetadata]| -> f64 {
+            phrases.iter().map(|phrase| phrase.duration_ms).sum()
+        };
+        assert!((total(&default) - 4000.0).abs() < 1.0);
+        assert!((total(&short) - 4000.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_build_metadata_uses_default_phrase_limit() {
+        let text = "The quick brown fox jumps over the lazy dog and keeps running far away";
+        let audio_bytes = create_test_wav_with_duration(4000.0);
+
+        let metadata = build_metadata(&audio_bytes, text, 0, 0.0).unwrap();
+
+        assert_eq!(
+            SegmentationConfig::default().max_phrase_words,
+            DEFAULT_MAX_PHRASE_WORDS
+        );
+        assert!(metadata
+            .phrases
+            .iter()
+            .all(|phrase| phrase.words.len() <= DEFAULT_MAX_PHRASE_WORDS));
+    }
+
+    #[test]
+    fn test_validate_max_phrase_words() {
+        assert_eq!(validate_max_phrase_words(1).unwrap(), 1);
+        assert_eq!(validate_max_phrase_words(50).unwrap(), 50);
+        assert!(validate_max_phrase_words(0).is_err());
+        assert!(validate_max_phrase_words(51).is_err());
+    }
+
+    #[test]
+    fn test_validate_waveform_points() {
+        assert_eq!(validate_waveform_points(None).unwrap(), None);
+        assert_eq!(validate_waveform_points(Some(1)).unwrap(), Some(1));
+        assert_eq!(
+            validate_waveform_points(Some(MAX_WAVEFORM_POINTS)).unwrap(),
+            Some(MAX_WAVEFORM_POINTS)
+        );
+        assert!(validate_waveform_points(Some(0)).is_err());
+        assert!(validate_waveform_points(Some(MAX_WAVEFORM_POINTS + 1)).is_err());
+    }
+
+    #[test]
+    fn test_attach_waveform() {
+        let audio_bytes = create_test_wav_with_duration(1000.0);
+        let mut metadata = build_metadata(&audio_bytes, "Hello world", 0, 0.0).unwrap();
+
+        attach_waveform(&mut metadata, &audio_bytes, None).unwrap();
+        assert_eq!(metadata.waveform, None);
+        let json = serde_json::to_string(&metadata).unwrap();
+        assert!(!json.contains("waveform"));
+
+        attach_waveform(&mut metadata, &audio_bytes, Some(64)).unwrap();
+        let waveform = metadata.waveform.unwrap();
+        assert_eq!(waveform.len(), 64);
+        assert!(waveform.iter().all(|peak| (0.0..=1.0).contains(peak)));
+    }
+
     #[test]
     fn test_validate_phrases_valid() {
         let text = "Hello world";
@@ -447,4 +840,148 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_original_casing_recoverable_after_acronym_normalization() {
+        let text = "Plug in the USB port. Stop right here. STOP RIGHT HERE, NASA said.";
+        let audio_bytes = create_test_wav_with_duration(4000.0);
+        let config = normalization::NormalizationConfig {
+            all_caps: true,
+            ..Default::default()
+        };
+        let metadata = build_metadata_for_version(
+            &audio_bytes,
+            normalization::normalize_for_tts_with_config(text, &config),
+            0,
+            0.0,
+            MetadataVersion::V2,
+            4,
+        )
+        .unwrap();
+
+        assert_eq!(metadata.original_text.as_deref(), Some(text));
+
+        // Every phrase's source slice, with its exact casing, in order
+        let mut cursor = 0;
+        let mut saw_changed_case = false;
+        for phrase in &metadata.phrases {
+            let start = phrase.char_offset_start.unwrap();
+            let end = phrase.char_offset_end.unwrap();
+            assert_eq!(&metadata.text[start..end], phrase.text);
+
+            let original = phrase.original_text.as_deref().unwrap_or(&phrase.text);
+            let pos = text[cursor..]
+                .find(original)
+                .unwrap_or_else(|| panic!("{:?} is not in the original text", original));
+            cursor += pos + original.len();
+            saw_changed_case |= original != phrase.text && original.to_lowercase() == phrase.text;
+        }
+        assert!(saw_changed_case);
+
+        let originals: Vec<&str> = metadata
+            .phrases
+            .iter()
+            .filter_map(|phrase| phrase.original_text.as_deref())
+            .collect();
+        assert!(
+            originals.iter().any(|o| o.contains("USB")),
+            "{:?}",
+            originals
+        );
+        assert!(
+            originals.iter().any(|o| o.ends_with("STOP")),
+            "{:?}",
+            originals
+        );
+        assert!(originals.contains(&"RIGHT HERE"), "{:?}", originals);
+    }
+
+    fn total_duration(metadata: &ChunkMetadata) -> f64 {
+        metadata.phrases.iter().map(|p| p.duration_ms).sum()
+    }
+
+    #[test]
+    fn test_validate_min_phrase_ms() {
+        assert_eq!(validate_min_phrase_ms(None).unwrap(), None);
+        assert_eq!(validate_min_phrase_ms(Some(0)).unwrap(), Some(0));
+        assert_eq!(
+            validate_min_phrase_ms(Some(MAX_MIN_PHRASE_MS)).unwrap(),
+            Some(MAX_MIN_PHRASE_MS)
+        );
+        assert!(validate_min_phrase_ms(Some(MAX_MIN_PHRASE_MS + 1)).is_err());
+    }
+
+    #[test]
+    fn test_min_phrase_duration_lifts_short_interjection() {
+        let text = "Oh! The committee reviewed the proposal in great detail today.";
+        let audio_bytes = create_test_wav_with_duration(3000.0);
+        let mut metadata =
+            build_metadata_with_options(&audio_bytes, text, 0, 0.0, true, false, 20).unwrap();
+        assert_eq!(metadata.phrases[0].text, "Oh!");
+        assert!(metadata.phrases[0].duration_ms < 250.0);
+        let total = total_duration(&metadata);
+        let phrase_count = metadata.phrases.len();
+
+        apply_min_phrase_duration(&mut metadata, Some(250));
+
+        assert_eq!(metadata.phrases.len(), phrase_count);
+        assert!(metadata
+            .phrases
+            .iter()
+            .all(|p| p.duration_ms >= 250.0 - 1e-9));
+        assert!((metadata.phrases[0].duration_ms - 250.0).abs() < 1e-9);
+        assert!((total_duration(&metadata) - total).abs() < 1e-6);
+        // Phrases stay back to back
+        for pair in metadata.phrases.windows(2) {
+            assert!((pair[0].start_ms + pair[0].duration_ms - pair[1].start_ms).abs() < 1e-6);
+        }
+        // Redistributing alone is not worth a warning
+        assert!(metadata.validation.unwrap().warnings.is_empty());
+    }
+
+    #[test]
+    fn test_min_phrase_duration_merges_when_chunk_is_too_short() {
+        let text = "Oh! Ah! Well, fine.";
+        let audio_bytes = create_test_wav_with_duration(500.0);
+        let mut metadata =
+            build_metadata_with_options(&audio_bytes, text, 0, 0.0, true, false, 20).unwrap();
+        assert!(metadata.phrases.len() > 2);
+        let total = total_duration(&metadata);
+
+        apply_min_phrase_duration(&mut metadata, Some(200));
+
+        assert!(metadata.phrases.len() <= 2);
+        assert!(metadata
+            .phrases
+            .iter()
+            .all(|p| p.duration_ms >= 200.0 - 1e-9));
+        assert!((total_duration(&metadata) - total).abs() < 1e-6);
+        for phrase in &metadata.phrases {
+            let (start, end) = (
+                phrase.char_offset_start.unwrap(),
+                phrase.char_offset_end.unwrap(),
+            );
+            assert_eq!(&metadata.text[start..end], phrase.text);
+        }
+
+        let warnings = metadata.validation.unwrap().warnings;
+        assert!(!warnings.is_empty());
+        assert!(warnings
+            .iter()
+            .all(|w| w.warning_type == "short_phrase_merged"));
+    }
+
+    #[test]
+    fn test_min_phrase_duration_off_leaves_metadata_alone() {
+        let audio_bytes = create_test_wav_with_duration(1000.0);
+        let mut metadata =
+            build_metadata(&audio_bytes, "Oh! Hello there, friend.", 0, 0.0).unwrap();
+        let before: Vec<f64> = metadata.phrases.iter().map(|p| p.duration_ms).collect();
+
+        apply_min_phrase_duration(&mut metadata, None);
+        apply_min_phrase_duration(&mut metadata, Some(0));
+
+        let after: Vec<f64> = metadata.phrases.iter().map(|p| p.duration_ms).collect();
+        assert_eq!(before, after);
+    }
 }
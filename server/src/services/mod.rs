@@ -1,2 +1,8 @@
+pub mod archive;
+pub mod audio_cache;
+pub mod batch;
+pub mod estimate;
+pub mod generation_stats;
+pub mod jobs;
 pub mod metadata_builder;
 pub mod streaming;
@@ -1,23 +1,73 @@
 use axum::{body::Bytes, http::header, response::Response};
-use std::time::Instant;
-use tokio_stream::{wrappers::ReceiverStream, StreamExt};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio_stream::{wrappers::ReceiverStream, Stream, StreamExt};
 
 use crate::chunking::{chunk_text, ChunkingConfig};
-use crate::config::constants::{MAX_TEXT_LENGTH, MULTIPART_BOUNDARY};
+use crate::config::constants::MULTIPART_BOUNDARY;
 use crate::error::{Result, TtsError};
-use crate::models::{ChunkMetadata, TTSRequest};
+use crate::fair_queue;
+use crate::kokoro::PooledTTS;
+use crate::models::{ChunkMetadata, MetadataVersion, StreamError, StreamProgress, TTSRequest};
 use crate::server::AppState;
+use crate::services::estimate::EstimateModel;
+use crate::text_processing::normalization::{self, NormalizationResult};
+use crate::text_processing::quotes::{self, QuoteStyle};
+use crate::text_processing::speed_markup;
+
+/// What a stream does once a chunk has failed every attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChunkFailureMode {
+    /// Send an error part in the chunk's place and carry on with the rest
+    #[default]
+    Skip,
+    /// Send an error part and stop generating; the stream ends after it
+    Abort,
+}
+
+impl ChunkFailureMode {
+    /// Parse a `TTS_CHUNK_FAILURE_MODE` value; anything unrecognized means `skip`
+    pub fn parse(value: &str) -> Self {
+        match value.to_lowercase().as_str() {
+            "abort" => Self::Abort,
+            _ => Self::Skip,
+        }
+    }
+}
+
+/// Retry policy for chunks that fail during multipart streaming
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ChunkRetryConfig {
+    /// Extra attempts after the first failure
+    pub retries: u32,
+    pub failure_mode: ChunkFailureMode,
+}
+
+impl Default for ChunkRetryConfig {
+    fn default() -> Self {
+        Self {
+            retries: 2,
+            failure_mode: ChunkFailureMode::Skip,
+        }
+    }
+}
+
+/// Content type of keep-alive parts; clients skip parts of types they don't know
+const KEEP_ALIVE_CONTENT_TYPE: &str = "application/x-keep-alive";
 
 fn create_boundary_start() -> String {
     format!("\r\n--{}\r\n", MULTIPART_BOUNDARY)
 }
 
-fn create_boundary_end() -> String {
+pub(crate) fn create_boundary_end() -> String {
     format!("\r\n--{}--\r\n", MULTIPART_BOUNDARY)
 }
 
-fn create_metadata_part(metadata: &ChunkMetadata) -> Result<Bytes> {
-    let json = serde_json::to_string(metadata)?;
+pub(crate) fn create_json_part(value: &impl Serialize) -> Result<Bytes> {
+    let json = serde_json::to_string(value)?;
 
     let part = format!(
         "{}Content-Type: application/json\r\n\r\n{}\r\n",
@@ -28,7 +78,7 @@ fn create_metadata_part(metadata: &ChunkMetadata) -> Result<Bytes> {
     Ok(Bytes::from(part))
 }
 
-fn create_audio_part(audio_bytes: Vec<u8>) -> Bytes {
+pub(crate) fn create_audio_part(audio_bytes: Vec<u8>) -> Bytes {
     let mut part = Vec::new();
 
     // Boundary + headers
@@ -45,35 +95,74 @@ fn create_audio_part(audio_bytes: Vec<u8>) -> Bytes {
     Bytes::from(part)
 }
 
-/// Generate a single chunk with metadata
+/// An empty part sent to keep idle connections open
+pub(crate) fn create_keep_alive_part() -> Bytes {
+    Bytes::from(format!(
+        "{}Content-Type: {}\r\nContent-Length: 0\r\n\r\n",
+        create_boundary_start(),
+        KEEP_ALIVE_CONTENT_TYPE
+    ))
+}
+
+/// Pass `parts` through, adding a keep-alive part whenever none arrives
+/// within `interval`
+///
+/// A slow first chunk can otherwise leave the connection silent long enough
+/// for a proxy to close it. Parts are only ever inserted between whole parts.
+fn with_keep_alive<S, E>(
+    parts: S,
+    interval: Duration,
+) -> impl Stream<Item = std::result::Result<Bytes, E>>
+where
+    S: Stream<Item = std::result::Result<Bytes, E>> + Unpin,
+{
+    futures::stream::unfold(parts, move |mut parts| async move {
+        match tokio::time::timeout(interval, parts.next()).await {
+            Ok(Some(part)) => Some((part, parts)),
+            Ok(None) => None,
+            Err(_) => Some((Ok(create_keep_alive_part()), parts)),
+        }
+    })
+}
+
+/// Generate a single chunk with metadata on an already-acquired engine
+///
+/// Synthesis options (voice, channels, sample rate) are taken from the
+/// originating request; `chunk` holds the normalized text to synthesize and
+/// the original wording it came from, spoken at `speed`.
 async fn generate_chunk_with_metadata(
-    state: &AppState,
-    text: &str,
-    voice: &str,
+    tts: PooledTTS,
+    chunk: NormalizationResult,
     speed: f32,
+    req: &TTSRequest,
     chunk_index: usize,
     start_offset_ms: f64,
+    metadata_version: MetadataVersion,
 ) -> Result<(ChunkMetadata, Vec<u8>)> {
     use crate::services::metadata_builder;
     use crate::utils::temp_file::TempFile;
 
-    // Acquire TTS engine
-    let tts = state
-        .tts_pool
-        .acquire()
-        .await
-        .map_err(|e| TtsError::TtsEngine(e.to_string()))?;
-
     // Generate unique temp file
     let temp_file = TempFile::new();
     let temp_path = temp_file.as_str().to_string();
-    let text_clone = text.to_string();
-    let voice_clone = voice.to_string();
+    let text_clone = chunk.normalized.clone();
+    let voice_clone = req.voice.clone();
+    let mono = req.mono;
+    let pitch = req.pitch();
+
+    // Pitch shifting shortens the audio, so synthesize that much slower first
+    let engine_speed = speed / pitch;
 
     // Generate audio in blocking thread
     let generation_result = tokio::task::spawn_blocking(move || {
-        futures::executor::block_on(tts.speak(&text_clone, &temp_path, &voice_clone, speed))
-            .map_err(|e| TtsError::TtsEngine(e.to_string()))
+        futures::executor::block_on(tts.speak(
+            &text_clone,
+            &temp_path,
+            &voice_clone,
+            engine_speed,
+            mono,
+        ))
+        .map_err(crate::kokoro::engine_error)
     })
     .await?;
 
@@ -81,55 +170,180 @@ async fn generate_chunk_with_metadata(
     generation_result?;
 
     // Read generated audio file
-    let audio_bytes = tokio::fs::read(temp_file.path()).await?;
+    let mut audio_bytes = temp_file.read().await?;
 
     // TempFile will automatically clean up when it goes out of scope
 
+    audio_bytes = crate::audio::resample::shift_pitch_wav(&audio_bytes, pitch)?;
+
+    // Resample before building metadata so durations reflect the output header
+    if let Some(sample_rate) = req.sample_rate {
+        audio_bytes = crate::audio::resample::resample_wav(&audio_bytes, sample_rate)?;
+    }
+    if let Some(bits) = req.bit_depth {
+        audio_bytes = crate::audio::wav_utils::convert_bit_depth(&audio_bytes, bits)?;
+    }
+
     // Build metadata using shared function
-    let metadata =
-        metadata_builder::build_metadata(&audio_bytes, text, chunk_index, start_offset_ms)?;
+    let mut metadata = metadata_builder::build_metadata_for_version(
+        &audio_bytes,
+        chunk,
+        chunk_index,
+        start_offset_ms,
+        metadata_version,
+        req.max_phrase_words(),
+    )?;
+    metadata_builder::apply_min_phrase_duration(&mut metadata, req.min_phrase_ms);
+    metadata_builder::attach_waveform(&mut metadata, &audio_bytes, req.waveform_points)?;
 
     Ok((metadata, audio_bytes))
 }
 
+/// Pair each chunk with the slice of the original text it was normalized from
+///
+/// Chunks are located in order in the normalized text. A chunk that can't be
+/// located (the chunker may rewrite whitespace) carries no original text.
+fn chunk_normalization(full: &NormalizationResult, chunks: &[String]) -> Vec<NormalizationResult> {
+    let mut cursor = 0;
+
+    chunks
+        .iter()
+        .map(|chunk| {
+            let located = full.normalized[cursor..]
+                .find(chunk.as_str())
+                .map(|pos| cursor + pos)
+                .and_then(|start| {
+                    let end = start + chunk.len();
+                    full.slice(start, end).map(|result| (end, result))
+                });
+
+            match located {
+                Some((end, result)) => {
+                    cursor = end;
+                    result
+                }
+                None => NormalizationResult {
+                    original: chunk.clone(),
+                    normalized: chunk.clone(),
+                    char_mapping: (0..chunk.len()).collect(),
+                },
+            }
+        })
+        .collect()
+}
+
 /// Generate TTS audio with multipart streaming response
-pub async fn generate_tts_stream(state: AppState, req: TTSRequest) -> Result<Response> {
+///
+/// Metadata parts follow the schema of `metadata_version`. With `progress`,
+/// each chunk's audio part is followed by a [`StreamProgress`] JSON part.
+///
+/// A failed chunk is retried per `state.chunk_retry`; if every attempt fails
+/// a [`StreamError`] part naming the chunk is sent instead, and in abort mode
+/// no further chunks are sent.
+pub async fn generate_tts_stream(
+    state: AppState,
+    mut req: TTSRequest,
+    metadata_version: MetadataVersion,
+    progress: bool,
+    max_text_length: usize,
+) -> Result<Response> {
     let start = Instant::now();
 
     tracing::debug!(
         "TTS multipart streaming request - text_len={}, voice='{}', speed={}",
         req.text.len(),
         req.voice,
-        req.speed
+        req.speed()
     );
 
+    crate::server::resolve_request_voice(&state, &mut req)?;
+    crate::server::apply_voice_defaults(&state, &mut req);
+
     // Validate text
     if req.text.trim().is_empty() {
         return Err(TtsError::EmptyText);
     }
 
     // Validate text length to prevent DoS
-    if req.text.len() > MAX_TEXT_LENGTH {
+    if req.text.len() > max_text_length {
         return Err(TtsError::InvalidRequest(format!(
             "Text too long: {} chars (max {})",
             req.text.len(),
-            MAX_TEXT_LENGTH
+            max_text_length
         )));
     }
 
-    // Validate speed
-    if req.speed <= 0.0 || req.speed > 3.0 {
-        return Err(TtsError::InvalidSpeed(req.speed));
-    }
+    // Break up (or refuse) tokens too long for the engine
+    req.text = state.long_tokens.apply(&req.text)?;
+
+    // Validate speed (or clamp it, if configured)
+    req.speed = Some(crate::utils::speed::resolve_speed(
+        req.speed(),
+        state.min_speed,
+        state.speed_clamp,
+    )?);
+    req.max_phrase_words =
+        Some(crate::services::metadata_builder::validate_max_phrase_words(req.max_phrase_words())?);
+    req.min_phrase_ms =
+        crate::services::metadata_builder::validate_min_phrase_ms(req.min_phrase_ms)?;
+    req.waveform_points =
+        crate::services::metadata_builder::validate_waveform_points(req.waveform_points)?;
+
+    // Validate requested output sample rate
+    crate::audio::resample::validate_sample_rate(req.sample_rate)?;
+    crate::audio::wav_utils::validate_bit_depth(req.bit_depth)?;
+
+    // Inline {{speed:N}} markup splits the text into segments with their own speed;
+    // the tags themselves never reach the engine or the metadata
+    let segments = speed_markup::parse_speed_markup(
+        &req.text,
+        req.speed(),
+        state.min_speed,
+        state.speed_clamp,
+    )?;
 
     // Normalize text for TTS (semantic + unicode normalization)
     // This ensures currency, percentages, and special characters are properly converted
-    // BEFORE chunking, so the TTS engine receives clean, speakable text
-    let normalized_text = crate::text_processing::normalization::normalize_simple(&req.text);
+    // BEFORE chunking, so the TTS engine receives clean, speakable text.
+    // The mapping is kept so each chunk's metadata can carry the original wording.
+    let config = ChunkingConfig {
+        strategy: req.chunk_strategy,
+        ..Default::default()
+    };
+    let mut chunks = Vec::new();
+    let mut normalized_length = 0;
+    for segment in segments {
+        let norm_result = crate::server::prepare_text(&state, &req, &segment.text);
+        normalized_length += norm_result.normalized.len();
+        crate::server::check_normalized_length(&state, normalized_length)?;
+
+        // Split normalized text into chunks
+        let segment_chunks = chunk_text(&norm_result.normalized, &config);
+        chunks.extend(
+            chunk_normalization(&norm_result, &segment_chunks)
+                .into_iter()
+                .map(|chunk| match state.quotes {
+                    QuoteStyle::Spoken => quotes::speak_normalized(&chunk),
+                    _ => chunk,
+                })
+                .map(|chunk| match &state.say_punctuation {
+                    // Named after chunking, so chunks still end at sentences
+                    Some(names) => names.speak_normalized(&chunk),
+                    None => chunk,
+                })
+                .map(|chunk| (chunk, segment.speed)),
+        );
+    }
 
-    // Split normalized text into chunks
-    let config = ChunkingConfig::default();
-    let chunks = chunk_text(&normalized_text, &config);
+    // Nothing left to say (only emoji, punctuation or symbols)
+    if !chunks
+        .iter()
+        .any(|(chunk, _)| normalization::has_speakable_content(&chunk.normalized))
+    {
+        return Err(TtsError::InvalidRequest(
+            "No speakable content in text".to_string(),
+        ));
+    }
 
     tracing::debug!(
         "Streaming {} text chunks with multipart format",
@@ -141,50 +355,128 @@ pub async fn generate_tts_stream(state: AppState, req: TTSRequest) -> Result<Res
 
     // Clone for background task
     let state_clone = state.clone();
-    let voice_clone = req.voice.clone();
-    let speed = req.speed;
+    let req = Arc::new(req);
 
     // Spawn background task to generate and stream chunks
-    tokio::spawn(async move {
+    tokio::spawn(fair_queue::inherit_client(async move {
         if chunks.is_empty() {
             let _ = tx.send(Ok(Bytes::from(create_boundary_end()))).await;
             return;
         }
 
         // === ALL CHUNKS (parallel processing - send as ready) ===
-        // Calculate estimated offsets for all chunks
+        // Calculate estimated offsets for all chunks, at this voice's pace
+        let model = EstimateModel::for_voice(
+            &state_clone.generation_stats,
+            &state_clone.voice_rates,
+            &req.voice,
+        );
         let mut chunk_offsets = Vec::new();
         let mut temp_offset = 0.0;
 
-        for (i, chunk_text) in chunks.iter().enumerate() {
-            chunk_offsets.push((i, chunk_text.clone(), temp_offset));
-            // Estimate duration based on character count (rough approximation)
-            // Average speech rate: ~150 words/min = ~2.5 words/sec = ~400ms/word
-            // Average word length: ~5 chars => ~80ms/char
-            temp_offset += (chunk_text.len() as f64) * 80.0;
+        for (i, (chunk, speed)) in chunks.iter().enumerate() {
+            chunk_offsets.push((i, chunk.clone(), *speed, temp_offset));
+            temp_offset += model.audio_ms(chunk.normalized.chars().count(), *speed);
         }
 
         // Spawn ALL chunks in parallel and collect their join handles
         let mut handles = Vec::new();
+        let total = chunk_offsets.len();
+        let completed = progress.then(|| Arc::new(tokio::sync::Mutex::new(0usize)));
+        let retry = state_clone.chunk_retry;
+        let aborted = Arc::new(AtomicBool::new(false));
 
-        for (chunk_index, chunk_text, start_offset) in chunk_offsets {
+        for (chunk_index, chunk, speed, start_offset) in chunk_offsets {
             let state = state_clone.clone();
-            let voice = voice_clone.clone();
+            let req = req.clone();
             let tx_clone = tx.clone();
+            let completed = completed.clone();
+            let aborted = aborted.clone();
 
             // Each chunk sends itself as soon as ready
-            let handle = tokio::spawn(async move {
-                match generate_chunk_with_metadata(
-                    &state,
-                    &chunk_text,
-                    &voice,
-                    speed,
-                    chunk_index,
-                    start_offset,
-                )
-                .await
-                {
-                    Ok((metadata, audio_bytes)) => {
+            let handle = tokio::spawn(fair_queue::inherit_client(async move {
+                let mut failures = 0;
+                let result = loop {
+                    if aborted.load(Ordering::SeqCst) {
+                        return;
+                    }
+
+                    // Stop waiting for an engine as soon as the client goes away
+                    let tts = tokio::select! {
+                        biased;
+                        _ = tx_clone.closed() => {
+                            tracing::debug!("Client disconnected, skipping chunk {}", chunk_index);
+                            return;
+                        }
+                        tts = state.tts_pool.acquire() => tts,
+                    };
+
+                    let result = match tts {
+                        Ok(tts) => {
+                            // The receiver may have gone away while we were acquiring
+                            if tx_clone.is_closed() {
+                                tracing::debug!(
+                                    "Client disconnected, skipping chunk {}",
+                                    chunk_index
+                                );
+                                return;
+                            }
+                            if aborted.load(Ordering::SeqCst) {
+                                return;
+                            }
+
+                            let generation_start = Instant::now();
+                            generate_chunk_with_metadata(
+                                tts,
+                                chunk.clone(),
+                                speed,
+                                &req,
+                                chunk_index,
+                                start_offset,
+                                metadata_version,
+                            )
+                            .await
+                            .map(|generated| (generated, generation_start))
+                        }
+                        Err(e) => Err(e),
+                    };
+
+                    match result {
+                        Err(e) if failures < retry.retries => {
+                            failures += 1;
+                            tracing::warn!(
+                                "Chunk {} failed (attempt {} of {}), retrying: {}",
+                                chunk_index,
+                                failures,
+                                retry.retries + 1,
+                                e
+                            );
+                        }
+                        result => break result,
+                    }
+                };
+
+                match result {
+                    Ok(((metadata, audio_bytes), generation_start)) => {
+                        // Chunks finishing after an abort are dropped
+                        if aborted.load(Ordering::SeqCst) {
+                            return;
+                        }
+
+                        let chars = metadata.text.chars().count();
+                        state.generation_stats.record(
+                            chars,
+                            speed,
+                            metadata.duration_ms,
+                            generation_start.elapsed(),
+                        );
+                        state.generation_stats.record_voice(
+                            &req.voice,
+                            chars,
+                            speed,
+                            metadata.duration_ms,
+                        );
+
                         tracing::debug!(
                             "Chunk {} ready ({:.0}ms duration), sending immediately",
                             chunk_index,
@@ -192,19 +484,45 @@ pub async fn generate_tts_stream(state: AppState, req: TTSRequest) -> Result<Res
                         );
 
                         // Send metadata part immediately
-                        if let Ok(metadata_bytes) = create_metadata_part(&metadata) {
-                            let _ = tx_clone.send(Ok(metadata_bytes)).await;
+                        if let Ok(metadata_bytes) = create_json_part(&metadata) {
+                            if tx_clone.send(Ok(metadata_bytes)).await.is_err() {
+                                tracing::debug!("Client disconnected before chunk {}", chunk_index);
+                                return;
+                            }
                         }
 
                         // Send audio part immediately
                         let audio_part = create_audio_part(audio_bytes);
                         let _ = tx_clone.send(Ok(audio_part)).await;
+
+                        if let Some(completed) = completed {
+                            // Counted and sent under the lock so progress never goes backwards
+                            let mut completed = completed.lock().await;
+                            *completed += 1;
+                            if let Ok(part) =
+                                create_json_part(&StreamProgress::new(*completed, total))
+                            {
+                                let _ = tx_clone.send(Ok(part)).await;
+                            }
+                        }
                     }
                     Err(e) => {
-                        let _ = tx_clone.send(Err(e.to_string())).await;
+                        tracing::error!("Chunk {} failed: {}", chunk_index, e);
+
+                        // Only the first failure is reported when aborting
+                        if retry.failure_mode == ChunkFailureMode::Abort
+                            && aborted.swap(true, Ordering::SeqCst)
+                        {
+                            return;
+                        }
+                        if let Ok(part) =
+                            create_json_part(&StreamError::new(chunk_index, e.to_string()))
+                        {
+                            let _ = tx_clone.send(Ok(part)).await;
+                        }
                     }
                 }
-            });
+            }));
 
             handles.push(handle);
         }
@@ -222,12 +540,15 @@ pub async fn generate_tts_stream(state: AppState, req: TTSRequest) -> Result<Res
             chunks.len(),
             start.elapsed()
         );
-    });
+    }));
 
     // Create streaming response with multipart content type
     let stream = ReceiverStream::new(rx).map(|result| result.map_err(std::io::Error::other));
 
-    let body = axum::body::Body::from_stream(stream);
+    let body = match state.stream_keep_alive {
+        Some(interval) => axum::body::Body::from_stream(with_keep_alive(stream, interval)),
+        None => axum::body::Body::from_stream(stream),
+    };
 
     Ok(Response::builder()
         .header(
@@ -235,6 +556,7 @@ pub async fn generate_tts_stream(state: AppState, req: TTSRequest) -> Result<Res
             format!("multipart/mixed; boundary={}", MULTIPART_BOUNDARY),
         )
         .header(header::TRANSFER_ENCODING, "chunked")
+        .header("Metadata-Version", metadata_version.as_str())
         .body(body)
         .unwrap())
 }
@@ -242,10 +564,13 @@ pub async fn generate_tts_stream(state: AppState, req: TTSRequest) -> Result<Res
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::constants::{DEFAULT_AUDIO_MS_PER_CHAR, MAX_TEXT_LENGTH};
+    use crate::kokoro::fake::fake_pool;
     use crate::models::PhraseMetadata;
+    use std::time::Duration;
 
     #[test]
-    fn test_create_metadata_part() {
+    fn test_create_json_part() {
         let metadata = ChunkMetadata {
             version: Some("2.0".to_string()),
             chunk_index: 0,
@@ -264,9 +589,10 @@ mod tests {
             start_offset_ms: 0.0,
             validation: None,
             debug_info: None,
+            waveform: None,
         };
 
-        let result = create_metadata_part(&metadata);
+        let result = create_json_part(&metadata);
         assert!(result.is_ok());
 
         let part = result.unwrap();
@@ -317,9 +643,11 @@ mod tests {
         }
 
         // Validate speed
-        if req.speed <= 0.0 || req.speed > 3.0 {
-            return Err(TtsError::InvalidSpeed(req.speed));
-        }
+        crate::utils::speed::resolve_speed(
+            req.speed(),
+            crate::config::constants::DEFAULT_MIN_SPEED,
+            false,
+        )?;
 
         Ok(())
     }
@@ -329,8 +657,9 @@ mod tests {
         let req = TTSRequest {
             text: "".to_string(),
             voice: "af_heart".to_string(),
-            speed: 1.0,
+            speed: Some(1.0),
             enable_chunking: false,
+            ..Default::default()
         };
 
         let result = validate_streaming_request(&req);
@@ -347,8 +676,9 @@ mod tests {
         let req = TTSRequest {
             text: "   \n\t  ".to_string(),
             voice: "af_heart".to_string(),
-            speed: 1.0,
+            speed: Some(1.0),
             enable_chunking: false,
+            ..Default::default()
         };
 
         let result = validate_streaming_request(&req);
@@ -368,8 +698,9 @@ mod tests {
         let req = TTSRequest {
             text: long_text,
             voice: "af_heart".to_string(),
-            speed: 1.0,
+            speed: Some(1.0),
             enable_chunking: false,
+            ..Default::default()
         };
 
         let result = validate_streaming_request(&req);
@@ -393,8 +724,9 @@ mod tests {
         let req = TTSRequest {
             text,
             voice: "af_heart".to_string(),
-            speed: 1.0,
+            speed: Some(1.0),
             enable_chunking: false,
+            ..Default::default()
         };
 
         let result = validate_streaming_request(&req);
@@ -409,8 +741,9 @@ mod tests {
         let req = TTSRequest {
             text,
             voice: "af_heart".to_string(),
-            speed: 1.0,
+            speed: Some(1.0),
             enable_chunking: false,
+            ..Default::default()
         };
 
         let result = validate_streaming_request(&req);
@@ -434,8 +767,9 @@ mod tests {
             let req = TTSRequest {
                 text,
                 voice: "af_heart".to_string(),
-                speed: 1.0,
+                speed: Some(1.0),
                 enable_chunking: false,
+                ..Default::default()
             };
 
             let result = validate_streaming_request(&req);
@@ -470,8 +804,9 @@ mod tests {
         let req = TTSRequest {
             text: very_long_text,
             voice: "af_heart".to_string(),
-            speed: 1.0,
+            speed: Some(1.0),
             enable_chunking: false,
+            ..Default::default()
         };
 
         let result = validate_streaming_request(&req);
@@ -592,6 +927,7 @@ mod tests {
         let config = ChunkingConfig {
             max_chunk_size: 50,
             min_chunk_size: 10,
+            ..Default::default()
         };
         let chunks = chunk_text(&normalized, &config);
 
@@ -731,12 +1067,12 @@ mod tests {
 
         let mut offsets = Vec::new();
         let mut temp_offset = 0.0;
+        let model = EstimateModel::default();
 
         // This mimics the offset calculation in generate_tts_stream
         for chunk_text in &chunks {
             offsets.push(temp_offset);
-            // ~80ms per character estimation
-            temp_offset += (chunk_text.len() as f64) * 80.0;
+            temp_offset += model.audio_ms(chunk_text.chars().count(), 1.0);
         }
 
         // Verify offsets are increasing
@@ -755,14 +1091,14 @@ mod tests {
         let ratio_1_to_0 = offsets[1] / chunks[0].len() as f64;
         let ratio_2_to_1 = (offsets[2] - offsets[1]) / chunks[1].len() as f64;
 
-        // Both should be ~80ms/char
+        // Both should be the default ms/char
         assert!(
-            (ratio_1_to_0 - 80.0).abs() < 1.0,
-            "Offset ratio should be approximately 80ms/char"
+            (ratio_1_to_0 - DEFAULT_AUDIO_MS_PER_CHAR).abs() < 1.0,
+            "Offset ratio should be approximately the default ms/char"
         );
         assert!(
-            (ratio_2_to_1 - 80.0).abs() < 1.0,
-            "Offset ratio should be approximately 80ms/char"
+            (ratio_2_to_1 - DEFAULT_AUDIO_MS_PER_CHAR).abs() < 1.0,
+            "Offset ratio should be approximately the default ms/char"
         );
     }
 
@@ -828,6 +1164,7 @@ mod tests {
             start_offset_ms: 0.0,
             validation: None,
             debug_info: None,
+            waveform: None,
         };
 
         assert_eq!(
@@ -835,4 +1172,691 @@ mod tests {
             "Metadata should contain chunk_index for client ordering"
         );
     }
+
+    // ===== Cancellation Tests =====
+
+    fn multi_chunk_request() -> TTSRequest {
+        TTSRequest {
+            text: "This sentence is long enough to make the chunker split the input. ".repeat(20),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_stops_acquiring_engines_after_disconnect() {
+        let (pool, log) = fake_pool(1, 200, Duration::from_millis(100));
+        let state = AppState::for_tests(pool);
+
+        let req = multi_chunk_request();
+        let chunk_count = chunk_text(&req.text, &ChunkingConfig::default()).len();
+        assert!(chunk_count > 4);
+
+        let response = generate_tts_stream(
+            state.clone(),
+            req,
+            MetadataVersion::default(),
+            false,
+            MAX_TEXT_LENGTH,
+        )
+        .await
+        .unwrap();
+
+        // Client disconnects before reading anything
+        drop(response);
+
+        // Long enough for every chunk to finish if nothing were cancelled
+        tokio::time::sleep(Duration::from_millis(100 * chunk_count as u64)).await;
+
+        assert!(
+            log.calls() <= 1,
+            "Expected at most the in-flight chunk to synthesize, got {} of {}",
+            log.calls(),
+            chunk_count
+        );
+        assert!(state.tts_pool.stats().total_requests <= 2);
+        assert_eq!(state.tts_pool.stats().active_requests, 0);
+
+        // Audio generated before the disconnect is still cleaned up
+        for path in log.outputs() {
+            assert!(!path.exists(), "Temp file leaked: {:?}", path);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_generates_all_chunks_when_connected() {
+        let (pool, log) = fake_pool(2, 200, Duration::ZERO);
+        let state = AppState::for_tests(pool);
+
+        let req = multi_chunk_request();
+        let chunk_count = chunk_text(&req.text, &ChunkingConfig::default()).len();
+
+        let response = generate_tts_stream(
+            state,
+            req,
+            MetadataVersion::default(),
+            false,
+            MAX_TEXT_LENGTH,
+        )
+        .await
+        .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        assert_eq!(log.calls(), chunk_count);
+        let body = String::from_utf8_lossy(&body);
+        assert_eq!(body.matches("Content-Type: audio/wav").count(), chunk_count);
+        assert!(body.ends_with(&create_boundary_end()));
+    }
+
+    #[tokio::test]
+    async fn test_stream_rejects_text_expanding_past_normalized_cap() {
+        let (pool, log) = fake_pool(1, 200, Duration::ZERO);
+        let state = AppState {
+            max_normalized_length: 20_000,
+            ..AppState::for_tests(pool)
+        };
+        let req = TTSRequest {
+            text: "$1 ".repeat(3_000),
+            ..Default::default()
+        };
+
+        match generate_tts_stream(
+            state,
+            req,
+            MetadataVersion::default(),
+            false,
+            MAX_TEXT_LENGTH,
+        )
+        .await
+        {
+            Err(TtsError::InvalidRequest(msg)) => {
+                assert!(msg.contains("Normalized text too long"), "{}", msg)
+            }
+            other => panic!("Expected InvalidRequest, got: {:?}", other.map(|_| ())),
+        }
+        assert_eq!(log.calls(), 0);
+    }
+
+    // ===== Chunk Retry Tests =====
+
+    /// Engine that fails on text containing "Unlucky" a set number of times
+    struct UnluckyEngine {
+        failures_left: std::sync::atomic::AtomicUsize,
+        inner: crate::kokoro::fake::FakeEngine,
+    }
+
+    impl crate::kokoro::SpeechEngine for UnluckyEngine {
+        fn speak(
+            &self,
+            text: &str,
+            output_path: &str,
+            style: &str,
+            speed: f32,
+            mono: bool,
+        ) -> std::result::Result<(), Box<dyn std::error::Error>> {
+            if text.contains("Unlucky")
+                && self
+                    .failures_left
+                    .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                    .is_ok()
+            {
+                return Err("engine crashed".into());
+            }
+            self.inner.speak(text, output_path, style, speed, mono)
+        }
+    }
+
+    /// Stream a request whose "Unlucky" chunk fails `failures` times
+    ///
+    /// Returns the index of that chunk, the chunk count and the body.
+    async fn stream_with_unlucky_chunk(
+        failures: usize,
+        retry: ChunkRetryConfig,
+    ) -> (usize, usize, String) {
+        let filler = "This sentence is long enough to make the chunker split the input. ";
+        let req = TTSRequest {
+            text: format!(
+                "{}Unlucky sentence in the middle. {}",
+                filler.repeat(3),
+                filler.repeat(3)
+            ),
+            ..Default::default()
+        };
+        let chunks = chunk_text(&req.text, &ChunkingConfig::default());
+        let unlucky = chunks.iter().position(|c| c.contains("Unlucky")).unwrap();
+
+        let engine = UnluckyEngine {
+            failures_left: std::sync::atomic::AtomicUsize::new(failures),
+            inner: crate::kokoro::fake::FakeEngine {
+                duration_ms: 200,
+                delay: Duration::from_millis(20),
+                log: Arc::default(),
+            },
+        };
+        let pool = crate::kokoro::TTSPool::from_engines(vec![Box::new(engine)]).unwrap();
+        let state = AppState {
+            chunk_retry: retry,
+            ..AppState::for_tests(pool)
+        };
+
+        let response = generate_tts_stream(
+            state,
+            req,
+            MetadataVersion::default(),
+            false,
+            MAX_TEXT_LENGTH,
+        )
+        .await
+        .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            unlucky,
+            chunks.len(),
+            String::from_utf8_lossy(&body).into_owned(),
+        )
+    }
+
+    fn error_parts(body: &str) -> Vec<serde_json::Value> {
+        body.split("Content-Type: application/json\r\n\r\n")
+            .skip(1)
+            .filter_map(|rest| rest.split("\r\n").next())
+            .map(|json| serde_json::from_str::<serde_json::Value>(json).unwrap())
+            .filter(|part| part["type"] == "error")
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_stream_retry_recovers_failed_chunk() {
+        let (_, chunk_count, body) =
+            stream_with_unlucky_chunk(1, ChunkRetryConfig::default()).await;
+
+        assert!(error_parts(&body).is_empty());
+        assert_eq!(body.matches("Content-Type: audio/wav").count(), chunk_count);
+        assert!(body.ends_with(&create_boundary_end()));
+    }
+
+    #[tokio::test]
+    async fn test_stream_skips_chunk_after_retries_run_out() {
+        let retry = ChunkRetryConfig {
+            retries: 1,
+            failure_mode: ChunkFailureMode::Skip,
+        };
+        let (unlucky, chunk_count, body) = stream_with_unlucky_chunk(2, retry).await;
+
+        let errors = error_parts(&body);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0]["chunk_index"], unlucky);
+        assert!(errors[0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("engine crashed"));
+
+        // Every other chunk still arrives
+        assert_eq!(
+            body.matches("Content-Type: audio/wav").count(),
+            chunk_count - 1
+        );
+        assert!(body.ends_with(&create_boundary_end()));
+    }
+
+    #[tokio::test]
+    async fn test_stream_abort_stops_after_failed_chunk() {
+        let retry = ChunkRetryConfig {
+            retries: 0,
+            failure_mode: ChunkFailureMode::Abort,
+        };
+        let (unlucky, chunk_count, body) = stream_with_unlucky_chunk(1, retry).await;
+
+        let errors = error_parts(&body);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0]["chunk_index"], unlucky);
+
+        // Chunks queued behind the failure are never sent
+        assert!(body.matches("Content-Type: audio/wav").count() <= unlucky + 1);
+        assert!(body.matches("Content-Type: audio/wav").count() < chunk_count);
+        assert!(body.ends_with(&create_boundary_end()));
+    }
+
+    // ===== Progress Tests =====
+
+    async fn stream_body(progress: bool) -> (usize, String) {
+        let (pool, _) = fake_pool(2, 200, Duration::ZERO);
+        let req = multi_chunk_request();
+        let chunk_count = chunk_text(&req.text, &ChunkingConfig::default()).len();
+
+        let response = generate_tts_stream(
+            AppState::for_tests(pool),
+            req,
+            MetadataVersion::default(),
+            progress,
+            MAX_TEXT_LENGTH,
+        )
+        .await
+        .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (chunk_count, String::from_utf8_lossy(&body).into_owned())
+    }
+
+    #[tokio::test]
+    async fn test_stream_progress_part_per_chunk() {
+        let (chunk_count, body) = stream_body(true).await;
+
+        let progress: Vec<serde_json::Value> = body
+            .split("Content-Type: application/json\r\n\r\n")
+            .skip(1)
+            .filter_map(|rest| rest.split("\r\n").next())
+            .map(|json| serde_json::from_str::<serde_json::Value>(json).unwrap())
+            .filter(|part| part["type"] == "progress")
+            .collect();
+
+        assert_eq!(progress.len(), chunk_count);
+        for (i, part) in progress.iter().enumerate() {
+            assert_eq!(part["completed"], i + 1);
+            assert_eq!(part["total"], chunk_count);
+        }
+        assert_eq!(body.matches("Content-Type: audio/wav").count(), chunk_count);
+    }
+
+    #[tokio::test]
+    async fn test_stream_keep_alive_parts_while_first_chunk_is_slow() {
+        // One chunk taking 300ms, with a keep-alive after every 50ms of quiet
+        let (pool, _) = fake_pool(1, 100, Duration::from_millis(300));
+        let state = AppState {
+            stream_keep_alive: Some(Duration::from_millis(50)),
+            ..AppState::for_tests(pool)
+        };
+        let req = TTSRequest {
+            text: "Hello there.".to_string(),
+            ..Default::default()
+        };
+
+        let response = generate_tts_stream(
+            state,
+            req,
+            MetadataVersion::default(),
+            false,
+            MAX_TEXT_LENGTH,
+        )
+        .await
+        .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8_lossy(&body);
+
+        let keep_alive = String::from_utf8_lossy(&create_keep_alive_part()).into_owned();
+        let first_audio = body.find("Content-Type: audio/wav").unwrap();
+        let before_audio = body[..first_audio].matches(&keep_alive).count();
+        assert!(before_audio >= 3, "{} keep-alive parts", before_audio);
+        assert!(body.starts_with(&keep_alive));
+
+        // Still well-formed: every part has headers and the stream ends properly
+        assert_eq!(body.matches("Content-Type: audio/wav").count(), 1);
+        assert!(body.ends_with(&create_boundary_end()));
+    }
+
+    #[tokio::test]
+    async fn test_stream_keep_alive_off_by_default() {
+        let (pool, _) = fake_pool(1, 100, Duration::from_millis(100));
+        let req = TTSRequest {
+            text: "Hello there.".to_string(),
+            ..Default::default()
+        };
+
+        let response = generate_tts_stream(
+            AppState::for_tests(pool),
+            req,
+            MetadataVersion::default(),
+            false,
+            MAX_TEXT_LENGTH,
+        )
+        .await
+        .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(!String::from_utf8_lossy(&body).contains(KEEP_ALIVE_CONTENT_TYPE));
+    }
+
+    #[tokio::test]
+    async fn test_stream_progress_off_by_default() {
+        let (_, body) = stream_body(false).await;
+        assert!(!body.contains("\"progress\""));
+    }
+
+    #[tokio::test]
+    async fn test_stream_offsets_follow_voice_rate() {
+        use crate::kokoro::voice_aliases::VoiceAliases;
+        use crate::services::estimate::VoiceRates;
+
+        let (pool, _) = fake_pool(2, 200, Duration::ZERO);
+        let state = AppState {
+            voice_rates: Arc::new(
+                VoiceRates::parse(Some("bm_george=90,af_sky=50"), &VoiceAliases::default())
+                    .unwrap(),
+            ),
+            ..AppState::for_tests(pool)
+        };
+
+        let last_offset = |voice: &str| {
+            let state = state.clone();
+            let req = TTSRequest {
+                voice: voice.to_string(),
+                ..multi_chunk_request()
+            };
+            async move {
+                let response = generate_tts_stream(
+                    state,
+                    req,
+                    MetadataVersion::default(),
+                    false,
+                    MAX_TEXT_LENGTH,
+                )
+                .await
+                .unwrap();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                String::from_utf8_lossy(&body)
+                    .split("Content-Type: application/json\r\n\r\n")
+                    .skip(1)
+                    .filter_map(|rest| rest.split("\r\n").next())
+                    .map(|json| serde_json::from_str::<serde_json::Value>(json).unwrap())
+                    .filter_map(|part| part["start_offset_ms"].as_f64())
+                    .fold(0.0, f64::max)
+            }
+        };
+
+        let slow = last_offset("bm_george").await;
+        let fast = last_offset("af_sky").await;
+        assert!(fast > 0.0);
+        assert!(
+            (slow / fast - 90.0 / 50.0).abs() < 0.01,
+            "{} vs {}",
+            slow,
+            fast
+        );
+    }
+
+    // ===== Metadata Version Tests =====
+
+    async fn first_metadata(text: &str, version: MetadataVersion) -> (Response, serde_json::Value) {
+        let (pool, _) = fake_pool(1, 300, Duration::ZERO);
+        let req = TTSRequest {
+            text: text.to_string(),
+            ..Default::default()
+        };
+
+        let response = generate_tts_stream(
+            AppState::for_tests(pool),
+            req,
+            version,
+            false,
+            MAX_TEXT_LENGTH,
+        )
+        .await
+        .unwrap();
+        let (parts, body) = response.into_parts();
+        let body = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        let body = String::from_utf8_lossy(&body);
+
+        let json = body
+            .split("Content-Type: application/json\r\n\r\n")
+            .nth(1)
+            .and_then(|rest| rest.split("\r\n").next())
+            .unwrap();
+        (
+            Response::from_parts(parts, axum::body::Body::empty()),
+            serde_json::from_str(json).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_stream_metadata_version_negotiation() {
+        let (v1_response, v1) = first_metadata("It costs $100.", MetadataVersion::V1).await;
+        let (v2_response, v2) = first_metadata("It costs $100.", MetadataVersion::V2).await;
+
+        assert_eq!(
+            v1_response.headers().get("Metadata-Version").unwrap(),
+            "1.0"
+        );
+        assert_eq!(
+            v2_response.headers().get("Metadata-Version").unwrap(),
+            "2.0"
+        );
+
+        assert!(v1.get("version").is_none());
+        assert!(v1.get("validation").is_none());
+        assert_eq!(v2["version"], "2.0");
+        assert!(v2.get("validation").is_some());
+        assert_eq!(
+            v1["phrases"].as_array().unwrap().len(),
+            v2["phrases"].as_array().unwrap().len()
+        );
+    }
+
+    // ===== Original Text Tests =====
+
+    #[tokio::test]
+    async fn test_stream_metadata_includes_original_text() {
+        let (_, metadata) = first_metadata("It costs $100.", MetadataVersion::V2).await;
+
+        assert_eq!(metadata["text"], "It costs one hundred dollars.");
+        assert_eq!(metadata["original_text"], "It costs $100.");
+        assert_eq!(
+            metadata["phrases"][0]["text"],
+            "It costs one hundred dollars."
+        );
+        assert_eq!(metadata["phrases"][0]["original_text"], "It costs $100.");
+    }
+
+    #[tokio::test]
+    async fn test_stream_metadata_waveform_is_opt_in() {
+        let (_, metadata) = first_metadata("Hello there.", MetadataVersion::V2).await;
+        assert!(metadata.get("waveform").is_none());
+
+        let req = TTSRequest {
+            text: "Hello there, this has a waveform.".to_string(),
+            waveform_points: Some(50),
+            ..Default::default()
+        };
+        let response = generate_tts_stream(
+            AppState::for_tests(crate::kokoro::fake::tone_pool(1)),
+            req,
+            MetadataVersion::default(),
+            false,
+            MAX_TEXT_LENGTH,
+        )
+        .await
+        .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8_lossy(&body);
+        let json = body
+            .split("Content-Type: application/json\r\n\r\n")
+            .nth(1)
+            .and_then(|rest| rest.split("\r\n").next())
+            .unwrap();
+        let metadata: serde_json::Value = serde_json::from_str(json).unwrap();
+
+        let waveform = metadata["waveform"].as_array().unwrap();
+        assert_eq!(waveform.len(), 50);
+        let peaks: Vec<f64> = waveform.iter().map(|v| v.as_f64().unwrap()).collect();
+        assert!(peaks.iter().all(|peak| (0.0..=1.0).contains(peak)));
+        assert!(peaks.iter().any(|&peak| peak > 0.0));
+    }
+
+    #[tokio::test]
+    async fn test_stream_rejects_invalid_waveform_points() {
+        let (pool, _) = fake_pool(1, 300, Duration::ZERO);
+        let req = TTSRequest {
+            text: "Hello".to_string(),
+            waveform_points: Some(0),
+            ..Default::default()
+        };
+        let result = generate_tts_stream(
+            AppState::for_tests(pool),
+            req,
+            MetadataVersion::default(),
+            false,
+            MAX_TEXT_LENGTH,
+        )
+        .await;
+        assert!(matches!(result, Err(TtsError::InvalidRequest(_))));
+    }
+
+    #[test]
+    fn test_chunk_normalization_carries_original_per_chunk() {
+        let text = "The first item is $5. ".repeat(10) + "The last one costs $100.";
+        let full = normalization::normalize_for_tts(&text);
+        let chunks = chunk_text(&full.normalized, &ChunkingConfig::default());
+        assert!(chunks.len() > 1);
+
+        let results = chunk_normalization(&full, &chunks);
+        assert_eq!(results.len(), chunks.len());
+        for (result, chunk) in results.iter().zip(&chunks) {
+            assert_eq!(&result.normalized, chunk);
+        }
+
+        let last = results.last().unwrap();
+        assert!(last.normalized.ends_with("one hundred dollars."));
+        assert!(last.original.ends_with("$100."));
+        assert!(!last.original.contains("dollars"));
+    }
+
+    // ===== Speed Markup Tests =====
+
+    #[tokio::test]
+    async fn test_stream_speed_markup_stripped_from_metadata() {
+        let (pool, log) = fake_pool(1, 1000, Duration::ZERO);
+        let req = TTSRequest {
+            text: "{{speed:0.5}}Slowly now.{{/speed}} Back to normal.".to_string(),
+            ..Default::default()
+        };
+
+        let response = generate_tts_stream(
+            AppState::for_tests(pool),
+            req,
+            MetadataVersion::V2,
+            false,
+            MAX_TEXT_LENGTH,
+        )
+        .await
+        .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8_lossy(&body);
+
+        assert_eq!(log.calls(), 2);
+        assert!(!body.contains("{{"));
+
+        let metadata: Vec<serde_json::Value> = body
+            .split("Content-Type: application/json\r\n\r\n")
+            .skip(1)
+            .map(|rest| serde_json::from_str(rest.split("\r\n").next().unwrap()).unwrap())
+            .collect();
+        let slow = metadata
+            .iter()
+            .find(|m| m["text"] == "Slowly now.")
+            .unwrap();
+        let normal = metadata
+            .iter()
+            .find(|m| m["text"] == "Back to normal.")
+            .unwrap();
+        assert!((slow["duration_ms"].as_f64().unwrap() - 2000.0).abs() < 1.0);
+        assert!((normal["duration_ms"].as_f64().unwrap() - 1000.0).abs() < 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_stream_rejects_non_speakable_input() {
+        let (pool, log) = fake_pool(1, 100, Duration::ZERO);
+        let req = TTSRequest {
+            text: "\u{1F600}\u{1F600} !!!".to_string(),
+            ..Default::default()
+        };
+
+        let result = generate_tts_stream(
+            AppState::for_tests(pool),
+            req,
+            MetadataVersion::V2,
+            false,
+            MAX_TEXT_LENGTH,
+        )
+        .await;
+        assert!(matches!(result, Err(TtsError::InvalidRequest(_))));
+        assert_eq!(log.calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_stream_speed_clamp() {
+        let req = TTSRequest {
+            text: "Hello".to_string(),
+            speed: Some(3.01),
+            ..Default::default()
+        };
+
+        let (pool, _) = fake_pool(1, 100, Duration::ZERO);
+        let strict = generate_tts_stream(
+            AppState::for_tests(pool),
+            req.clone(),
+            MetadataVersion::V2,
+            false,
+            MAX_TEXT_LENGTH,
+        )
+        .await;
+        assert!(matches!(strict, Err(TtsError::InvalidSpeed { .. })));
+
+        let (pool, log) = fake_pool(1, 100, Duration::ZERO);
+        let state = AppState {
+            speed_clamp: true,
+            ..AppState::for_tests(pool)
+        };
+        let response = generate_tts_stream(state, req, MetadataVersion::V2, false, MAX_TEXT_LENGTH)
+            .await
+            .unwrap();
+        let _ = axum::body::to_bytes(response.into_body(), usize::MAX).await;
+        assert_eq!(log.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_stream_without_normalization() {
+        let (pool, log) = fake_pool(1, 300, Duration::ZERO);
+        let req = TTSRequest {
+            text: "It costs $100.".to_string(),
+            normalize: false,
+            ..Default::default()
+        };
+
+        let response = generate_tts_stream(
+            AppState::for_tests(pool),
+            req,
+            MetadataVersion::V2,
+            false,
+            MAX_TEXT_LENGTH,
+        )
+        .await
+        .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8_lossy(&body);
+        let json = body
+            .split("Content-Type: application/json\r\n\r\n")
+            .nth(1)
+            .and_then(|rest| rest.split("\r\n").next())
+            .unwrap();
+        let metadata: serde_json::Value = serde_json::from_str(json).unwrap();
+
+        assert_eq!(log.texts(), vec!["It costs $100.".to_string()]);
+        assert_eq!(metadata["text"], "It costs $100.");
+        // Nothing was rewritten, so there's no separate original wording
+        assert!(metadata["original_text"].is_null());
+    }
 }
@@ -0,0 +1,176 @@
+/// Parenthetical asides spoken apart from the surrounding text
+///
+/// Narration sounds more natural when "(as it happens)" is read a little
+/// faster and quieter than the sentence around it. When enabled, each
+/// parenthesized span becomes its own segment, parentheses included, with
+/// the configured speed and gain adjustments.
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::config::constants::MAX_SPEED;
+use crate::text_processing::speed_markup::SpeechSegment;
+
+lazy_static! {
+    /// Innermost `(...)` span; nested parentheses are read from the inside out
+    static ref ASIDE_REGEX: Regex = Regex::new(r"\([^()]*\)").unwrap();
+}
+
+/// How parenthetical asides are spoken relative to their surroundings
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct AsideConfig {
+    /// Added to the surrounding speed, then kept within the accepted range
+    pub speed_delta: f32,
+    /// Volume change in decibels; negative is quieter
+    pub gain_db: f32,
+}
+
+impl Default for AsideConfig {
+    fn default() -> Self {
+        Self {
+            speed_delta: 0.1,
+            gain_db: -3.0,
+        }
+    }
+}
+
+/// Split parenthetical asides out of each segment
+///
+/// Asides take their segment's speed and gain, adjusted by `config`, with
+/// the speed kept between `min_speed` and `MAX_SPEED`. Spans with nothing
+/// speakable in them, like "(1)" or "()", stay where they are.
+pub fn split_asides(
+    segments: Vec<SpeechSegment>,
+    config: &AsideConfig,
+    min_speed: f32,
+) -> Vec<SpeechSegment> {
+    let mut result = Vec::new();
+
+    for segment in segments {
+        let mut last_end = 0;
+        for aside in ASIDE_REGEX.find_iter(&segment.text) {
+            if !aside.as_str().chars().any(char::is_alphabetic) {
+                continue;
+            }
+            push_segment(
+                &mut result,
+                &segment.text[last_end..aside.start()],
+                &segment,
+            );
+            push_segment(
+                &mut result,
+                aside.as_str(),
+                &SpeechSegment {
+                    text: String::new(),
+                    speed: (segment.speed + config.speed_delta).clamp(min_speed, MAX_SPEED),
+                    gain_db: segment.gain_db + config.gain_db,
+                    pitch_scale: segment.pitch_scale,
+                    pause_after: None,
+                },
+            );
+            last_end = aside.end();
+        }
+        push_segment(&mut result, &segment.text[last_end..], &segment);
+    }
+
+    result
+}
+
+fn push_segment(segments: &mut Vec<SpeechSegment>, text: &str, like: &SpeechSegment) {
+    let text = text.trim();
+    if !text.is_empty() {
+        segments.push(SpeechSegment {
+            text: text.to_string(),
+            speed: like.speed,
+            gain_db: like.gain_db,
+            pitch_scale: like.pitch_scale,
+            pause_after: like.pause_after,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::constants::DEFAULT_MIN_SPEED;
+
+    fn segment(text: &str, speed: f32, gain_db: f32) -> SpeechSegment {
+        SpeechSegment {
+            text: text.to_string(),
+            speed,
+            gain_db,
+            pitch_scale: 1.0,
+            pause_after: None,
+        }
+    }
+
+    #[test]
+    fn test_asides_are_split_with_adjusted_speed_and_gain() {
+        let config = AsideConfig {
+            speed_delta: 0.2,
+            gain_db: -4.0,
+        };
+        let segments = vec![segment("He left (as it happens) early.", 1.0, 0.0)];
+
+        assert_eq!(
+            split_asides(segments, &config, DEFAULT_MIN_SPEED),
+            vec![
+                segment("He left", 1.0, 0.0),
+                segment("(as it happens)", 1.2, -4.0),
+                segment("early.", 1.0, 0.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_asides_build_on_segment_speed() {
+        let config = AsideConfig::default();
+        let segments = vec![
+            segment("Slow (aside)", 0.5, 0.0),
+            segment("Fast (aside)", 3.0, 0.0),
+        ];
+
+        let speeds: Vec<f32> = split_asides(segments, &config, DEFAULT_MIN_SPEED)
+            .iter()
+            .map(|segment| segment.speed)
+            .collect();
+        assert_eq!(speeds, vec![0.5, 0.6, 3.0, MAX_SPEED]);
+    }
+
+    #[test]
+    fn test_asides_keep_to_configured_min_speed() {
+        let config = AsideConfig {
+            speed_delta: -0.3,
+            gain_db: 0.0,
+        };
+        let segments = vec![segment("Slow (aside)", 0.6, 0.0)];
+
+        let speeds: Vec<f32> = split_asides(segments, &config, 0.5)
+            .iter()
+            .map(|segment| segment.speed)
+            .collect();
+        assert_eq!(speeds, vec![0.6, 0.5]);
+    }
+
+    #[test]
+    fn test_unspeakable_parentheses_are_not_asides() {
+        let config = AsideConfig::default();
+        let text = "Step (1) of () the plan";
+
+        assert_eq!(
+            split_asides(vec![segment(text, 1.0, 0.0)], &config, DEFAULT_MIN_SPEED),
+            vec![segment(text, 1.0, 0.0)]
+        );
+    }
+
+    #[test]
+    fn test_nested_asides_split_innermost() {
+        let segments = split_asides(
+            vec![segment("A (b (c) d) e", 1.0, 0.0)],
+            &AsideConfig::default(),
+            DEFAULT_MIN_SPEED,
+        );
+        let texts: Vec<&str> = segments.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["A (b", "(c)", "d) e"]);
+    }
+}
@@ -0,0 +1,202 @@
+/// Bulleted and numbered lists read item by item
+///
+/// Read as plain text, "- eggs\n- milk\n- bread" runs together into one
+/// breathless sentence. When enabled, each line starting with a bullet
+/// (`-`, `*`, `+`, `•`) or a number (`1.`, `2)`) becomes its own segment,
+/// followed by a pause. Bullets are dropped, and numbers can be spoken as a
+/// lead-in ("One, eggs").
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use std::time::Duration;
+
+use crate::text_processing::semantic_normalization::cardinal_words;
+use crate::text_processing::speed_markup::SpeechSegment;
+
+lazy_static! {
+    /// A list item line: bullet or item number, then the item's text
+    static ref LIST_ITEM_REGEX: Regex = Regex::new(
+        r"^[ \t]*(?:[-*+•‣◦▪]|(\d{1,3})[.)])[ \t]+(\S.*?)\s*$"
+    ).unwrap();
+}
+
+/// How list items are spoken
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ListConfig {
+    /// Silence after each item
+    #[serde(
+        rename = "pause_ms",
+        serialize_with = "crate::utils::millis::serialize"
+    )]
+    pub pause: Duration,
+    /// Start numbered items with their number ("One, eggs")
+    pub speak_numbers: bool,
+}
+
+impl Default for ListConfig {
+    fn default() -> Self {
+        Self {
+            pause: Duration::from_millis(400),
+            speak_numbers: true,
+        }
+    }
+}
+
+/// Split list items out of each segment
+///
+/// Items keep their segment's speed and gain and are followed by
+/// `config.pause`. Lines around the list stay together as they were.
+pub fn split_list_items(segments: Vec<SpeechSegment>, config: &ListConfig) -> Vec<SpeechSegment> {
+    let mut result = Vec::new();
+
+    for segment in segments {
+        if !segment
+            .text
+            .lines()
+            .any(|line| LIST_ITEM_REGEX.is_match(line))
+        {
+            result.push(segment);
+            continue;
+        }
+
+        let mut prose = Vec::new();
+        for line in segment.text.lines() {
+            let Some(item) = list_item(line, config) else {
+                prose.push(line);
+                continue;
+            };
+            push_segment(&mut result, &prose.join("\n"), &segment, None);
+            prose.clear();
+            push_segment(&mut result, &item, &segment, Some(config.pause));
+        }
+        push_segment(
+            &mut result,
+            &prose.join("\n"),
+            &segment,
+            segment.pause_after,
+        );
+    }
+
+    result
+}
+
+/// Spoken text of a list item line, or None for any other line
+fn list_item(line: &str, config: &ListConfig) -> Option<String> {
+    let cap = LIST_ITEM_REGEX.captures(line)?;
+    let text = &cap[2];
+
+    let number = cap
+        .get(1)
+        .filter(|_| config.speak_numbers)
+        .and_then(|number| number.as_str().parse().ok())
+        .and_then(cardinal_words);
+    Some(match number {
+        Some(mut words) => {
+            words[..1].make_ascii_uppercase();
+            format!("{}, {}", words, text)
+        }
+        None => text.to_string(),
+    })
+}
+
+fn push_segment(
+    segments: &mut Vec<SpeechSegment>,
+    text: &str,
+    like: &SpeechSegment,
+    pause_after: Option<Duration>,
+) {
+    let text = text.trim();
+    if !text.is_empty() {
+        segments.push(SpeechSegment {
+            text: text.to_string(),
+            pause_after,
+            ..like.clone()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(text: &str) -> SpeechSegment {
+        SpeechSegment {
+            text: text.to_string(),
+            speed: 1.0,
+            gain_db: 0.0,
+            pitch_scale: 1.0,
+            pause_after: None,
+        }
+    }
+
+    fn texts(segments: &[SpeechSegment]) -> Vec<&str> {
+        segments.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_bullet_items_are_split_with_pauses() {
+        let config = ListConfig::default();
+        let segments = split_list_items(
+            vec![segment("You will need:\n- eggs\n* milk\n• bread")],
+            &config,
+        );
+
+        assert_eq!(
+            texts(&segments),
+            vec!["You will need:", "eggs", "milk", "bread"]
+        );
+        let pauses: Vec<_> = segments.iter().map(|s| s.pause_after).collect();
+        assert_eq!(
+            pauses,
+            vec![
+                None,
+                Some(config.pause),
+                Some(config.pause),
+                Some(config.pause)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_numbered_items_gain_spoken_numbers() {
+        let text = "1. Preheat the oven\n2) Mix the batter\n10. Serve";
+
+        let spoken = split_list_items(vec![segment(text)], &ListConfig::default());
+        assert_eq!(
+            texts(&spoken),
+            vec!["One, Preheat the oven", "Two, Mix the batter", "Ten, Serve"]
+        );
+
+        let config = ListConfig {
+            speak_numbers: false,
+            ..ListConfig::default()
+        };
+        let unspoken = split_list_items(vec![segment(text)], &config);
+        assert_eq!(
+            texts(&unspoken),
+            vec!["Preheat the oven", "Mix the batter", "Serve"]
+        );
+    }
+
+    #[test]
+    fn test_text_without_list_is_unchanged() {
+        for text in ["Plain text.", "-5 degrees\n2020. A year", "A - B"] {
+            assert_eq!(
+                split_list_items(vec![segment(text)], &ListConfig::default()),
+                vec![segment(text)]
+            );
+        }
+    }
+
+    #[test]
+    fn test_items_keep_segment_speed_and_gain() {
+        let slow = SpeechSegment {
+            speed: 0.8,
+            gain_db: -3.0,
+            ..segment("- one\n- two")
+        };
+
+        let segments = split_list_items(vec![slow], &ListConfig::default());
+        assert!(segments.iter().all(|s| s.speed == 0.8 && s.gain_db == -3.0));
+    }
+}
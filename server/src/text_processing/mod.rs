@@ -1,3 +1,9 @@
 /// Text processing utilities for TTS
+pub mod asides;
+pub mod lists;
 pub mod normalization;
+pub mod punctuation;
+pub mod quotes;
+pub mod semantic_normalization;
 pub mod sentence_splitting;
+pub mod speed_markup;
@@ -8,8 +8,11 @@
 use lazy_static::lazy_static;
 use num2words::Num2Words;
 use regex::{Captures, Regex};
+use serde::Serialize;
 use unicode_normalization::UnicodeNormalization;
 
+use super::semantic_normalization::{self, FractionStyle};
+
 lazy_static! {
     /// Currency with scale words (billion, million, trillion)
     static ref CURRENCY_SCALE_REGEX: Regex = Regex::new(
@@ -21,9 +24,15 @@ lazy_static! {
         r"\$(\d+(?:\.\d+)?)\b"
     ).unwrap();
 
-    /// Percentage patterns
+    /// Bare number with a scale word ("12.5 million", "3 billion"); currency
+    /// amounts are claimed by the currency patterns first
+    static ref NUMBER_SCALE_REGEX: Regex = Regex::new(
+        r"(?i)\b(\d+(?:\.\d+)?)\s+(thousand|million|billion|trillion)\b"
+    ).unwrap();
+
+    /// Percentage patterns, with an optional range ("50-60%", "50%–60%")
     static ref PERCENTAGE_REGEX: Regex = Regex::new(
-        r"(\d+(?:\.\d+)?)\s*%"
+        r"(?:(\d+(?:\.\d+)?)\s*%?\s*[-–]\s*)?(\d+(?:\.\d+)?)\s*%"
     ).unwrap();
 }
 
@@ -38,7 +47,40 @@ pub struct NormalizationResult {
     pub char_mapping: Vec<usize>,
 }
 
-#[derive(Debug, Clone)]
+impl NormalizationResult {
+    /// Result for text passed through without any normalization
+    pub fn unchanged(text: &str) -> NormalizationResult {
+        NormalizationResult {
+            original: text.to_string(),
+            normalized: text.to_string(),
+            char_mapping: (0..text.len()).collect(),
+        }
+    }
+
+    /// Sub-result for `normalized[start..end]` and the original text it came from
+    ///
+    /// Used to carry the mapping through chunking, so each chunk knows the
+    /// user's original wording. Returns None if the range can't be mapped.
+    pub fn slice(&self, start: usize, end: usize) -> Option<NormalizationResult> {
+        let (orig_start, orig_end) = map_normalized_to_original(start, end, self)?;
+        let original = self.original.get(orig_start..orig_end)?.to_string();
+        let normalized = self.normalized.get(start..end)?.to_string();
+        let char_mapping = self
+            .char_mapping
+            .get(start..end)?
+            .iter()
+            .map(|&pos| pos.saturating_sub(orig_start).min(original.len()))
+            .collect();
+
+        Some(NormalizationResult {
+            original,
+            normalized,
+            char_mapping,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct NormalizationInfo {
     /// Whether Unicode normalization was applied
     #[allow(dead_code)]
@@ -51,28 +93,183 @@ pub struct NormalizationInfo {
     pub normalized_length: usize,
 }
 
+/// How standalone symbols ("&", "@", "#", "+", "=") are expanded into words
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SymbolStyle {
+    /// Leave all symbols untouched
+    Off,
+    /// Expand symbols only where the reading is unambiguous ("R&D", "#1", "2+2").
+    /// Emails, handles and URLs are left intact.
+    Conservative,
+    /// Expand every symbol, including inside emails and handles ("user at host")
+    Full,
+}
+
+impl SymbolStyle {
+    /// Parse a `TTS_SYMBOL_STYLE` value; anything unrecognized means `conservative`
+    pub fn parse(value: &str) -> Self {
+        match value.to_lowercase().as_str() {
+            "off" => Self::Off,
+            "full" => Self::Full,
+            _ => Self::Conservative,
+        }
+    }
+}
+
+/// How emoji are handled before synthesis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmojiStyle {
+    /// Pass emoji through to the engine unchanged
+    Keep,
+    /// Remove emoji, including modifiers and ZWJ sequences
+    Strip,
+    /// Speak common emoji as words ("😀" → "smiley face"), stripping the rest
+    Words,
+}
+
+impl EmojiStyle {
+    /// Parse a `TTS_EMOJI_STYLE` value; anything unrecognized means `strip`
+    pub fn parse(value: &str) -> Self {
+        match value.to_lowercase().as_str() {
+            "keep" => Self::Keep,
+            "words" => Self::Words,
+            _ => Self::Strip,
+        }
+    }
+}
+
+/// Words for common emoji, used by `EmojiStyle::Words`
+///
+/// Looked up by the sequence's base character, so skin-tone and
+/// presentation variants share an entry ("👍🏽" → "thumbs up").
+const EMOJI_WORDS: &[(char, &str)] = &[
+    ('\u{1F600}', "smiley face"),
+    ('\u{1F603}', "smiley face"),
+    ('\u{1F604}', "smiley face"),
+    ('\u{1F642}', "smiling face"),
+    ('\u{1F60A}', "smiling face"),
+    ('\u{1F602}', "laughing"),
+    ('\u{1F923}', "laughing"),
+    ('\u{1F609}', "wink"),
+    ('\u{1F60D}', "heart eyes"),
+    ('\u{1F60E}', "cool"),
+    ('\u{1F622}', "crying face"),
+    ('\u{1F62D}', "crying face"),
+    ('\u{1F62E}', "surprised face"),
+    ('\u{1F621}', "angry face"),
+    ('\u{1F914}', "thinking face"),
+    ('\u{1F44D}', "thumbs up"),
+    ('\u{1F44E}', "thumbs down"),
+    ('\u{1F44F}', "clapping"),
+    ('\u{1F44B}', "waving hand"),
+    ('\u{1F64F}', "folded hands"),
+    ('\u{2764}', "heart"),
+    ('\u{1F494}', "broken heart"),
+    ('\u{1F525}', "fire"),
+    ('\u{1F389}', "party popper"),
+    ('\u{1F680}', "rocket"),
+    ('\u{2B50}', "star"),
+    ('\u{2705}', "check mark"),
+    ('\u{274C}', "cross mark"),
+    ('\u{1F4AF}', "one hundred"),
+];
+
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+
+/// Options controlling the semantic normalization passes
+///
+/// The on/off passes are all off by default: each guesses at the meaning of
+/// text that could be read another way, so they are enabled per deployment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct NormalizationConfig {
+    /// How fractions like "3/4" are spoken
+    pub fraction_style: FractionStyle,
+    /// How symbols like "&" and "#" are spoken
+    pub symbol_style: SymbolStyle,
+    /// Read roman numerals in headings and regnal names ("Chapter XIV", "Louis XVI")
+    pub roman_numerals: bool,
+    /// Whether emoji are stripped, spoken as words or kept
+    pub emoji_style: EmojiStyle,
+    /// Expand unit abbreviations after numbers ("5kg" → "five kilograms")
+    pub units: bool,
+    /// Read times with a zone ("3 PM EST" → "three PM Eastern Standard Time")
+    pub time_zones: bool,
+    /// Lowercase all-caps words and spell or keep known acronyms
+    /// ("STOP" → "stop", "USB" → "U.S.B")
+    pub all_caps: bool,
+    /// Rejoin words hyphenated across a line break ("inter-\nnational" →
+    /// "international"); some end-of-line hyphens are real
+    pub dehyphenate: bool,
+    /// Read decades as words ("1990s" → "nineteen nineties", "80s" → "eighties")
+    pub decades: bool,
+    /// Read arithmetic and comparison operators as words ("x = 2 * 3" →
+    /// "x equals 2 times 3"); technical text uses the same symbols for other
+    /// things
+    pub math: bool,
+    /// Read letters joined to a number together ("COVID-19" → "covid
+    /// nineteen", "MP3" → "em pee three")
+    pub alphanumerics: bool,
+    /// Read "v2" as "version two"; only applies with `alphanumerics`
+    pub versions: bool,
+    /// Spell out ligatures and fold fullwidth forms to ASCII ("ﬁ" → "fi",
+    /// "ＡＢＣ" → "ABC")
+    pub fold_forms: bool,
+}
+
+impl Default for NormalizationConfig {
+    fn default() -> Self {
+        Self {
+            fraction_style: FractionStyle::Named,
+            symbol_style: SymbolStyle::Conservative,
+            roman_numerals: false,
+            emoji_style: EmojiStyle::Strip,
+            units: false,
+            time_zones: false,
+            all_caps: false,
+            dehyphenate: false,
+            decades: false,
+            math: false,
+            alphanumerics: false,
+            versions: false,
+            fold_forms: false,
+        }
+    }
+}
+
 /// Normalize text for TTS while tracking the original
 ///
 /// This function handles:
-/// - Semantic normalization (currency, percentages, etc.)
+/// - Semantic normalization (currency, percentages, fractions, etc.)
 /// - Smart quotes → ASCII quotes
 /// - En/em dashes → ASCII hyphen
 /// - Ellipsis → three dots
 /// - Non-breaking spaces → regular spaces
 /// - Soft hyphens → removed
+/// - Ligatures → separate letters, fullwidth forms → ASCII
 /// - Unicode normalization (NFC form)
 ///
 /// All transformations are tracked to maintain accurate position mapping
 /// from normalized text back to original text.
 pub fn normalize_for_tts(text: &str) -> NormalizationResult {
+    normalize_for_tts_with_config(text, &NormalizationConfig::default())
+}
+
+/// Normalize text for TTS using the given semantic normalization options
+pub fn normalize_for_tts_with_config(
+    text: &str,
+    config: &NormalizationConfig,
+) -> NormalizationResult {
     let original = text.to_string();
 
     // PHASE 1: Apply semantic normalization with position tracking
-    let (semantically_normalized, semantic_mapping) = normalize_semantic_with_tracking(text);
+    let (semantically_normalized, semantic_mapping) =
+        normalize_semantic_with_tracking(text, config);
 
     // PHASE 2: Apply Unicode normalization with position tracking
-    let (mut normalized, unicode_mapping) =
-        normalize_unicode_with_tracking(&semantically_normalized);
+    let (normalized, unicode_mapping) =
+        normalize_unicode_with_tracking(&semantically_normalized, config.fold_forms);
 
     // PHASE 3: Compose mappings - map from final normalized to original
     // unicode_mapping[i] gives position in semantically_normalized
@@ -87,10 +284,8 @@ pub fn normalize_for_tts(text: &str) -> NormalizationResult {
         }
     }
 
-    // PHASE 4: Collapse multiple spaces (this may invalidate some mappings slightly)
-    while normalized.contains("  ") {
-        normalized = normalized.replace("  ", " ");
-    }
+    // PHASE 4: Collapse multiple spaces, keeping the mapping in step
+    let (normalized, char_mapping) = collapse_spaces(&normalized, &char_mapping);
 
     // PHASE 5: Apply Unicode normalization (NFC form)
     let normalized = normalized.nfc().collect::<String>();
@@ -102,11 +297,36 @@ pub fn normalize_for_tts(text: &str) -> NormalizationResult {
     }
 }
 
-/// Apply semantic normalization (currency, percentages) with position tracking
+/// Collapse runs of spaces into one, dropping the mapping entries of removed spaces
+pub(crate) fn collapse_spaces(text: &str, mapping: &[usize]) -> (String, Vec<usize>) {
+    let mut result = String::with_capacity(text.len());
+    let mut result_mapping = Vec::with_capacity(mapping.len());
+    let fallback = mapping.last().copied().unwrap_or(0);
+    let mut prev_space = false;
+
+    for (i, ch) in text.char_indices() {
+        if ch == ' ' && prev_space {
+            continue;
+        }
+        prev_space = ch == ' ';
+
+        result.push(ch);
+        for j in i..i + ch.len_utf8() {
+            result_mapping.push(mapping.get(j).copied().unwrap_or(fallback));
+        }
+    }
+
+    (result, result_mapping)
+}
+
+/// Apply semantic normalization (currency, percentages, fractions) with position tracking
 ///
 /// Returns: (normalized_text, byte_mapping)
 /// where byte_mapping[i] = original byte position for byte i in normalized text
-fn normalize_semantic_with_tracking(text: &str) -> (String, Vec<usize>) {
+fn normalize_semantic_with_tracking(
+    text: &str,
+    config: &NormalizationConfig,
+) -> (String, Vec<usize>) {
     let mut result = String::with_capacity(text.len() * 2);
     let mut mapping = Vec::new();
     let mut last_end = 0;
@@ -144,6 +364,99 @@ fn normalize_semantic_with_tracking(text: &str) -> (String, Vec<usize>) {
         }
     }
 
+    // Bare numbers with a scale ("12.5 million people" → "twelve point five
+    // million people"), skipping anything the currency patterns claimed
+    let scaled = NUMBER_SCALE_REGEX
+        .captures_iter(text)
+        .filter(|cap| {
+            let start = cap.get(0).map_or(0, |m| m.start());
+            !text[..start]
+                .chars()
+                .next_back()
+                .is_some_and(|c| matches!(c, '$' | '£' | '€' | '.' | ',') || c.is_alphanumeric())
+        })
+        .filter_map(|cap| {
+            let m = cap.get(0)?;
+            Some((m.start(), m.end(), format_number_with_scale(&cap)?))
+        })
+        .collect();
+    push_non_overlapping(&mut matches, scaled);
+
+    // A possessive after an amount read in the plural ("$10.3 billion's" →
+    // "ten point three billion dollars'")
+    attach_plural_possessives(text, &mut matches);
+
+    // Words split across lines ("inter-\nnational" → "international")
+    if config.dehyphenate {
+        let breaks = semantic_normalization::line_break_hyphen_matches(text);
+        push_non_overlapping(&mut matches, breaks);
+    }
+
+    // Fractions (skipping anything already claimed by currency/percentages)
+    let fractions = semantic_normalization::fraction_matches(text, config.fraction_style);
+    push_non_overlapping(&mut matches, fractions);
+
+    // Decades ("1990s" → "nineteen nineties"), before units claim the "s"
+    if config.decades {
+        let decades = semantic_normalization::decade_matches(text);
+        push_non_overlapping(&mut matches, decades);
+    }
+
+    // Roman numerals ("Chapter XIV" → "Chapter fourteen")
+    if config.roman_numerals {
+        let numerals = semantic_normalization::roman_numeral_matches(text);
+        push_non_overlapping(&mut matches, numerals);
+    }
+
+    // Times with a zone ("3 PM EST" → "three PM Eastern Standard Time")
+    if config.time_zones {
+        let times = semantic_normalization::time_zone_matches(
+            text,
+            semantic_normalization::DEFAULT_TIME_ZONES,
+        );
+        push_non_overlapping(&mut matches, times);
+    }
+
+    // Letters joined to a number ("COVID-19" → "covid nineteen"), before
+    // all-caps words so the prefix isn't lowercased on its own
+    if config.alphanumerics {
+        let tokens = semantic_normalization::alphanumeric_matches(
+            text,
+            semantic_normalization::DEFAULT_ALPHANUMERIC_PREFIXES,
+            config.versions,
+        );
+        push_non_overlapping(&mut matches, tokens);
+    }
+
+    // Units ("5kg" → "five kilograms", "-5°C" → "minus five degrees Celsius")
+    if config.units {
+        let units =
+            semantic_normalization::unit_matches(text, semantic_normalization::DEFAULT_UNITS);
+        push_non_overlapping(&mut matches, units);
+    }
+
+    // All-caps words ("STOP" → "stop", "USB" → "U.S.B"), after time zones and
+    // units so their abbreviations are expanded rather than respelled
+    if config.all_caps {
+        let words = semantic_normalization::all_caps_matches(
+            text,
+            semantic_normalization::DEFAULT_ACRONYMS,
+        );
+        push_non_overlapping(&mut matches, words);
+    }
+
+    // Emoji ("😀" → "" or "smiley face"), before symbols so keycaps like "#️⃣" stay whole
+    push_non_overlapping(&mut matches, emoji_matches(text, config.emoji_style));
+
+    // Math operators ("a < b" → "a less than b"), before symbols so "+" and
+    // "=" follow the math pass's stricter operand rules
+    if config.math {
+        push_non_overlapping(&mut matches, math_matches(text));
+    }
+
+    // Symbols ("&" → "and", "#1" → "number 1")
+    push_non_overlapping(&mut matches, symbol_matches(text, config.symbol_style));
+
     // Sort matches by start position
     matches.sort_by_key(|(start, _, _)| *start);
 
@@ -180,15 +493,453 @@ fn normalize_semantic_with_tracking(text: &str) -> (String, Vec<usize>) {
     (result, mapping)
 }
 
+/// Fold an "'s" straight after a replacement ending in a plural into the match
+///
+/// "billion dollars's" reads oddly; the plural possessive is "dollars'".
+/// A singular replacement keeps its "'s" ("one dollar's").
+fn attach_plural_possessives(text: &str, matches: &mut [(usize, usize, String)]) {
+    for (_, end, replacement) in matches.iter_mut() {
+        if !replacement.ends_with('s') {
+            continue;
+        }
+        let rest = &text[*end..];
+        let Some(suffix) = ["'s", "\u{2019}s"]
+            .into_iter()
+            .find(|suffix| rest.starts_with(suffix))
+        else {
+            continue;
+        };
+        if rest[suffix.len()..]
+            .chars()
+            .next()
+            .is_some_and(char::is_alphanumeric)
+        {
+            continue;
+        }
+        *end += suffix.len();
+        replacement.push('\'');
+    }
+}
+
+/// Add matches from an additional pass, dropping any that overlap existing ones
+fn push_non_overlapping(
+    matches: &mut Vec<(usize, usize, String)>,
+    candidates: Vec<(usize, usize, String)>,
+) {
+    for (start, end, replacement) in candidates {
+        let overlaps = matches.iter().any(|(s, e, _)| start < *e && *s < end);
+        if !overlaps {
+            matches.push((start, end, replacement));
+        }
+    }
+}
+
+/// Find emoji sequences to strip or replace with words
+///
+/// A sequence is a base emoji plus everything attached to it: variation
+/// selectors, skin-tone modifiers, tag characters, ZWJ-joined emoji
+/// ("👨‍👩‍👧") and flag pairs, so no partial codepoints are left behind.
+/// Keycaps ("1️⃣") are reduced to their digit or symbol.
+fn emoji_matches(text: &str, style: EmojiStyle) -> Vec<(usize, usize, String)> {
+    let mut matches = Vec::new();
+    if style == EmojiStyle::Keep {
+        return matches;
+    }
+
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let keycap = matches!(c, '0'..='9' | '#' | '*') && is_keycap_tail(&text[start + 1..]);
+        if !keycap && !is_emoji(c) {
+            continue;
+        }
+
+        // Absorb the rest of the sequence
+        let mut end = start + c.len_utf8();
+        let mut joined = false;
+        let mut after_zwj = false;
+        let mut flag_half = is_regional_indicator(c);
+        while let Some(&(pos, next)) = chars.peek() {
+            let attached = if after_zwj {
+                after_zwj = false;
+                is_emoji(next)
+            } else if next == ZERO_WIDTH_JOINER {
+                after_zwj = true;
+                joined = true;
+                true
+            } else if flag_half && is_regional_indicator(next) {
+                flag_half = false;
+                true
+            } else {
+                is_emoji_modifier(next)
+            };
+            if !attached {
+                break;
+            }
+            end = pos + next.len_utf8();
+            chars.next();
+        }
+
+        let prev = text[..start].chars().next_back();
+        let next = text[end..].chars().next();
+
+        let word = match style {
+            EmojiStyle::Words if !joined => EMOJI_WORDS
+                .iter()
+                .find(|(emoji, _)| *emoji == c)
+                .map(|(_, word)| *word),
+            _ => None,
+        };
+
+        let replacement = if keycap {
+            c.to_string()
+        } else if let Some(word) = word {
+            let mut replacement = String::new();
+            if prev.is_some_and(|p| !p.is_whitespace()) {
+                replacement.push(' ');
+            }
+            replacement.push_str(word);
+            if next.is_some_and(char::is_alphanumeric) {
+                replacement.push(' ');
+            }
+            replacement
+        } else if prev.is_some_and(char::is_alphanumeric) && next.is_some_and(char::is_alphanumeric)
+        {
+            // Don't glue the surrounding words together
+            " ".to_string()
+        } else {
+            String::new()
+        };
+
+        // Drop the space before a stripped emoji at the end of a clause ("Done 👍!")
+        let start = match prev {
+            Some(p)
+                if replacement.is_empty()
+                    && p == ' '
+                    && !next.is_some_and(char::is_alphanumeric) =>
+            {
+                start - 1
+            }
+            _ => start,
+        };
+
+        matches.push((start, end, replacement));
+    }
+
+    matches
+}
+
+/// Whether a character starts an emoji sequence
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F000..=0x1FAFF // pictographs, emoticons, transport, flags, supplemental symbols
+            | 0x2600..=0x27BF // miscellaneous symbols and dingbats
+            | 0x231A..=0x231B
+            | 0x23E9..=0x23F3
+            | 0x23F8..=0x23FA
+            | 0x2B05..=0x2B07
+            | 0x2B1B..=0x2B1C
+            | 0x2B50
+            | 0x2B55
+    )
+}
+
+/// Characters that modify the emoji before them rather than standing alone
+fn is_emoji_modifier(c: char) -> bool {
+    matches!(
+        c as u32,
+        0xFE0E..=0xFE0F // variation selectors
+            | 0x20E3 // combining enclosing keycap
+            | 0x1F3FB..=0x1F3FF // skin tones
+            | 0xE0020..=0xE007F // tag sequences (subdivision flags)
+    )
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    matches!(c as u32, 0x1F1E6..=0x1F1FF)
+}
+
+/// Whether the text continues a keycap ("1️⃣" is "1", U+FE0F, U+20E3)
+fn is_keycap_tail(rest: &str) -> bool {
+    rest.strip_prefix('\u{FE0F}')
+        .unwrap_or(rest)
+        .starts_with('\u{20E3}')
+}
+
+/// Find symbols that should be spoken as words
+///
+/// Each symbol is replaced by its word, padded with spaces where it was
+/// glued to neighbouring text ("R&D" → "R and D").
+fn symbol_matches(text: &str, style: SymbolStyle) -> Vec<(usize, usize, String)> {
+    let mut matches = Vec::new();
+    if style == SymbolStyle::Off {
+        return matches;
+    }
+
+    for (pos, c) in text.char_indices() {
+        if !matches!(c, '&' | '@' | '#' | '+' | '=') {
+            continue;
+        }
+
+        let end = pos + c.len_utf8();
+        let prev = text[..pos].chars().next_back();
+        let next = text[end..].chars().next();
+
+        if style == SymbolStyle::Conservative && is_protected_token(text, pos) {
+            continue;
+        }
+
+        let word = match c {
+            // "&amp;" style entities are markup, not prose
+            '&' if is_html_entity(&text[end..]) => continue,
+            '&' => "and",
+            '#' if next.is_some_and(|n| n.is_ascii_digit()) => "number",
+            '#' if style == SymbolStyle::Full || !next.is_some_and(char::is_alphanumeric) => "hash",
+            '@' if style == SymbolStyle::Full || is_standalone(prev, next) => "at",
+            '+' | '=' if is_between_operands(text, pos, end) => {
+                if c == '+' {
+                    "plus"
+                } else {
+                    "equals"
+                }
+            }
+            _ => continue,
+        };
+
+        matches.push((pos, end, spaced_word(prev, next, word)));
+    }
+
+    matches
+}
+
+/// A symbol's word, padded with spaces where the symbol was glued to
+/// neighbouring text
+fn spaced_word(prev: Option<char>, next: Option<char>, word: &str) -> String {
+    let mut replacement = String::new();
+    if prev.is_some_and(|p| !p.is_whitespace()) {
+        replacement.push(' ');
+    }
+    replacement.push_str(word);
+    if next.is_some_and(|n| !n.is_whitespace()) {
+        replacement.push(' ');
+    }
+    replacement
+}
+
+/// Operators read by the math pass, longest first so "<=" wins over "<"
+///
+/// The flag marks symbols that double as punctuation ("rock-solid",
+/// "and/or"), which are only read when spaced out ("10 - 3", "x / 2").
+const MATH_OPERATORS: &[(&str, &str, bool)] = &[
+    ("<=", "less than or equal to", false),
+    (">=", "greater than or equal to", false),
+    ("!=", "does not equal", false),
+    ("==", "equals", false),
+    ("=", "equals", false),
+    ("+", "plus", false),
+    ("-", "minus", true),
+    ("\u{2212}", "minus", false),
+    ("*", "times", false),
+    ("\u{00D7}", "times", false),
+    ("/", "divided by", true),
+    ("\u{00F7}", "divided by", false),
+    ("<", "less than", false),
+    (">", "greater than", false),
+    ("\u{2264}", "less than or equal to", false),
+    ("\u{2265}", "greater than or equal to", false),
+    ("\u{2260}", "does not equal", false),
+];
+
+/// Find arithmetic and comparison operators to read as words
+///
+/// Only operators between two operands count, where an operand is a number
+/// or a single letter ("x = 2 + 3 * 4", "a < b"), so "C++", "->" and
+/// "Q&A - answers" are left alone.
+fn math_matches(text: &str) -> Vec<(usize, usize, String)> {
+    let mut matches = Vec::new();
+    let mut pos = 0;
+
+    while let Some(c) = text[pos..].chars().next() {
+        let rest = &text[pos..];
+        let Some(&(op, word, needs_spaces)) = MATH_OPERATORS
+            .iter()
+            .find(|(op, _, _)| rest.starts_with(op))
+        else {
+            pos += c.len_utf8();
+            continue;
+        };
+
+        let end = pos + op.len();
+        if is_math_operator(text, pos, end, needs_spaces) {
+            let prev = text[..pos].chars().next_back();
+            let next = text[end..].chars().next();
+            matches.push((pos, end, spaced_word(prev, next, word)));
+        }
+        pos = end;
+    }
+
+    matches
+}
+
+/// Check for an operator between two math operands, spaced the same on
+/// both sides
+///
+/// Brackets around operands are looked through ("(2 + 3) * 4"), and the
+/// right operand may be negative ("x = -5").
+fn is_math_operator(text: &str, start: usize, end: usize, needs_spaces: bool) -> bool {
+    let before = &text[..start];
+    let after = &text[end..];
+    let spaced_before = before.ends_with(char::is_whitespace);
+    let spaced_after = after.starts_with(char::is_whitespace);
+    if spaced_before != spaced_after || (needs_spaces && !spaced_before) {
+        return false;
+    }
+
+    let before = before.trim_end().trim_end_matches(')');
+    let after = after
+        .trim_start()
+        .trim_start_matches('(')
+        .trim_start_matches(['-', '\u{2212}']);
+
+    let mut left: Vec<char> = before
+        .chars()
+        .rev()
+        .take_while(|&c| is_operand_char(c))
+        .collect();
+    left.reverse();
+    let left: String = left.into_iter().collect();
+    let right: String = after.chars().take_while(|&c| is_operand_char(c)).collect();
+
+    is_math_operand(left.trim_start_matches(['.', ',']))
+        && is_math_operand(right.trim_end_matches(['.', ',']))
+}
+
+fn is_operand_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '.' || c == ','
+}
+
+/// A number ("4", "3.5", "1,000") or a single-letter variable ("x")
+fn is_math_operand(token: &str) -> bool {
+    let mut chars = token.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => c.is_alphanumeric(),
+        (Some(_), Some(_)) => {
+            token.chars().any(|c| c.is_ascii_digit())
+                && token
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || c == '.' || c == ',')
+        }
+        (None, _) => false,
+    }
+}
+
+/// Check whether the whitespace-delimited token containing `pos` is an email, handle or URL
+fn is_protected_token(text: &str, pos: usize) -> bool {
+    let start = text[..pos]
+        .rfind(char::is_whitespace)
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let end = text[pos..]
+        .find(char::is_whitespace)
+        .map(|i| pos + i)
+        .unwrap_or(text.len());
+    let token = &text[start..end];
+
+    if token.contains("://") || token.starts_with("www.") {
+        return true;
+    }
+
+    // Emails ("user@host.com") and handles ("@user")
+    match token.find('@') {
+        Some(at) => token[at + 1..].starts_with(|c: char| c.is_alphanumeric() || c == '_'),
+        None => false,
+    }
+}
+
+fn is_html_entity(rest: &str) -> bool {
+    let name_len = rest
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '#')
+        .count();
+    name_len > 0 && rest[name_len..].starts_with(';')
+}
+
+fn is_standalone(prev: Option<char>, next: Option<char>) -> bool {
+    !matches!(prev, Some(p) if !p.is_whitespace()) && !matches!(next, Some(n) if !n.is_whitespace())
+}
+
+/// Check for arithmetic context: an operand on both sides ("2+2", "x = 5")
+///
+/// Spacing must be symmetric so signs like "and +1" aren't read as operators.
+fn is_between_operands(text: &str, start: usize, end: usize) -> bool {
+    let spaced_before = text[..start].ends_with(char::is_whitespace);
+    let spaced_after = text[end..].starts_with(char::is_whitespace);
+    if spaced_before != spaced_after {
+        return false;
+    }
+
+    let prev = text[..start].trim_end().chars().next_back();
+    let next = text[end..].trim_start().chars().next();
+
+    match (prev, next) {
+        (Some(p), Some(n)) => p.is_alphanumeric() && n.is_alphanumeric(),
+        _ => false,
+    }
+}
+
+/// Letters a typographic ligature stands for ("ﬁ" → "fi")
+fn ligature_letters(ch: char) -> Option<&'static str> {
+    match ch {
+        '\u{FB00}' => Some("ff"),
+        '\u{FB01}' => Some("fi"),
+        '\u{FB02}' => Some("fl"),
+        '\u{FB03}' => Some("ffi"),
+        '\u{FB04}' => Some("ffl"),
+        '\u{FB05}' | '\u{FB06}' => Some("st"),
+        '\u{0132}' => Some("IJ"),
+        '\u{0133}' => Some("ij"),
+        _ => None,
+    }
+}
+
+/// ASCII equivalent of a fullwidth form ("Ａ" → "A", "１" → "1")
+fn fullwidth_to_ascii(ch: char) -> Option<char> {
+    match ch {
+        // The fullwidth block mirrors printable ASCII at a fixed offset
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(ch as u32 - 0xFEE0),
+        // Ideographic space
+        '\u{3000}' => Some(' '),
+        _ => None,
+    }
+}
+
 /// Apply Unicode normalization with position tracking
 ///
+/// With `fold_forms`, ligatures are spelled out and fullwidth forms folded
+/// to ASCII; every output byte maps back to the start of its source character.
+///
 /// Returns: (normalized_text, byte_mapping)
 /// where byte_mapping[i] = byte position in input text for byte i in output
-fn normalize_unicode_with_tracking(text: &str) -> (String, Vec<usize>) {
+fn normalize_unicode_with_tracking(text: &str, fold_forms: bool) -> (String, Vec<usize>) {
     let mut result = String::with_capacity(text.len());
     let mut mapping = Vec::new();
 
     for (byte_idx, ch) in text.char_indices() {
+        if fold_forms {
+            if let Some(letters) = ligature_letters(ch) {
+                result.push_str(letters);
+                for _ in 0..letters.len() {
+                    mapping.push(byte_idx);
+                }
+                continue;
+            }
+            if let Some(ascii) = fullwidth_to_ascii(ch) {
+                result.push(ascii);
+                mapping.push(byte_idx);
+                continue;
+            }
+        }
+
         match ch {
             // Left and right double quotes → ASCII double quote
             '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => {
@@ -263,25 +1014,58 @@ fn format_currency_with_scale(caps: &Captures) -> String {
     format!("{} {} dollars", amount_words, scale_word)
 }
 
+/// Format a bare number with a scale word ("3 billion" → "three billion")
+fn format_number_with_scale(caps: &Captures) -> Option<String> {
+    let amount = caps[1].parse::<f64>().ok()?;
+    Some(format!(
+        "{} {}",
+        format_number_for_speech(amount),
+        caps[2].to_lowercase()
+    ))
+}
+
 /// Format simple currency for speech
 fn format_currency_simple(caps: &Captures) -> String {
-    let amount_str = &caps[1];
-    let amount = match amount_str.parse::<f64>() {
-        Ok(num) => num,
-        Err(_) => return caps[0].to_string(),
-    };
-    format_currency_for_speech(amount)
+    match amount_to_cents(&caps[1]) {
+        Some(cents) => format_currency_for_speech(cents),
+        None => caps[0].to_string(),
+    }
+}
+
+/// Convert a decimal amount ("1.995") to whole cents
+///
+/// Amounts with more than two decimal places are rounded to the nearest
+/// cent, halves rounding up, and a carry rolls over into the dollars
+/// ("1.995" is 200 cents). The arithmetic is done on the digits themselves,
+/// since a float can't represent most such amounts exactly.
+fn amount_to_cents(amount: &str) -> Option<u64> {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    let dollars = whole.parse::<u64>().ok()?;
+
+    let mut digits = fraction.bytes().map(|b| u64::from(b - b'0'));
+    let cents = digits.next().unwrap_or(0) * 10 + digits.next().unwrap_or(0);
+    let round_up = u64::from(digits.next().unwrap_or(0) >= 5);
+
+    dollars.checked_mul(100)?.checked_add(cents + round_up)
 }
 
-/// Format percentage for speech
+/// Format percentage (or percentage range) for speech
 fn format_percentage(caps: &Captures) -> String {
-    let number_str = &caps[1];
-    let number = match number_str.parse::<f64>() {
+    let number = match caps[2].parse::<f64>() {
         Ok(num) => num,
         Err(_) => return caps[0].to_string(),
     };
     let number_words = format_number_for_speech(number);
-    format!("{} percent", number_words)
+
+    match caps.get(1).map(|m| m.as_str().parse::<f64>()) {
+        Some(Ok(low)) => format!(
+            "{} to {} percent",
+            format_number_for_speech(low),
+            number_words
+        ),
+        Some(Err(_)) => caps[0].to_string(),
+        None => format!("{} percent", number_words),
+    }
 }
 
 /// Format a number for speech, handling both integers and decimals
@@ -331,29 +1115,29 @@ fn format_decimal_for_speech(num: f64) -> String {
     }
 }
 
-/// Format currency amount for speech with dollars and cents
-fn format_currency_for_speech(amount: f64) -> String {
-    let dollars = amount.floor() as i64;
-    let cents = ((amount.fract() * 100.0).round()) as i64;
-
-    let dollar_words = match Num2Words::new(dollars).to_words() {
-        Ok(words) => words,
-        Err(_) => dollars.to_string(),
-    };
-
-    let cent_words = match Num2Words::new(cents).to_words() {
-        Ok(words) => words,
-        Err(_) => cents.to_string(),
+/// Format a currency amount in cents for speech with dollars and cents
+fn format_currency_for_speech(total_cents: u64) -> String {
+    let dollars = total_cents / 100;
+    let cents = total_cents % 100;
+
+    let count_words = |count: u64, unit: &str| {
+        let words = i64::try_from(count)
+            .ok()
+            .and_then(|n| Num2Words::new(n).to_words().ok())
+            .unwrap_or_else(|| count.to_string());
+        let plural = if count == 1 { "" } else { "s" };
+        format!("{} {}{}", words, unit, plural)
     };
 
     match (dollars, cents) {
         (0, 0) => "zero dollars".to_string(),
-        (0, c) if c == 1 => format!("{} cent", cent_words),
-        (0, _) => format!("{} cents", cent_words),
-        (d, 0) if d == 1 => format!("{} dollar", dollar_words),
-        (_, 0) => format!("{} dollars", dollar_words),
-        (_, c) if c == 1 => format!("{} dollars and {} cent", dollar_words, cent_words),
-        (_, _) => format!("{} dollars and {} cents", dollar_words, cent_words),
+        (0, _) => count_words(cents, "cent"),
+        (_, 0) => count_words(dollars, "dollar"),
+        (_, _) => format!(
+            "{} and {}",
+            count_words(dollars, "dollar"),
+            count_words(cents, "cent")
+        ),
     }
 }
 
@@ -374,11 +1158,76 @@ pub fn get_normalization_info(result: &NormalizationResult) -> NormalizationInfo
     }
 }
 
+/// A single replacement made during normalization
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NormalizationChange {
+    /// Text as written in the original
+    pub original: String,
+    /// Text it was replaced with
+    pub normalized: String,
+}
+
+/// List the replacements normalization made, as original → normalized pairs
+///
+/// Built from the character mapping: runs of normalized text that don't copy
+/// the original verbatim are reported along with the original text they
+/// stand in for. Whitespace-only differences are left out.
+pub fn describe_changes(result: &NormalizationResult) -> Vec<NormalizationChange> {
+    let original = &result.original;
+    let mut changes = Vec::new();
+    // Original position just past the last character copied verbatim
+    let mut orig_cursor = 0;
+    // Normalized text replacing original[orig_cursor..] so far
+    let mut pending = String::new();
+
+    for (i, ch) in result.normalized.char_indices() {
+        let copied_from = result.char_mapping.get(i).copied().filter(|&pos| {
+            pos >= orig_cursor && original.get(pos..).is_some_and(|rest| rest.starts_with(ch))
+        });
+
+        match copied_from {
+            Some(pos) => {
+                push_change(&mut changes, &original[orig_cursor..pos], &pending);
+                pending.clear();
+                orig_cursor = pos + ch.len_utf8();
+            }
+            None => pending.push(ch),
+        }
+    }
+    push_change(&mut changes, &original[orig_cursor..], &pending);
+
+    changes
+}
+
+fn push_change(changes: &mut Vec<NormalizationChange>, original: &str, normalized: &str) {
+    let (original, normalized) = (original.trim(), normalized.trim());
+    if original != normalized {
+        changes.push(NormalizationChange {
+            original: original.to_string(),
+            normalized: normalized.to_string(),
+        });
+    }
+}
+
 /// Simple normalization for cases that don't need character mapping
 pub fn normalize_simple(text: &str) -> String {
     normalize_for_tts(text).normalized
 }
 
+/// Simple normalization using the given semantic normalization options
+pub fn normalize_simple_with_config(text: &str, config: &NormalizationConfig) -> String {
+    normalize_for_tts_with_config(text, config).normalized
+}
+
+/// Check whether normalized text has anything the engine can speak
+///
+/// Input made only of emoji, punctuation or unexpanded symbols has no letters
+/// or digits left after normalization. Symbols that normalization expands
+/// into words ("&" → "and") count as speakable.
+pub fn has_speakable_content(normalized: &str) -> bool {
+    normalized.chars().any(char::is_alphanumeric)
+}
+
 /// Find the corresponding text in the original string given a normalized position
 ///
 /// This function uses the char_mapping to accurately map byte positions
@@ -459,13 +1308,23 @@ fn find_char_boundary(text: &str, pos: usize, forward: bool) -> usize {
 ///
 /// This function attempts to find the original text that corresponds to
 /// a given normalized phrase, using position hints and mapping information.
+/// The phrase at `hint_position` wins over an earlier occurrence of the same
+/// normalized text, whose original wording or casing may differ ("stop. STOP.").
 pub fn extract_original_phrase(
     normalized_phrase: &str,
     full_text_result: &NormalizationResult,
     hint_position: Option<usize>,
 ) -> String {
+    let at_hint = hint_position.filter(|&pos| {
+        full_text_result
+            .normalized
+            .get(pos..pos + normalized_phrase.len())
+            == Some(normalized_phrase)
+    });
+
     // Try to find in normalized text first
-    if let Some(norm_pos) = full_text_result.normalized.find(normalized_phrase) {
+    if let Some(norm_pos) = at_hint.or_else(|| full_text_result.normalized.find(normalized_phrase))
+    {
         let norm_end = norm_pos + normalized_phrase.len();
 
         // Try to map back to original
@@ -552,6 +1411,54 @@ mod tests {
         assert_eq!(result.normalized, "non breaking");
     }
 
+    fn with_fold_forms() -> NormalizationConfig {
+        NormalizationConfig {
+            fold_forms: true,
+            ..NormalizationConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_ligatures_spelled_out() {
+        let text = "The \u{FB01}nal e\u{FB03}cient \u{FB02}ow";
+        let result = normalize_for_tts_with_config(text, &with_fold_forms());
+        assert_eq!(result.normalized, "The final efficient flow");
+        assert_eq!(result.char_mapping.len(), result.normalized.len());
+
+        let start = result.normalized.find("efficient").unwrap();
+        let (orig_start, orig_end) =
+            map_normalized_to_original(start, start + "efficient".len(), &result).unwrap();
+        assert_eq!(&text[orig_start..orig_end], "e\u{FB03}cient");
+
+        // Each letter of a ligature maps to the ligature itself
+        let ffi = text.find('\u{FB03}').unwrap();
+        assert_eq!(&result.char_mapping[start + 1..start + 4], &[ffi, ffi, ffi]);
+    }
+
+    #[test]
+    fn test_fullwidth_folded_to_ascii() {
+        let text = "\u{FF21}\u{FF22}\u{FF23}\u{3000}\u{FF48}\u{FF49}\u{FF01}";
+        let result = normalize_for_tts_with_config(text, &with_fold_forms());
+        assert_eq!(result.normalized, "ABC hi!");
+        assert_eq!(result.char_mapping.len(), result.normalized.len());
+
+        // Fullwidth characters are three bytes each
+        assert_eq!(&result.char_mapping[..3], &[0, 3, 6]);
+        let (orig_start, orig_end) = map_normalized_to_original(4, 6, &result).unwrap();
+        assert_eq!(&text[orig_start..orig_end], "\u{FF48}\u{FF49}");
+    }
+
+    #[test]
+    fn test_fold_forms_can_be_disabled() {
+        let config = NormalizationConfig {
+            fold_forms: false,
+            ..NormalizationConfig::default()
+        };
+        let text = "\u{FB01}le \u{FF21}\u{FF22}";
+        let result = normalize_for_tts_with_config(text, &config);
+        assert_eq!(result.normalized, text);
+    }
+
     #[test]
     fn test_multiple_spaces_collapsed() {
         let text = "too    many     spaces";
@@ -571,6 +1478,39 @@ mod tests {
         assert!(!result.normalized.contains("$10.3"));
     }
 
+    #[test]
+    fn test_bare_number_with_scale() {
+        for (text, expected) in [
+            (
+                "12.5 million people voted",
+                "twelve point five million people voted",
+            ),
+            ("About 3 billion", "About three billion"),
+            ("2 Thousand years", "two thousand years"),
+        ] {
+            assert_eq!(normalize_for_tts(text).normalized, expected, "{}", text);
+        }
+
+        let text = "Roughly 12.5 million.";
+        let result = normalize_for_tts(text);
+        let start = result.normalized.find("twelve").unwrap();
+        let end = start + "twelve point five million".len();
+        let (orig_start, orig_end) = map_normalized_to_original(start, end, &result).unwrap();
+        assert_eq!(&text[orig_start..orig_end], "12.5 million");
+    }
+
+    #[test]
+    fn test_scale_pass_does_not_double_apply_to_currency() {
+        let result = normalize_for_tts("Sold $12.5 million and 3 billion shares");
+        assert_eq!(
+            result.normalized,
+            "Sold twelve point five million dollars and three billion shares"
+        );
+        // "$12.5 million" reads once, as dollars, even split by extra spaces
+        let result = normalize_for_tts("$12.5   million");
+        assert_eq!(result.normalized, "twelve point five million dollars");
+    }
+
     #[test]
     fn test_simple_currency() {
         let text = "Price is $23.45";
@@ -581,6 +1521,21 @@ mod tests {
         assert!(!result.normalized.contains("$23.45"));
     }
 
+    #[test]
+    fn test_currency_rounding_carries_into_dollars() {
+        for (text, expected) in [
+            ("$1.995", "two dollars"),
+            ("$0.999", "one dollar"),
+            ("$9.999", "ten dollars"),
+            ("$1.994", "one dollar and ninety-nine cents"),
+            ("$0.005", "one cent"),
+            ("$1.01", "one dollar and one cent"),
+            ("$0.29", "twenty-nine cents"),
+        ] {
+            assert_eq!(normalize_for_tts(text).normalized, expected, "{}", text);
+        }
+    }
+
     #[test]
     fn test_percentage() {
         let text = "Growth was 50%";
@@ -589,6 +1544,87 @@ mod tests {
         assert!(!result.normalized.contains("50%"));
     }
 
+    #[test]
+    fn test_collapsed_spaces_keep_mapping_aligned() {
+        let result = normalize_for_tts("Hello   world");
+        assert_eq!(result.normalized, "Hello world");
+        assert_eq!(result.char_mapping.len(), result.normalized.len());
+
+        let pos = result.normalized.find("world").unwrap();
+        assert_eq!(result.char_mapping[pos], 8);
+    }
+
+    #[test]
+    fn test_describe_changes() {
+        let result = normalize_for_tts("Pay $100  now\u{2014}it\u{2019}s 50% off");
+        assert_eq!(
+            describe_changes(&result),
+            vec![
+                NormalizationChange {
+                    original: "$100".to_string(),
+                    normalized: "one hundred dollars".to_string(),
+                },
+                NormalizationChange {
+                    original: "\u{2014}".to_string(),
+                    normalized: "-".to_string(),
+                },
+                NormalizationChange {
+                    original: "\u{2019}".to_string(),
+                    normalized: "'".to_string(),
+                },
+                NormalizationChange {
+                    original: "50%".to_string(),
+                    normalized: "fifty percent".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_describe_changes_none_for_plain_text() {
+        assert!(describe_changes(&normalize_for_tts("Just words here.")).is_empty());
+    }
+
+    #[test]
+    fn test_percentage_range() {
+        let result = normalize_for_tts("Between 50-60% agreed");
+        assert_eq!(result.normalized, "Between fifty to sixty percent agreed");
+
+        // The whole spoken range maps back to the whole original range
+        let start = result.normalized.find("fifty").unwrap();
+        let end = start + "fifty to sixty percent".len();
+        let (orig_start, orig_end) = map_normalized_to_original(start, end, &result).unwrap();
+        assert_eq!(&result.original[orig_start..orig_end], "50-60%");
+
+        // Following text still maps correctly
+        let pos = result.normalized.find("agreed").unwrap();
+        let (orig_start, _) = map_normalized_to_original(pos, pos + 6, &result).unwrap();
+        assert_eq!(orig_start, result.original.find("agreed").unwrap());
+    }
+
+    #[test]
+    fn test_percentage_range_decimal_and_variants() {
+        let result = normalize_for_tts("Support was 33.5-40%.");
+        assert!(result
+            .normalized
+            .contains("thirty-three point five to forty percent"));
+
+        for text in ["50%-60%", "50 – 60%"] {
+            assert_eq!(
+                normalize_for_tts(text).normalized,
+                "fifty to sixty percent",
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn test_out_of_left_as_is() {
+        let result = normalize_for_tts("3 out of 5 respondents");
+        assert_eq!(result.normalized, "3 out of 5 respondents");
+    }
+
     // ===== Combined Normalization Tests (CRITICAL REGRESSION TESTS) =====
 
     #[test]
@@ -644,9 +1680,14 @@ mod tests {
     fn test_smart_quote_after_currency() {
         let text = "$10.3 billion's impact";
         let result = normalize_for_tts(text);
-        assert!(result
-            .normalized
-            .contains("ten point three billion dollars"));
+        assert_eq!(result.normalized, "ten point three billion dollars' impact");
+
+        // Curly apostrophes too, while singular amounts keep their "'s"
+        let result = normalize_for_tts("$5\u{2019}s worth and $1's worth");
+        assert_eq!(
+            result.normalized,
+            "five dollars' worth and one dollar's worth"
+        );
 
         // Test position mapping
         if let Some(pos) = result.normalized.find("billion") {
@@ -680,6 +1721,44 @@ mod tests {
 
     // ===== Position Mapping Tests =====
 
+    #[test]
+    fn test_has_speakable_content() {
+        assert!(has_speakable_content("Hello"));
+        assert!(has_speakable_content("42"));
+        assert!(has_speakable_content("\u{00E9}t\u{00E9}"));
+        assert!(!has_speakable_content(""));
+        assert!(!has_speakable_content("  \n\t"));
+        assert!(!has_speakable_content("?!... --- ***"));
+        assert!(!has_speakable_content("\u{1F600}\u{1F389}\u{1F44D}"));
+    }
+
+    #[test]
+    fn test_symbol_expansion_is_speakable() {
+        let result = normalize_for_tts(" & ");
+        assert!(has_speakable_content(&result.normalized));
+    }
+
+    #[test]
+    fn test_slice_maps_chunk_back_to_original() {
+        let result = normalize_for_tts("First part. It costs $100 today.");
+        let start = result.normalized.find("It costs").unwrap();
+        let end = result.normalized.len();
+
+        let slice = result.slice(start, end).unwrap();
+        assert_eq!(slice.normalized, "It costs one hundred dollars today.");
+        assert_eq!(slice.original, "It costs $100 today.");
+        assert_eq!(slice.char_mapping.len(), slice.normalized.len());
+
+        let phrase_start = slice.normalized.find("one hundred dollars").unwrap();
+        let (orig_start, orig_end) = map_normalized_to_original(
+            phrase_start,
+            phrase_start + "one hundred dollars".len(),
+            &slice,
+        )
+        .unwrap();
+        assert_eq!(&slice.original[orig_start..orig_end], "$100");
+    }
+
     #[test]
     fn test_map_normalized_to_original_simple() {
         let text = "Hello world";
@@ -751,6 +1830,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_extract_original_phrase_prefers_hint_position() {
+        let text = "Stop right here. STOP RIGHT HERE.";
+        let config = NormalizationConfig {
+            all_caps: true,
+            ..NormalizationConfig::default()
+        };
+        let result = normalize_for_tts_with_config(text, &config);
+        assert_eq!(result.normalized, "Stop right here. stop right here.");
+
+        let second = result.normalized.rfind("right here.").unwrap();
+        assert_eq!(
+            extract_original_phrase("right here.", &result, Some(second)),
+            "RIGHT HERE."
+        );
+        // Without a hint the first occurrence is used
+        assert_eq!(
+            extract_original_phrase("right here.", &result, None),
+            "right here."
+        );
+        // A stale hint falls back to searching
+        assert_eq!(
+            extract_original_phrase("right here.", &result, Some(1)),
+            "right here."
+        );
+    }
+
     // ===== Info Tests =====
 
     #[test]
@@ -831,4 +1937,473 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_fraction_normalization_default_named() {
+        let result = normalize_for_tts("Add 1/2 cup and 3/4 teaspoon.");
+        assert_eq!(
+            result.normalized,
+            "Add one half cup and three quarters teaspoon."
+        );
+    }
+
+    #[test]
+    fn test_fraction_normalization_over_style() {
+        let config = NormalizationConfig {
+            fraction_style: FractionStyle::Over,
+            ..NormalizationConfig::default()
+        };
+        let result = normalize_for_tts_with_config("Use a 5/8 bolt.", &config);
+        assert_eq!(result.normalized, "Use a five over eight bolt.");
+    }
+
+    #[test]
+    fn test_fraction_normalization_leaves_dates() {
+        let result = normalize_for_tts("Meeting on 10/11/2024.");
+        assert_eq!(result.normalized, "Meeting on 10/11/2024.");
+    }
+
+    #[test]
+    fn test_fraction_mapping_to_original() {
+        let text = "Add 1/2 cup";
+        let result = normalize_for_tts(text);
+        let start = result.normalized.find("one half").unwrap();
+        let (orig_start, orig_end) =
+            map_normalized_to_original(start, start + "one half".len(), &result).unwrap();
+        assert_eq!(&text[orig_start..orig_end], "1/2");
+    }
+
+    fn with_roman_numerals() -> NormalizationConfig {
+        NormalizationConfig {
+            roman_numerals: true,
+            ..NormalizationConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_roman_numeral_normalization() {
+        let text = "Chapter XIV: Louis XVI returns.";
+        let result = normalize_for_tts_with_config(text, &with_roman_numerals());
+        assert_eq!(
+            result.normalized,
+            "Chapter fourteen: Louis the Sixteenth returns."
+        );
+
+        // Off by default
+        assert_eq!(normalize_for_tts(text).normalized, text);
+    }
+
+    #[test]
+    fn test_roman_numeral_normalization_disabled() {
+        let config = NormalizationConfig {
+            roman_numerals: false,
+            ..NormalizationConfig::default()
+        };
+        let result = normalize_for_tts_with_config("Chapter XIV", &config);
+        assert_eq!(result.normalized, "Chapter XIV");
+    }
+
+    #[test]
+    fn test_roman_numeral_mapping_to_original() {
+        let text = "Read Chapter XIV now";
+        let result = normalize_for_tts_with_config(text, &with_roman_numerals());
+        let start = result.normalized.find("fourteen").unwrap();
+        let (orig_start, orig_end) =
+            map_normalized_to_original(start, start + "fourteen".len(), &result).unwrap();
+        assert_eq!(&text[orig_start..orig_end], "XIV");
+    }
+
+    #[test]
+    fn test_unit_normalization() {
+        let config = NormalizationConfig {
+            units: true,
+            ..NormalizationConfig::default()
+        };
+        let result = normalize_for_tts_with_config("5kg at 10mph in 30°C", &config);
+        assert_eq!(
+            result.normalized,
+            "five kilograms at ten miles per hour in thirty degrees Celsius"
+        );
+
+        // Off by default
+        let result = normalize_for_tts("5kg");
+        assert_eq!(result.normalized, "5kg");
+    }
+
+    #[test]
+    fn test_time_zone_normalization() {
+        let text = "Doors open at 3 PM EST, not noon UTC.";
+        let config = NormalizationConfig {
+            time_zones: true,
+            ..NormalizationConfig::default()
+        };
+        let result = normalize_for_tts_with_config(text, &config);
+        assert_eq!(
+            result.normalized,
+            "Doors open at three PM Eastern Standard Time, not noon Coordinated Universal Time."
+        );
+
+        let phrase = "three PM Eastern Standard Time";
+        let start = result.normalized.find(phrase).unwrap();
+        let (orig_start, orig_end) =
+            map_normalized_to_original(start, start + phrase.len(), &result).unwrap();
+        assert_eq!(&text[orig_start..orig_end], "3 PM EST");
+
+        // Off by default
+        let result = normalize_for_tts("at 3 PM EST");
+        assert_eq!(result.normalized, "at 3 PM EST");
+    }
+
+    #[test]
+    fn test_all_caps_words_and_toggle() {
+        let text = "NASA said STOP using the USB port";
+        let config = NormalizationConfig {
+            all_caps: true,
+            ..NormalizationConfig::default()
+        };
+        let result = normalize_for_tts_with_config(text, &config);
+        assert_eq!(result.normalized, "NASA said stop using the U.S.B port");
+
+        // Positions after the longer spelled acronym still map back
+        let port = result.normalized.find("port").unwrap();
+        let (orig_start, orig_end) = map_normalized_to_original(port, port + 4, &result).unwrap();
+        assert_eq!(&text[orig_start..orig_end], "port");
+
+        // Off by default
+        let result = normalize_for_tts(text);
+        assert_eq!(result.normalized, text);
+    }
+
+    #[test]
+    fn test_dehyphenation_toggle_and_mapping() {
+        let text = "An inter-\nnational, state-of-\nthe-art soft\u{00AD}ware plan";
+        let config = NormalizationConfig {
+            dehyphenate: true,
+            ..NormalizationConfig::default()
+        };
+        let result = normalize_for_tts_with_config(text, &config);
+        assert_eq!(
+            result.normalized,
+            "An international, state-of-the-art software plan"
+        );
+
+        // The rejoined word maps back to its split original
+        let start = result.normalized.find("international").unwrap();
+        let (orig_start, orig_end) =
+            map_normalized_to_original(start, start + "international".len(), &result).unwrap();
+        assert_eq!(&text[orig_start..orig_end], "inter-\nnational");
+
+        // Off by default: the line break stays
+        let result = normalize_for_tts(text);
+        assert!(result.normalized.contains("inter-\nnational"));
+        assert!(result.normalized.contains("software"));
+    }
+
+    #[test]
+    fn test_unit_mapping_with_degree_symbol() {
+        let text = "Low of -5°C tonight";
+        let config = NormalizationConfig {
+            units: true,
+            ..NormalizationConfig::default()
+        };
+        let result = normalize_for_tts_with_config(text, &config);
+        assert_eq!(
+            result.normalized,
+            "Low of minus five degrees Celsius tonight"
+        );
+
+        let phrase = "minus five degrees Celsius";
+        let start = result.normalized.find(phrase).unwrap();
+        let (orig_start, orig_end) =
+            map_normalized_to_original(start, start + phrase.len(), &result).unwrap();
+        assert_eq!(&text[orig_start..orig_end], "-5°C");
+
+        let pos = result.normalized.find("tonight").unwrap();
+        let (orig_start, _) = map_normalized_to_original(pos, pos + 7, &result).unwrap();
+        assert_eq!(orig_start, text.find("tonight").unwrap());
+    }
+
+    fn with_emoji(emoji_style: EmojiStyle) -> NormalizationConfig {
+        NormalizationConfig {
+            emoji_style,
+            ..NormalizationConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_emoji_stripped_by_default() {
+        let result = normalize_for_tts("Great job \u{1F600} see you soon\u{1F44D}\u{1F3FD}!");
+        assert_eq!(result.normalized, "Great job see you soon!");
+
+        let result = normalize_for_tts("Mind\u{1F525}blown");
+        assert_eq!(result.normalized, "Mind blown");
+    }
+
+    #[test]
+    fn test_emoji_words() {
+        let config = with_emoji(EmojiStyle::Words);
+        let result =
+            normalize_for_tts_with_config("Great job\u{1F600} \u{2764}\u{FE0F} it", &config);
+        assert_eq!(result.normalized, "Great job smiley face heart it");
+
+        // Skin tones share the base emoji's word; unknown emoji are stripped
+        let result = normalize_for_tts_with_config("\u{1F44D}\u{1F3FF} ok \u{1F9FF}", &config);
+        assert_eq!(result.normalized, "thumbs up ok");
+    }
+
+    #[test]
+    fn test_emoji_zwj_sequence_removed_whole() {
+        // Man, ZWJ, woman, ZWJ, girl
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        for style in [EmojiStyle::Strip, EmojiStyle::Words] {
+            let text = format!("Our {} trip", family);
+            let result = normalize_for_tts_with_config(&text, &with_emoji(style));
+            assert_eq!(result.normalized, "Our trip");
+            assert!(!result.normalized.contains(ZERO_WIDTH_JOINER));
+        }
+    }
+
+    #[test]
+    fn test_emoji_flags_and_keycaps() {
+        let result = normalize_for_tts("Go \u{1F1EC}\u{1F1E7} team, step 1\u{FE0F}\u{20E3}");
+        assert_eq!(result.normalized, "Go team, step 1");
+    }
+
+    #[test]
+    fn test_emoji_kept_when_configured() {
+        let text = "Hi \u{1F600}";
+        let result = normalize_for_tts_with_config(text, &with_emoji(EmojiStyle::Keep));
+        assert_eq!(result.normalized, text);
+    }
+
+    #[test]
+    fn test_emoji_mapping_to_original() {
+        let text = "I \u{1F600} paid $5 today";
+        let result = normalize_for_tts(text);
+        assert_eq!(result.normalized, "I paid five dollars today");
+
+        let start = result.normalized.find("five dollars").unwrap();
+        let (orig_start, orig_end) =
+            map_normalized_to_original(start, start + "five dollars".len(), &result).unwrap();
+        assert_eq!(&text[orig_start..orig_end], "$5");
+
+        let pos = result.normalized.find("today").unwrap();
+        let (orig_start, _) = map_normalized_to_original(pos, pos + 5, &result).unwrap();
+        assert_eq!(orig_start, text.find("today").unwrap());
+    }
+
+    fn with_symbols(symbol_style: SymbolStyle) -> NormalizationConfig {
+        NormalizationConfig {
+            symbol_style,
+            ..NormalizationConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_symbol_ampersand() {
+        let result = normalize_for_tts("R&D spending");
+        assert_eq!(result.normalized, "R and D spending");
+
+        let result = normalize_for_tts("salt & pepper");
+        assert_eq!(result.normalized, "salt and pepper");
+    }
+
+    #[test]
+    fn test_symbol_number_sign() {
+        let result = normalize_for_tts("He was the #1 pick.");
+        assert_eq!(result.normalized, "He was the number 1 pick.");
+    }
+
+    #[test]
+    fn test_symbol_plus_and_equals() {
+        let result = normalize_for_tts("2+2 = 4");
+        assert_eq!(result.normalized, "2 plus 2 equals 4");
+    }
+
+    #[test]
+    fn test_symbol_plus_not_expanded_outside_arithmetic() {
+        let result = normalize_for_tts("I like C++ and +1 votes");
+        assert_eq!(result.normalized, "I like C++ and +1 votes");
+    }
+
+    #[test]
+    fn test_symbol_email_intact_by_default() {
+        let text = "Write to user@example.com or @porua";
+        let result = normalize_for_tts(text);
+        assert_eq!(result.normalized, text);
+    }
+
+    #[test]
+    fn test_symbol_email_intact_when_off() {
+        let text = "Email user@example.com about R&D #1";
+        let result = normalize_for_tts_with_config(text, &with_symbols(SymbolStyle::Off));
+        assert_eq!(result.normalized, text);
+    }
+
+    #[test]
+    fn test_symbol_full_expands_email() {
+        let result = normalize_for_tts_with_config("user@host", &with_symbols(SymbolStyle::Full));
+        assert_eq!(result.normalized, "user at host");
+    }
+
+    #[test]
+    fn test_symbol_standalone_at_and_hash() {
+        let result = normalize_for_tts("meet @ noon, press #");
+        assert_eq!(result.normalized, "meet at noon, press hash");
+    }
+
+    #[test]
+    fn test_symbol_urls_and_entities_untouched() {
+        let text = "See https://example.com/?a=1&b=2 and &amp; here";
+        let result = normalize_for_tts(text);
+        assert_eq!(result.normalized, text);
+    }
+
+    #[test]
+    fn test_symbol_mapping_to_original() {
+        let text = "salt & pepper";
+        let result = normalize_for_tts(text);
+        let start = result.normalized.find("and").unwrap();
+        let (orig_start, orig_end) = map_normalized_to_original(start, start + 3, &result).unwrap();
+        assert_eq!(&text[orig_start..orig_end], "&");
+    }
+
+    #[test]
+    fn test_alphanumeric_normalization_and_toggles() {
+        let text = "COVID-19 news: the G7 ships v2 as an MP3.";
+        let enabled = NormalizationConfig {
+            all_caps: true,
+            alphanumerics: true,
+            versions: true,
+            ..NormalizationConfig::default()
+        };
+        let result = normalize_for_tts_with_config(text, &enabled);
+        assert_eq!(
+            result.normalized,
+            "covid nineteen news: the gee seven ships version two as an em pee three."
+        );
+
+        let start = result.normalized.find("em pee three").unwrap();
+        let (orig_start, orig_end) =
+            map_normalized_to_original(start, start + "em pee three".len(), &result).unwrap();
+        assert_eq!(&text[orig_start..orig_end], "MP3");
+
+        let config = NormalizationConfig {
+            versions: false,
+            ..enabled
+        };
+        let result = normalize_for_tts_with_config(text, &config);
+        assert_eq!(
+            result.normalized,
+            "covid nineteen news: the gee seven ships v2 as an em pee three."
+        );
+
+        let config = NormalizationConfig {
+            alphanumerics: false,
+            ..enabled
+        };
+        let result = normalize_for_tts_with_config(text, &config);
+        assert_eq!(
+            result.normalized,
+            "covid-19 news: the G7 ships v2 as an MP3."
+        );
+
+        // Off by default
+        assert_eq!(normalize_for_tts(text).normalized, text);
+    }
+
+    #[test]
+    fn test_decade_normalization_and_toggle() {
+        let text = "Music of the 1990s beat the 80s.";
+        let config = NormalizationConfig {
+            decades: true,
+            ..NormalizationConfig::default()
+        };
+        let result = normalize_for_tts_with_config(text, &config);
+        assert_eq!(
+            result.normalized,
+            "Music of the nineteen nineties beat the eighties."
+        );
+
+        let start = result.normalized.find("eighties").unwrap();
+        let (orig_start, orig_end) =
+            map_normalized_to_original(start, start + "eighties".len(), &result).unwrap();
+        assert_eq!(&text[orig_start..orig_end], "80s");
+
+        // Off by default
+        let result = normalize_for_tts(text);
+        assert_eq!(result.normalized, text);
+    }
+
+    fn with_math() -> NormalizationConfig {
+        NormalizationConfig {
+            math: true,
+            ..NormalizationConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_math_operators_read_as_words() {
+        let cases = [
+            ("2 + 2 = 4", "2 plus 2 equals 4"),
+            ("a < b", "a less than b"),
+            ("x = 2 + 3 * 4", "x equals 2 plus 3 times 4"),
+            ("10 - 3 / 2 > 1", "10 minus 3 divided by 2 greater than 1"),
+            ("(2 + 3) * 4", "(2 plus 3) times 4"),
+            (
+                "if x <= 5 and y != 0",
+                "if x less than or equal to 5 and y does not equal 0",
+            ),
+            ("2*3 = 6", "2 times 3 equals 6"),
+            ("x = -5", "x equals -5"),
+            ("6 ÷ 2 ≠ 4", "6 divided by 2 does not equal 4"),
+        ];
+        for (text, expected) in cases {
+            let result = normalize_for_tts_with_config(text, &with_math());
+            assert_eq!(result.normalized, expected, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn test_math_guards_leave_punctuation_alone() {
+        for text in [
+            "A rock-solid plan, and/or a backup",
+            "Use C++ or -> arrows",
+            "Q&A - answers inside",
+            "**bold** text",
+            "w/o sugar",
+        ] {
+            let result = normalize_for_tts_with_config(text, &with_math());
+            assert!(
+                !result.normalized.contains("minus")
+                    && !result.normalized.contains("divided")
+                    && !result.normalized.contains("times")
+                    && !result.normalized.contains("than"),
+                "{:?} → {:?}",
+                text,
+                result.normalized
+            );
+        }
+        assert_eq!(
+            normalize_for_tts_with_config("rock-solid and/or", &with_math()).normalized,
+            "rock-solid and/or"
+        );
+    }
+
+    #[test]
+    fn test_math_is_off_by_default() {
+        let result = normalize_for_tts("a < b and 6 / 2");
+        assert_eq!(result.normalized, "a < b and 6 / 2");
+    }
+
+    #[test]
+    fn test_math_mapping_to_original() {
+        let text = "x <= 5";
+        let result = normalize_for_tts_with_config(text, &with_math());
+        let words = "less than or equal to";
+        let start = result.normalized.find(words).unwrap();
+        let (orig_start, orig_end) =
+            map_normalized_to_original(start, start + words.len(), &result).unwrap();
+        assert_eq!(&text[orig_start..orig_end], "<=");
+    }
 }
@@ -0,0 +1,231 @@
+/// Punctuation read aloud by name, for proofreading and accessibility
+///
+/// Listeners checking a transcript, or following along without the text,
+/// may need to hear "Hello comma world period" rather than the pauses the
+/// engine would otherwise make. This runs on text that has already been
+/// normalized and split into sentences, so decimal points are gone and
+/// sentence boundaries are kept.
+use serde::{Serialize, Serializer};
+
+use super::normalization::{collapse_spaces, NormalizationResult};
+
+/// Names used unless overridden
+const DEFAULT_NAMES: &[(char, &str)] = &[
+    (',', "comma"),
+    ('.', "period"),
+    ('!', "exclamation mark"),
+    ('?', "question mark"),
+    (';', "semicolon"),
+    (':', "colon"),
+    ('(', "left paren"),
+    (')', "right paren"),
+    ('"', "quote"),
+    ('…', "ellipsis"),
+    ('—', "em dash"),
+    ('–', "en dash"),
+];
+
+/// Which punctuation marks are spoken, and what they are called
+#[derive(Debug, Clone, PartialEq)]
+pub struct PunctuationNames {
+    names: Vec<(char, String)>,
+}
+
+/// A `mark → name` map of the marks that are spoken
+impl Serialize for PunctuationNames {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.names.iter().map(|(mark, name)| (mark, name)))
+    }
+}
+
+impl Default for PunctuationNames {
+    fn default() -> Self {
+        Self {
+            names: DEFAULT_NAMES
+                .iter()
+                .map(|&(mark, name)| (mark, name.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl PunctuationNames {
+    /// The default names with `spec` applied on top
+    ///
+    /// `spec` is `;`-separated `mark=name` entries, e.g. `.=full stop;!=bang`.
+    /// The mark is always a single character, so `;` and `=` can be renamed
+    /// too (`;=semi`). An empty name stops a mark from being spoken.
+    pub fn with_overrides(spec: &str) -> Result<Self, String> {
+        let mut names = Self::default();
+        let mut rest = spec.trim();
+
+        while !rest.is_empty() {
+            let mut chars = rest.chars();
+            let mark = chars.next().unwrap_or_default();
+            if chars.next() != Some('=') {
+                return Err(format!(
+                    "Invalid punctuation name {:?}: expected mark=name",
+                    rest
+                ));
+            }
+            let entry = chars.as_str();
+            let (name, next) = entry.split_once(';').unwrap_or((entry, ""));
+            names.set(mark, name.trim());
+            rest = next.trim_start();
+        }
+
+        Ok(names)
+    }
+
+    fn set(&mut self, mark: char, name: &str) {
+        self.names.retain(|&(existing, _)| existing != mark);
+        if !name.is_empty() {
+            self.names.push((mark, name.to_string()));
+        }
+    }
+
+    /// Spoken name of a punctuation mark, if it is read aloud
+    pub fn name(&self, mark: char) -> Option<&str> {
+        self.names
+            .iter()
+            .find(|&&(existing, _)| existing == mark)
+            .map(|(_, name)| name.as_str())
+    }
+
+    /// `text` with its punctuation replaced by spoken names
+    pub fn speak(&self, text: &str) -> String {
+        let mapping: Vec<usize> = (0..text.len()).collect();
+        self.speak_with_tracking(text, &mapping).0
+    }
+
+    /// Like [`Self::speak`], keeping the mapping back to the original text
+    ///
+    /// Each name maps to the position of the mark it replaces, so phrase
+    /// timings still point at the user's own text.
+    pub fn speak_normalized(&self, result: &NormalizationResult) -> NormalizationResult {
+        let (normalized, char_mapping) =
+            self.speak_with_tracking(&result.normalized, &result.char_mapping);
+        NormalizationResult {
+            original: result.original.clone(),
+            normalized,
+            char_mapping,
+        }
+    }
+
+    fn speak_with_tracking(&self, text: &str, mapping: &[usize]) -> (String, Vec<usize>) {
+        let mut spoken = String::with_capacity(text.len() * 2);
+        let mut spoken_mapping = Vec::with_capacity(mapping.len() * 2);
+        let original_at = |pos: usize| {
+            mapping
+                .get(pos)
+                .copied()
+                .unwrap_or(mapping.last().copied().unwrap_or(0))
+        };
+
+        let mut chars = text.char_indices();
+        while let Some((i, ch)) = chars.next() {
+            let (name, len) = if text[i..].starts_with("...") {
+                // Three periods are an ellipsis, not three sentence ends
+                chars.nth(1);
+                (self.name('…'), 3)
+            } else if between_digits(text, i, ch) {
+                (None, ch.len_utf8())
+            } else {
+                (self.name(ch), ch.len_utf8())
+            };
+
+            match name {
+                Some(name) => {
+                    // The trailing space maps past the mark, so the name
+                    // covers exactly the mark in the original
+                    spoken.push(' ');
+                    spoken.push_str(name);
+                    spoken.push(' ');
+                    spoken_mapping.extend(std::iter::repeat_n(original_at(i), name.len() + 1));
+                    spoken_mapping.push(original_at(i + len));
+                }
+                None => {
+                    spoken.push_str(&text[i..i + len]);
+                    spoken_mapping.extend((i..i + len).map(original_at));
+                }
+            }
+        }
+
+        let (collapsed, collapsed_mapping) = collapse_spaces(&spoken, &spoken_mapping);
+        let start = collapsed.len() - collapsed.trim_start_matches(' ').len();
+        let end = collapsed.trim_end_matches(' ').len().max(start);
+        (
+            collapsed[start..end].to_string(),
+            collapsed_mapping[start..end].to_vec(),
+        )
+    }
+}
+
+/// Whether `ch` at `i` separates digits, as in "1,000" or "3.5"
+fn between_digits(text: &str, i: usize, ch: char) -> bool {
+    matches!(ch, ',' | '.' | ':')
+        && text[..i].ends_with(|c: char| c.is_ascii_digit())
+        && text[i + ch.len_utf8()..].starts_with(|c: char| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text_processing::normalization::{self, map_normalized_to_original};
+
+    #[test]
+    fn test_speaks_default_names() {
+        let names = PunctuationNames::default();
+        assert_eq!(names.speak("Hello, world."), "Hello comma world period");
+        assert_eq!(
+            names.speak("Really?! (Yes.)"),
+            "Really question mark exclamation mark left paren Yes period right paren"
+        );
+    }
+
+    #[test]
+    fn test_text_without_punctuation_is_unchanged() {
+        let names = PunctuationNames::default();
+        assert_eq!(names.speak("don't stop now"), "don't stop now");
+    }
+
+    #[test]
+    fn test_numbers_and_ellipses() {
+        let names = PunctuationNames::default();
+        assert_eq!(
+            names.speak("Wait... 1,000 at 3.5"),
+            "Wait ellipsis 1,000 at 3.5"
+        );
+        assert_eq!(names.speak("Wait…"), "Wait ellipsis");
+    }
+
+    #[test]
+    fn test_overrides() {
+        let names = PunctuationNames::with_overrides(".=full stop;;=semi;,=").unwrap();
+        assert_eq!(names.name('.'), Some("full stop"));
+        assert_eq!(names.name(';'), Some("semi"));
+        assert_eq!(names.name(','), None);
+        assert_eq!(names.name('?'), Some("question mark"));
+        assert_eq!(names.speak("Yes, no; done."), "Yes, no semi done full stop");
+
+        assert!(PunctuationNames::with_overrides("period").is_err());
+        assert_eq!(
+            PunctuationNames::with_overrides("").unwrap(),
+            PunctuationNames::default()
+        );
+    }
+
+    #[test]
+    fn test_mapping_points_at_marks() {
+        let names = PunctuationNames::default();
+        let result = names.speak_normalized(&normalization::normalize_for_tts("Hi, $5."));
+        assert_eq!(result.normalized, "Hi comma five dollars period");
+        assert_eq!(result.char_mapping.len(), result.normalized.len());
+
+        let comma = result.normalized.find("comma").unwrap();
+        assert_eq!(
+            map_normalized_to_original(comma, comma + "comma".len(), &result),
+            Some((2, 3))
+        );
+    }
+}
@@ -0,0 +1,349 @@
+/// Quotation marks read as prosody or spoken aloud
+///
+/// Quotes are normally just punctuation the engine glosses over. In
+/// dialogue-heavy text each quotation can instead be spoken as its own
+/// segment with a slight pause around it and a shifted pitch and speed, so
+/// speech stands apart from narration. For accessibility, quotes can be
+/// read as "quote" and "unquote" instead.
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use std::time::Duration;
+
+use super::normalization::{collapse_spaces, NormalizationResult};
+use crate::config::constants::MAX_SPEED;
+use crate::text_processing::speed_markup::SpeechSegment;
+
+lazy_static! {
+    /// A straight or curly double-quoted span on one line
+    static ref QUOTE_REGEX: Regex = Regex::new(r#""[^"\n]*"|“[^“”\n]*”"#).unwrap();
+}
+
+/// How quotation marks are handled
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuoteStyle {
+    /// Read like other punctuation
+    #[default]
+    Ignore,
+    /// Quotations are spoken apart from the surrounding text
+    Prosody(QuoteProsody),
+    /// Quotations are announced with "quote" and closed with "unquote"
+    Spoken,
+}
+
+impl QuoteStyle {
+    /// Parse `ignore`, `prosody` or `spoken`; prosody uses `prosody`'s settings
+    pub fn parse(value: &str, prosody: QuoteProsody) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "ignore" => Ok(Self::Ignore),
+            "prosody" => Ok(Self::Prosody(prosody)),
+            "spoken" => Ok(Self::Spoken),
+            other => Err(format!(
+                "Invalid quote style {:?}: expected ignore, prosody or spoken",
+                other
+            )),
+        }
+    }
+}
+
+/// How quotations are spoken relative to their surroundings
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct QuoteProsody {
+    /// Added to the surrounding speed, then kept within the accepted range
+    pub speed_delta: f32,
+    /// Multiplies the surrounding pitch; above 1 is higher
+    pub pitch_scale: f32,
+    /// Silence before and after each quotation
+    #[serde(
+        rename = "pause_ms",
+        serialize_with = "crate::utils::millis::serialize"
+    )]
+    pub pause: Duration,
+}
+
+impl Default for QuoteProsody {
+    fn default() -> Self {
+        Self {
+            speed_delta: 0.0,
+            pitch_scale: 1.05,
+            pause: Duration::from_millis(150),
+        }
+    }
+}
+
+/// Split quotations out of each segment
+///
+/// Quotations take their segment's speed, pitch and gain, adjusted by
+/// `config`, quote marks included, with the speed kept between `min_speed`
+/// and `MAX_SPEED`. The text before a quotation and the quotation itself are
+/// followed by `config.pause`. Quotes with nothing speakable in them, like
+/// `""` or `"5"`, stay where they are.
+pub fn split_quotes(
+    segments: Vec<SpeechSegment>,
+    config: &QuoteProsody,
+    min_speed: f32,
+) -> Vec<SpeechSegment> {
+    let pause = (!config.pause.is_zero()).then_some(config.pause);
+    let mut result = Vec::new();
+
+    for segment in segments {
+        let mut last_end = 0;
+        for quote in QUOTE_REGEX.find_iter(&segment.text) {
+            if !quote.as_str().chars().any(char::is_alphabetic) {
+                continue;
+            }
+            push_segment(
+                &mut result,
+                &segment.text[last_end..quote.start()],
+                &SpeechSegment {
+                    pause_after: pause.max(segment.pause_after),
+                    ..segment.clone()
+                },
+            );
+            push_segment(
+                &mut result,
+                quote.as_str(),
+                &SpeechSegment {
+                    text: String::new(),
+                    speed: (segment.speed + config.speed_delta).clamp(min_speed, MAX_SPEED),
+                    gain_db: segment.gain_db,
+                    pitch_scale: segment.pitch_scale * config.pitch_scale,
+                    pause_after: pause,
+                },
+            );
+            last_end = quote.end();
+        }
+        push_segment(&mut result, &segment.text[last_end..], &segment);
+    }
+
+    result
+}
+
+fn push_segment(segments: &mut Vec<SpeechSegment>, text: &str, like: &SpeechSegment) {
+    let text = text.trim();
+    if !text.is_empty() {
+        segments.push(SpeechSegment {
+            text: text.to_string(),
+            ..like.clone()
+        });
+    }
+}
+
+/// `text` with its quotation marks read as "quote" and "unquote"
+///
+/// Runs on normalized text, where curly quotes are already straight.
+pub fn speak(text: &str) -> String {
+    let mapping: Vec<usize> = (0..text.len()).collect();
+    speak_with_tracking(text, &mapping).0
+}
+
+/// Like [`speak`], keeping the mapping back to the original text
+pub fn speak_normalized(result: &NormalizationResult) -> NormalizationResult {
+    let (normalized, char_mapping) = speak_with_tracking(&result.normalized, &result.char_mapping);
+    NormalizationResult {
+        original: result.original.clone(),
+        normalized,
+        char_mapping,
+    }
+}
+
+/// Replace each quote mark with its spoken word
+///
+/// Whether a mark opens or closes a quotation is judged from the text around
+/// it rather than by pairing, so a quotation split across chunks still reads
+/// right. A mark after a digit with no quotation open is an inch or second
+/// sign ("5\"") and a mark with spaces on both sides isn't a quote at all;
+/// both are left alone.
+fn speak_with_tracking(text: &str, mapping: &[usize]) -> (String, Vec<usize>) {
+    let mut spoken = String::with_capacity(text.len() * 2);
+    let mut spoken_mapping = Vec::with_capacity(mapping.len() * 2);
+    let original_at = |pos: usize| {
+        mapping
+            .get(pos)
+            .copied()
+            .unwrap_or(mapping.last().copied().unwrap_or(0))
+    };
+    let mut open = false;
+
+    for (i, ch) in text.char_indices() {
+        let word = if ch == '"' {
+            let before = text[..i].chars().next_back();
+            let after = text[i + 1..].chars().next();
+            let space_before = before.is_none_or(|c| c.is_whitespace() || "([{".contains(c));
+            let space_after = after.is_none_or(char::is_whitespace);
+            if space_before && !space_after {
+                Some("quote")
+            } else if !space_before && (open || !before.is_some_and(|c| c.is_ascii_digit())) {
+                Some("unquote")
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        match word {
+            Some(word) => {
+                open = word == "quote";
+                // The trailing space maps past the mark, so the word covers
+                // exactly the mark in the original
+                spoken.push(' ');
+                spoken.push_str(word);
+                spoken.push(' ');
+                spoken_mapping.extend(std::iter::repeat_n(original_at(i), word.len() + 1));
+                spoken_mapping.push(original_at(i + 1));
+            }
+            None => {
+                spoken.push(ch);
+                spoken_mapping.extend((i..i + ch.len_utf8()).map(original_at));
+            }
+        }
+    }
+
+    let (collapsed, collapsed_mapping) = collapse_spaces(&spoken, &spoken_mapping);
+    let start = collapsed.len() - collapsed.trim_start_matches(' ').len();
+    let end = collapsed.trim_end_matches(' ').len().max(start);
+    (
+        collapsed[start..end].to_string(),
+        collapsed_mapping[start..end].to_vec(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::constants::DEFAULT_MIN_SPEED;
+    use crate::text_processing::normalization::{self, map_normalized_to_original};
+
+    fn segment(text: &str) -> SpeechSegment {
+        SpeechSegment {
+            text: text.to_string(),
+            speed: 1.0,
+            gain_db: 0.0,
+            pitch_scale: 1.0,
+            pause_after: None,
+        }
+    }
+
+    #[test]
+    fn test_spoken_quotes() {
+        assert_eq!(
+            speak(r#"She said "hello there" and left."#),
+            "She said quote hello there unquote and left."
+        );
+        assert_eq!(
+            speak(r#"He asked, "Ready?" Then: "Go.""#),
+            "He asked, quote Ready? unquote Then: quote Go. unquote"
+        );
+    }
+
+    #[test]
+    fn test_spoken_quotes_split_across_chunks() {
+        assert_eq!(
+            speak(r#"It began, "Once upon"#),
+            "It began, quote Once upon"
+        );
+        assert_eq!(
+            speak(r#"a time." She smiled."#),
+            "a time. unquote She smiled."
+        );
+    }
+
+    #[test]
+    fn test_non_quotes_are_not_spoken() {
+        assert_eq!(speak(r#"A 5" screen"#), r#"A 5" screen"#);
+        assert_eq!(speak(r#"a " b"#), r#"a " b"#);
+        assert_eq!(speak("no quotes here"), "no quotes here");
+        // Inside a quotation, a mark after a digit closes it
+        assert_eq!(speak(r#""Room 101""#), "quote Room 101 unquote");
+    }
+
+    #[test]
+    fn test_spoken_mapping_points_at_marks() {
+        let text = "“Hi,” she said.";
+        let result = speak_normalized(&normalization::normalize_for_tts(text));
+        assert_eq!(result.normalized, "quote Hi, unquote she said.");
+        assert_eq!(result.char_mapping.len(), result.normalized.len());
+
+        let word = result.normalized.find("unquote").unwrap();
+        let (start, end) =
+            map_normalized_to_original(word, word + "unquote".len(), &result).unwrap();
+        assert_eq!(&text[start..end], "”");
+    }
+
+    #[test]
+    fn test_prosody_splits_quotations() {
+        let config = QuoteProsody {
+            speed_delta: -0.1,
+            pitch_scale: 1.1,
+            pause: Duration::from_millis(200),
+        };
+        let segments = split_quotes(
+            vec![segment("He said “Stop right there” and ran.")],
+            &config,
+            DEFAULT_MIN_SPEED,
+        );
+        let pause = Some(Duration::from_millis(200));
+
+        assert_eq!(
+            segments,
+            vec![
+                SpeechSegment {
+                    pause_after: pause,
+                    ..segment("He said")
+                },
+                SpeechSegment {
+                    speed: 0.9,
+                    pitch_scale: 1.1,
+                    pause_after: pause,
+                    ..segment("“Stop right there”")
+                },
+                segment("and ran."),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_prosody_keeps_to_configured_min_speed() {
+        let config = QuoteProsody {
+            speed_delta: -0.5,
+            ..QuoteProsody::default()
+        };
+        let segments = split_quotes(
+            vec![SpeechSegment {
+                speed: 0.6,
+                ..segment("He said “wait”")
+            }],
+            &config,
+            0.5,
+        );
+        let speeds: Vec<f32> = segments.iter().map(|segment| segment.speed).collect();
+        assert_eq!(speeds, vec![0.6, 0.5]);
+    }
+
+    #[test]
+    fn test_prosody_leaves_unspeakable_quotes() {
+        let text = r#"Type "" or "42" here"#;
+        assert_eq!(
+            split_quotes(
+                vec![segment(text)],
+                &QuoteProsody::default(),
+                DEFAULT_MIN_SPEED
+            ),
+            vec![segment(text)]
+        );
+    }
+
+    #[test]
+    fn test_parse_style() {
+        let prosody = QuoteProsody::default();
+        assert_eq!(QuoteStyle::parse("ignore", prosody), Ok(QuoteStyle::Ignore));
+        assert_eq!(
+            QuoteStyle::parse(" Prosody ", prosody),
+            Ok(QuoteStyle::Prosody(prosody))
+        );
+        assert_eq!(QuoteStyle::parse("SPOKEN", prosody), Ok(QuoteStyle::Spoken));
+        assert!(QuoteStyle::parse("loud", prosody).is_err());
+    }
+}
@@ -0,0 +1,1440 @@
+/// Additional semantic normalization passes
+///
+/// Each pass scans the input text and returns replacement spans as
+/// `(start_byte, end_byte, replacement)` tuples. The spans are merged with the
+/// currency/percentage matches and applied by the position-tracking code in
+/// `normalization.rs`, so every pass here stays mapping-safe by construction.
+use lazy_static::lazy_static;
+use num2words::Num2Words;
+use regex::Regex;
+use serde::Serialize;
+
+lazy_static! {
+    /// Slash-separated digit groups, optionally preceded by a whole number ("1 1/2")
+    static ref FRACTION_REGEX: Regex = Regex::new(
+        r"(?:(\d+)[ \t]+)?(\d+(?:/\d+)+)"
+    ).unwrap();
+
+    /// Capitalized word followed by an uppercase roman numeral, with an
+    /// optional preceding title ("King Henry VIII", "Chapter XIV")
+    static ref ROMAN_NUMERAL_REGEX: Regex = Regex::new(
+        r"\b(?:([A-Z][a-z]+)[ \t]+)?([A-Z][A-Za-z]*)[ \t]+([IVXLCDM]+)\b"
+    ).unwrap();
+
+    /// Number followed by a possible unit abbreviation ("5kg", "10 mph", "-5°C")
+    static ref UNIT_REGEX: Regex = Regex::new(
+        r"([-−])?(\d+(?:\.\d+)?) ?(°[CF]|[A-Za-z]+(?:/[A-Za-z]+)?)"
+    ).unwrap();
+
+    /// Time of day followed by a possible time zone abbreviation
+    /// ("3 PM EST", "10:30am UTC", "noon GMT", "14:00 CET")
+    static ref TIME_ZONE_REGEX: Regex = Regex::new(
+        r"(?:(\d{1,2})(?::(\d{2}))?[ \t]*([AaPp])\.?[Mm]\.?|(\d{1,2}):(\d{2})|\b((?i:noon|midnight)))[ \t]+([A-Z]{2,5})\b"
+    ).unwrap();
+
+    /// Word written in capitals ("NASA", "STOP", "DON'T")
+    static ref ALL_CAPS_REGEX: Regex = Regex::new(
+        r"\b[A-Z]{2,}(?:'[A-Z]+)?\b"
+    ).unwrap();
+
+    /// Word broken across lines at a hyphen ("inter-\nnational"), capturing
+    /// the word before the break (with any earlier compound parts), the
+    /// hyphen and the first letter after the break
+    static ref LINE_BREAK_HYPHEN_REGEX: Regex = Regex::new(
+        r"((?:\p{L}+-)*\p{L}+)([-\u{AD}])[ \t]*\r?\n[ \t]*(\p{L})"
+    ).unwrap();
+
+    /// Decade written as a plural year ("1990s", "80s", "'80s", "1960's"),
+    /// capturing the digits before the final zero
+    static ref DECADE_REGEX: Regex = Regex::new(
+        r"['\u{2019}]?\b(\d|\d{3})0['\u{2019}]?s\b"
+    ).unwrap();
+
+    /// Letters followed by a number, optionally after a hyphen ("COVID-19",
+    /// "MP3", "F-16"), or a dotted version ("v1.2.3")
+    static ref ALPHANUMERIC_REGEX: Regex = Regex::new(
+        r"\b([A-Za-z]+)(-?)(\d+(?:\.\d+)*)\b"
+    ).unwrap();
+
+    /// Canonical roman numeral form (rejects "IIII", "VX", "IC", ...)
+    static ref CANONICAL_ROMAN_REGEX: Regex = Regex::new(
+        r"^M{0,3}(?:CM|CD|D?C{0,3})(?:XC|XL|L?X{0,3})(?:IX|IV|V?I{0,3})$"
+    ).unwrap();
+}
+
+/// Largest denominator read as a fraction
+///
+/// Larger denominators are far more likely to be dates ("3/25") or scores.
+const MAX_FRACTION_DENOMINATOR: i64 = 16;
+
+/// Well-known slash references that must never be read as fractions
+const FRACTION_EXCEPTIONS: &[&str] = &["9/11"];
+
+/// Words after which a roman numeral is read as a cardinal ("Chapter fourteen")
+const ROMAN_HEADING_WORDS: &[&str] = &[
+    "act", "appendix", "book", "canto", "chapter", "episode", "part", "phase", "scene", "section",
+    "stage", "volume", "war",
+];
+
+/// Titles after which "Name + numeral" is read as a regnal ordinal
+const REGNAL_TITLES: &[&str] = &[
+    "King", "Queen", "Pope", "Emperor", "Empress", "Tsar", "Czar", "Prince", "Princess", "Duke",
+];
+
+/// Names read as regnal ordinals even without a title ("Louis XVI")
+const REGNAL_NAMES: &[&str] = &[
+    "Alexander",
+    "Alfonso",
+    "Anne",
+    "Benedict",
+    "Boniface",
+    "Catherine",
+    "Charles",
+    "Clement",
+    "Constantine",
+    "Edward",
+    "Elizabeth",
+    "Ferdinand",
+    "Francis",
+    "Frederick",
+    "George",
+    "Gregory",
+    "Gustav",
+    "Henry",
+    "Innocent",
+    "Ivan",
+    "James",
+    "John",
+    "Leo",
+    "Louis",
+    "Ludwig",
+    "Mary",
+    "Napoleon",
+    "Nicholas",
+    "Paul",
+    "Peter",
+    "Philip",
+    "Pius",
+    "Ramesses",
+    "Richard",
+    "Urban",
+    "Victor",
+    "Wilhelm",
+    "William",
+];
+
+/// Largest regnal number converted; keeps numerals to I, V and X so words
+/// like "MIX" or "CD" are never read as kings
+const MAX_REGNAL_NUMBER: i64 = 39;
+
+/// Spoken names of a unit abbreviation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnitName {
+    /// Abbreviation as written after the number (case-sensitive)
+    pub symbol: &'static str,
+    /// Name after exactly one ("1kg" → "one kilogram")
+    pub singular: &'static str,
+    /// Name after any other quantity ("5kg" → "five kilograms")
+    pub plural: &'static str,
+}
+
+const fn unit(symbol: &'static str, singular: &'static str, plural: &'static str) -> UnitName {
+    UnitName {
+        symbol,
+        singular,
+        plural,
+    }
+}
+
+/// Unit abbreviations expanded by default
+///
+/// Abbreviations that are also common words or other shorthand ("in", "m"
+/// for million, "g" for network generations) are deliberately left out.
+pub const DEFAULT_UNITS: &[UnitName] = &[
+    unit("mg", "milligram", "milligrams"),
+    unit("kg", "kilogram", "kilograms"),
+    unit("lb", "pound", "pounds"),
+    unit("lbs", "pound", "pounds"),
+    unit("oz", "ounce", "ounces"),
+    unit("mm", "millimeter", "millimeters"),
+    unit("cm", "centimeter", "centimeters"),
+    unit("km", "kilometer", "kilometers"),
+    unit("ft", "foot", "feet"),
+    unit("ml", "milliliter", "milliliters"),
+    unit("mL", "milliliter", "milliliters"),
+    unit("mph", "mile per hour", "miles per hour"),
+    unit("kph", "kilometer per hour", "kilometers per hour"),
+    unit("km/h", "kilometer per hour", "kilometers per hour"),
+    unit("°C", "degree Celsius", "degrees Celsius"),
+    unit("°F", "degree Fahrenheit", "degrees Fahrenheit"),
+    unit("kWh", "kilowatt hour", "kilowatt hours"),
+    unit("kHz", "kilohertz", "kilohertz"),
+    unit("MHz", "megahertz", "megahertz"),
+    unit("GHz", "gigahertz", "gigahertz"),
+    unit("MB", "megabyte", "megabytes"),
+    unit("GB", "gigabyte", "gigabytes"),
+    unit("TB", "terabyte", "terabytes"),
+];
+
+/// Spoken name of a time zone abbreviation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeZoneName {
+    /// Abbreviation as written after the time (case-sensitive)
+    pub abbreviation: &'static str,
+    /// Full name read in its place
+    pub name: &'static str,
+}
+
+const fn zone(abbreviation: &'static str, name: &'static str) -> TimeZoneName {
+    TimeZoneName { abbreviation, name }
+}
+
+/// Time zone abbreviations expanded by default
+///
+/// Ambiguous abbreviations ("IST" is India, Ireland or Israel) are left out.
+pub const DEFAULT_TIME_ZONES: &[TimeZoneName] = &[
+    zone("UTC", "Coordinated Universal Time"),
+    zone("GMT", "Greenwich Mean Time"),
+    zone("BST", "British Summer Time"),
+    zone("CET", "Central European Time"),
+    zone("CEST", "Central European Summer Time"),
+    zone("ET", "Eastern Time"),
+    zone("EST", "Eastern Standard Time"),
+    zone("EDT", "Eastern Daylight Time"),
+    zone("CT", "Central Time"),
+    zone("CST", "Central Standard Time"),
+    zone("CDT", "Central Daylight Time"),
+    zone("MT", "Mountain Time"),
+    zone("MST", "Mountain Standard Time"),
+    zone("MDT", "Mountain Daylight Time"),
+    zone("PT", "Pacific Time"),
+    zone("PST", "Pacific Standard Time"),
+    zone("PDT", "Pacific Daylight Time"),
+    zone("AKST", "Alaska Standard Time"),
+    zone("HST", "Hawaii Standard Time"),
+    zone("JST", "Japan Standard Time"),
+    zone("AEST", "Australian Eastern Standard Time"),
+    zone("AEDT", "Australian Eastern Daylight Time"),
+];
+
+/// How a known acronym is read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AcronymReading {
+    /// Left as written and read as a word ("NASA")
+    Keep,
+    /// Spelled out letter by letter ("USB" → "U.S.B")
+    Spell,
+}
+
+/// An all-caps word that should not be read as an ordinary word
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Acronym {
+    /// The word as written (case-sensitive)
+    pub word: &'static str,
+    pub reading: AcronymReading,
+}
+
+const fn keep(word: &'static str) -> Acronym {
+    Acronym {
+        word,
+        reading: AcronymReading::Keep,
+    }
+}
+
+const fn spell(word: &'static str) -> Acronym {
+    Acronym {
+        word,
+        reading: AcronymReading::Spell,
+    }
+}
+
+/// Acronyms recognized by default
+///
+/// Only words the heuristics would get wrong need an entry: pronounceable
+/// acronyms that lowercasing would hide, and spelled ones containing vowels.
+/// Short words without vowels ("BBC", "HTML") are left as written anyway.
+pub const DEFAULT_ACRONYMS: &[Acronym] = &[
+    keep("NASA"),
+    keep("NATO"),
+    keep("UNESCO"),
+    keep("UNICEF"),
+    keep("OPEC"),
+    keep("FIFA"),
+    keep("AIDS"),
+    keep("ASCII"),
+    keep("JPEG"),
+    keep("SCUBA"),
+    keep("NVIDIA"),
+    keep("IKEA"),
+    keep("LEGO"),
+    spell("USB"),
+    spell("USA"),
+    spell("EU"),
+    spell("UN"),
+    spell("UK"),
+    spell("AI"),
+    spell("API"),
+    spell("CEO"),
+    spell("CIA"),
+    spell("FBI"),
+    spell("IBM"),
+    spell("ID"),
+    spell("IOU"),
+    spell("IRS"),
+    spell("ISBN"),
+    spell("UFO"),
+    spell("UI"),
+    spell("URL"),
+    spell("USSR"),
+];
+
+/// Shortest unknown all-caps word read as an ordinary word
+const MIN_CAPS_WORD_LEN: usize = 4;
+
+/// How the letters before a number are read ("COVID" in "COVID-19")
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlphanumericPrefix {
+    /// The letters as written (matched ignoring case)
+    pub letters: &'static str,
+    pub reading: &'static str,
+}
+
+const fn prefix(letters: &'static str, reading: &'static str) -> AlphanumericPrefix {
+    AlphanumericPrefix { letters, reading }
+}
+
+/// Letter prefixes recognized by default
+///
+/// Only prefixes the heuristics would get wrong need an entry: mixed-case
+/// names, and capitals that are read as a word or expanded.
+pub const DEFAULT_ALPHANUMERIC_PREFIXES: &[AlphanumericPrefix] = &[
+    prefix("COVID", "covid"),
+    prefix("SARS", "sars"),
+    prefix("MERS", "mers"),
+    prefix("MiG", "mig"),
+    prefix("WW", "world war"),
+];
+
+/// Longest number after a prefix read as a whole ("G7", "A380");
+/// longer digit runs are part numbers or codes and are left alone
+const MAX_ALPHANUMERIC_DIGITS: usize = 4;
+
+/// Spoken names of the letters "a" to "z"
+const LETTER_NAMES: [&str; 26] = [
+    "ay",
+    "bee",
+    "see",
+    "dee",
+    "ee",
+    "ef",
+    "gee",
+    "aitch",
+    "eye",
+    "jay",
+    "kay",
+    "el",
+    "em",
+    "en",
+    "oh",
+    "pee",
+    "cue",
+    "ar",
+    "ess",
+    "tee",
+    "you",
+    "vee",
+    "double you",
+    "ex",
+    "why",
+    "zee",
+];
+
+/// How `N/M` fractions are read aloud
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FractionStyle {
+    /// Named fractions: "one half", "three quarters", "five eighths"
+    Named,
+    /// Literal form: "one over two", "five over eight"
+    Over,
+}
+
+impl FractionStyle {
+    /// Parse a `TTS_FRACTION_STYLE` value; anything unrecognized means `named`
+    pub fn parse(value: &str) -> Self {
+        match value.to_lowercase().as_str() {
+            "over" => Self::Over,
+            _ => Self::Named,
+        }
+    }
+}
+
+/// Find fractions ("1/2", "3/4", "1 1/2") and their spoken replacements
+///
+/// Only proper fractions with small denominators are converted. Dates
+/// ("10/11/2024", "01/02"), URLs and paths are left untouched.
+pub fn fraction_matches(text: &str, style: FractionStyle) -> Vec<(usize, usize, String)> {
+    let mut matches = Vec::new();
+
+    for cap in FRACTION_REGEX.captures_iter(text) {
+        let (Some(full), Some(fraction)) = (cap.get(0), cap.get(2)) else {
+            continue;
+        };
+
+        if !is_standalone_number(text, full.start(), full.end())
+            || FRACTION_EXCEPTIONS.contains(&fraction.as_str())
+        {
+            continue;
+        }
+
+        // More than one slash means a date or path, not a fraction
+        let parts: Vec<&str> = fraction.as_str().split('/').collect();
+        if parts.len() != 2 {
+            continue;
+        }
+
+        let whole = cap.get(1).map(|w| w.as_str());
+        if let Some(replacement) = format_fraction(whole, parts[0], parts[1], style) {
+            matches.push((full.start(), full.end(), replacement));
+        }
+    }
+
+    matches
+}
+
+/// Find roman numerals in headings and regnal names and their spoken replacements
+///
+/// Numerals are only converted in two contexts, so ordinary capitalized words
+/// and the pronoun "I" are left alone:
+/// - after a heading word: "Chapter XIV" → "Chapter fourteen", "Part I" → "Part one"
+/// - after a monarch's or pope's name: "Louis XVI" → "Louis the Sixteenth",
+///   "King Henry VIII" → "King Henry the Eighth". A bare "I" is only converted
+///   when a title precedes the name ("Queen Elizabeth I").
+pub fn roman_numeral_matches(text: &str) -> Vec<(usize, usize, String)> {
+    let mut matches = Vec::new();
+
+    for cap in ROMAN_NUMERAL_REGEX.captures_iter(text) {
+        let (Some(word), Some(numeral)) = (cap.get(2), cap.get(3)) else {
+            continue;
+        };
+        let Some(value) = parse_roman(numeral.as_str()) else {
+            continue;
+        };
+        let title = cap.get(1).map(|t| t.as_str());
+
+        let replacement = if ROMAN_HEADING_WORDS.contains(&word.as_str().to_lowercase().as_str()) {
+            cardinal_words(value)
+        } else if is_regnal_name(title, word.as_str(), value) {
+            regnal_ordinal(value)
+        } else {
+            None
+        };
+
+        if let Some(replacement) = replacement {
+            matches.push((numeral.start(), numeral.end(), replacement));
+        }
+    }
+
+    matches
+}
+
+/// Find quantities with unit abbreviations ("5kg", "10mph", "-5°C") and their
+/// spoken replacements, using the given unit table
+///
+/// The unit takes its singular form only after exactly one ("1kg" → "one
+/// kilogram"). A leading minus is read as "minus" unless it joins a range
+/// ("3-5kg"). Numbers glued to words or identifiers are left alone.
+pub fn unit_matches(text: &str, units: &[UnitName]) -> Vec<(usize, usize, String)> {
+    let mut matches = Vec::new();
+
+    for cap in UNIT_REGEX.captures_iter(text) {
+        let (Some(full), Some(number), Some(symbol)) = (cap.get(0), cap.get(2), cap.get(3)) else {
+            continue;
+        };
+        let Some(unit) = units.iter().find(|u| u.symbol == symbol.as_str()) else {
+            continue;
+        };
+
+        // A minus after a number is a range dash, not a sign
+        let before = text[..full.start()].chars().next_back();
+        let negative = cap.get(1).is_some() && !before.is_some_and(|c| c.is_alphanumeric());
+        let start = if negative {
+            full.start()
+        } else {
+            number.start()
+        };
+
+        let before = text[..start].chars().next_back();
+        let after = text[full.end()..].chars().next();
+        if before.is_some_and(|c| c.is_alphanumeric() || c == '.')
+            || after.is_some_and(char::is_alphanumeric)
+        {
+            continue;
+        }
+
+        let Some(quantity) = number_words(number.as_str()) else {
+            continue;
+        };
+        let name = if number.as_str() == "1" {
+            unit.singular
+        } else {
+            unit.plural
+        };
+        let sign = if negative { "minus " } else { "" };
+
+        matches.push((start, full.end(), format!("{}{} {}", sign, quantity, name)));
+    }
+
+    matches
+}
+
+/// Find times of day followed by a time zone abbreviation ("3 PM EST",
+/// "noon UTC") and their spoken replacements, using the given zone table
+///
+/// The time is read out ("10:30am" → "ten thirty AM") and the zone replaced
+/// by its full name. Abbreviations are only expanded directly after a time,
+/// so "EST" elsewhere (or inside a word) is left alone.
+pub fn time_zone_matches(text: &str, zones: &[TimeZoneName]) -> Vec<(usize, usize, String)> {
+    let mut matches = Vec::new();
+
+    for cap in TIME_ZONE_REGEX.captures_iter(text) {
+        let (Some(full), Some(abbreviation)) = (cap.get(0), cap.get(7)) else {
+            continue;
+        };
+        let Some(zone) = zones
+            .iter()
+            .find(|z| z.abbreviation == abbreviation.as_str())
+        else {
+            continue;
+        };
+
+        // Skip digits glued to a word, version or decimal ("v3 PM", "1.5 PM")
+        let before = text[..full.start()].chars().next_back();
+        if before.is_some_and(|c| c.is_alphanumeric() || c == '.' || c == ':') {
+            continue;
+        }
+
+        let time = if let Some(word) = cap.get(6) {
+            Some(word.as_str().to_string())
+        } else if let Some(hour) = cap.get(1) {
+            let meridiem = if cap[3].eq_ignore_ascii_case("a") {
+                "AM"
+            } else {
+                "PM"
+            };
+            clock_words(hour.as_str(), cap.get(2).map(|m| m.as_str()), true)
+                .map(|time| format!("{} {}", time, meridiem))
+        } else {
+            clock_words(&cap[4], Some(&cap[5]), false)
+        };
+        let Some(time) = time else {
+            continue;
+        };
+
+        matches.push((full.start(), full.end(), format!("{} {}", time, zone.name)));
+    }
+
+    matches
+}
+
+/// Find all-caps words and how to read them
+///
+/// Known acronyms are kept or spelled with dots per `acronyms`. Other
+/// all-caps words of at least four letters that contain a vowel are emphasis
+/// rather than acronyms and are lowercased ("STOP" → "stop"), so they are
+/// neither spelled out nor shouted. Shorter or vowelless words ("OK", "BBC")
+/// are left alone.
+pub fn all_caps_matches(text: &str, acronyms: &[Acronym]) -> Vec<(usize, usize, String)> {
+    let mut matches = Vec::new();
+
+    for m in ALL_CAPS_REGEX.find_iter(text) {
+        let word = m.as_str();
+        let replacement = match acronyms.iter().find(|a| a.word == word) {
+            Some(acronym) => match acronym.reading {
+                AcronymReading::Keep => continue,
+                AcronymReading::Spell => spelled_with_dots(word),
+            },
+            None => {
+                let letters = word.chars().filter(|c| c.is_ascii_uppercase()).count();
+                let has_vowel = word.chars().any(|c| "AEIOUY".contains(c));
+                if letters < MIN_CAPS_WORD_LEN || !has_vowel {
+                    continue;
+                }
+                word.to_lowercase()
+            }
+        };
+        matches.push((m.start(), m.end(), replacement));
+    }
+
+    matches
+}
+
+/// Find words split across lines by a hyphen and rejoin them
+///
+/// "inter-\nnational" becomes "international". The hyphen is kept, and only
+/// the line break removed, where it is more likely part of a compound: the
+/// word before it already has a hyphen ("state-of-\nthe-art") or the next
+/// part is capitalized ("Anglo-\nSaxon"). A soft hyphen always marks a split
+/// word.
+pub fn line_break_hyphen_matches(text: &str) -> Vec<(usize, usize, String)> {
+    LINE_BREAK_HYPHEN_REGEX
+        .captures_iter(text)
+        .map(|cap| {
+            let before = &cap[1];
+            let hyphen = cap.get(2).unwrap();
+            let next = cap.get(3).unwrap();
+            let compound = hyphen.as_str() == "-"
+                && (before.contains('-') || next.as_str().chars().all(char::is_uppercase));
+            let replacement = if compound { "-" } else { "" };
+            (hyphen.start(), next.start(), replacement.to_string())
+        })
+        .collect()
+}
+
+/// Find decades and read them as words
+///
+/// "1990s" becomes "nineteen nineties", "1800s" "eighteen hundreds", and a
+/// two-digit "80s" or "'80s" "eighties". Numbers glued to a decimal point,
+/// a currency sign or letters ("2.50s", "$100s", "A380s") are left alone.
+pub fn decade_matches(text: &str) -> Vec<(usize, usize, String)> {
+    DECADE_REGEX
+        .captures_iter(text)
+        .filter_map(|cap| {
+            let full = cap.get(0)?;
+            let glued = text[..full.start()]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_alphanumeric() || matches!(c, '.' | ',' | '$' | '£' | '€'));
+            if glued {
+                return None;
+            }
+            Some((full.start(), full.end(), decade_words(&cap[1])?))
+        })
+        .collect()
+}
+
+/// Find letters joined to a number and read them together
+///
+/// "COVID-19" becomes "covid nineteen", "MP3" "em pee three" and "G7" "gee
+/// seven". Prefixes in `prefixes` use their reading; other capitals are
+/// spelled by letter name, unless they form a pronounceable word of four or
+/// more letters ("SARS"). Lowercase prefixes are only spelled when short and
+/// vowelless ("mp3"), so words like "top10" are left alone.
+///
+/// A lowercase "v" marks a version ("v2" → "version two", "v1.2" → "version
+/// one point two"); with `versions` off these keep their written form.
+/// Tokens inside URLs, paths and emails are never touched.
+pub fn alphanumeric_matches(
+    text: &str,
+    prefixes: &[AlphanumericPrefix],
+    versions: bool,
+) -> Vec<(usize, usize, String)> {
+    ALPHANUMERIC_REGEX
+        .captures_iter(text)
+        .filter_map(|cap| {
+            let full = cap.get(0)?;
+            if is_glued_token(text, full.start(), full.end()) {
+                return None;
+            }
+            let (letters, number) = (&cap[1], &cap[3]);
+
+            let replacement = if letters == "v" && cap[2].is_empty() {
+                if !versions {
+                    return None;
+                }
+                let parts: Option<Vec<String>> = number
+                    .split('.')
+                    .map(|part| cardinal_words(part.parse().ok()?))
+                    .collect();
+                format!("version {}", parts?.join(" point "))
+            } else {
+                let is_whole = !number.contains('.')
+                    && number.len() <= MAX_ALPHANUMERIC_DIGITS
+                    && (number == "0" || !number.starts_with('0'));
+                if !is_whole {
+                    return None;
+                }
+                format!(
+                    "{} {}",
+                    prefix_words(letters, prefixes)?,
+                    cardinal_words(number.parse().ok()?)?
+                )
+            };
+            Some((full.start(), full.end(), replacement))
+        })
+        .collect()
+}
+
+/// Check whether a token is part of a URL, path, email or file name
+fn is_glued_token(text: &str, start: usize, end: usize) -> bool {
+    let joiner = |c: char| matches!(c, '.' | '/' | '\\' | '@' | ':' | '_');
+    let before = text[..start].chars().next_back().is_some_and(joiner);
+    let mut after = text[end..].chars();
+    let after = after.next().is_some_and(joiner) && after.next().is_some_and(char::is_alphanumeric);
+    before || after
+}
+
+/// Spoken form of the letters before a number, if they should be read
+fn prefix_words(letters: &str, prefixes: &[AlphanumericPrefix]) -> Option<String> {
+    if let Some(prefix) = prefixes
+        .iter()
+        .find(|p| p.letters.eq_ignore_ascii_case(letters))
+    {
+        return Some(prefix.reading.to_string());
+    }
+
+    let upper = letters.chars().all(|c| c.is_ascii_uppercase());
+    let has_vowel = letters.chars().any(|c| "AEIOUYaeiouy".contains(c));
+    if upper && has_vowel && letters.len() >= MIN_CAPS_WORD_LEN {
+        return Some(letters.to_lowercase());
+    }
+    if !upper && (has_vowel || letters.len() > 3) {
+        return None;
+    }
+    let names: Vec<&str> = letters
+        .bytes()
+        .map(|b| LETTER_NAMES[(b.to_ascii_lowercase() - b'a') as usize])
+        .collect();
+    Some(names.join(" "))
+}
+
+/// Spoken decade for the digits before its final zero ("199" → "nineteen nineties")
+fn decade_words(digits: &str) -> Option<String> {
+    let decade = |digit: u32| -> Option<&'static str> {
+        Some(match digit {
+            1 => "tens",
+            2 => "twenties",
+            3 => "thirties",
+            4 => "forties",
+            5 => "fifties",
+            6 => "sixties",
+            7 => "seventies",
+            8 => "eighties",
+            9 => "nineties",
+            _ => return None,
+        })
+    };
+
+    if digits.len() == 1 {
+        return decade(digits.parse().ok()?).map(str::to_string);
+    }
+    if digits.starts_with('0') {
+        return None;
+    }
+    let century: i64 = digits[..2].parse().ok()?;
+    match digits[2..].parse().ok()? {
+        // "2000s" → "two thousands", "1800s" → "eighteen hundreds"
+        0 if century % 10 == 0 => Some(format!("{} thousands", cardinal_words(century / 10)?)),
+        0 => Some(format!("{} hundreds", cardinal_words(century)?)),
+        digit => Some(format!("{} {}", cardinal_words(century)?, decade(digit)?)),
+    }
+}
+
+/// "USB" → "U.S.B", without a trailing dot that would double up with a period
+fn spelled_with_dots(word: &str) -> String {
+    word.chars()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Spoken form of a clock time ("3" → "three", "10:05" → "ten oh five")
+///
+/// On a 12-hour clock (followed by AM/PM) whole hours are just the hour; on a
+/// 24-hour clock they are read "o'clock".
+fn clock_words(hour: &str, minutes: Option<&str>, twelve_hour: bool) -> Option<String> {
+    let hour: i64 = hour.parse().ok()?;
+    let max_hour = if twelve_hour { 12 } else { 23 };
+    if hour > max_hour || (twelve_hour && hour == 0) {
+        return None;
+    }
+    let hour_words = cardinal_words(hour)?;
+
+    let minutes: i64 = match minutes {
+        Some(minutes) => minutes.parse().ok()?,
+        None => 0,
+    };
+    match minutes {
+        0 if twelve_hour => Some(hour_words),
+        0 => Some(format!("{} o'clock", hour_words)),
+        1..=9 => Some(format!("{} oh {}", hour_words, cardinal_words(minutes)?)),
+        10..=59 => Some(format!("{} {}", hour_words, cardinal_words(minutes)?)),
+        _ => None,
+    }
+}
+
+/// Spoken form of a plain decimal number ("5" → "five", "2.5" → "two point five")
+fn number_words(number: &str) -> Option<String> {
+    let (integer, fraction) = match number.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (number, None),
+    };
+    let mut words = cardinal_words(integer.parse().ok()?)?;
+
+    if let Some(fraction) = fraction {
+        let digits: Option<Vec<String>> = fraction
+            .chars()
+            .map(|d| cardinal_words(d.to_digit(10)? as i64))
+            .collect();
+        words = format!("{} point {}", words, digits?.join(" "));
+    }
+
+    Some(words)
+}
+
+/// Check whether "Name + numeral" reads as a monarch or pope
+fn is_regnal_name(title: Option<&str>, name: &str, value: i64) -> bool {
+    if value > MAX_REGNAL_NUMBER {
+        return false;
+    }
+
+    if title.is_some_and(|t| REGNAL_TITLES.contains(&t)) {
+        return true;
+    }
+
+    // Without a title "I" is far more likely to be the pronoun ("Then John I ...")
+    value > 1 && REGNAL_NAMES.contains(&name)
+}
+
+/// Regnal ordinal with article and capitalization: "the Sixteenth"
+fn regnal_ordinal(value: i64) -> Option<String> {
+    let ordinal = Num2Words::new(value).ordinal().to_words().ok()?;
+    let mut chars = ordinal.chars();
+    let first = chars.next()?;
+    Some(format!("the {}{}", first.to_uppercase(), chars.as_str()))
+}
+
+/// Parse a canonical uppercase roman numeral
+fn parse_roman(numeral: &str) -> Option<i64> {
+    if numeral.is_empty() || !CANONICAL_ROMAN_REGEX.is_match(numeral) {
+        return None;
+    }
+
+    let digit = |c: char| match c {
+        'I' => 1,
+        'V' => 5,
+        'X' => 10,
+        'L' => 50,
+        'C' => 100,
+        'D' => 500,
+        'M' => 1000,
+        _ => 0,
+    };
+
+    let values: Vec<i64> = numeral.chars().map(digit).collect();
+    let total = values
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| match values.get(i + 1) {
+            Some(&next) if next > v => -v,
+            _ => v,
+        })
+        .sum();
+
+    Some(total)
+}
+
+/// Check that a numeric span isn't glued to surrounding words, paths or decimals
+fn is_standalone_number(text: &str, start: usize, end: usize) -> bool {
+    let before = text[..start].chars().next_back();
+    let mut after = text[end..].chars();
+    let next = after.next();
+    let next_next = after.next();
+
+    if before.is_some_and(|c| c.is_alphanumeric() || matches!(c, '/' | '.' | ':' | '-')) {
+        return false;
+    }
+
+    match next {
+        Some(c) if c.is_alphanumeric() || c == '/' => false,
+        // "1/2.5" or "1/2,000" continue the number
+        Some('.') | Some(',') => !next_next.is_some_and(|c| c.is_ascii_digit()),
+        _ => true,
+    }
+}
+
+/// Format a (possibly mixed) fraction for speech, or None if it shouldn't be converted
+fn format_fraction(
+    whole: Option<&str>,
+    numerator: &str,
+    denominator: &str,
+    style: FractionStyle,
+) -> Option<String> {
+    // Leading zeros ("01/02") are a strong date signal
+    if numerator.starts_with('0') || denominator.starts_with('0') {
+        return None;
+    }
+
+    let numerator: i64 = numerator.parse().ok()?;
+    let denominator: i64 = denominator.parse().ok()?;
+
+    if !(2..=MAX_FRACTION_DENOMINATOR).contains(&denominator) || numerator >= denominator {
+        return None;
+    }
+
+    let fraction = match style {
+        FractionStyle::Named => named_fraction(numerator, denominator, whole.is_some())?,
+        FractionStyle::Over => format!(
+            "{} over {}",
+            cardinal_words(numerator)?,
+            cardinal_words(denominator)?
+        ),
+    };
+
+    match whole {
+        Some(whole) => Some(format!(
+            "{} and {}",
+            cardinal_words(whole.parse().ok()?)?,
+            fraction
+        )),
+        None => Some(fraction),
+    }
+}
+
+/// Named fraction: "one half", "two thirds", "five eighths" ("a half" in mixed numbers)
+fn named_fraction(numerator: i64, denominator: i64, mixed: bool) -> Option<String> {
+    let singular = match denominator {
+        2 => "half".to_string(),
+        4 => "quarter".to_string(),
+        d => Num2Words::new(d).ordinal().to_words().ok()?,
+    };
+
+    if numerator == 1 {
+        if mixed {
+            let article = if singular.starts_with('e') { "an" } else { "a" };
+            return Some(format!("{} {}", article, singular));
+        }
+        return Some(format!("one {}", singular));
+    }
+
+    let plural = if denominator == 2 {
+        "halves".to_string()
+    } else {
+        format!("{}s", singular)
+    };
+
+    Some(format!("{} {}", cardinal_words(numerator)?, plural))
+}
+
+/// Spoken cardinal number ("21" → "twenty-one")
+pub fn cardinal_words(num: i64) -> Option<String> {
+    Num2Words::new(num).to_words().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replace_all(text: &str, style: FractionStyle) -> String {
+        let mut result = text.to_string();
+        for (start, end, replacement) in fraction_matches(text, style).into_iter().rev() {
+            result.replace_range(start..end, &replacement);
+        }
+        result
+    }
+
+    #[test]
+    fn test_fraction_one_half() {
+        assert_eq!(
+            replace_all("Add 1/2 cup of sugar", FractionStyle::Named),
+            "Add one half cup of sugar"
+        );
+    }
+
+    #[test]
+    fn test_fraction_three_quarters() {
+        assert_eq!(replace_all("3/4", FractionStyle::Named), "three quarters");
+    }
+
+    #[test]
+    fn test_fraction_general_denominator() {
+        assert_eq!(
+            replace_all("a 5/8 inch bolt", FractionStyle::Named),
+            "a five eighths inch bolt"
+        );
+        assert_eq!(replace_all("2/3", FractionStyle::Named), "two thirds");
+    }
+
+    #[test]
+    fn test_fraction_over_style() {
+        assert_eq!(replace_all("5/8", FractionStyle::Over), "five over eight");
+        assert_eq!(replace_all("1/2", FractionStyle::Over), "one over two");
+    }
+
+    #[test]
+    fn test_mixed_number() {
+        assert_eq!(
+            replace_all("1 1/2 cups", FractionStyle::Named),
+            "one and a half cups"
+        );
+        assert_eq!(
+            replace_all("2 1/8 miles", FractionStyle::Named),
+            "two and an eighth miles"
+        );
+        assert_eq!(
+            replace_all("2 3/4", FractionStyle::Named),
+            "two and three quarters"
+        );
+    }
+
+    #[test]
+    fn test_date_not_treated_as_fraction() {
+        let text = "Due on 10/11/2024 at noon";
+        assert!(fraction_matches(text, FractionStyle::Named).is_empty());
+        assert_eq!(replace_all(text, FractionStyle::Named), text);
+    }
+
+    #[test]
+    fn test_zero_padded_date_not_treated_as_fraction() {
+        assert!(fraction_matches("on 01/02", FractionStyle::Named).is_empty());
+    }
+
+    #[test]
+    fn test_large_denominator_and_improper_fraction_ignored() {
+        assert!(fraction_matches("on 3/25", FractionStyle::Named).is_empty());
+        assert!(fraction_matches("open 24/7", FractionStyle::Named).is_empty());
+        assert!(fraction_matches("a 50/50 chance", FractionStyle::Named).is_empty());
+    }
+
+    #[test]
+    fn test_known_reference_ignored() {
+        assert!(fraction_matches("after 9/11", FractionStyle::Named).is_empty());
+    }
+
+    #[test]
+    fn test_paths_and_versions_ignored() {
+        assert!(fraction_matches("see /docs/1/2", FractionStyle::Named).is_empty());
+        assert!(fraction_matches("api v1/2", FractionStyle::Named).is_empty());
+        assert!(fraction_matches("1/2.5", FractionStyle::Named).is_empty());
+    }
+
+    #[test]
+    fn test_fraction_at_sentence_end() {
+        assert_eq!(
+            replace_all("Use 1/4.", FractionStyle::Named),
+            "Use one quarter."
+        );
+    }
+
+    fn replace_roman(text: &str) -> String {
+        let mut result = text.to_string();
+        for (start, end, replacement) in roman_numeral_matches(text).into_iter().rev() {
+            result.replace_range(start..end, &replacement);
+        }
+        result
+    }
+
+    #[test]
+    fn test_parse_roman() {
+        assert_eq!(parse_roman("I"), Some(1));
+        assert_eq!(parse_roman("XIV"), Some(14));
+        assert_eq!(parse_roman("XVIII"), Some(18));
+        assert_eq!(parse_roman("MCMXCIX"), Some(1999));
+        assert_eq!(parse_roman("IIII"), None);
+        assert_eq!(parse_roman("VX"), None);
+        assert_eq!(parse_roman(""), None);
+    }
+
+    #[test]
+    fn test_roman_chapter_heading() {
+        assert_eq!(replace_roman("Chapter XIV"), "Chapter fourteen");
+        assert_eq!(
+            replace_roman("CHAPTER III. The Storm"),
+            "CHAPTER three. The Storm"
+        );
+        assert_eq!(replace_roman("Act III, Scene II"), "Act three, Scene two");
+        assert_eq!(replace_roman("Part I"), "Part one");
+        assert_eq!(
+            replace_roman("after World War II ended"),
+            "after World War two ended"
+        );
+    }
+
+    #[test]
+    fn test_roman_regnal_name() {
+        assert_eq!(
+            replace_roman("Louis XVI was executed"),
+            "Louis the Sixteenth was executed"
+        );
+        assert_eq!(
+            replace_roman("King Henry VIII's wives"),
+            "King Henry the Eighth's wives"
+        );
+        assert_eq!(
+            replace_roman("Queen Elizabeth I"),
+            "Queen Elizabeth the First"
+        );
+    }
+
+    #[test]
+    fn test_roman_pronoun_i_untouched() {
+        for text in [
+            "I think so.",
+            "Then I left.",
+            "John I said no.",
+            "Later Elizabeth I met him.",
+            "the part I liked",
+        ] {
+            assert_eq!(replace_roman(text), text, "{} should be unchanged", text);
+        }
+    }
+
+    fn replace_units(text: &str) -> String {
+        let mut result = text.to_string();
+        for (start, end, replacement) in unit_matches(text, DEFAULT_UNITS).into_iter().rev() {
+            result.replace_range(start..end, &replacement);
+        }
+        result
+    }
+
+    #[test]
+    fn test_unit_singular_and_plural() {
+        assert_eq!(replace_units("1kg"), "one kilogram");
+        assert_eq!(replace_units("5kg"), "five kilograms");
+        assert_eq!(replace_units("1 ft and 6 ft"), "one foot and six feet");
+        assert_eq!(replace_units("2.5 kg"), "two point five kilograms");
+    }
+
+    #[test]
+    fn test_unit_compound_and_degrees() {
+        assert_eq!(replace_units("10mph"), "ten miles per hour");
+        assert_eq!(replace_units("80 km/h"), "eighty kilometers per hour");
+        assert_eq!(
+            replace_units("in 30°C heat"),
+            "in thirty degrees Celsius heat"
+        );
+        assert_eq!(replace_units("-5°C"), "minus five degrees Celsius");
+        assert_eq!(
+            replace_units("It was -1°F."),
+            "It was minus one degree Fahrenheit."
+        );
+    }
+
+    #[test]
+    fn test_unit_range_dash_is_not_minus() {
+        assert_eq!(replace_units("3-5kg"), "3-five kilograms");
+    }
+
+    #[test]
+    fn test_unknown_or_glued_units_untouched() {
+        for text in [
+            "5 in the box",
+            "5g network",
+            "A4kg",
+            "5kgs",
+            "v1.5GB",
+            "5m people",
+        ] {
+            assert_eq!(replace_units(text), text, "{} should be unchanged", text);
+        }
+    }
+
+    #[test]
+    fn test_custom_unit_table() {
+        let units = [unit("hp", "horsepower", "horsepower")];
+        let matches = unit_matches("a 300hp engine", &units);
+        assert_eq!(
+            matches,
+            vec![(2, 7, "three hundred horsepower".to_string())]
+        );
+        assert!(unit_matches("5kg", &units).is_empty());
+    }
+
+    fn replace_time_zones(text: &str) -> String {
+        let mut result = text.to_string();
+        for (start, end, replacement) in time_zone_matches(text, DEFAULT_TIME_ZONES)
+            .into_iter()
+            .rev()
+        {
+            result.replace_range(start..end, &replacement);
+        }
+        result
+    }
+
+    #[test]
+    fn test_time_zone_after_meridiem() {
+        assert_eq!(
+            replace_time_zones("at 3 PM EST"),
+            "at three PM Eastern Standard Time"
+        );
+        assert_eq!(
+            replace_time_zones("Starts 10:30am PDT."),
+            "Starts ten thirty AM Pacific Daylight Time."
+        );
+        assert_eq!(
+            replace_time_zones("by 9:05 p.m. GMT"),
+            "by nine oh five PM Greenwich Mean Time"
+        );
+    }
+
+    #[test]
+    fn test_time_zone_after_noon_and_24_hour_clock() {
+        assert_eq!(
+            replace_time_zones("noon UTC"),
+            "noon Coordinated Universal Time"
+        );
+        assert_eq!(
+            replace_time_zones("Midnight CET"),
+            "Midnight Central European Time"
+        );
+        assert_eq!(
+            replace_time_zones("at 14:00 UTC"),
+            "at fourteen o'clock Coordinated Universal Time"
+        );
+    }
+
+    #[test]
+    fn test_time_zone_letters_elsewhere_untouched() {
+        for text in [
+            "3 PM ESTATE",
+            "The EST office",
+            "at 3 PM BEST",
+            "at 3 PM",
+            "at 13 PM EST",
+            "at 25:00 UTC",
+            "v3 PM EST",
+            "the afternoon UTC",
+            "3 PM est",
+        ] {
+            assert_eq!(
+                replace_time_zones(text),
+                text,
+                "{} should be unchanged",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn test_custom_time_zone_table() {
+        let zones = [zone("IST", "India Standard Time")];
+        assert_eq!(
+            time_zone_matches("5 PM IST", &zones),
+            vec![(0, 8, "five PM India Standard Time".to_string())]
+        );
+        assert!(time_zone_matches("5 PM EST", &zones).is_empty());
+    }
+
+    #[test]
+    fn test_roman_ordinary_capitalized_words_untouched() {
+        for text in [
+            "Press MIX to blend",
+            "The DJ played a MIX",
+            "Henry MIX",
+            "Chapter IIII",
+            "Louis CD",
+            "Version XL",
+        ] {
+            assert_eq!(replace_roman(text), text, "{} should be unchanged", text);
+        }
+    }
+
+    fn replace_all_caps(text: &str) -> String {
+        let mut result = text.to_string();
+        for (start, end, replacement) in all_caps_matches(text, DEFAULT_ACRONYMS).into_iter().rev()
+        {
+            result.replace_range(start..end, &replacement);
+        }
+        result
+    }
+
+    #[test]
+    fn test_all_caps_known_acronyms() {
+        assert_eq!(replace_all_caps("NASA said so"), "NASA said so");
+        assert_eq!(
+            replace_all_caps("Plug in the USB cable."),
+            "Plug in the U.S.B cable."
+        );
+        assert_eq!(replace_all_caps("Made in the USA."), "Made in the U.S.A.");
+    }
+
+    #[test]
+    fn test_all_caps_emphasis_is_lowercased() {
+        assert_eq!(replace_all_caps("NASA said STOP"), "NASA said stop");
+        assert_eq!(replace_all_caps("I DON'T know"), "I don't know");
+        assert_eq!(replace_all_caps("BREAKING NEWS"), "breaking news");
+    }
+
+    #[test]
+    fn test_all_caps_short_or_vowelless_words_unchanged() {
+        assert_eq!(
+            replace_all_caps("OK, the BBC and HTML"),
+            "OK, the BBC and HTML"
+        );
+        assert_eq!(replace_all_caps("A NEW day"), "A NEW day");
+        // Mixed case and words glued to digits aren't all-caps words
+        assert_eq!(replace_all_caps("NaSA MP3 COVID19"), "NaSA MP3 COVID19");
+    }
+
+    fn replace_line_break_hyphens(text: &str) -> String {
+        let mut result = text.to_string();
+        for (start, end, replacement) in line_break_hyphen_matches(text).into_iter().rev() {
+            result.replace_range(start..end, &replacement);
+        }
+        result
+    }
+
+    #[test]
+    fn test_line_break_hyphen_rejoins_split_words() {
+        assert_eq!(
+            replace_line_break_hyphens("an inter-\nnational treaty"),
+            "an international treaty"
+        );
+        assert_eq!(
+            replace_line_break_hyphens("a well-\r\n  known fact"),
+            "a wellknown fact"
+        );
+        assert_eq!(
+            replace_line_break_hyphens("hyphen-\nation and separ-\nation"),
+            "hyphenation and separation"
+        );
+    }
+
+    #[test]
+    fn test_line_break_hyphen_keeps_compounds() {
+        assert_eq!(
+            replace_line_break_hyphens("a state-of-\nthe-art design"),
+            "a state-of-the-art design"
+        );
+        assert_eq!(
+            replace_line_break_hyphens("the Anglo-\nSaxon kings"),
+            "the Anglo-Saxon kings"
+        );
+    }
+
+    #[test]
+    fn test_line_break_hyphen_ignores_other_hyphens() {
+        for text in [
+            "a well-known fact",
+            "pages 3-\n4",
+            "first line -\nsecond line",
+            "ends with a dash-\n\nNew paragraph",
+        ] {
+            assert!(line_break_hyphen_matches(text).is_empty(), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn test_line_break_soft_hyphen_always_joins() {
+        assert_eq!(
+            replace_line_break_hyphens("inter\u{AD}\nnational"),
+            "international"
+        );
+        assert_eq!(
+            replace_line_break_hyphens("Anglo\u{AD}\nSaxon"),
+            "AngloSaxon"
+        );
+    }
+
+    #[test]
+    fn test_all_caps_custom_dictionary() {
+        let acronyms = [spell("SCUBA"), keep("STOP")];
+        let matches = all_caps_matches("SCUBA STOP", &acronyms);
+        assert_eq!(matches, vec![(0, 5, "S.C.U.B.A".to_string())]);
+    }
+
+    fn replace_decades(text: &str) -> String {
+        let mut result = text.to_string();
+        for (start, end, replacement) in decade_matches(text).into_iter().rev() {
+            result.replace_range(start..end, &replacement);
+        }
+        result
+    }
+
+    #[test]
+    fn test_decades_are_read_as_words() {
+        assert_eq!(
+            replace_decades("The 1990s and the 80s"),
+            "The nineteen nineties and the eighties"
+        );
+        assert_eq!(
+            replace_decades("'60s music, the 1920's, the 2010s"),
+            "sixties music, the nineteen twenties, the twenty tens"
+        );
+        assert_eq!(
+            replace_decades("the 1800s, the 2000s, in her 50s"),
+            "the eighteen hundreds, the two thousands, in her fifties"
+        );
+    }
+
+    fn replace_alphanumerics(text: &str, versions: bool) -> String {
+        let mut result = text.to_string();
+        for (start, end, replacement) in
+            alphanumeric_matches(text, DEFAULT_ALPHANUMERIC_PREFIXES, versions)
+                .into_iter()
+                .rev()
+        {
+            result.replace_range(start..end, &replacement);
+        }
+        result
+    }
+
+    #[test]
+    fn test_alphanumerics_are_read_together() {
+        assert_eq!(
+            replace_alphanumerics("COVID-19 spread while the G7 met", true),
+            "covid nineteen spread while the gee seven met"
+        );
+        assert_eq!(
+            replace_alphanumerics("An MP3 or an mp3, on the I-95 in a V8", true),
+            "An em pee three or an em pee three, on the eye ninety-five in a vee eight"
+        );
+        assert_eq!(
+            replace_alphanumerics("Covid-19 after WW2, the SARS2 virus", true),
+            "covid nineteen after world war two, the sars two virus"
+        );
+    }
+
+    #[test]
+    fn test_alphanumeric_versions_follow_the_setting() {
+        assert_eq!(
+            replace_alphanumerics("Upgrade to v2 or v1.10.3", true),
+            "Upgrade to version two or version one point ten point three"
+        );
+        assert_eq!(
+            replace_alphanumerics("Upgrade to v2 or v1.10.3", false),
+            "Upgrade to v2 or v1.10.3"
+        );
+        // Other prefixes are still read with versions off
+        assert_eq!(replace_alphanumerics("MP3 v2", false), "em pee three v2");
+    }
+
+    #[test]
+    fn test_non_alphanumerics_are_left_alone() {
+        for text in [
+            "top10",
+            "Win10",
+            "A12345",
+            "B007",
+            "MP3s",
+            "MP3.5",
+            "site.com/v2",
+            "mp3.example.org",
+            "user1@example.com",
+            "file_v2",
+            "3D",
+        ] {
+            assert!(
+                alphanumeric_matches(text, DEFAULT_ALPHANUMERIC_PREFIXES, true).is_empty(),
+                "{:?}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn test_alphanumerics_custom_dictionary() {
+        let prefixes = [prefix("PS", "PlayStation")];
+        let matches = alphanumeric_matches("PS5", &prefixes, true);
+        assert_eq!(matches, vec![(0, 3, "PlayStation five".to_string())]);
+    }
+
+    #[test]
+    fn test_non_decades_are_left_alone() {
+        for text in [
+            "1995s", "85s", "00s", "2.50s", "$100s", "A380s", "10000s", "0990s", "1990sx",
+        ] {
+            assert!(decade_matches(text).is_empty(), "{:?}", text);
+        }
+    }
+}
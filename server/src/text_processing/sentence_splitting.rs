@@ -6,6 +6,9 @@ const COMMON_ABBREVIATIONS: &[&str] = &[
     "i.e", "e.g", "vs", "Inc", "Corp", "Ltd", "Ave", "St", "Rd", "Blvd", "Mt",
 ];
 
+/// Characters that end a sentence unless configured otherwise
+pub const DEFAULT_SENTENCE_ENDINGS: &[char] = &['.', '!', '?'];
+
 /// Check if a period is likely part of an abbreviation
 fn is_abbreviation(text: &str, period_pos: usize) -> bool {
     // Look backwards for word before period
@@ -46,23 +49,33 @@ fn is_abbreviation(text: &str, period_pos: usize) -> bool {
 /// - Multiple sentence-ending punctuation (., !, ?)
 /// - Initials (e.g., "J. K. Rowling")
 pub fn split_sentences(text: &str) -> Vec<String> {
+    split_sentences_with_endings(text, DEFAULT_SENTENCE_ENDINGS)
+}
+
+/// Split text into sentences, ending them only at the given characters
+///
+/// A period keeps its abbreviation and decimal guards and only ends a
+/// sentence before a capitalised word; any other ending splits right away.
+pub fn split_sentences_with_endings(text: &str, endings: &[char]) -> Vec<String> {
     let mut sentences = Vec::new();
     let mut current_sentence = String::new();
     let chars: Vec<char> = text.chars().collect();
 
     let mut i = 0;
+    // Byte offset of chars[i], for slicing `text`
+    let mut byte = 0;
     while i < chars.len() {
         let ch = chars[i];
         current_sentence.push(ch);
 
         // Check for sentence-ending punctuation
-        if ch == '.' || ch == '!' || ch == '?' {
+        if endings.contains(&ch) {
             // Look ahead for space and capital letter
             let next_is_space = i + 1 < chars.len() && chars[i + 1].is_whitespace();
             let after_space_is_capital = i + 2 < chars.len() && chars[i + 2].is_ascii_uppercase();
 
             // Check if it's an abbreviation (only for periods)
-            let is_abbrev = ch == '.' && is_abbreviation(&text[..i + 1], i);
+            let is_abbrev = ch == '.' && is_abbreviation(&text[..byte + 1], byte);
 
             // Check if it's a decimal number
             let prev_is_digit = i > 0 && chars[i - 1].is_ascii_digit();
@@ -80,6 +93,7 @@ pub fn split_sentences(text: &str) -> Vec<String> {
         }
 
         i += 1;
+        byte += ch.len_utf8();
     }
 
     // Add last sentence
@@ -91,6 +105,53 @@ pub fn split_sentences(text: &str) -> Vec<String> {
     sentences
 }
 
+/// Whether text ends with an ellipsis ("..." or "…")
+pub fn ends_with_ellipsis(text: &str) -> bool {
+    let text = text.trim_end();
+    text.ends_with("...") || text.ends_with('…')
+}
+
+/// Split text after each ellipsis ("..." or "…") that sits between words
+///
+/// Each part keeps its ellipsis, so it still ends on a trailing-off
+/// intonation. An ellipsis at the very start or end of the text, or with
+/// nothing speakable on one side, doesn't split.
+pub fn split_after_ellipses(text: &str) -> Vec<String> {
+    let has_words = |s: &str| s.chars().any(char::is_alphanumeric);
+    let mut parts = Vec::new();
+    let mut part_start = 0;
+    let mut search_from = 0;
+
+    while let Some((start, end)) = next_ellipsis(text, search_from) {
+        search_from = end;
+        if has_words(&text[part_start..start]) && has_words(&text[end..]) {
+            parts.push(text[part_start..end].trim().to_string());
+            part_start = end;
+        }
+    }
+
+    let rest = text[part_start..].trim();
+    if !rest.is_empty() {
+        parts.push(rest.to_string());
+    }
+    parts
+}
+
+/// Byte range of the next ellipsis at or after `from`, including any further dots
+fn next_ellipsis(text: &str, mut from: usize) -> Option<(usize, usize)> {
+    loop {
+        let start = from + text[from..].find(['.', '…'])?;
+        let run = text[start..]
+            .find(|c: char| c != '.' && c != '…')
+            .map_or(text.len(), |len| start + len);
+        let dots = &text[start..run];
+        if dots.contains('…') || dots.len() >= 3 {
+            return Some((start, run));
+        }
+        from = run;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -221,4 +282,47 @@ mod tests {
         assert_eq!(sentences.len(), 1);
         assert_eq!(sentences[0], "Pi is approximately 3.14159.");
     }
+
+    #[test]
+    fn test_split_after_ellipses() {
+        assert_eq!(
+            split_after_ellipses("Well... I suppose so… fine."),
+            vec!["Well...", "I suppose so…", "fine."]
+        );
+        assert_eq!(
+            split_after_ellipses("Wait....what?"),
+            vec!["Wait....", "what?"]
+        );
+    }
+
+    #[test]
+    fn test_ellipses_at_edges_or_lone_dots_do_not_split() {
+        assert_eq!(
+            split_after_ellipses("...and then it ended..."),
+            vec!["...and then it ended..."]
+        );
+        assert_eq!(
+            split_after_ellipses("Pi is 3.14. Done.. ok"),
+            vec!["Pi is 3.14. Done.. ok"]
+        );
+        assert!(split_after_ellipses("  ").is_empty());
+    }
+
+    #[test]
+    fn test_ends_with_ellipsis() {
+        assert!(ends_with_ellipsis("Well... "));
+        assert!(ends_with_ellipsis("so…"));
+        assert!(!ends_with_ellipsis("Done."));
+    }
+
+    #[test]
+    fn test_abbreviation_after_multibyte_text() {
+        let endings = ['.', '\u{3002}'];
+        let sentences =
+            split_sentences_with_endings("\u{4f60}\u{597d}\u{3002} Dr. Smith is here.", &endings);
+        assert_eq!(
+            sentences,
+            vec!["\u{4f60}\u{597d}\u{3002}", "Dr. Smith is here."]
+        );
+    }
 }
@@ -0,0 +1,200 @@
+/// Inline speed markup for per-segment speaking rates
+///
+/// Text may wrap parts of a sentence in `{{speed:0.8}}...{{/speed}}` to speak
+/// them at a different speed. Everything outside the markup uses the
+/// request's base speed. Markup cannot be nested.
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::time::Duration;
+
+use crate::error::{Result, TtsError};
+use crate::utils::speed::resolve_speed;
+
+lazy_static! {
+    /// Opening `{{speed:N}}` or closing `{{/speed}}` tag
+    static ref SPEED_TAG_REGEX: Regex = Regex::new(
+        r"\{\{\s*(?:speed\s*:\s*([^}]*?)|(/)\s*speed)\s*\}\}"
+    ).unwrap();
+}
+
+/// A run of text spoken at a single speed
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechSegment {
+    pub text: String,
+    pub speed: f32,
+    /// Volume change in decibels (0 = as synthesized)
+    pub gain_db: f32,
+    /// Pitch relative to the request's (1 = unchanged)
+    pub pitch_scale: f32,
+    /// Silence after the segment, unless it ends the text
+    pub pause_after: Option<Duration>,
+}
+
+/// Check whether the text contains any speed markup
+pub fn has_speed_markup(text: &str) -> bool {
+    SPEED_TAG_REGEX.is_match(text)
+}
+
+/// Split text into segments at their marked-up speeds
+///
+/// Whitespace-only segments are dropped. Unbalanced or nested tags are
+/// rejected; marked-up speeds outside `[min_speed, MAX_SPEED]` are rejected,
+/// or clamped when `clamp_speed` is set.
+pub fn parse_speed_markup(
+    text: &str,
+    base_speed: f32,
+    min_speed: f32,
+    clamp_speed: bool,
+) -> Result<Vec<SpeechSegment>> {
+    let mut segments = Vec::new();
+    let mut current_speed = base_speed;
+    let mut inside = false;
+    let mut last_end = 0;
+
+    for cap in SPEED_TAG_REGEX.captures_iter(text) {
+        let Some(tag) = cap.get(0) else {
+            continue;
+        };
+        push_segment(&mut segments, &text[last_end..tag.start()], current_speed);
+        last_end = tag.end();
+
+        if cap.get(2).is_some() {
+            if !inside {
+                return Err(markup_error("closing {{/speed}} without an opening tag"));
+            }
+            inside = false;
+            current_speed = base_speed;
+        } else {
+            if inside {
+                return Err(markup_error("speed tags cannot be nested"));
+            }
+            let value = cap.get(1).map_or("", |m| m.as_str());
+            current_speed = parse_speed(value, min_speed, clamp_speed)?;
+            inside = true;
+        }
+    }
+
+    if inside {
+        return Err(markup_error("missing closing {{/speed}} tag"));
+    }
+    push_segment(&mut segments, &text[last_end..], current_speed);
+
+    Ok(segments)
+}
+
+fn push_segment(segments: &mut Vec<SpeechSegment>, text: &str, speed: f32) {
+    let text = text.trim();
+    if !text.is_empty() {
+        segments.push(SpeechSegment {
+            text: text.to_string(),
+            speed,
+            gain_db: 0.0,
+            pitch_scale: 1.0,
+            pause_after: None,
+        });
+    }
+}
+
+fn parse_speed(value: &str, min_speed: f32, clamp: bool) -> Result<f32> {
+    let speed: f32 = value
+        .trim()
+        .parse()
+        .map_err(|_| markup_error(&format!("invalid speed '{}'", value.trim())))?;
+
+    resolve_speed(speed, min_speed, clamp)
+}
+
+fn markup_error(message: &str) -> TtsError {
+    TtsError::InvalidRequest(format!("Invalid speed markup: {}", message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::constants::DEFAULT_MIN_SPEED;
+
+    fn segment(text: &str, speed: f32) -> SpeechSegment {
+        SpeechSegment {
+            text: text.to_string(),
+            speed,
+            gain_db: 0.0,
+            pitch_scale: 1.0,
+            pause_after: None,
+        }
+    }
+
+    #[test]
+    fn test_plain_text_is_one_segment() {
+        assert!(!has_speed_markup("Hello world."));
+        assert_eq!(
+            parse_speed_markup("Hello world.", 1.2, DEFAULT_MIN_SPEED, false).unwrap(),
+            vec![segment("Hello world.", 1.2)]
+        );
+    }
+
+    #[test]
+    fn test_marked_segment_uses_its_speed() {
+        let text = "This is {{speed:0.8}}very important{{/speed}}, really.";
+        assert!(has_speed_markup(text));
+        assert_eq!(
+            parse_speed_markup(text, 1.0, DEFAULT_MIN_SPEED, false).unwrap(),
+            vec![
+                segment("This is", 1.0),
+                segment("very important", 0.8),
+                segment(", really.", 1.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tags_tolerate_whitespace() {
+        assert_eq!(
+            parse_speed_markup(
+                "{{ speed: 1.5 }}Fast{{ /speed }}",
+                1.0,
+                DEFAULT_MIN_SPEED,
+                false
+            )
+            .unwrap(),
+            vec![segment("Fast", 1.5)]
+        );
+    }
+
+    #[test]
+    fn test_rejects_unbalanced_and_nested_tags() {
+        for text in [
+            "{{speed:0.8}}never closed",
+            "stray close{{/speed}}",
+            "{{speed:0.8}}a {{speed:0.5}}b{{/speed}}{{/speed}}",
+        ] {
+            assert!(
+                matches!(
+                    parse_speed_markup(text, 1.0, DEFAULT_MIN_SPEED, false),
+                    Err(TtsError::InvalidRequest(_))
+                ),
+                "{} should be rejected",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_speeds() {
+        assert!(matches!(
+            parse_speed_markup("{{speed:fast}}x{{/speed}}", 1.0, DEFAULT_MIN_SPEED, false),
+            Err(TtsError::InvalidRequest(_))
+        ));
+        assert!(matches!(
+            parse_speed_markup("{{speed:5}}x{{/speed}}", 1.0, DEFAULT_MIN_SPEED, false),
+            Err(TtsError::InvalidSpeed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_clamps_marked_speed_when_enabled() {
+        assert_eq!(
+            parse_speed_markup("{{speed:5}}x{{/speed}}", 1.0, DEFAULT_MIN_SPEED, true).unwrap(),
+            vec![segment("x", 3.0)]
+        );
+    }
+}
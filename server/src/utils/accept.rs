@@ -0,0 +1,257 @@
+/// `Accept` header parsing and audio format negotiation
+///
+/// Only audio ranges (`audio/...`, plus `*/*`) take part in choosing an
+/// encoder; an `Accept` header that names no audio type at all, such as
+/// `multipart/mixed` or `application/json`, leaves the choice to the server
+/// default.
+use serde::{Deserialize, Serialize};
+
+/// Audio encodings a client can ask for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioFormat {
+    Wav,
+    #[serde(alias = "mpeg")]
+    Mp3,
+    Ogg,
+}
+
+impl AudioFormat {
+    /// Media type used in `Content-Type`
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "audio/wav",
+            AudioFormat::Mp3 => "audio/mpeg",
+            AudioFormat::Ogg => "audio/ogg",
+        }
+    }
+
+    /// Name used for the request `format` field
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "wav",
+            AudioFormat::Mp3 => "mp3",
+            AudioFormat::Ogg => "ogg",
+        }
+    }
+
+    /// Parse a format name (`wav`, `mp3`/`mpeg` or `ogg`), case-insensitively
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "wav" => Ok(AudioFormat::Wav),
+            "mp3" | "mpeg" => Ok(AudioFormat::Mp3),
+            "ogg" => Ok(AudioFormat::Ogg),
+            other => Err(format!(
+                "Invalid audio format {:?}: expected wav, mp3 or ogg",
+                other
+            )),
+        }
+    }
+
+    /// Format for a media type, including common aliases like `audio/x-wav`
+    pub fn from_mime(media_type: &str) -> Option<Self> {
+        match media_type {
+            "audio/wav" | "audio/wave" | "audio/x-wav" | "audio/vnd.wave" => Some(AudioFormat::Wav),
+            "audio/mpeg" | "audio/mp3" => Some(AudioFormat::Mp3),
+            "audio/ogg" => Some(AudioFormat::Ogg),
+            _ => None,
+        }
+    }
+}
+
+/// One entry of an `Accept` header
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaRange {
+    /// Lowercased, without parameters (e.g. `audio/*`)
+    pub media_type: String,
+    /// The `q` parameter, 1.0 when absent
+    pub quality: f32,
+}
+
+/// Parse an `Accept` header value into its media ranges
+///
+/// Malformed `q` values count as 1.0; empty entries are skipped.
+pub fn parse_accept(value: &str) -> Vec<MediaRange> {
+    value
+        .split(',')
+        .filter_map(|item| {
+            let mut parts = item.split(';');
+            let media_type = parts.next()?.trim().to_ascii_lowercase();
+            if media_type.is_empty() {
+                return None;
+            }
+            let quality = parts
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0)
+                .clamp(0.0, 1.0);
+            Some(MediaRange {
+                media_type,
+                quality,
+            })
+        })
+        .collect()
+}
+
+/// Pick the audio format to produce for an `Accept` header
+///
+/// Each supported format gets the quality of the most specific range that
+/// matches it (`audio/wav` over `audio/*` over `*/*`), and the best one wins;
+/// ties go to `default`, then to the order of `supported`. Returns `default`
+/// when the header is absent or names no audio type, and `None` when it
+/// names audio types but none that can be produced.
+pub fn negotiate_audio_format(
+    accept: Option<&str>,
+    supported: &[AudioFormat],
+    default: AudioFormat,
+) -> Option<AudioFormat> {
+    let ranges: Vec<MediaRange> = accept
+        .map(parse_accept)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|range| range.media_type.starts_with("audio/") || range.media_type == "*/*")
+        .collect();
+    if ranges.is_empty() {
+        return Some(default);
+    }
+
+    let quality = |format: AudioFormat| {
+        let specificity = |range: &MediaRange| {
+            if AudioFormat::from_mime(&range.media_type) == Some(format) {
+                Some(2)
+            } else if range.media_type == "audio/*" {
+                Some(1)
+            } else if range.media_type == "*/*" {
+                Some(0)
+            } else {
+                None
+            }
+        };
+        ranges
+            .iter()
+            .filter_map(|range| specificity(range).map(|s| (s, range.quality)))
+            .max_by_key(|(s, _)| *s)
+            .map(|(_, q)| q)
+            .unwrap_or(0.0)
+    };
+
+    let mut best: Option<(AudioFormat, f32)> = None;
+    for &format in supported {
+        let q = quality(format);
+        let better = match best {
+            None => true,
+            Some((current, best_q)) => {
+                q > best_q || (q == best_q && format == default && current != default)
+            }
+        };
+        if q > 0.0 && better {
+            best = Some((format, q));
+        }
+    }
+    best.map(|(format, _)| format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: &[AudioFormat] = &[AudioFormat::Wav, AudioFormat::Mp3, AudioFormat::Ogg];
+    const WAV_ONLY: &[AudioFormat] = &[AudioFormat::Wav];
+
+    fn negotiate(accept: Option<&str>, supported: &[AudioFormat]) -> Option<AudioFormat> {
+        negotiate_audio_format(accept, supported, AudioFormat::Wav)
+    }
+
+    #[test]
+    fn test_parse_format_names() {
+        assert_eq!(AudioFormat::parse("wav"), Ok(AudioFormat::Wav));
+        assert_eq!(AudioFormat::parse(" MP3 "), Ok(AudioFormat::Mp3));
+        assert_eq!(AudioFormat::parse("mpeg"), Ok(AudioFormat::Mp3));
+        assert_eq!(AudioFormat::parse("ogg"), Ok(AudioFormat::Ogg));
+        assert!(AudioFormat::parse("flac").is_err());
+    }
+
+    #[test]
+    fn test_parse_accept_with_quality() {
+        let ranges = parse_accept("Audio/MPEG;q=0.5, audio/wav , */*;level=1;q=0.1,");
+        assert_eq!(
+            ranges,
+            vec![
+                MediaRange {
+                    media_type: "audio/mpeg".to_string(),
+                    quality: 0.5
+                },
+                MediaRange {
+                    media_type: "audio/wav".to_string(),
+                    quality: 1.0
+                },
+                MediaRange {
+                    media_type: "*/*".to_string(),
+                    quality: 0.1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_accept_bad_quality_counts_as_one() {
+        assert_eq!(parse_accept("audio/ogg;q=high")[0].quality, 1.0);
+        assert_eq!(parse_accept("audio/ogg;q=7")[0].quality, 1.0);
+    }
+
+    #[test]
+    fn test_each_audio_type_selects_its_format() {
+        assert_eq!(negotiate(Some("audio/wav"), ALL), Some(AudioFormat::Wav));
+        assert_eq!(negotiate(Some("audio/x-wav"), ALL), Some(AudioFormat::Wav));
+        assert_eq!(negotiate(Some("audio/mpeg"), ALL), Some(AudioFormat::Mp3));
+        assert_eq!(negotiate(Some("audio/ogg"), ALL), Some(AudioFormat::Ogg));
+    }
+
+    #[test]
+    fn test_default_without_audio_preference() {
+        assert_eq!(negotiate(None, ALL), Some(AudioFormat::Wav));
+        assert_eq!(negotiate(Some("*/*"), ALL), Some(AudioFormat::Wav));
+        assert_eq!(negotiate(Some("audio/*"), ALL), Some(AudioFormat::Wav));
+        assert_eq!(
+            negotiate(Some("multipart/mixed, application/json"), ALL),
+            Some(AudioFormat::Wav)
+        );
+        assert_eq!(
+            negotiate_audio_format(Some("*/*"), ALL, AudioFormat::Ogg),
+            Some(AudioFormat::Ogg)
+        );
+    }
+
+    #[test]
+    fn test_quality_orders_preferences() {
+        assert_eq!(
+            negotiate(Some("audio/wav;q=0.2, audio/ogg;q=0.8"), ALL),
+            Some(AudioFormat::Ogg)
+        );
+        // A specific range overrides a wildcard, even with a lower quality
+        assert_eq!(
+            negotiate(Some("audio/*, audio/wav;q=0"), ALL),
+            Some(AudioFormat::Mp3)
+        );
+    }
+
+    #[test]
+    fn test_unsupported_audio_types_are_not_acceptable() {
+        assert_eq!(negotiate(Some("audio/mpeg"), WAV_ONLY), None);
+        assert_eq!(negotiate(Some("audio/ogg, audio/mpeg"), WAV_ONLY), None);
+        assert_eq!(negotiate(Some("audio/wav;q=0"), WAV_ONLY), None);
+        // A fallback wildcard still allows the supported format
+        assert_eq!(
+            negotiate(Some("audio/mpeg, */*;q=0.1"), WAV_ONLY),
+            Some(AudioFormat::Wav)
+        );
+    }
+
+    #[test]
+    fn test_format_names() {
+        assert_eq!(AudioFormat::Mp3.mime_type(), "audio/mpeg");
+        assert_eq!(AudioFormat::Ogg.as_str(), "ogg");
+        let format: AudioFormat = serde_json::from_str("\"mpeg\"").unwrap();
+        assert_eq!(format, AudioFormat::Mp3);
+    }
+}
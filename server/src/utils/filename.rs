@@ -0,0 +1,167 @@
+/// Download filenames for generated audio
+///
+/// Responses carry `Content-Disposition: attachment` so browsers save them
+/// under a meaningful name. Client-provided names are reduced to a safe
+/// basename: no directories, quotes or control characters can reach the
+/// header or the user's filesystem.
+use axum::http::HeaderValue;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Longest filename stem kept from a request, in characters
+const MAX_STEM_LEN: usize = 100;
+
+/// Reduce a client-provided filename to a safe basename, without extension
+///
+/// Returns `None` when nothing usable is left.
+pub fn sanitize_filename(name: &str) -> Option<String> {
+    // Drop any directory components, whichever separator they use
+    let base = name.rsplit(['/', '\\']).next().unwrap_or("");
+
+    let cleaned: String = base
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | ' ') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    // No hidden files or ".." names, and no stray separators at the edges
+    let stem = cleaned.trim_matches(|c: char| matches!(c, '.' | ' ' | '_'));
+    let stem = strip_extension(stem);
+
+    if stem.is_empty() {
+        return None;
+    }
+    Some(stem.chars().take(MAX_STEM_LEN).collect())
+}
+
+/// Drop a trailing audio extension the client already supplied
+fn strip_extension(stem: &str) -> &str {
+    match stem.rsplit_once('.') {
+        Some((base, ext)) if ext.eq_ignore_ascii_case("wav") => base.trim_end_matches('.'),
+        _ => stem,
+    }
+}
+
+/// Filename for a response: the sanitized request name, or `speech_<UTC time>_<voice>`
+pub fn output_filename(requested: Option<&str>, voice: &str, extension: &str) -> String {
+    let stem = requested.and_then(sanitize_filename).unwrap_or_else(|| {
+        let voice = sanitize_filename(voice).unwrap_or_else(|| "voice".to_string());
+        format!("speech_{}_{}", utc_timestamp(SystemTime::now()), voice)
+    });
+    format!("{}.{}", stem, extension)
+}
+
+/// `Content-Disposition` header offering the audio as a download
+pub fn content_disposition(requested: Option<&str>, voice: &str, extension: &str) -> HeaderValue {
+    let filename = output_filename(requested, voice, extension);
+    // Sanitized names are plain ASCII without quotes, so this can't fail
+    HeaderValue::from_str(&format!("attachment; filename=\"{}\"", filename))
+        .unwrap_or_else(|_| HeaderValue::from_static("attachment"))
+}
+
+/// Format a time as `YYYYMMDD-HHMMSS` in UTC
+fn utc_timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+
+    // Days since 1970-01-01 to a civil date (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        year,
+        month,
+        day,
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60,
+        secs_of_day % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_sanitize_keeps_safe_names() {
+        assert_eq!(
+            sanitize_filename("chapter-1 intro"),
+            Some("chapter-1 intro".into())
+        );
+        assert_eq!(sanitize_filename("chapter_1.wav"), Some("chapter_1".into()));
+        assert_eq!(sanitize_filename("notes.v2.WAV"), Some("notes.v2".into()));
+    }
+
+    #[test]
+    fn test_sanitize_blocks_traversal_and_injection() {
+        assert_eq!(sanitize_filename("../../etc/passwd"), Some("passwd".into()));
+        assert_eq!(sanitize_filename("C:\\Users\\me\\a.wav"), Some("a".into()));
+        assert_eq!(
+            sanitize_filename("a\"; filename=evil.exe"),
+            Some("a__ filename_evil.exe".into())
+        );
+        assert_eq!(
+            sanitize_filename("x\r\nSet-Cookie: y"),
+            Some("x__Set-Cookie_ y".into())
+        );
+        assert_eq!(sanitize_filename(".hidden"), Some("hidden".into()));
+        assert_eq!(sanitize_filename(".."), None);
+        assert_eq!(sanitize_filename("/"), None);
+        assert_eq!(sanitize_filename("résumé"), Some("r_sum".into()));
+    }
+
+    #[test]
+    fn test_sanitize_truncates_long_names() {
+        let long = "a".repeat(500);
+        assert_eq!(sanitize_filename(&long).unwrap().len(), MAX_STEM_LEN);
+    }
+
+    #[test]
+    fn test_output_filename_uses_request_name() {
+        assert_eq!(
+            output_filename(Some("my clip"), "af_heart", "wav"),
+            "my clip.wav"
+        );
+        assert_eq!(
+            output_filename(Some("my clip.wav"), "af_heart", "wav"),
+            "my clip.wav"
+        );
+    }
+
+    #[test]
+    fn test_output_filename_default() {
+        for requested in [None, Some(""), Some("../")] {
+            let name = output_filename(requested, "af_sarah.4+af_nicole.6", "wav");
+            assert!(name.starts_with("speech_"), "{}", name);
+            assert!(name.ends_with("_af_sarah.4_af_nicole.6.wav"), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_content_disposition_header() {
+        let header = content_disposition(Some("../a\"b"), "bf_lily", "wav");
+        assert_eq!(header, "attachment; filename=\"a_b.wav\"");
+    }
+
+    #[test]
+    fn test_utc_timestamp() {
+        assert_eq!(utc_timestamp(UNIX_EPOCH), "19700101-000000");
+        let time = UNIX_EPOCH + Duration::from_secs(1_709_210_096);
+        assert_eq!(utc_timestamp(time), "20240229-123456");
+    }
+}
@@ -0,0 +1,192 @@
+/// Minimal `multipart/form-data` parsing for uploads
+///
+/// Bodies are parsed from memory, so callers cap their size before handing
+/// them over. Only what form uploads use is supported: `Content-Disposition`
+/// names and filenames, and each part's `Content-Type`.
+use bytes::Bytes;
+
+/// One field or file of a form
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormPart {
+    /// The `name` from `Content-Disposition`
+    pub name: String,
+    /// Present for file uploads
+    pub filename: Option<String>,
+    /// The part's `Content-Type`, if it sent one
+    pub content_type: Option<String>,
+    pub data: Bytes,
+}
+
+/// Boundary of a `multipart/form-data` content type, or None for any other type
+pub fn boundary(content_type: &str) -> Option<String> {
+    let mut params = split_params(content_type).into_iter();
+    let media_type = params.next()?;
+    if !media_type.eq_ignore_ascii_case("multipart/form-data") {
+        return None;
+    }
+    params
+        .find_map(|param| param_value(&param, "boundary"))
+        .filter(|boundary| !boundary.is_empty())
+}
+
+/// Split a form body into its parts
+pub fn parse(body: &[u8], boundary: &str) -> Result<Vec<FormPart>, String> {
+    let delimiter = format!("--{}", boundary);
+    let next_delimiter = format!("\r\n{}", delimiter);
+
+    // Anything before the first delimiter is preamble
+    let start = find(body, delimiter.as_bytes()).ok_or("missing opening boundary")?;
+    let mut rest = &body[start + delimiter.len()..];
+
+    let mut parts = Vec::new();
+    loop {
+        if rest.starts_with(b"--") {
+            return Ok(parts);
+        }
+        rest = rest
+            .strip_prefix(b"\r\n")
+            .ok_or("malformed boundary line")?;
+        let end = find(rest, next_delimiter.as_bytes()).ok_or("missing closing boundary")?;
+        parts.push(parse_part(&rest[..end])?);
+        rest = &rest[end + next_delimiter.len()..];
+    }
+}
+
+fn parse_part(raw: &[u8]) -> Result<FormPart, String> {
+    let (headers, data) = match raw.strip_prefix(b"\r\n") {
+        // No headers at all
+        Some(data) => (&[][..], data),
+        None => {
+            let end = find(raw, b"\r\n\r\n").ok_or("part headers are not terminated")?;
+            (&raw[..end], &raw[end + 4..])
+        }
+    };
+    let headers = std::str::from_utf8(headers).map_err(|_| "part headers are not UTF-8")?;
+
+    let mut name = None;
+    let mut filename = None;
+    let mut content_type = None;
+    for line in headers.split("\r\n") {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if key.trim().eq_ignore_ascii_case("content-disposition") {
+            for param in split_params(value).iter().skip(1) {
+                name = name.or_else(|| param_value(param, "name"));
+                filename = filename.or_else(|| param_value(param, "filename"));
+            }
+        } else if key.trim().eq_ignore_ascii_case("content-type") {
+            content_type = Some(value.to_string());
+        }
+    }
+
+    Ok(FormPart {
+        name: name.ok_or("part has no name")?,
+        filename,
+        content_type,
+        data: Bytes::copy_from_slice(data),
+    })
+}
+
+/// Split a header value at `;`, ignoring semicolons inside quotes
+fn split_params(value: &str) -> Vec<String> {
+    let mut params = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in value.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                current.push(c);
+            }
+            ';' if !quoted => params.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    params.push(current);
+    params.into_iter().map(|p| p.trim().to_string()).collect()
+}
+
+/// Value of a `key=value` or `key="value"` parameter named `key`
+fn param_value(param: &str, key: &str) -> Option<String> {
+    let (name, value) = param.split_once('=')?;
+    if !name.trim().eq_ignore_ascii_case(key) {
+        return None;
+    }
+    let value = value.trim();
+    let value = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value);
+    Some(value.to_string())
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &str = "preamble\r\n\
+        --XyZ\r\n\
+        Content-Disposition: form-data; name=\"voice\"\r\n\
+        \r\n\
+        af_heart\r\n\
+        --XyZ\r\n\
+        Content-Disposition: form-data; name=\"file\"; filename=\"a;b.txt\"\r\n\
+        Content-Type: text/plain\r\n\
+        \r\n\
+        Hello\r\nworld\r\n\
+        --XyZ--\r\n";
+
+    #[test]
+    fn test_boundary_from_content_type() {
+        assert_eq!(
+            boundary("multipart/form-data; boundary=XyZ").as_deref(),
+            Some("XyZ")
+        );
+        assert_eq!(
+            boundary("Multipart/Form-Data; charset=utf-8; boundary=\"a b\"").as_deref(),
+            Some("a b")
+        );
+        assert_eq!(boundary("application/json"), None);
+        assert_eq!(boundary("multipart/form-data"), None);
+    }
+
+    #[test]
+    fn test_parse_fields_and_files() {
+        let parts = parse(BODY.as_bytes(), "XyZ").unwrap();
+        assert_eq!(parts.len(), 2);
+
+        assert_eq!(parts[0].name, "voice");
+        assert_eq!(parts[0].filename, None);
+        assert_eq!(parts[0].data.as_ref(), b"af_heart");
+
+        assert_eq!(parts[1].name, "file");
+        assert_eq!(parts[1].filename.as_deref(), Some("a;b.txt"));
+        assert_eq!(parts[1].content_type.as_deref(), Some("text/plain"));
+        // Line breaks inside the data are kept
+        assert_eq!(parts[1].data.as_ref(), b"Hello\r\nworld");
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_bodies() {
+        assert!(parse(b"no boundary here", "XyZ").is_err());
+        assert!(parse(
+            b"--XyZ\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\nunterminated",
+            "XyZ"
+        )
+        .is_err());
+        assert!(parse(
+            b"--XyZ\r\nContent-Type: text/plain\r\n\r\nx\r\n--XyZ--",
+            "XyZ"
+        )
+        .is_err());
+        assert_eq!(parse(b"--XyZ--\r\n", "XyZ").unwrap(), vec![]);
+    }
+}
@@ -0,0 +1,25 @@
+/// 128-bit FNV-1a hash
+///
+/// Used instead of `DefaultHasher`, whose output may change between Rust
+/// releases and would silently invalidate anything persisted with it (such
+/// as the on-disk audio cache).
+pub fn fnv1a_128(bytes: &[u8]) -> u128 {
+    const OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+    const PRIME: u128 = 0x0000000001000000000000000000013b;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ byte as u128).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fnv1a_128_known_value() {
+        // Reference value for the empty input is the offset basis
+        assert_eq!(fnv1a_128(b""), 0x6c62272e07bb014262b821756295c58d);
+        assert_ne!(fnv1a_128(b"a"), fnv1a_128(b"b"));
+    }
+}
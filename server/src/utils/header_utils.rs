@@ -1,6 +1,8 @@
 use axum::{extract::ConnectInfo, extract::Request, http::HeaderMap};
 use std::net::{IpAddr, SocketAddr};
 
+use crate::config::trusted_proxies::TrustedProxies;
+
 /// Extract API key from HTTP headers
 ///
 /// Supports both X-API-Key header and Authorization: Bearer header.
@@ -38,51 +40,95 @@ pub fn extract_api_key(headers: &HeaderMap) -> Option<String> {
 
 /// Extract client IP address from HTTP request
 ///
-/// Supports X-Forwarded-For, X-Real-IP headers (for proxies/load balancers),
-/// and falls back to connection IP address.
+/// Forwarding headers are only believed when the connection comes from a
+/// trusted proxy; otherwise anyone could pick their own rate-limit bucket.
 ///
 /// # Priority Order
-/// 1. X-Forwarded-For header (leftmost IP = original client)
+/// 1. X-Forwarded-For header, walked right to left past trusted proxies
+///    (the first untrusted hop is the client)
 /// 2. X-Real-IP header (nginx proxy)
 /// 3. Connection IP from socket address
 ///
+/// Steps 1 and 2 are skipped when the peer isn't in `trusted`.
+///
 /// # Examples
 ///
 /// ```ignore
 /// use axum::extract::Request;
+/// use porua_server::config::trusted_proxies::TrustedProxies;
 /// use porua_server::utils::header_utils::extract_client_ip;
 ///
-/// let ip = extract_client_ip(&request)?;
+/// let ip = extract_client_ip(&request, &TrustedProxies::default())?;
 /// println!("Client IP: {}", ip);
 /// ```
-pub fn extract_client_ip<B>(request: &Request<B>) -> Result<IpAddr, String> {
-    // Try X-Forwarded-For first (for proxies/load balancers)
-    if let Some(forwarded_for) = request.headers().get("x-forwarded-for") {
-        if let Ok(forwarded_str) = forwarded_for.to_str() {
-            // Take leftmost IP (original client)
-            if let Some(ip_str) = forwarded_str.split(',').next() {
-                if let Ok(ip) = ip_str.trim().parse::<IpAddr>() {
-                    return Ok(ip);
-                }
-            }
-        }
+pub fn extract_client_ip<B>(
+    request: &Request<B>,
+    trusted: &TrustedProxies,
+) -> Result<IpAddr, String> {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|connect_info| connect_info.0.ip().to_canonical())
+        .ok_or_else(|| "Unable to extract client IP address".to_string())?;
+
+    if !trusted.contains(peer) {
+        return Ok(peer);
+    }
+
+    // Try X-Forwarded-For first (for proxies/load balancers). Once it is
+    // present X-Real-IP isn't consulted, so a client can't swap in its own
+    // X-Real-IP by sending a forwarded chain that doesn't parse.
+    if request.headers().contains_key("x-forwarded-for") {
+        return Ok(forwarded_client(request.headers(), trusted).unwrap_or(peer));
     }
 
     // Try X-Real-IP (nginx)
     if let Some(real_ip) = request.headers().get("x-real-ip") {
-        if let Ok(ip_str) = real_ip.to_str() {
-            if let Ok(ip) = ip_str.trim().parse::<IpAddr>() {
-                return Ok(ip);
-            }
+        if let Some(ip) = real_ip.to_str().ok().and_then(parse_hop) {
+            return Ok(ip);
         }
     }
 
-    // Fallback: Extract from connection (axum extensions)
-    if let Some(connect_info) = request.extensions().get::<ConnectInfo<SocketAddr>>() {
-        return Ok(connect_info.0.ip());
+    Ok(peer)
+}
+
+/// Find the client in the X-Forwarded-For chain
+///
+/// Each proxy appends the address it received the request from, so hops are
+/// read from the right; the first one that isn't a trusted proxy is the
+/// client. Anything further left was supplied by that client and can't be
+/// believed. Multiple X-Forwarded-For headers are read as one list.
+///
+/// Reading stops at a hop that doesn't parse, since nothing to the left of it
+/// can be traced back through trusted proxies. If every hop read is trusted,
+/// the leftmost of them is taken.
+fn forwarded_client(headers: &HeaderMap, trusted: &TrustedProxies) -> Option<IpAddr> {
+    let hops: Vec<&str> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .flat_map(|value| value.to_str().unwrap_or_default().split(','))
+        .collect();
+
+    let mut nearest = None;
+    for hop in hops.iter().rev() {
+        let Some(ip) = parse_hop(hop) else {
+            break;
+        };
+        if !trusted.contains(ip) {
+            return Some(ip);
+        }
+        nearest = Some(ip);
     }
+    nearest
+}
 
-    Err("Unable to extract client IP address".to_string())
+/// Parse one forwarded address, tolerating ports ("1.2.3.4:80", "[::1]:80")
+fn parse_hop(hop: &str) -> Option<IpAddr> {
+    let hop = hop.trim();
+    hop.parse::<IpAddr>()
+        .or_else(|_| hop.parse::<SocketAddr>().map(|addr| addr.ip()))
+        .ok()
+        .map(|ip| ip.to_canonical())
 }
 
 #[cfg(test)]
@@ -123,4 +169,123 @@ mod tests {
         headers.insert("authorization", "InvalidFormat".parse().unwrap());
         assert_eq!(extract_api_key(&headers), None);
     }
+
+    fn request_from(peer: &str, headers: &[(&'static str, &str)]) -> Request<()> {
+        let mut request = Request::new(());
+        for (name, value) in headers {
+            request.headers_mut().append(*name, value.parse().unwrap());
+        }
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(peer.parse::<SocketAddr>().unwrap()));
+        request
+    }
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    fn proxies(list: &str) -> TrustedProxies {
+        TrustedProxies::parse(Some(list)).unwrap()
+    }
+
+    #[test]
+    fn test_client_ip_direct_connection() {
+        let request = request_from("203.0.113.7:5000", &[]);
+        assert_eq!(
+            extract_client_ip(&request, &TrustedProxies::default()),
+            Ok(ip("203.0.113.7"))
+        );
+
+        let request = request_from("[2001:db8::7]:5000", &[]);
+        assert_eq!(
+            extract_client_ip(&request, &TrustedProxies::default()),
+            Ok(ip("2001:db8::7"))
+        );
+    }
+
+    #[test]
+    fn test_client_ip_from_trusted_proxy() {
+        let trusted = proxies("10.0.0.0/8");
+
+        let request = request_from("10.0.0.2:80", &[("x-forwarded-for", "198.51.100.4")]);
+        assert_eq!(
+            extract_client_ip(&request, &trusted),
+            Ok(ip("198.51.100.4"))
+        );
+
+        // Client-supplied entries to the left of the real client are ignored
+        let request = request_from(
+            "10.0.0.2:80",
+            &[("x-forwarded-for", "1.1.1.1, 2001:db8::9, 10.0.0.5")],
+        );
+        assert_eq!(extract_client_ip(&request, &trusted), Ok(ip("2001:db8::9")));
+
+        let request = request_from("10.0.0.2:80", &[("x-real-ip", "198.51.100.4")]);
+        assert_eq!(
+            extract_client_ip(&request, &trusted),
+            Ok(ip("198.51.100.4"))
+        );
+    }
+
+    #[test]
+    fn test_client_ip_ignores_spoofed_headers_from_untrusted_peer() {
+        let request = request_from(
+            "203.0.113.7:5000",
+            &[("x-forwarded-for", "1.2.3.4"), ("x-real-ip", "5.6.7.8")],
+        );
+        assert_eq!(
+            extract_client_ip(&request, &proxies("10.0.0.0/8")),
+            Ok(ip("203.0.113.7"))
+        );
+    }
+
+    #[test]
+    fn test_client_ip_ipv4_mapped_peer() {
+        let request = request_from(
+            "[::ffff:10.0.0.2]:80",
+            &[("x-forwarded-for", "198.51.100.4")],
+        );
+        assert_eq!(
+            extract_client_ip(&request, &proxies("10.0.0.0/8")),
+            Ok(ip("198.51.100.4"))
+        );
+    }
+
+    #[test]
+    fn test_client_ip_stops_at_unparsable_hop() {
+        let trusted = proxies("10.0.0.0/8");
+
+        // Junk the client put on the left doesn't hide the hops proxies added
+        let request = request_from("10.0.0.2:80", &[("x-forwarded-for", "unknown, 1.2.3.4")]);
+        assert_eq!(extract_client_ip(&request, &trusted), Ok(ip("1.2.3.4")));
+
+        // Behind unparsable junk, the nearest trusted hop is as far as it goes
+        let request = request_from(
+            "10.0.0.2:80",
+            &[("x-forwarded-for", "1.2.3.4, junk, 10.0.0.5")],
+        );
+        assert_eq!(extract_client_ip(&request, &trusted), Ok(ip("10.0.0.5")));
+
+        let request = request_from("10.0.0.2:80", &[("x-forwarded-for", "unknown")]);
+        assert_eq!(extract_client_ip(&request, &trusted), Ok(ip("10.0.0.2")));
+    }
+
+    #[test]
+    fn test_client_ip_junk_forwarded_for_does_not_fall_back_to_real_ip() {
+        let request = request_from(
+            "10.0.0.2:80",
+            &[("x-forwarded-for", "junk"), ("x-real-ip", "5.6.7.8")],
+        );
+        assert_eq!(
+            extract_client_ip(&request, &proxies("10.0.0.0/8")),
+            Ok(ip("10.0.0.2"))
+        );
+    }
+
+    #[test]
+    fn test_client_ip_requires_connection_info() {
+        let request = Request::new(());
+        assert!(extract_client_ip(&request, &TrustedProxies::default()).is_err());
+    }
 }
@@ -0,0 +1,48 @@
+/// Serialize durations as whole milliseconds
+///
+/// Used with `#[serde(serialize_with = ...)]` so settings read back in the
+/// same unit their `_MS` variables are written in, instead of serde's
+/// `{ "secs", "nanos" }` form.
+use serde::Serializer;
+use std::time::Duration;
+
+pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(duration.as_millis() as u64)
+}
+
+/// Like [`serialize`], with `None` as `null`
+pub fn serialize_option<S: Serializer>(
+    duration: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match duration {
+        Some(duration) => serialize(duration, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct Pauses {
+        #[serde(serialize_with = "serialize")]
+        pause: Duration,
+        #[serde(serialize_with = "serialize_option")]
+        keep_alive: Option<Duration>,
+    }
+
+    #[test]
+    fn test_durations_as_millis() {
+        let json = serde_json::to_value(Pauses {
+            pause: Duration::from_millis(1500),
+            keep_alive: None,
+        })
+        .unwrap();
+
+        assert_eq!(json["pause"], 1500);
+        assert!(json["keep_alive"].is_null());
+    }
+}
@@ -1,2 +1,11 @@
+pub mod accept;
+pub mod base64;
+pub mod filename;
+pub mod form_data;
+pub mod hash;
 pub mod header_utils;
+pub mod millis;
+pub mod redact;
+pub mod speed;
 pub mod temp_file;
+pub mod zip;
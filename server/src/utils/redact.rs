@@ -0,0 +1,66 @@
+/// Redaction helpers for logging user content and secrets
+///
+/// Request text and API keys never appear verbatim in logs at info level or
+/// above. Text is logged as its length plus a short hash, so identical inputs
+/// can still be correlated; the raw text is only emitted at trace level
+/// (`RUST_LOG=tts_server=trace`). API keys are only ever logged as a
+/// fingerprint.
+use super::hash::fnv1a_128;
+
+/// Short stable hash of a value, safe to log
+fn short_hash(value: &str) -> String {
+    // Fold both halves in; FNV's top bits barely change for a differing last byte
+    let hash = fnv1a_128(value.as_bytes());
+    format!("{:08x}", (hash ^ (hash >> 64)) as u32)
+}
+
+/// Loggable summary of user text: its length and a hash, never the content
+pub fn text_summary(text: &str) -> String {
+    format!("len={} hash={}", text.chars().count(), short_hash(text))
+}
+
+/// Loggable fingerprint of an API key
+///
+/// No characters of the key itself are included, only a truncated hash that
+/// identifies which key was used.
+pub fn key_fingerprint(key: &str) -> String {
+    format!("key#{}", short_hash(key))
+}
+
+/// Log request text: a summary at info level, the full content at trace
+pub fn log_text(label: &str, text: &str) {
+    tracing::info!("{}: {}", label, text_summary(text));
+    tracing::trace!("{} (full): {:?}", label, text);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_summary_hides_content() {
+        let text = "My bank PIN is 4921";
+        let summary = text_summary(text);
+
+        assert!(!summary.contains("PIN"));
+        assert!(!summary.contains("4921"));
+        assert!(summary.starts_with("len=19 hash="));
+
+        // Stable for the same input, different for different input
+        assert_eq!(summary, text_summary(text));
+        assert_ne!(summary, text_summary("My bank PIN is 4922"));
+    }
+
+    #[test]
+    fn test_key_fingerprint_hides_key() {
+        let key = "sk-live-0123456789abcdef";
+        let fingerprint = key_fingerprint(key);
+
+        assert_eq!(fingerprint.len(), "key#".len() + 8);
+        assert!(!fingerprint.contains("sk-"));
+        assert!(!fingerprint.contains("0123"));
+        assert!(!fingerprint.contains("cdef"));
+        assert_eq!(fingerprint, key_fingerprint(key));
+        assert_ne!(fingerprint, key_fingerprint("sk-live-other"));
+    }
+}
@@ -0,0 +1,102 @@
+use crate::config::constants::{MAX_SPEED, MIN_SPEED};
+use crate::error::{Result, TtsError};
+
+/// Validate a requested speed, or clamp it into range when clamping is enabled
+///
+/// Speeds must be within `[min_speed, MAX_SPEED]`. With `clamp` set,
+/// out-of-range values are pulled into that range with a warning instead of
+/// failing the request. NaN is always rejected.
+pub fn resolve_speed(speed: f32, min_speed: f32, clamp: bool) -> Result<f32> {
+    if (min_speed..=MAX_SPEED).contains(&speed) {
+        return Ok(speed);
+    }
+
+    if !clamp || speed.is_nan() {
+        return Err(TtsError::InvalidSpeed {
+            speed,
+            min: min_speed,
+        });
+    }
+
+    let clamped = speed.clamp(min_speed, MAX_SPEED);
+    tracing::warn!("Clamping out-of-range speed {} to {}", speed, clamped);
+    Ok(clamped)
+}
+
+/// Parse a minimum speed (the `TTS_MIN_SPEED` format)
+///
+/// It must lie within `[MIN_SPEED, MAX_SPEED]`.
+pub fn parse_min_speed(value: &str) -> std::result::Result<f32, String> {
+    match value.trim().parse::<f32>() {
+        Ok(speed) if (MIN_SPEED..=MAX_SPEED).contains(&speed) => Ok(speed),
+        _ => Err(format!(
+            "Invalid minimum speed {:?}: expected a number from {} to {}",
+            value, MIN_SPEED, MAX_SPEED
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::config::constants::DEFAULT_MIN_SPEED;
+
+    #[test]
+    fn test_in_range_speed_unchanged() {
+        for clamp in [false, true] {
+            assert_eq!(resolve_speed(1.0, DEFAULT_MIN_SPEED, clamp).unwrap(), 1.0);
+            assert_eq!(resolve_speed(0.5, DEFAULT_MIN_SPEED, clamp).unwrap(), 0.5);
+            assert_eq!(resolve_speed(0.25, DEFAULT_MIN_SPEED, clamp).unwrap(), 0.25);
+            assert_eq!(resolve_speed(3.0, DEFAULT_MIN_SPEED, clamp).unwrap(), 3.0);
+            assert_eq!(resolve_speed(0.1, MIN_SPEED, clamp).unwrap(), 0.1);
+        }
+    }
+
+    #[test]
+    fn test_strict_mode_rejects_out_of_range() {
+        for speed in [3.01, 0.0, 0.01, 0.1, 0.24] {
+            assert!(
+                matches!(
+                    resolve_speed(speed, DEFAULT_MIN_SPEED, false),
+                    Err(TtsError::InvalidSpeed { .. })
+                ),
+                "{} should be rejected",
+                speed
+            );
+        }
+        assert!(matches!(
+            resolve_speed(0.4, 0.5, false),
+            Err(TtsError::InvalidSpeed { min, .. }) if min == 0.5
+        ));
+    }
+
+    #[test]
+    fn test_clamp_mode_clamps_out_of_range() {
+        assert_eq!(resolve_speed(3.01, DEFAULT_MIN_SPEED, true).unwrap(), 3.0);
+        assert_eq!(
+            resolve_speed(10.0, DEFAULT_MIN_SPEED, true).unwrap(),
+            MAX_SPEED
+        );
+        assert_eq!(
+            resolve_speed(-1.0, DEFAULT_MIN_SPEED, true).unwrap(),
+            DEFAULT_MIN_SPEED
+        );
+        assert_eq!(resolve_speed(0.01, 0.5, true).unwrap(), 0.5);
+    }
+
+    #[test]
+    fn test_nan_always_rejected() {
+        assert!(resolve_speed(f32::NAN, DEFAULT_MIN_SPEED, true).is_err());
+        assert!(resolve_speed(f32::NAN, DEFAULT_MIN_SPEED, false).is_err());
+    }
+
+    #[test]
+    fn test_parse_min_speed() {
+        assert_eq!(parse_min_speed("0.5").unwrap(), 0.5);
+        assert_eq!(parse_min_speed(" 0.1 ").unwrap(), MIN_SPEED);
+        assert!(parse_min_speed("0.05").is_err());
+        assert!(parse_min_speed("4").is_err());
+        assert!(parse_min_speed("slow").is_err());
+    }
+}
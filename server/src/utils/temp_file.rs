@@ -1,7 +1,70 @@
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 use tokio::fs;
 use uuid::Uuid;
 
+use crate::error::{Result, TtsError};
+
+/// Prefix of every temp file created by the server, used to recognise strays
+const TEMP_FILE_PREFIX: &str = "tts_";
+
+/// Directory for temporary audio files: `TTS_TEMP_DIR`, or the system temp dir
+pub fn temp_dir() -> PathBuf {
+    resolve_temp_dir(std::env::var("TTS_TEMP_DIR").ok())
+}
+
+fn resolve_temp_dir(value: Option<String>) -> PathBuf {
+    match value.as_deref().map(str::trim) {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => std::env::temp_dir(),
+    }
+}
+
+/// Create the configured temp directory if it doesn't exist yet
+pub fn init_temp_dir() -> std::io::Result<PathBuf> {
+    let dir = temp_dir();
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Remove temp files older than `max_age` left behind by a crashed process
+///
+/// Only files named like ours (`tts_*.wav`) are touched, so this is safe to
+/// run against a shared directory such as `/tmp`. Returns the number removed.
+pub fn sweep_stale_files(dir: &Path, max_age: Duration) -> std::io::Result<usize> {
+    let now = SystemTime::now();
+    let mut removed = 0;
+
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if !name.starts_with(TEMP_FILE_PREFIX) || !name.ends_with(".wav") {
+            continue;
+        }
+
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+
+        let age = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok());
+        if age.is_some_and(|age| age >= max_age) {
+            match std::fs::remove_file(entry.path()) {
+                Ok(()) => removed += 1,
+                Err(e) => tracing::warn!("Failed to remove stale temp file {:?}: {}", name, e),
+            }
+        }
+    }
+
+    Ok(removed)
+}
+
 /// Automatically cleaned-up temporary file
 pub struct TempFile {
     path: PathBuf,
@@ -14,9 +77,14 @@ impl Default for TempFile {
 }
 
 impl TempFile {
-    /// Create a new temporary file with .wav extension
+    /// Create a new temporary file with .wav extension in the configured directory
     pub fn new() -> Self {
-        let path = std::env::temp_dir().join(format!("tts_{}.wav", Uuid::new_v4()));
+        Self::new_in(&temp_dir())
+    }
+
+    /// Create a new temporary file with .wav extension in `dir`
+    pub fn new_in(dir: &Path) -> Self {
+        let path = dir.join(format!("{}{}.wav", TEMP_FILE_PREFIX, Uuid::new_v4()));
         Self { path }
     }
 
@@ -29,6 +97,35 @@ impl TempFile {
     pub fn as_str(&self) -> &str {
         self.path.to_str().unwrap_or("")
     }
+
+    /// Read the whole file
+    pub async fn read(&self) -> Result<Vec<u8>> {
+        fs::read(&self.path)
+            .await
+            .map_err(|e| self.io_error("read", e))
+    }
+
+    /// Replace the file's contents
+    pub async fn write(&self, data: impl AsRef<[u8]>) -> Result<()> {
+        fs::write(&self.path, data)
+            .await
+            .map_err(|e| self.io_error("write", e))
+    }
+
+    /// Open the file for streaming reads
+    pub async fn open(&self) -> Result<fs::File> {
+        fs::File::open(&self.path)
+            .await
+            .map_err(|e| self.io_error("open", e))
+    }
+
+    fn io_error(&self, operation: &'static str, source: std::io::Error) -> TtsError {
+        TtsError::TempIo {
+            operation,
+            path: self.path.clone(),
+            source,
+        }
+    }
 }
 
 impl Drop for TempFile {
@@ -62,4 +159,112 @@ mod tests {
         // File should be cleaned up
         assert!(!path.exists());
     }
+
+    #[tokio::test]
+    async fn test_temp_file_respects_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = {
+            let temp = TempFile::new_in(dir.path());
+            assert_eq!(temp.path().parent(), Some(dir.path()));
+            fs::write(temp.path(), b"test").await.unwrap();
+            temp.path().to_path_buf()
+        };
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_resolve_temp_dir() {
+        assert_eq!(
+            resolve_temp_dir(Some("/var/tmp/porua".to_string())),
+            PathBuf::from("/var/tmp/porua")
+        );
+        assert_eq!(resolve_temp_dir(None), std::env::temp_dir());
+        assert_eq!(
+            resolve_temp_dir(Some("  ".to_string())),
+            std::env::temp_dir()
+        );
+    }
+
+    #[test]
+    fn test_sweep_removes_old_files_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let old = dir.path().join("tts_old.wav");
+        let fresh = dir.path().join("tts_fresh.wav");
+        let unrelated = dir.path().join("other_old.wav");
+
+        for path in [&old, &fresh, &unrelated] {
+            std::fs::write(path, b"test").unwrap();
+        }
+        let two_hours_ago = SystemTime::now() - Duration::from_secs(2 * 60 * 60);
+        for path in [&old, &unrelated] {
+            std::fs::File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(two_hours_ago)
+                .unwrap();
+        }
+
+        let removed = sweep_stale_files(dir.path(), Duration::from_secs(60 * 60)).unwrap();
+
+        assert_eq!(removed, 1);
+        assert!(!old.exists());
+        assert!(fresh.exists());
+        assert!(unrelated.exists());
+    }
+
+    #[tokio::test]
+    async fn test_temp_file_read_write_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let temp = TempFile::new_in(dir.path());
+        temp.write(b"audio").await.unwrap();
+        assert_eq!(temp.read().await.unwrap(), b"audio");
+        assert!(temp.open().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_unwritable_temp_dir_reports_path_and_operation() {
+        // A regular file where the directory should be can't hold files,
+        // whatever the permissions of the user running the tests
+        let dir = tempfile::tempdir().unwrap();
+        let not_a_dir = dir.path().join("not_a_dir");
+        std::fs::write(&not_a_dir, b"").unwrap();
+        let temp = TempFile::new_in(&not_a_dir);
+
+        let err = temp.write(b"audio").await.unwrap_err();
+        assert!(matches!(
+            err,
+            TtsError::TempIo {
+                operation: "write",
+                ..
+            }
+        ));
+        let message = err.to_string();
+        assert!(message.starts_with("Temp file write failed for "));
+        assert!(message.contains(temp.as_str()));
+    }
+
+    #[tokio::test]
+    async fn test_missing_temp_file_reports_read_and_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let temp = TempFile::new_in(&dir.path().join("missing"));
+
+        let err = temp.read().await.unwrap_err();
+        assert!(err.to_string().contains("Temp file read failed"));
+        assert!(err.to_string().contains(temp.as_str()));
+        match temp.open().await.unwrap_err() {
+            TtsError::TempIo {
+                operation,
+                path,
+                source,
+            } => {
+                assert_eq!(operation, "open");
+                assert_eq!(path, temp.path());
+                assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+    }
 }
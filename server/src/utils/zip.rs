@@ -0,0 +1,196 @@
+/// DOS date for 1980-01-01, the earliest a zip can record; entries are undated
+const DOS_DATE: u16 = (1 << 5) | 1;
+
+/// Version 2.0: the minimum for stored entries in a directory-free archive
+const ZIP_VERSION: u16 = 20;
+
+/// General purpose flag marking entry names as UTF-8
+const UTF8_NAMES: u16 = 1 << 11;
+
+/// Minimal zip archive writer
+///
+/// Entries are stored uncompressed (WAV audio barely compresses) and written
+/// one at a time, so an archive can be streamed entry by entry without
+/// holding all of it in memory. Only the central directory, a few dozen bytes
+/// per entry, is kept until the archive is finished.
+#[derive(Default)]
+pub struct ZipStream {
+    central_directory: Vec<u8>,
+    entries: u16,
+    offset: u32,
+}
+
+impl ZipStream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bytes of one stored entry: its local header followed by `data`
+    ///
+    /// Fails if the archive would outgrow the classic (non-zip64) format:
+    /// 65535 entries or 4 GiB.
+    pub fn entry(&mut self, name: &str, data: &[u8]) -> Result<Vec<u8>, String> {
+        let too_large = || format!("zip archive too large to add {}", name);
+        let size = u32::try_from(data.len()).map_err(|_| too_large())?;
+        let name_len = u16::try_from(name.len()).map_err(|_| too_large())?;
+        let entries = self.entries.checked_add(1).ok_or_else(too_large)?;
+        let crc = crc32(data);
+
+        let mut local = Vec::with_capacity(30 + name.len() + data.len());
+        local.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        push_common_fields(&mut local, crc, size, name_len);
+        local.extend_from_slice(&0u16.to_le_bytes()); // extra field length
+        local.extend_from_slice(name.as_bytes());
+        local.extend_from_slice(data);
+
+        let cd = &mut self.central_directory;
+        cd.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        cd.extend_from_slice(&ZIP_VERSION.to_le_bytes()); // version made by
+        push_common_fields(cd, crc, size, name_len);
+        cd.extend_from_slice(&[0; 12]); // extra, comment, disk, attributes
+        cd.extend_from_slice(&self.offset.to_le_bytes());
+        cd.extend_from_slice(name.as_bytes());
+
+        let local_len = u32::try_from(local.len()).map_err(|_| too_large())?;
+        self.offset = self.offset.checked_add(local_len).ok_or_else(too_large)?;
+        self.entries = entries;
+        Ok(local)
+    }
+
+    /// Bytes closing the archive: the central directory and its end record
+    pub fn finish(self) -> Result<Vec<u8>, String> {
+        let cd_len = u32::try_from(self.central_directory.len())
+            .map_err(|_| "zip central directory too large".to_string())?;
+
+        let mut end = self.central_directory;
+        end.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+        end.extend_from_slice(&[0; 4]); // this disk, directory's disk
+        end.extend_from_slice(&self.entries.to_le_bytes());
+        end.extend_from_slice(&self.entries.to_le_bytes());
+        end.extend_from_slice(&cd_len.to_le_bytes());
+        end.extend_from_slice(&self.offset.to_le_bytes());
+        end.extend_from_slice(&0u16.to_le_bytes()); // comment length
+        Ok(end)
+    }
+}
+
+/// Fields shared by local and central headers, from "version needed" on
+fn push_common_fields(out: &mut Vec<u8>, crc: u32, size: u32, name_len: u16) {
+    out.extend_from_slice(&ZIP_VERSION.to_le_bytes());
+    out.extend_from_slice(&UTF8_NAMES.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes()); // stored, no compression
+    out.extend_from_slice(&0u16.to_le_bytes()); // modification time
+    out.extend_from_slice(&DOS_DATE.to_le_bytes());
+    out.extend_from_slice(&crc.to_le_bytes());
+    out.extend_from_slice(&size.to_le_bytes()); // compressed size
+    out.extend_from_slice(&size.to_le_bytes()); // uncompressed size
+    out.extend_from_slice(&name_len.to_le_bytes());
+}
+
+/// CRC-32 (IEEE 802.3), as zip requires for every entry
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &byte| {
+        (0..8).fold(crc ^ byte as u32, |crc, _| {
+            if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
+
+/// Read the entries of a stored-only archive, checking every CRC
+///
+/// Only understands what [`ZipStream`] writes; None if anything is off.
+#[cfg(test)]
+pub fn read_stored_entries(archive: &[u8]) -> Option<Vec<(String, Vec<u8>)>> {
+    let u16_at = |pos: usize| {
+        Some(u16::from_le_bytes(
+            archive.get(pos..pos + 2)?.try_into().ok()?,
+        ))
+    };
+    let u32_at = |pos: usize| {
+        Some(u32::from_le_bytes(
+            archive.get(pos..pos + 4)?.try_into().ok()?,
+        ))
+    };
+
+    let end = archive.len().checked_sub(22)?;
+    if u32_at(end)? != 0x0605_4b50 {
+        return None;
+    }
+    let count = u16_at(end + 10)? as usize;
+    let mut pos = u32_at(end + 16)? as usize;
+
+    let mut entries = Vec::with_capacity(count);
+    for _ in 0..count {
+        if u32_at(pos)? != 0x0201_4b50 || u16_at(pos + 10)? != 0 {
+            return None;
+        }
+        let crc = u32_at(pos + 16)?;
+        let size = u32_at(pos + 20)? as usize;
+        let name_len = u16_at(pos + 28)? as usize;
+        let local = u32_at(pos + 42)? as usize;
+        let name = String::from_utf8(archive.get(pos + 46..pos + 46 + name_len)?.to_vec()).ok()?;
+
+        if u32_at(local)? != 0x0403_4b50 {
+            return None;
+        }
+        let data_start = local + 30 + u16_at(local + 26)? as usize + u16_at(local + 28)? as usize;
+        let data = archive.get(data_start..data_start + size)?.to_vec();
+        if crc32(&data) != crc {
+            return None;
+        }
+
+        entries.push((name, data));
+        pos += 46 + name_len;
+    }
+    Some(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32_known_values() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn test_entries_round_trip() {
+        let mut zip = ZipStream::new();
+        let mut archive = zip.entry("001.wav", b"RIFF audio").unwrap();
+        archive.extend(zip.entry("manifest.json", b"{}").unwrap());
+        archive.extend(zip.finish().unwrap());
+
+        assert_eq!(
+            read_stored_entries(&archive).unwrap(),
+            vec![
+                ("001.wav".to_string(), b"RIFF audio".to_vec()),
+                ("manifest.json".to_string(), b"{}".to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_archive_is_just_an_end_record() {
+        let archive = ZipStream::new().finish().unwrap();
+        assert_eq!(archive.len(), 22);
+        assert_eq!(read_stored_entries(&archive).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_corrupt_entry_is_detected() {
+        let mut zip = ZipStream::new();
+        let mut archive = zip.entry("a.txt", b"hello").unwrap();
+        archive.extend(zip.finish().unwrap());
+
+        // Flip a byte of the stored data
+        let data_pos = 30 + "a.txt".len();
+        archive[data_pos] ^= 0xff;
+        assert!(read_stored_entries(&archive).is_none());
+    }
+}
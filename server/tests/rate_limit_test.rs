@@ -3,9 +3,11 @@ use axum::{
     http::{Request, StatusCode},
 };
 use porua_server::auth::ApiKeys;
+use porua_server::config::constants::DEFAULT_MAX_NORMALIZED_LENGTH;
 use porua_server::kokoro::TTSPool;
 use porua_server::rate_limit::{PerKeyRateLimiter, RateLimitConfig, RateLimiterMode};
 use porua_server::server::{create_router, AppState};
+use porua_server::utils::accept::AudioFormat;
 use std::sync::Arc;
 use std::time::Duration;
 use tower::ServiceExt;
@@ -54,6 +56,37 @@ async fn create_test_app(rate_config: RateLimitConfig, with_auth: bool) -> axum:
         api_keys,
         rate_limiter,
         request_timeout: Duration::from_secs(60), // Default timeout for tests
+        max_request_timeout: Duration::from_secs(300),
+        normalization: Default::default(),
+        concurrency_limiter: None,
+        max_audio_duration: Duration::from_secs(30 * 60),
+        cors: Default::default(),
+        generation_stats: Default::default(),
+        audio_cache: None,
+        speed_clamp: false,
+        voice_aliases: Default::default(),
+        wav_metadata: false,
+        concat_sample_rate: Default::default(),
+        chunking_min_length: porua_server::config::constants::DEFAULT_CHUNKING_MIN_LENGTH,
+        validation_headers: false,
+        long_tokens: Default::default(),
+        chunk_retry: Default::default(),
+        jobs: porua_server::services::jobs::JobStore::new(std::time::Duration::from_secs(600), 1),
+        ellipsis_pause: None,
+        espeak_data_dir: None,
+        asides: None,
+        lists: None,
+        fallback_voice: None,
+        max_normalized_length: DEFAULT_MAX_NORMALIZED_LENGTH,
+        say_punctuation: None,
+        stream_keep_alive: None,
+        quotes: Default::default(),
+        voice_rates: Default::default(),
+        response_headers: Default::default(),
+        min_speed: porua_server::config::constants::DEFAULT_MIN_SPEED,
+        voice_defaults: Default::default(),
+        default_audio_format: AudioFormat::Wav,
+        config: Default::default(),
     };
 
     create_router(state)
@@ -190,6 +190,7 @@ fn test_streaming_chunk_boundaries_on_normalized() {
     let config = ChunkingConfig {
         max_chunk_size: 80,
         min_chunk_size: 20,
+        ..Default::default()
     };
     let chunks = chunk_text(&normalized, &config);
 
@@ -16,6 +16,8 @@ pub struct InstallProgress {
     pub details: Option<String>,
 }
 
+// Keep in step with the server, which names the voices URL in its corrupt
+// voices file error (VOICES_DOWNLOAD_URL) and checks the archive is complete
 const MODELS: &[(&str, &str, u64)] = &[
     (
         "kokoro-v1.0.onnx",